@@ -7,10 +7,13 @@
 ///
 use std::io::{Read, Seek};
 
-use rand::{distributions::WeightedIndex, prelude::Distribution, thread_rng};
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
 use tracing::trace;
 
-use crate::ss2_common::{read_string_with_size, read_u32};
+use crate::{
+    ss2_common::{read_string_with_size, read_u32},
+    Result,
+};
 
 #[derive(Debug, Clone)]
 pub struct Song {
@@ -25,7 +28,7 @@ pub struct SongSection {
 }
 
 impl SongSection {
-    pub fn get_next_option(&self, maybe_cue: Option<String>) -> u32 {
+    pub fn get_next_option(&self, maybe_cue: Option<String>, rng: &mut impl Rng) -> u32 {
         // Figure out which option to try...
         let mut section_opt = 0;
         if let Some(cue) = maybe_cue {
@@ -39,7 +42,7 @@ impl SongSection {
         }
 
         let option = &self.options[section_opt as usize];
-        option.choose_random()
+        option.choose_random(rng)
     }
 }
 
@@ -50,15 +53,14 @@ pub struct SongSectionOption {
 }
 
 impl SongSectionOption {
-    pub fn choose_random(&self) -> u32 {
-        let mut rng = thread_rng();
+    pub fn choose_random(&self, rng: &mut impl Rng) -> u32 {
         let weights = self
             .sub_options
             .iter()
             .map(|s| s.probability)
             .collect::<Vec<u32>>();
         let weight_index = WeightedIndex::new(weights).unwrap();
-        let idx = weight_index.sample(&mut rng);
+        let idx = weight_index.sample(rng);
 
         self.sub_options[idx].next_index
     }
@@ -76,11 +78,11 @@ pub struct SongPlayContext {
 }
 
 impl Song {
-    pub fn read<T: Read + Seek>(reader: &mut T) -> Song {
-        let _unk_header = read_u32(reader);
+    pub fn read<T: Read + Seek>(reader: &mut T) -> Result<Song> {
+        let _unk_header = read_u32(reader)?;
 
-        let song_name = read_string_with_size(reader, 9);
-        let contact = read_string_with_size(reader, 27);
+        let song_name = read_string_with_size(reader, 9)?;
+        let contact = read_string_with_size(reader, 27)?;
 
         trace!(
             "header: {} song_name: {} contact: {}",
@@ -89,17 +91,17 @@ impl Song {
             &contact
         );
 
-        let num_sections = read_u32(reader);
+        let num_sections = read_u32(reader)?;
 
         trace!("sections: {}", num_sections);
 
         let mut sections = Vec::new();
         for _ in 0..num_sections {
-            let section = read_section(reader);
+            let section = read_section(reader)?;
             sections.push(section);
         }
 
-        Song { sections }
+        Ok(Song { sections })
     }
 
     ///
@@ -139,11 +141,12 @@ impl Song {
         &self,
         current_context: SongPlayContext,
         cue: Option<String>,
+        rng: &mut impl Rng,
     ) -> (SongPlayContext, String) {
         // For the current song, check if any of the options
 
         let current_section = &self.sections[current_context.current_section as usize];
-        let new_section = current_section.get_next_option(cue);
+        let new_section = current_section.get_next_option(cue, rng);
         (
             SongPlayContext {
                 current_section: new_section,
@@ -153,13 +156,13 @@ impl Song {
     }
 }
 
-fn read_section<T: Read + Seek>(reader: &mut T) -> SongSection {
-    let name = read_string_with_size(reader, 36);
-    let _unk1 = read_u32(reader);
-    let _unk2 = read_u32(reader);
-    let wav_file = read_string_with_size(reader, 32);
+fn read_section<T: Read + Seek>(reader: &mut T) -> Result<SongSection> {
+    let name = read_string_with_size(reader, 36)?;
+    let _unk1 = read_u32(reader)?;
+    let _unk2 = read_u32(reader)?;
+    let wav_file = read_string_with_size(reader, 32)?;
 
-    let num_options = read_u32(reader);
+    let num_options = read_u32(reader)?;
 
     trace!(
         "- reading section {} with wav: {}, {} options (unk1: {}, unk2: {}):",
@@ -172,20 +175,20 @@ fn read_section<T: Read + Seek>(reader: &mut T) -> SongSection {
 
     let mut options = Vec::new();
     for _ in 0..num_options {
-        let option = read_section_option(reader);
+        let option = read_section_option(reader)?;
         options.push(option);
     }
 
-    SongSection {
+    Ok(SongSection {
         name,
         wav_file,
         options,
-    }
+    })
 }
 
-fn read_section_option<T: Read + Seek>(reader: &mut T) -> SongSectionOption {
-    let schema = read_string_with_size(reader, 36);
-    let sub_option_count = read_u32(reader);
+fn read_section_option<T: Read + Seek>(reader: &mut T) -> Result<SongSectionOption> {
+    let schema = read_string_with_size(reader, 36)?;
+    let sub_option_count = read_u32(reader)?;
 
     trace!(
         "-- reading section option - schema: {}, sub options: {}",
@@ -195,24 +198,24 @@ fn read_section_option<T: Read + Seek>(reader: &mut T) -> SongSectionOption {
 
     let mut sub_options = Vec::new();
     for _ in 0..sub_option_count {
-        sub_options.push(read_sub_option(reader));
+        sub_options.push(read_sub_option(reader)?);
     }
-    SongSectionOption {
+    Ok(SongSectionOption {
         schema: schema.to_ascii_lowercase(),
         sub_options,
-    }
+    })
 }
 
-fn read_sub_option<T: Read + Seek>(reader: &mut T) -> SubOption {
-    let next_index = read_u32(reader);
-    let probability = read_u32(reader);
+fn read_sub_option<T: Read + Seek>(reader: &mut T) -> Result<SubOption> {
+    let next_index = read_u32(reader)?;
+    let probability = read_u32(reader)?;
     trace!(
         "--- reading sub option - next_index: {}, probability: {}",
         next_index,
         probability,
     );
-    SubOption {
+    Ok(SubOption {
         next_index,
         probability,
-    }
+    })
 }
@@ -4,6 +4,7 @@ use engine::{
     assets::asset_cache::{AssetCache},
     audio::{AudioClip, BackgroundMusic},
 };
+use rand::RngCore;
 use tracing::info;
 
 use crate::importers::AUDIO_IMPORTER;
@@ -14,10 +15,13 @@ pub struct SongPlayer {
     song: Song,
     name_to_clip: HashMap<String, Rc<AudioClip>>,
     play_state: SongPlayContext,
+    // The player is driven by the audio context, outside of the game update,
+    // so it owns its own generator for picking the next section.
+    rng: Box<dyn RngCore>,
 }
 
 impl SongPlayer {
-    pub fn new(song: &Song, asset_cache: &mut AssetCache) -> SongPlayer {
+    pub fn new(song: &Song, asset_cache: &mut AssetCache, rng: Box<dyn RngCore>) -> SongPlayer {
         let mut name_to_clip = HashMap::new();
         let wav_files = song.all_wav_files();
 
@@ -43,13 +47,16 @@ impl SongPlayer {
             song: my_song,
             name_to_clip,
             play_state,
+            rng,
         }
     }
 }
 
 impl BackgroundMusic<String> for SongPlayer {
     fn next_clip(&mut self, cue: Option<String>) -> Option<Rc<engine::audio::AudioClip>> {
-        let (next_state, clip_name) = self.song.play_next(self.play_state.clone(), cue.clone());
+        let (next_state, clip_name) =
+            self.song
+                .play_next(self.play_state.clone(), cue.clone(), &mut self.rng);
         self.play_state = next_state.clone();
 
         let maybe_audio_clip = self
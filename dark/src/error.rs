@@ -0,0 +1,133 @@
+///
+/// error.rs
+///
+/// Error type for reading dark engine files. Failures carry the file, chunk, and byte offset
+/// when they are known, so that a truncated or modded file can be reported instead of panicking.
+///
+use std::{error, fmt, io};
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    Io(io::Error),
+    MissingChunk(String),
+    InvalidData(String),
+}
+
+#[derive(Debug)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub file: Option<String>,
+    pub chunk: Option<String>,
+    pub offset: Option<u64>,
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Error {
+    pub fn new(kind: ErrorKind) -> Error {
+        Error {
+            kind,
+            file: None,
+            chunk: None,
+            offset: None,
+        }
+    }
+
+    pub fn missing_chunk(chunk_name: &str) -> Error {
+        Error::new(ErrorKind::MissingChunk(chunk_name.to_owned()))
+    }
+
+    pub fn invalid_data(message: impl Into<String>) -> Error {
+        Error::new(ErrorKind::InvalidData(message.into()))
+    }
+
+    ///
+    /// in_chunk
+    ///
+    /// Record the chunk, and offset in the file, that was being read when the error occurred.
+    /// The innermost chunk wins, so wrapping an error that already has a chunk is a no-op.
+    pub fn in_chunk(mut self, chunk_name: &str, offset: u64) -> Error {
+        if self.chunk.is_none() {
+            self.chunk = Some(chunk_name.to_owned());
+            self.offset = Some(offset);
+        }
+        self
+    }
+
+    pub fn in_file(mut self, file_name: &str) -> Error {
+        if self.file.is_none() {
+            self.file = Some(file_name.to_owned());
+        }
+        self
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::new(ErrorKind::Io(err))
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::Io(err) => write!(f, "{}", err),
+            ErrorKind::MissingChunk(chunk_name) => write!(f, "missing chunk {}", chunk_name),
+            ErrorKind::InvalidData(message) => write!(f, "invalid data: {}", message),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(file) = &self.file {
+            write!(f, " in {}", file)?;
+        }
+        if let Some(chunk) = &self.chunk {
+            write!(f, " (chunk {}", chunk)?;
+            if let Some(offset) = self.offset {
+                write!(f, " at offset {}", offset)?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::ss2_common::read_u32;
+
+    #[test]
+    fn test_truncated_read_returns_error() {
+        let mut reader = Cursor::new(vec![1, 2]);
+        let err = read_u32(&mut reader).unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::Io(_)));
+    }
+
+    #[test]
+    fn test_error_message_includes_location() {
+        let err = Error::invalid_data("bad cell count")
+            .in_chunk("WREXT", 128)
+            .in_chunk("OUTER", 0)
+            .in_file("earth.mis");
+
+        assert_eq!(
+            err.to_string(),
+            "invalid data: bad cell count in earth.mis (chunk WREXT at offset 128)"
+        );
+    }
+}
@@ -18,7 +18,10 @@ use engine::{
 use image::ImageBuffer;
 use tracing::info;
 
-use crate::ss2_common::{read_bytes, read_i16, read_u16, read_u32, read_u8};
+use crate::{
+    ss2_common::{read_bytes, read_i16, read_u16, read_u32, read_u8},
+    Error, Result,
+};
 
 pub struct Font {
     pub char_to_info: HashMap<char, CharInfo>,
@@ -51,20 +54,20 @@ pub struct CharInfo {
 }
 
 impl FontHeader {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T) -> FontHeader {
-        let format = read_u16(reader);
-        let _unk = read_u8(reader);
-        let palette = read_u8(reader);
-        let _zeros1 = read_bytes(reader, 32);
-        let first_char = read_i16(reader);
-        let last_char = read_i16(reader);
-        let _zeros2 = read_bytes(reader, 32);
-        let width_offset = read_u32(reader);
-        let bitmap_offset = read_u32(reader);
-        let row_width = read_u16(reader);
-        let num_rows = read_u16(reader);
-
-        FontHeader {
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T) -> Result<FontHeader> {
+        let format = read_u16(reader)?;
+        let _unk = read_u8(reader)?;
+        let palette = read_u8(reader)?;
+        let _zeros1 = read_bytes(reader, 32)?;
+        let first_char = read_i16(reader)?;
+        let last_char = read_i16(reader)?;
+        let _zeros2 = read_bytes(reader, 32)?;
+        let width_offset = read_u32(reader)?;
+        let bitmap_offset = read_u32(reader)?;
+        let row_width = read_u16(reader)?;
+        let num_rows = read_u16(reader)?;
+
+        Ok(FontHeader {
             format,
             palette,
             first_char,
@@ -73,7 +76,7 @@ impl FontHeader {
             bitmap_offset,
             row_width,
             num_rows,
-        }
+        })
     }
 }
 
@@ -134,47 +137,49 @@ impl Font {
 
         mesh::create(vertices)
     }
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T) -> Font {
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T) -> Result<Font> {
         // Get total length of file
         // Needed so we can get the size of the bitmap
         let mut _vec = Vec::new();
-        let _end = reader.read_to_end(&mut _vec).unwrap();
-        let end_bytes = reader.stream_position().unwrap();
+        let _end = reader.read_to_end(&mut _vec)?;
+        let end_bytes = reader.stream_position()?;
 
         // Then rewind and start reading...
-        reader.seek(io::SeekFrom::Start(0)).unwrap();
+        reader.seek(io::SeekFrom::Start(0))?;
 
-        let header = FontHeader::read(reader);
+        let header = FontHeader::read(reader)?;
 
         // Currently, we don't support any palettes
-        assert!(header.palette == 0);
+        if header.palette != 0 {
+            return Err(Error::invalid_data(format!(
+                "unsupported font palette: {}",
+                header.palette
+            )));
+        }
 
         info!("Loading font with header: {:?}", header);
 
         let num_chars = header.last_char - header.first_char + 1;
 
         let mut widths = Vec::new();
-        reader
-            .seek(io::SeekFrom::Start(header.width_offset as u64))
-            .unwrap();
+        reader.seek(io::SeekFrom::Start(header.width_offset as u64))?;
 
         for _ in 0..num_chars {
-            widths.push(read_u16(reader))
+            widths.push(read_u16(reader)?)
         }
 
         // Read bitmap data
-        reader
-            .seek(io::SeekFrom::Start(header.bitmap_offset as u64))
-            .unwrap();
+        reader.seek(io::SeekFrom::Start(header.bitmap_offset as u64))?;
 
         let bitmap_size = end_bytes - header.bitmap_offset as u64;
-        let bitmap = read_bytes(reader, bitmap_size as usize);
+        let bitmap = read_bytes(reader, bitmap_size as usize)?;
 
         let mut char_to_info = HashMap::new();
         let mut texture_packer = TexturePacker::<image::Rgba<u8>>::new_rgba(512, 512);
         for n in 0..num_chars - 1 {
             let code = header.first_char + n;
-            let ascii = char::from_u32(code as u32).unwrap();
+            let ascii = char::from_u32(code as u32)
+                .ok_or_else(|| Error::invalid_data(format!("invalid font character: {}", code)))?;
             let idx = n as usize;
 
             let column = widths[idx];
@@ -237,11 +242,11 @@ impl Font {
         // img.save("test1.png").unwrap();
         // panic!("saving images");
 
-        Font {
+        Ok(Font {
             texture,
             char_to_info,
             base_height: header.num_rows as f32,
-        }
+        })
     }
 }
 
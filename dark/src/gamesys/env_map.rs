@@ -1,12 +1,12 @@
-use std::{io};
+use std::io;
 
-
-use shipyard::{IntoIter};
+use shipyard::IntoIter;
 use tracing::trace;
 
 use crate::{
-    ss2_chunk_file_reader::ChunkFileTableOfContents,
-    ss2_common::{read_bytes, read_u32}, TagDatabase,
+    ss2_chunk_file_reader::{with_chunk, ChunkFileTableOfContents},
+    ss2_common::{read_bytes, read_u32},
+    Result, TagDatabase,
 };
 
 pub struct EnvMap {}
@@ -15,24 +15,23 @@ impl EnvMap {
     pub fn read<T: io::Read + io::Seek>(
         table_of_contents: &ChunkFileTableOfContents,
         reader: &mut T,
-    ) -> TagDatabase {
-        let test = table_of_contents.get_chunk("ENV_SOUND".to_owned()).unwrap();
-
-        reader.seek(io::SeekFrom::Start(test.offset));
-        let local_required_size = read_u32(reader);
-
-        trace!("local required size: {}", &local_required_size);
-        let _local_required = read_bytes(reader, local_required_size as usize);
-
-        // for _ in 0..count {
-        //     let c0 = read_char(reader);
-        //     let c1 = read_char(reader);
-        //     trace!("-- read tag: {c0} {c1}");
-        // }
-
-        
-
-        // trace!("tag_database: {:#?}", tag_database);
-        TagDatabase::read(reader)
+    ) -> Result<TagDatabase> {
+        let test = table_of_contents.require_chunk("ENV_SOUND")?;
+        with_chunk(reader, "ENV_SOUND", |reader| {
+            reader.seek(io::SeekFrom::Start(test.offset))?;
+            let local_required_size = read_u32(reader)?;
+
+            trace!("local required size: {}", &local_required_size);
+            let _local_required = read_bytes(reader, local_required_size as usize)?;
+
+            // for _ in 0..count {
+            //     let c0 = read_char(reader);
+            //     let c1 = read_char(reader);
+            //     trace!("-- read tag: {c0} {c1}");
+            // }
+
+            // trace!("tag_database: {:#?}", tag_database);
+            TagDatabase::read(reader)
+        })
     }
 }
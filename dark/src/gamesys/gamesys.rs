@@ -1,7 +1,7 @@
 use core::fmt;
 use std::{collections::HashMap, io};
 
-use rand::{distributions::WeightedIndex, prelude::Distribution, thread_rng};
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
 use shipyard::{Component, Get, IntoIter, IntoWithId, View, World};
 use tracing::info;
 
@@ -9,7 +9,7 @@ use crate::{
     properties::{LinkDefinition, LinkDefinitionWithData, PropertyDefinition},
     ss2_chunk_file_reader::{self},
     ss2_entity_info::{self, SystemShock2EntityInfo},
-    EnvMap, EnvSoundQuery, SoundSchema, SpeechDB, TagDatabase,
+    EnvMap, EnvSoundQuery, Result, SoundSchema, SpeechDB, TagDatabase,
 };
 
 pub struct Gamesys {
@@ -20,7 +20,11 @@ pub struct Gamesys {
 }
 
 impl Gamesys {
-    pub fn get_random_environmental_sound(&self, query: &EnvSoundQuery) -> Option<String> {
+    pub fn get_random_environmental_sound(
+        &self,
+        query: &EnvSoundQuery,
+        rng: &mut impl Rng,
+    ) -> Option<String> {
         let tag_query = query.to_tag_query(&self.speech_db.tag_map, &self.speech_db.value_map);
         let result = self.env_tag_map.query_match_all(&tag_query);
 
@@ -35,10 +39,9 @@ impl Gamesys {
 
         let samples = maybe_samples.unwrap();
 
-        let mut rng = thread_rng();
         let weights = samples.iter().map(|s| s.frequency).collect::<Vec<u8>>();
         let weight_index = WeightedIndex::new(weights).unwrap();
-        let idx = weight_index.sample(&mut rng);
+        let idx = weight_index.sample(rng);
 
         Some(samples[idx].sample_name.to_owned())
     }
@@ -49,8 +52,8 @@ pub fn read<T: io::Read + io::Seek>(
     links: &Vec<Box<dyn LinkDefinition>>,
     links_with_data: &Vec<Box<dyn LinkDefinitionWithData>>,
     properties: &Vec<Box<dyn PropertyDefinition<T>>>,
-) -> Gamesys {
-    let table_of_contents = ss2_chunk_file_reader::read_table_of_contents(reader);
+) -> Result<Gamesys> {
+    let table_of_contents = ss2_chunk_file_reader::read_table_of_contents(reader)?;
 
     let entity_info = ss2_entity_info::new(
         &table_of_contents,
@@ -58,23 +61,23 @@ pub fn read<T: io::Read + io::Seek>(
         links_with_data,
         properties,
         reader,
-    );
+    )?;
 
-    let sound_schema = SoundSchema::read(&table_of_contents, reader, &entity_info);
+    let sound_schema = SoundSchema::read(&table_of_contents, reader, &entity_info)?;
 
-    let env_tag_map = EnvMap::read(&table_of_contents, reader);
-    let speech_db = SpeechDB::read(&table_of_contents, reader);
+    let env_tag_map = EnvMap::read(&table_of_contents, reader)?;
+    let speech_db = SpeechDB::read(&table_of_contents, reader)?;
 
     // Uncomment to output debug info for voices:
     // debug_print_voices(&sound_schema, &speech_db);
     // panic!()
 
-    Gamesys {
+    Ok(Gamesys {
         entity_info,
         sound_schema,
         env_tag_map,
         speech_db,
-    }
+    })
 }
 
 fn debug_print_voices(sound_schema: &SoundSchema, speech_db: &SpeechDB) {
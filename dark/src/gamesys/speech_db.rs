@@ -1,9 +1,9 @@
 use std::io;
 
 use crate::{
-    ss2_chunk_file_reader::ChunkFileTableOfContents,
+    ss2_chunk_file_reader::{with_chunk, ChunkFileTableOfContents},
     ss2_common::{read_bytes, read_u32},
-    NameMap, TagDatabase,
+    NameMap, Result, TagDatabase,
 };
 
 #[derive(Debug, Clone)]
@@ -12,14 +12,14 @@ pub struct Voice {
 }
 
 impl Voice {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, num_concepts: usize) -> Voice {
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, num_concepts: usize) -> Result<Voice> {
         let mut tag_maps = Vec::new();
         for _ in 0..num_concepts {
-            let tag_database = TagDatabase::read(reader);
+            let tag_database = TagDatabase::read(reader)?;
             tag_maps.push(tag_database)
         }
 
-        Voice { tag_maps }
+        Ok(Voice { tag_maps })
     }
 }
 
@@ -35,38 +35,40 @@ impl SpeechDB {
     pub fn read<T: io::Read + io::Seek>(
         table_of_contents: &ChunkFileTableOfContents,
         reader: &mut T,
-    ) -> SpeechDB {
+    ) -> Result<SpeechDB> {
         // Read SchSamp chunk
-        let schema_chunk = table_of_contents.get_chunk("Speech_DB".to_owned()).unwrap();
-        let _end = schema_chunk.offset + schema_chunk.length;
-        reader.seek(io::SeekFrom::Start(schema_chunk.offset));
+        let schema_chunk = table_of_contents.require_chunk("Speech_DB")?;
+        with_chunk(reader, "Speech_DB", |reader| {
+            let _end = schema_chunk.offset + schema_chunk.length;
+            reader.seek(io::SeekFrom::Start(schema_chunk.offset))?;
 
-        let concept_map = NameMap::read(reader);
-        let tag_map = NameMap::read(reader);
-        let value_map = NameMap::read(reader);
+            let concept_map = NameMap::read(reader)?;
+            let tag_map = NameMap::read(reader)?;
+            let value_map = NameMap::read(reader)?;
 
-        // Read priority
-        let priority_size = read_u32(reader) * 4;
-        let _priority = read_bytes(reader, priority_size as usize);
+            // Read priority
+            let priority_size = read_u32(reader)? * 4;
+            let _priority = read_bytes(reader, priority_size as usize)?;
 
-        let flags_size = read_u32(reader) * 4;
-        let _flags = read_bytes(reader, flags_size as usize);
+            let flags_size = read_u32(reader)? * 4;
+            let _flags = read_bytes(reader, flags_size as usize)?;
 
-        let num_voices = read_u32(reader);
+            let num_voices = read_u32(reader)?;
 
-        let num_concepts = concept_map.count();
+            let num_concepts = concept_map.count();
 
-        let mut voices = Vec::new();
-        for idx in 0..num_voices {
-            let voice = Voice::read(reader, num_concepts);
-            voices.push(voice);
-        }
+            let mut voices = Vec::new();
+            for idx in 0..num_voices {
+                let voice = Voice::read(reader, num_concepts)?;
+                voices.push(voice);
+            }
 
-        SpeechDB {
-            concept_map,
-            tag_map,
-            value_map,
-            voices,
-        }
+            Ok(SpeechDB {
+                concept_map,
+                tag_map,
+                value_map,
+                voices,
+            })
+        })
     }
 }
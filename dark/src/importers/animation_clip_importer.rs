@@ -18,7 +18,8 @@ fn import_animation_cliip(
 
     let mps_motion = motiondb.get_mps_motions(name_without_extra_stuff.to_owned());
     let motion_stuff = motiondb.get_motion_stuff(name_without_extra_stuff.to_owned());
-    let motion_clip = MotionClip::read(reader, mps_motion);
+    let motion_clip = MotionClip::read(reader, mps_motion)
+        .unwrap_or_else(|err| panic!("Unable to load animation clip {}: {}", name, err));
 
     AnimationClip::create(&motion_clip, mps_motion, motion_stuff)
 }
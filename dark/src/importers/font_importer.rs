@@ -7,10 +7,12 @@ pub static FONT_IMPORTER: Lazy<AssetImporter<Box<dyn engine::Font>, Box<dyn engi
     Lazy::new(|| AssetImporter::define(load_font, |font, _cache, _config| font));
 
 fn load_font(
-    _name: String,
+    name: String,
     reader: &mut Box<dyn engine::assets::asset_paths::ReadableAndSeekable>,
     _assets: &mut AssetCache,
     _config: &(),
 ) -> Box<dyn engine::Font> {
-    Box::new(Font::read(reader))
+    let font =
+        Font::read(reader).unwrap_or_else(|err| panic!("Unable to load font {}: {}", name, err));
+    Box::new(font)
 }
@@ -22,22 +22,27 @@ pub enum SystemShockContentModel {
 }
 
 fn load_model(
-    _name: String,
+    name: String,
     reader: &mut Box<dyn engine::assets::asset_paths::ReadableAndSeekable>,
     _assets: &mut AssetCache,
     _config: &(),
 ) -> SystemShockContentModel {
-    let common_header = ss2_bin_header::read(reader);
+    let common_header = ss2_bin_header::read(reader)
+        .unwrap_or_else(|err| panic!("Unable to load model {}: {}", name, err));
     match common_header.bin_type {
         ss2_bin_header::BinFileType::Obj => {
-            SystemShockContentModel::Obj(ss2_bin_obj_loader::read(reader, &common_header))
+            let obj = ss2_bin_obj_loader::read(reader, &common_header)
+                .unwrap_or_else(|err| panic!("Unable to load model {}: {}", name, err));
+            SystemShockContentModel::Obj(obj)
         }
         ss2_bin_header::BinFileType::Mesh => {
-            let mut pathbuf = PathBuf::from(_name);
+            let mut pathbuf = PathBuf::from(&name);
             pathbuf.set_extension("cal");
             let cal_path = pathbuf.to_string_lossy();
             let skeleton = _assets.get(&SKELETON_IMPORTER, &cal_path);
-            SystemShockContentModel::Mesh(ss2_bin_ai_loader::read(reader, &common_header), skeleton)
+            let mesh = ss2_bin_ai_loader::read(reader, &common_header)
+                .unwrap_or_else(|err| panic!("Unable to load model {}: {}", name, err));
+            SystemShockContentModel::Mesh(mesh, skeleton)
         }
     }
 }
@@ -6,12 +6,14 @@ use once_cell::sync::Lazy;
 use crate::motion::MotionDB;
 
 fn import_motion_db(
-    _name: String,
+    name: String,
     reader: &mut Box<dyn engine::assets::asset_paths::ReadableAndSeekable>,
     _assets: &mut AssetCache,
     _config: &(),
 ) -> Rc<MotionDB> {
-    Rc::new(MotionDB::read(reader))
+    let motion_db = MotionDB::read(reader)
+        .unwrap_or_else(|err| panic!("Unable to load motion db {}: {}", name, err));
+    Rc::new(motion_db)
 }
 
 fn process_motion_db(
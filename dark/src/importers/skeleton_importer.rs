@@ -7,12 +7,13 @@ use crate::{
 };
 
 fn load_skeleton(
-    _name: String,
+    name: String,
     reader: &mut Box<dyn engine::assets::asset_paths::ReadableAndSeekable>,
     _assets: &mut AssetCache,
     _config: &(),
 ) -> SystemShock2Cal {
     ss2_cal_loader::read(reader)
+        .unwrap_or_else(|err| panic!("Unable to load skeleton {}: {}", name, err))
 }
 
 fn process_skeleton(
@@ -7,10 +7,10 @@ pub static SONG_IMPORTER: Lazy<AssetImporter<Song, Song, ()>> =
     Lazy::new(|| AssetImporter::define(load_song, |song, _cache, _config| song));
 
 fn load_song(
-    _name: String,
+    name: String,
     reader: &mut Box<dyn engine::assets::asset_paths::ReadableAndSeekable>,
     _assets: &mut AssetCache,
     _config: &(),
 ) -> Song {
-    Song::read(reader)
+    Song::read(reader).unwrap_or_else(|err| panic!("Unable to load song {}: {}", name, err))
 }
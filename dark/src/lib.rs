@@ -1,5 +1,6 @@
 pub mod audio;
 mod bitmap_animation;
+mod error;
 pub mod font;
 pub mod gamesys;
 pub mod mission;
@@ -22,6 +23,7 @@ pub mod properties;
 pub mod importers;
 
 pub use bitmap_animation::*;
+pub use error::{Error, ErrorKind, Result};
 pub use gamesys::*;
 pub use name_map::*;
 pub use tag_database::*;
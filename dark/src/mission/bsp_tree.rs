@@ -4,7 +4,7 @@ use cgmath::Vector3;
 use tracing::trace;
 
 use super::{Cell, Plane};
-use crate::ss2_common::*;
+use crate::{ss2_common::*, Error, Result};
 
 pub type BspNodeId = u32;
 
@@ -85,7 +85,7 @@ impl BspTree {
         }
     }
 
-    pub fn read<T: io::Read>(reader: &mut T, planes: &Vec<Cell>) -> BspTree {
+    pub fn read<T: io::Read>(reader: &mut T, planes: &Vec<Cell>) -> Result<BspTree> {
         // Read "extra planes"
         // Most maps don't use them - but looks like at least command1.mis and command2.mis
         //
@@ -93,21 +93,21 @@ impl BspTree {
         // https://github.com/volca02/openDarkEngine/blob/7a2d7baaf0fc5194a9066a635c6f44b0f7b26c56/src/services/worldrep/WorldRepService.cpp#L340
         //
         // This allows for BSP nodes that don't correspond to cells - they can just have a splitting plane.
-        let num_extra_planes = read_u32(reader);
+        let num_extra_planes = read_u32(reader)?;
         let mut extra_planes = Vec::new();
         for _ in 0..num_extra_planes {
-            let plane = Plane::read(reader);
+            let plane = Plane::read(reader)?;
             extra_planes.push(plane);
         }
 
-        let num_bsp_nodes = read_u32(reader);
+        let num_bsp_nodes = read_u32(reader)?;
 
         // First pass: read nodes and populate dictionary -> id
         let mut raw_node_map: HashMap<u32, RawBspNode> = HashMap::new();
         let mut raw_root_node = None;
 
         for idx in 0..num_bsp_nodes {
-            let node_header = read_u32(reader);
+            let node_header = read_u32(reader)?;
 
             // The first 4 byte are packed:
             // - 1 byte: flags
@@ -117,12 +117,14 @@ impl BspTree {
             //let node_id = first_bits + flags;
             // let node_id = node_header & 0xFFFFFF00 >> 8;
             // let flags = (node_header & 0x000000FF);
-            let normalized_flags = BspFlags::from_bits(flags).unwrap();
+            let normalized_flags = BspFlags::from_bits(flags).ok_or_else(|| {
+                Error::invalid_data(format!("unknown flags {} on bsp node {}", flags, node_id))
+            })?;
 
-            let cell = read_i32(reader);
-            let plane = read_u32(reader);
-            let front = read_i32(reader);
-            let back = read_i32(reader);
+            let cell = read_i32(reader)?;
+            let plane = read_u32(reader)?;
+            let front = read_i32(reader)?;
+            let back = read_i32(reader)?;
 
             let node = {
                 if normalized_flags.contains(BspFlags::LEAF) {
@@ -157,27 +159,31 @@ impl BspTree {
         }
 
         // Grab the root node
-        let root_node = Self::create_node_recursive(
-            planes,
-            &raw_node_map,
-            &raw_root_node.unwrap(),
-            &extra_planes,
-        );
-
-        BspTree {
+        let raw_root_node =
+            raw_root_node.ok_or_else(|| Error::invalid_data("bsp tree has no nodes"))?;
+        let root_node =
+            Self::create_node_recursive(planes, &raw_node_map, &raw_root_node, &extra_planes)?;
+
+        Ok(BspTree {
             root_node: Rc::new(root_node),
-        }
+        })
     }
     fn create_node_recursive(
         cells: &Vec<Cell>,
         raw_node_map: &HashMap<u32, RawBspNode>,
         raw_node: &RawBspNode,
         extra_planes: &Vec<Plane>,
-    ) -> BspNode {
+    ) -> Result<BspNode> {
+        let get_raw_node = |node_id: &BspNodeId| {
+            raw_node_map
+                .get(node_id)
+                .ok_or_else(|| Error::invalid_data(format!("missing bsp node {}", node_id)))
+        };
+
         match raw_node {
-            RawBspNode::Leaf { cell_idx } => BspNode::Leaf {
+            RawBspNode::Leaf { cell_idx } => Ok(BspNode::Leaf {
                 cell_idx: *cell_idx,
-            },
+            }),
             RawBspNode::Split {
                 cell_idx,
                 plane_idx,
@@ -195,9 +201,9 @@ impl BspTree {
                     Some(Rc::new(Self::create_node_recursive(
                         cells,
                         raw_node_map,
-                        raw_node_map.get(front).unwrap(),
+                        get_raw_node(front)?,
                         extra_planes,
-                    )))
+                    )?))
                 };
 
                 let back_node = if *back == 0xFFFFFF {
@@ -206,24 +212,32 @@ impl BspTree {
                     Some(Rc::new(Self::create_node_recursive(
                         cells,
                         raw_node_map,
-                        raw_node_map.get(back).unwrap(),
+                        get_raw_node(back)?,
                         extra_planes,
-                    )))
+                    )?))
                 };
 
                 // Handle the extra plane - the extra plane is used if the parent node does not correspond to an extra cell.
-                let plane = if *cell_idx < 0 {
-                    extra_planes[*plane_idx as usize].clone()
+                let maybe_plane = if *cell_idx < 0 {
+                    extra_planes.get(*plane_idx as usize)
                 } else {
-                    cells[*cell_idx as usize].planes[*plane_idx as usize].clone()
+                    cells
+                        .get(*cell_idx as usize)
+                        .and_then(|cell| cell.planes.get(*plane_idx as usize))
                 };
-
-                BspNode::Split {
+                let plane = maybe_plane.cloned().ok_or_else(|| {
+                    Error::invalid_data(format!(
+                        "bsp node references missing plane {} in cell {}",
+                        plane_idx, cell_idx
+                    ))
+                })?;
+
+                Ok(BspNode::Split {
                     cell_idx: *cell_idx,
                     plane,
                     front: front_node,
                     back: back_node,
-                }
+                })
             }
         }
     }
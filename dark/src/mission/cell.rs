@@ -18,7 +18,7 @@ use std::f32;
 use std::io;
 
 use crate::ss2_common::read_vec3;
-use crate::SCALE_FACTOR;
+use crate::{Error, Result, SCALE_FACTOR};
 
 use super::CellPortal;
 use super::Plane;
@@ -45,43 +45,43 @@ impl Cell {
         wr_ext: bool,
         cell_idx: u32,
         light_size: u8,
-    ) -> Cell {
-        let cell_num_verts = reader.read_u8().unwrap();
-        let cell_num_polys = reader.read_u8().unwrap();
-        let cell_num_render_polys = reader.read_u8().unwrap();
-        let portal_count = reader.read_u8().unwrap();
-        let cell_num_planes = reader.read_u8().unwrap();
-        let _cell_medium = reader.read_u8().unwrap();
-        let _cell_flags = reader.read_u8().unwrap();
+    ) -> Result<Cell> {
+        let cell_num_verts = reader.read_u8()?;
+        let cell_num_polys = reader.read_u8()?;
+        let cell_num_render_polys = reader.read_u8()?;
+        let portal_count = reader.read_u8()?;
+        let cell_num_planes = reader.read_u8()?;
+        let _cell_medium = reader.read_u8()?;
+        let _cell_flags = reader.read_u8()?;
 
-        let _nxn = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-        let _poly_map_size = reader.read_u16::<byteorder::LittleEndian>().unwrap();
+        let _nxn = reader.read_u32::<byteorder::LittleEndian>()?;
+        let _poly_map_size = reader.read_u16::<byteorder::LittleEndian>()?;
 
-        let cell_num_anim_lights = reader.read_u8().unwrap();
-        let _cell_flow_group = reader.read_u8().unwrap();
+        let cell_num_anim_lights = reader.read_u8()?;
+        let _cell_flow_group = reader.read_u8()?;
 
-        let center = read_vec3(reader) / SCALE_FACTOR;
-        let radius = reader.read_f32::<byteorder::LittleEndian>().unwrap() / SCALE_FACTOR;
+        let center = read_vec3(reader)? / SCALE_FACTOR;
+        let radius = reader.read_f32::<byteorder::LittleEndian>()? / SCALE_FACTOR;
 
         let mut vertices = vec![vec3(0.0, 0.0, 0.0); cell_num_verts as usize];
 
         for v in 0..cell_num_verts {
-            vertices[v as usize] = read_vec3(reader);
+            vertices[v as usize] = read_vec3(reader)?;
         }
 
         let mut polygons: Vec<Polygon> = Vec::new();
         for _ in 0..cell_num_polys {
-            let poly = read_polygon(reader);
+            let poly = read_polygon(reader)?;
             polygons.push(poly);
         }
 
         let mut textured_polygons: Vec<PolygonTexturing> = Vec::new();
         for _ in 0..cell_num_render_polys {
-            let textured_poly = read_polygon_texturing(reader, wr_ext);
+            let textured_poly = read_polygon_texturing(reader, wr_ext)?;
             textured_polygons.push(textured_poly);
         }
 
-        let _num_indices = reader.read_u32::<byteorder::LittleEndian>().unwrap();
+        let _num_indices = reader.read_u32::<byteorder::LittleEndian>()?;
         let mut polygon_indices: Vec<Vec<u8>> = Vec::new();
 
         for poly in 0..cell_num_polys {
@@ -90,7 +90,13 @@ impl Cell {
 
             let mut indices: Vec<u8> = Vec::new();
             for _i in 0..count {
-                let idx = reader.read_u8().unwrap();
+                let idx = reader.read_u8()?;
+                if idx >= cell_num_verts {
+                    return Err(Error::invalid_data(format!(
+                        "cell {} has vertex index {} but only {} vertices",
+                        cell_idx, idx, cell_num_verts
+                    )));
+                }
                 indices.push(idx);
             }
             polygon_indices.push(indices);
@@ -98,7 +104,7 @@ impl Cell {
 
         let mut planes: Vec<Plane> = Vec::new();
         for _ in 0..cell_num_planes {
-            let plane = Plane::read(reader);
+            let plane = Plane::read(reader)?;
             planes.push(plane);
         }
 
@@ -109,7 +115,14 @@ impl Cell {
             cell_num_anim_lights,
             cell_num_render_polys,
             light_size,
-        );
+        )?;
+
+        if portal_count > cell_num_polys {
+            return Err(Error::invalid_data(format!(
+                "cell {} has {} portals but only {} polygons",
+                cell_idx, portal_count, cell_num_polys
+            )));
+        }
 
         let portals = Self::collect_portals(&polygons, &polygon_indices, &vertices, portal_count);
 
@@ -126,7 +139,7 @@ impl Cell {
             vertices,
             lights,
         };
-        cell
+        Ok(cell)
     }
 
     pub fn debug_render(&self) -> Vec<SceneObject> {
@@ -257,16 +270,16 @@ pub struct Polygon {
     pub unk: u8,
 }
 
-fn read_polygon<T: io::Read>(reader: &mut T) -> Polygon {
-    let flags = reader.read_u8().unwrap();
-    let count = reader.read_u8().unwrap();
-    let plane_id = reader.read_u8().unwrap();
-    let clut_id = reader.read_u8().unwrap();
-    let target_cell = reader.read_u16::<byteorder::LittleEndian>().unwrap();
-    let motion_index = reader.read_u8().unwrap();
-    let unk = reader.read_u8().unwrap();
+fn read_polygon<T: io::Read>(reader: &mut T) -> Result<Polygon> {
+    let flags = reader.read_u8()?;
+    let count = reader.read_u8()?;
+    let plane_id = reader.read_u8()?;
+    let clut_id = reader.read_u8()?;
+    let target_cell = reader.read_u16::<byteorder::LittleEndian>()?;
+    let motion_index = reader.read_u8()?;
+    let unk = reader.read_u8()?;
 
-    Polygon {
+    Ok(Polygon {
         flags,
         count,
         plane_id,
@@ -274,7 +287,7 @@ fn read_polygon<T: io::Read>(reader: &mut T) -> Polygon {
         target_cell,
         motion_index,
         unk,
-    }
+    })
 }
 
 #[derive(Debug)]
@@ -290,9 +303,12 @@ pub struct PolygonTexturing {
     pub center: Vector3<f32>,
 }
 
-fn read_polygon_texturing<T: io::Read>(reader: &mut T, is_extended_rep: bool) -> PolygonTexturing {
-    let axis_u = read_vec3(reader);
-    let axis_v = read_vec3(reader);
+fn read_polygon_texturing<T: io::Read>(
+    reader: &mut T,
+    is_extended_rep: bool,
+) -> Result<PolygonTexturing> {
+    let axis_u = read_vec3(reader)?;
+    let axis_v = read_vec3(reader)?;
 
     let mut u: f32 = 0.0;
     let mut v: f32 = 0.0;
@@ -301,24 +317,24 @@ fn read_polygon_texturing<T: io::Read>(reader: &mut T, is_extended_rep: bool) ->
     let mut cached_surface: u16 = 0;
 
     if is_extended_rep {
-        u = reader.read_f32::<byteorder::LittleEndian>().unwrap() * 4096.0;
-        v = reader.read_f32::<byteorder::LittleEndian>().unwrap() * 4096.0;
-        texture_num = reader.read_u16::<byteorder::LittleEndian>().unwrap();
-        origin_vertex = reader.read_u16::<byteorder::LittleEndian>().unwrap();
+        u = reader.read_f32::<byteorder::LittleEndian>()? * 4096.0;
+        v = reader.read_f32::<byteorder::LittleEndian>()? * 4096.0;
+        texture_num = reader.read_u16::<byteorder::LittleEndian>()?;
+        origin_vertex = reader.read_u16::<byteorder::LittleEndian>()?;
         cached_surface = 0;
     } else {
-        u = f32::from(reader.read_u16::<byteorder::LittleEndian>().unwrap());
-        v = f32::from(reader.read_u16::<byteorder::LittleEndian>().unwrap());
+        u = f32::from(reader.read_u16::<byteorder::LittleEndian>()?);
+        v = f32::from(reader.read_u16::<byteorder::LittleEndian>()?);
 
-        texture_num = reader.read_u8().unwrap() as u16;
-        origin_vertex = reader.read_u8().unwrap() as u16;
-        cached_surface = reader.read_u16::<byteorder::LittleEndian>().unwrap();
+        texture_num = reader.read_u8()? as u16;
+        origin_vertex = reader.read_u8()? as u16;
+        cached_surface = reader.read_u16::<byteorder::LittleEndian>()?;
     }
 
-    let scale = reader.read_f32::<byteorder::LittleEndian>().unwrap();
-    let center = read_vec3(reader);
+    let scale = reader.read_f32::<byteorder::LittleEndian>()?;
+    let center = read_vec3(reader)?;
 
-    PolygonTexturing {
+    Ok(PolygonTexturing {
         axis_u,
         axis_v,
         u,
@@ -328,7 +344,7 @@ fn read_polygon_texturing<T: io::Read>(reader: &mut T, is_extended_rep: bool) ->
         cached_surface,
         scale,
         center,
-    }
+    })
 }
 
 fn read_lights<T: io::Read>(
@@ -338,15 +354,15 @@ fn read_lights<T: io::Read>(
     num_lights: u8,
     num_lightmaps: u8,
     light_size: u8,
-) -> Vec<LightInfo> {
+) -> Result<Vec<LightInfo>> {
     // Read lights
     for _ in 0..num_lights {
-        let _ = reader.read_i16::<byteorder::LittleEndian>().unwrap();
+        let _ = reader.read_i16::<byteorder::LittleEndian>()?;
     }
 
     let mut light_infos: Vec<LightInfo> = Vec::new();
     for _ in 0..num_lightmaps {
-        let li = read_light_info(poly_idx, reader);
+        let li = read_light_info(poly_idx, reader)?;
         light_infos.push(li);
     }
 
@@ -354,11 +370,11 @@ fn read_lights<T: io::Read>(
         let li = light_infos.get_mut(i as usize).unwrap();
         let lm_count = li.animation_flags.count_ones() + 1;
 
-        let lm_size = (light_size as u16) * li.lx * (li.ly as u16);
+        let lm_size = (light_size as usize) * (li.lx as usize) * (li.ly as usize);
 
         for idx in 0..lm_count {
-            let mut bytes = vec![0_u8; lm_size as usize];
-            reader.read_exact(&mut bytes).unwrap();
+            let mut bytes = vec![0_u8; lm_size];
+            reader.read_exact(&mut bytes)?;
 
             if idx == 0 {
                 let img = image::ImageBuffer::from_fn(li.lx as u32, li.ly as u32, |x, y| {
@@ -384,12 +400,12 @@ fn read_lights<T: io::Read>(
         }
     }
 
-    let light_count = reader.read_u32::<byteorder::LittleEndian>().unwrap();
+    let light_count = reader.read_u32::<byteorder::LittleEndian>()?;
     for _ in 0..light_count {
-        let _ = reader.read_u16::<byteorder::LittleEndian>().unwrap();
+        let _ = reader.read_u16::<byteorder::LittleEndian>()?;
     }
 
-    light_infos
+    Ok(light_infos)
 }
 
 #[derive(Debug)]
@@ -406,19 +422,19 @@ pub struct LightInfo {
     pub texture_pack_result: TexturePackResult,
 }
 
-fn read_light_info<T: io::Read>(debug_idx: u32, reader: &mut T) -> LightInfo {
-    let u = reader.read_i16::<byteorder::LittleEndian>().unwrap();
-    let v = reader.read_i16::<byteorder::LittleEndian>().unwrap();
+fn read_light_info<T: io::Read>(debug_idx: u32, reader: &mut T) -> Result<LightInfo> {
+    let u = reader.read_i16::<byteorder::LittleEndian>()?;
+    let v = reader.read_i16::<byteorder::LittleEndian>()?;
 
-    let lx = reader.read_u16::<byteorder::LittleEndian>().unwrap();
-    let ly = reader.read_u8().unwrap();
-    let lx8 = reader.read_u8().unwrap();
+    let lx = reader.read_u16::<byteorder::LittleEndian>()?;
+    let ly = reader.read_u8()?;
+    let lx8 = reader.read_u8()?;
 
-    let static_lightmap_pointer = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-    let dynamic_lightmap_pointer = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-    let animation_flags = reader.read_u32::<byteorder::LittleEndian>().unwrap();
+    let static_lightmap_pointer = reader.read_u32::<byteorder::LittleEndian>()?;
+    let dynamic_lightmap_pointer = reader.read_u32::<byteorder::LittleEndian>()?;
+    let animation_flags = reader.read_u32::<byteorder::LittleEndian>()?;
 
-    LightInfo {
+    Ok(LightInfo {
         debug_idx,
         u,
         v,
@@ -429,5 +445,5 @@ fn read_light_info<T: io::Read>(debug_idx: u32, reader: &mut T) -> LightInfo {
         dynamic_lightmap_pointer,
         animation_flags,
         texture_pack_result: TexturePackResult::DEFAULT,
-    }
+    })
 }
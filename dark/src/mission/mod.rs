@@ -1,6 +1,7 @@
 mod bsp_tree;
 mod cell;
 mod cell_portal;
+pub mod path_database;
 mod plane;
 pub mod render_params;
 pub mod room;
@@ -13,15 +14,17 @@ pub use bsp_tree::*;
 pub use cell::*;
 pub use cell_portal::*;
 pub use plane::*;
-use tracing::trace;
+use tracing::{trace, warn};
 
 use crate::properties::LinkDefinitionWithData;
 
-use crate::ss2_chunk_file_reader::ChunkFileTableOfContents;
+use crate::ss2_chunk_file_reader::{with_chunk, ChunkFileTableOfContents};
 use crate::ss2_common::read_bytes;
 use crate::ss2_common::read_plane;
 use crate::Gamesys;
 use crate::SCALE_FACTOR;
+use crate::{Error, Result};
+use path_database::*;
 use render_params::*;
 use room_database::*;
 
@@ -82,6 +85,7 @@ pub struct SystemShock2Level {
     pub obj_map: HashMap<i32, String>,
 
     pub room_database: RoomDatabase,
+    pub path_database: PathDatabase,
     pub song_params: SongParams,
     pub bsp_tree: BspTree,
 }
@@ -131,8 +135,8 @@ pub fn read<T: io::Read + io::Seek>(
     links: &Vec<Box<dyn LinkDefinition>>,
     links_with_data: &Vec<Box<dyn LinkDefinitionWithData>>,
     properties: &Vec<Box<dyn PropertyDefinition<T>>>,
-) -> SystemShock2Level {
-    let table_of_contents = ss2_chunk_file_reader::read_table_of_contents(reader);
+) -> Result<SystemShock2Level> {
+    let table_of_contents = ss2_chunk_file_reader::read_table_of_contents(reader)?;
 
     let mut wr_offset = 0;
     let mut wr_ext = false; // Extended representation
@@ -146,9 +150,7 @@ pub fn read<T: io::Read + io::Seek>(
     if wr_rgb {
         world_chunk_name = "WRRGB"
     }
-    let wr_chunk = table_of_contents
-        .get_chunk(world_chunk_name.to_string())
-        .unwrap();
+    let wr_chunk = table_of_contents.require_chunk(world_chunk_name)?;
 
     wr_offset = wr_chunk.offset;
 
@@ -156,55 +158,61 @@ pub fn read<T: io::Read + io::Seek>(
         light_size = 2
     }
 
-    reader.seek(SeekFrom::Start(wr_offset)).unwrap();
+    let mut packer = TexturePacker::<image::Rgb<u8>>::new_rgb(LIGHTMAP_SIZE, LIGHTMAP_SIZE);
+    let (cells, bsp_tree) = with_chunk(reader, world_chunk_name, |reader| {
+        reader.seek(SeekFrom::Start(wr_offset))?;
 
-    let _wr_unk = reader.read_u32::<byteorder::LittleEndian>().unwrap();
+        let _wr_unk = reader.read_u32::<byteorder::LittleEndian>()?;
 
-    // For WR_EXT - load extended attributes
-    // Not sure what a bunch of these are - but the light depth is important
-    // for us to properly read the light maps
-    if wr_ext {
-        let _wr_new_dark_unk = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-        let _wr_shadowed_water = reader.read_u32::<byteorder::LittleEndian>().unwrap();
+        // For WR_EXT - load extended attributes
+        // Not sure what a bunch of these are - but the light depth is important
+        // for us to properly read the light maps
+        if wr_ext {
+            let _wr_new_dark_unk = reader.read_u32::<byteorder::LittleEndian>()?;
+            let _wr_shadowed_water = reader.read_u32::<byteorder::LittleEndian>()?;
 
-        let wr_lm_bit_depth = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-        light_size = 2u8.pow(wr_lm_bit_depth + 1);
+            let wr_lm_bit_depth = reader.read_u32::<byteorder::LittleEndian>()?;
+            light_size = 2u8
+                .checked_pow(wr_lm_bit_depth + 1)
+                .ok_or_else(|| Error::invalid_data("lightmap bit depth out of range"))?;
 
-        let _wr_new_dark_unk2 = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-        let _wr_mysterious_value = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-    }
+            let _wr_new_dark_unk2 = reader.read_u32::<byteorder::LittleEndian>()?;
+            let _wr_mysterious_value = reader.read_u32::<byteorder::LittleEndian>()?;
+        }
 
-    let wr_num_cells = reader.read_u32::<byteorder::LittleEndian>().unwrap();
+        let wr_num_cells = reader.read_u32::<byteorder::LittleEndian>()?;
 
-    // Read cells
-    let mut cells: Vec<Cell> = Vec::new();
-    let mut packer = TexturePacker::<image::Rgb<u8>>::new_rgb(LIGHTMAP_SIZE, LIGHTMAP_SIZE);
-    for cell_idx in 0..wr_num_cells {
-        let cell = Cell::read(reader, &mut packer, wr_ext, cell_idx, light_size);
-        cells.push(cell);
-    }
+        // Read cells
+        let mut cells: Vec<Cell> = Vec::new();
+        for cell_idx in 0..wr_num_cells {
+            let cell = Cell::read(reader, &mut packer, wr_ext, cell_idx, light_size)?;
+            cells.push(cell);
+        }
 
-    let bsp_tree = BspTree::read(reader, &cells);
+        let bsp_tree = BspTree::read(reader, &cells)?;
 
-    if wr_ext {
-        let _ = read_bytes(reader, wr_num_cells as usize);
-    }
+        if wr_ext {
+            let _ = read_bytes(reader, wr_num_cells as usize)?;
+        }
 
-    let num_static_lights = read_u32(reader);
-    let num_dynamic_lights = read_u32(reader);
-    println!(
-        "static_lights: {:?} dynamic_lights {:?}",
-        num_static_lights, num_dynamic_lights
-    );
+        let num_static_lights = read_u32(reader)?;
+        let num_dynamic_lights = read_u32(reader)?;
+        println!(
+            "static_lights: {:?} dynamic_lights {:?}",
+            num_static_lights, num_dynamic_lights
+        );
 
-    let (obj_map, obj_texture_families) = read_obj_map(&table_of_contents, reader);
+        Ok((cells, bsp_tree))
+    })?;
+
+    let (obj_map, obj_texture_families) = read_obj_map(&table_of_contents, reader)?;
     let entity_info = ss2_entity_info::new(
         &table_of_contents,
         links,
         links_with_data,
         properties,
         reader,
-    );
+    )?;
 
     let textures = TextureList::read(
         &table_of_contents,
@@ -212,14 +220,25 @@ pub fn read<T: io::Read + io::Seek>(
         &entity_info,
         obj_texture_families,
         reader,
-    );
-    let all_geometry = create_geometry(asset_cache, &cells, &textures.0);
-
-    let _render_params = RenderParams::read(&table_of_contents, reader);
-    let room_database = RoomDatabase::read(&table_of_contents, reader);
-    let song_params = SongParams::read(&table_of_contents, reader);
+    )?;
+    let all_geometry = create_geometry(asset_cache, &cells, &textures.0)?;
+
+    let _render_params = RenderParams::read(&table_of_contents, reader)?;
+    let room_database = RoomDatabase::read(&table_of_contents, reader)?;
+    // The path database is only needed by the AI, so a mission with an unreadable one still loads
+    let path_database = match PathDatabase::read(&table_of_contents, reader) {
+        Ok(path_database) => path_database,
+        Err(err) => {
+            warn!(
+                "unable to read path database, AI will not navigate: {:?}",
+                err
+            );
+            PathDatabase::empty()
+        }
+    };
+    let song_params = SongParams::read(&table_of_contents, reader)?;
 
-    SystemShock2Level {
+    Ok(SystemShock2Level {
         bsp_tree,
         all_geometry,
         textures,
@@ -228,43 +247,46 @@ pub fn read<T: io::Read + io::Seek>(
         cells,
         entity_info,
         room_database,
+        path_database,
         song_params,
-    }
+    })
 }
 
 fn read_obj_map<T: io::Read + io::Seek>(
     table_of_contents: &ChunkFileTableOfContents,
     reader: &mut T,
-) -> (HashMap<i32, String>, Vec<(String, i32)>) {
-    let obj_map_chunk = table_of_contents.get_chunk("OBJ_MAP".to_string()).unwrap();
-    let len = obj_map_chunk.length;
-    reader.seek(SeekFrom::Start(obj_map_chunk.offset)).unwrap();
+) -> Result<(HashMap<i32, String>, Vec<(String, i32)>)> {
+    let obj_map_chunk = table_of_contents.require_chunk("OBJ_MAP")?;
+    with_chunk(reader, "OBJ_MAP", |reader| {
+        let len = obj_map_chunk.length;
+        reader.seek(SeekFrom::Start(obj_map_chunk.offset))?;
 
-    let end = obj_map_chunk.offset + len;
+        let end = obj_map_chunk.offset + len;
 
-    let mut texture_families = Vec::new();
+        let mut texture_families = Vec::new();
 
-    let mut obj_map = HashMap::new();
-    while reader.stream_position().unwrap() < end {
-        let obj_id = read_i32(reader);
-        let size = read_u32(reader);
+        let mut obj_map = HashMap::new();
+        while reader.stream_position()? < end {
+            let obj_id = read_i32(reader)?;
+            let size = read_u32(reader)?;
 
-        let str = read_string_with_size(reader, size as usize);
+            let str = read_string_with_size(reader, size as usize)?;
 
-        if str.starts_with("t_fam") {
-            texture_families.push((str.clone(), obj_id));
-        }
+            if str.starts_with("t_fam") {
+                texture_families.push((str.clone(), obj_id));
+            }
 
-        obj_map.insert(obj_id, str);
-    }
-    (obj_map, texture_families)
+            obj_map.insert(obj_id, str);
+        }
+        Ok((obj_map, texture_families))
+    })
 }
 
 fn create_geometry(
     asset_cache: &mut AssetCache,
     cells: &Vec<Cell>,
     textures: &Vec<SystemShock2Texture>,
-) -> Vec<SystemShock2Geometry> {
+) -> Result<Vec<SystemShock2Geometry>> {
     let mut all_geometry: Vec<SystemShock2Geometry> = Vec::new();
     let mut cell_idx = 0;
     for cell in cells {
@@ -276,7 +298,10 @@ fn create_geometry(
             let indices = &cell.polygon_indices[poly];
             let li = &cell.lights[poly];
             if li.debug_idx != cell_idx {
-                panic!("index doesn't match????");
+                return Err(Error::invalid_data(format!(
+                    "light info for cell {} is out of order",
+                    cell_idx
+                )));
             }
             let len = indices.len();
             // TODO: What are 249/247 - BACKHACK or something?
@@ -300,7 +325,14 @@ fn create_geometry(
             let sh_u = render_poly.u / 4096.0;
             let sh_v = render_poly.v / 4096.0;
 
-            let tex_info = &textures[render_poly.texture_num as usize];
+            let tex_info = textures
+                .get(render_poly.texture_num as usize)
+                .ok_or_else(|| {
+                    Error::invalid_data(format!(
+                        "cell {} uses unknown texture {}",
+                        cell_idx, render_poly.texture_num
+                    ))
+                })?;
             let texture_dim = texture_dimensions(asset_cache, tex_info);
 
             let rs_x = (texture_dim.width as f32) / 64.0;
@@ -358,7 +390,7 @@ fn create_geometry(
         }
         cell_idx += 1;
     }
-    all_geometry
+    Ok(all_geometry)
 }
 
 fn build_vertex(
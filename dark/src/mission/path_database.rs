@@ -0,0 +1,409 @@
+///
+/// path_database.rs
+///
+/// Reads the AI pathfinding database (the AIPATH chunk) - the cells the AI can walk on, and the
+/// links between them. Like the rest of the Dark path data, indices in the chunk are 1-based, and
+/// each table has an unused entry at index 0:
+///
+///   u32 cell count, cells[count + 1]
+///     u32 first cell vertex, u32 first link, u32 plane, vec3 center,
+///     u8 vertex count, u8 flags, u8 link count, u8 padding
+///   u32 plane count, planes[count + 1] (vec3 normal, f32 constant)
+///   u32 vertex count, vertices[count + 1] (vec3 point, u32 info)
+///   u32 link count, links[count + 1]
+///     u32 destination cell, u32 edge vertex, u32 edge vertex, u8 ok bits, u8 padding[3]
+///   u32 cell vertex count, u32 vertex index[count + 1]
+///
+use crate::{
+    ss2_chunk_file_reader::{with_chunk, ChunkFileTableOfContents},
+    ss2_common::{read_bytes, read_u32, read_u8, read_vec3},
+    Error, Result, SCALE_FACTOR,
+};
+
+use cgmath::{InnerSpace, Vector3};
+use tracing::trace;
+
+use std::io;
+use std::io::SeekFrom;
+
+const PLANE_SIZE: usize = 16;
+// Sizes of the other table entries, in bytes
+const CELL_SIZE: u64 = 28;
+const VERTEX_SIZE: u64 = 16;
+const LINK_SIZE: u64 = 16;
+const CELL_VERTEX_SIZE: u64 = 4;
+
+#[derive(Debug, Clone)]
+pub struct PathCell {
+    pub center: Vector3<f32>,
+    // Outline of the cell
+    pub vertices: Vec<Vector3<f32>>,
+    pub flags: u8,
+    pub links: Vec<PathLink>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PathLink {
+    // Index of the cell this link leads to
+    pub to_cell: usize,
+    // The shared edge the AI passes through to get to the other cell
+    pub edge: (Vector3<f32>, Vector3<f32>),
+    // Which kinds of movement (walking, swimming, etc) can use this link
+    pub ok_bits: u8,
+    // Cost of taking this link - the distance between the cell centers
+    pub cost: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PathDatabase {
+    pub cells: Vec<PathCell>,
+}
+
+struct RawCell {
+    first_vertex: u32,
+    first_link: u32,
+    center: Vector3<f32>,
+    vertex_count: u8,
+    flags: u8,
+    link_count: u8,
+}
+
+struct RawLink {
+    dest: u32,
+    vertex_1: u32,
+    vertex_2: u32,
+    ok_bits: u8,
+}
+
+impl PathDatabase {
+    pub fn empty() -> PathDatabase {
+        PathDatabase { cells: Vec::new() }
+    }
+
+    // Read the AIPATH chunk - not every mission has one, so fall back to an empty database
+    pub fn read<T: io::Read + io::Seek>(
+        table_of_contents: &ChunkFileTableOfContents,
+        reader: &mut T,
+    ) -> Result<PathDatabase> {
+        match table_of_contents.get_chunk("AIPATH".to_string()) {
+            None => Ok(PathDatabase::empty()),
+            Some(chunk) => with_chunk(reader, "AIPATH", |reader| {
+                reader.seek(SeekFrom::Start(chunk.offset))?;
+                PathDatabase::read_chunk(reader, chunk.length)
+            }),
+        }
+    }
+
+    ///
+    /// read_chunk
+    ///
+    /// Reads a chunk of `chunk_len` bytes. The table counts are checked against the chunk length
+    /// before reading each table, so a bad count is an error rather than a huge allocation.
+    pub fn read_chunk<T: io::Read>(reader: &mut T, chunk_len: u64) -> Result<PathDatabase> {
+        let mut remaining = chunk_len;
+
+        let cell_count = read_u32(reader)?;
+        take_table(&mut remaining, "cell", cell_count, CELL_SIZE)?;
+        let mut raw_cells = Vec::new();
+        for _ in 0..=cell_count {
+            let first_vertex = read_u32(reader)?;
+            let first_link = read_u32(reader)?;
+            let _plane = read_u32(reader)?;
+            let center = read_vec3(reader)? / SCALE_FACTOR;
+            let vertex_count = read_u8(reader)?;
+            let flags = read_u8(reader)?;
+            let link_count = read_u8(reader)?;
+            let _padding = read_u8(reader)?;
+            raw_cells.push(RawCell {
+                first_vertex,
+                first_link,
+                center,
+                vertex_count,
+                flags,
+                link_count,
+            });
+        }
+
+        let plane_count = read_u32(reader)?;
+        take_table(&mut remaining, "plane", plane_count, PLANE_SIZE as u64)?;
+        let _planes = read_bytes(reader, (plane_count as usize + 1) * PLANE_SIZE)?;
+
+        let vertex_count = read_u32(reader)?;
+        take_table(&mut remaining, "vertex", vertex_count, VERTEX_SIZE)?;
+        let mut vertices = Vec::new();
+        for _ in 0..=vertex_count {
+            vertices.push(read_vec3(reader)? / SCALE_FACTOR);
+            let _info = read_u32(reader)?;
+        }
+
+        let link_count = read_u32(reader)?;
+        take_table(&mut remaining, "link", link_count, LINK_SIZE)?;
+        let mut raw_links = Vec::new();
+        for _ in 0..=link_count {
+            let dest = read_u32(reader)?;
+            let vertex_1 = read_u32(reader)?;
+            let vertex_2 = read_u32(reader)?;
+            let ok_bits = read_u8(reader)?;
+            let _padding = read_bytes(reader, 3)?;
+            raw_links.push(RawLink {
+                dest,
+                vertex_1,
+                vertex_2,
+                ok_bits,
+            });
+        }
+
+        let cell_vertex_count = read_u32(reader)?;
+        take_table(
+            &mut remaining,
+            "cell vertex",
+            cell_vertex_count,
+            CELL_VERTEX_SIZE,
+        )?;
+        let mut cell_vertices = Vec::new();
+        for _ in 0..=cell_vertex_count {
+            cell_vertices.push(read_u32(reader)?);
+        }
+
+        trace!(
+            "path db - cells: {} links: {} vertices: {}",
+            cell_count,
+            link_count,
+            vertex_count
+        );
+
+        let get_vertex = |idx: u32| {
+            vertices
+                .get(idx as usize)
+                .copied()
+                .ok_or_else(|| Error::invalid_data("path vertex out of range"))
+        };
+
+        let mut cells = Vec::new();
+        for raw_cell in raw_cells.iter().skip(1) {
+            let mut cell_outline = Vec::new();
+            for i in 0..raw_cell.vertex_count as usize {
+                let vertex_idx = cell_vertices
+                    .get(raw_cell.first_vertex as usize + i)
+                    .ok_or_else(|| Error::invalid_data("path cell vertex out of range"))?;
+                cell_outline.push(get_vertex(*vertex_idx)?);
+            }
+
+            let mut links = Vec::new();
+            for i in 0..raw_cell.link_count as usize {
+                let raw_link = raw_links
+                    .get(raw_cell.first_link as usize + i)
+                    .ok_or_else(|| Error::invalid_data("path link out of range"))?;
+                if raw_link.dest == 0 || raw_link.dest > cell_count {
+                    return Err(Error::invalid_data("path link to unknown cell"));
+                }
+
+                let dest_center = raw_cells[raw_link.dest as usize].center;
+                links.push(PathLink {
+                    to_cell: raw_link.dest as usize - 1,
+                    edge: (
+                        get_vertex(raw_link.vertex_1)?,
+                        get_vertex(raw_link.vertex_2)?,
+                    ),
+                    ok_bits: raw_link.ok_bits,
+                    cost: (dest_center - raw_cell.center).magnitude(),
+                });
+            }
+
+            cells.push(PathCell {
+                center: raw_cell.center,
+                vertices: cell_outline,
+                flags: raw_cell.flags,
+                links,
+            });
+        }
+
+        Ok(PathDatabase { cells })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn neighbors(&self, cell: usize) -> &[PathLink] {
+        self.cells
+            .get(cell)
+            .map(|cell| cell.links.as_slice())
+            .unwrap_or(&[])
+    }
+
+    ///
+    /// find_cell
+    ///
+    /// The cell closest to the position - the AI uses this to get on (and off) the path network
+    pub fn find_cell(&self, position: Vector3<f32>) -> Option<usize> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(idx, cell)| (idx, (cell.center - position).magnitude2()))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(idx, _)| idx)
+    }
+}
+
+// Accounts for a table - its u32 count and count + 1 entries - against the bytes left in the chunk
+fn take_table(remaining: &mut u64, table: &str, count: u32, entry_size: u64) -> Result<()> {
+    let table_size = (count as u64 + 1) * entry_size + 4;
+    if table_size > *remaining {
+        return Err(Error::invalid_data(format!(
+            "path {} count {} overruns the AIPATH chunk",
+            table, count
+        )));
+    }
+    *remaining -= table_size;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use byteorder::{LittleEndian, WriteBytesExt};
+    use cgmath::vec3;
+    use std::io::Cursor;
+
+    // Writes a vector in Dark's coordinate space, so it reads back as the given vector
+    fn write_vec3(bytes: &mut Vec<u8>, v: Vector3<f32>) {
+        let v = v * SCALE_FACTOR;
+        bytes.write_f32::<LittleEndian>(-v.x).unwrap();
+        bytes.write_f32::<LittleEndian>(v.z).unwrap();
+        bytes.write_f32::<LittleEndian>(v.y).unwrap();
+    }
+
+    fn write_cell(
+        bytes: &mut Vec<u8>,
+        first_vertex: u32,
+        first_link: u32,
+        center: Vector3<f32>,
+        vertex_count: u8,
+        link_count: u8,
+    ) {
+        bytes.write_u32::<LittleEndian>(first_vertex).unwrap();
+        bytes.write_u32::<LittleEndian>(first_link).unwrap();
+        bytes.write_u32::<LittleEndian>(0).unwrap();
+        write_vec3(bytes, center);
+        bytes.extend([vertex_count, 1, link_count, 0]);
+    }
+
+    fn write_link(bytes: &mut Vec<u8>, dest: u32, vertex_1: u32, vertex_2: u32) {
+        bytes.write_u32::<LittleEndian>(dest).unwrap();
+        bytes.write_u32::<LittleEndian>(vertex_1).unwrap();
+        bytes.write_u32::<LittleEndian>(vertex_2).unwrap();
+        bytes.extend([1, 0, 0, 0]);
+    }
+
+    // Two square cells side by side, sharing the edge from vertex 2 to vertex 3
+    fn two_cell_chunk(link_dest: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.write_u32::<LittleEndian>(2).unwrap();
+        write_cell(&mut bytes, 0, 0, vec3(0.0, 0.0, 0.0), 0, 0);
+        write_cell(&mut bytes, 1, 1, vec3(1.0, 0.0, 1.0), 4, 1);
+        write_cell(&mut bytes, 5, 2, vec3(3.0, 0.0, 1.0), 4, 1);
+
+        bytes.write_u32::<LittleEndian>(0).unwrap();
+        bytes.extend([0; PLANE_SIZE]);
+
+        let points = [
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(2.0, 0.0, 0.0),
+            vec3(2.0, 0.0, 2.0),
+            vec3(0.0, 0.0, 2.0),
+            vec3(4.0, 0.0, 0.0),
+            vec3(4.0, 0.0, 2.0),
+        ];
+        bytes
+            .write_u32::<LittleEndian>(points.len() as u32 - 1)
+            .unwrap();
+        for point in points {
+            write_vec3(&mut bytes, point);
+            bytes.write_u32::<LittleEndian>(0).unwrap();
+        }
+
+        bytes.write_u32::<LittleEndian>(2).unwrap();
+        write_link(&mut bytes, 0, 0, 0);
+        write_link(&mut bytes, link_dest, 2, 3);
+        write_link(&mut bytes, 1, 2, 3);
+
+        let cell_vertices = [0, 1, 2, 3, 4, 2, 5, 6, 3];
+        bytes
+            .write_u32::<LittleEndian>(cell_vertices.len() as u32 - 1)
+            .unwrap();
+        for idx in cell_vertices {
+            bytes.write_u32::<LittleEndian>(idx).unwrap();
+        }
+
+        bytes
+    }
+
+    fn read(bytes: Vec<u8>) -> Result<PathDatabase> {
+        let len = bytes.len() as u64;
+        PathDatabase::read_chunk(&mut Cursor::new(bytes), len)
+    }
+
+    fn assert_near(a: Vector3<f32>, b: Vector3<f32>) {
+        assert!((a - b).magnitude() < 0.0001, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_read_cells_and_links() {
+        let db = read(two_cell_chunk(2)).unwrap();
+
+        assert_eq!(db.cells.len(), 2);
+        assert_near(db.cells[0].center, vec3(1.0, 0.0, 1.0));
+        assert_near(db.cells[1].center, vec3(3.0, 0.0, 1.0));
+        assert_eq!(db.cells[0].vertices.len(), 4);
+        assert_eq!(db.cells[0].flags, 1);
+
+        let link = &db.neighbors(0)[0];
+        assert_eq!(link.to_cell, 1);
+        assert_eq!(link.ok_bits, 1);
+        assert!((link.cost - 2.0).abs() < 0.0001);
+        assert_near(link.edge.0, vec3(2.0, 0.0, 0.0));
+        assert_near(link.edge.1, vec3(2.0, 0.0, 2.0));
+
+        assert_eq!(db.neighbors(1)[0].to_cell, 0);
+        assert!(db.neighbors(2).is_empty());
+    }
+
+    #[test]
+    fn test_find_cell() {
+        let db = read(two_cell_chunk(2)).unwrap();
+
+        assert_eq!(db.find_cell(vec3(0.5, 0.0, 0.5)), Some(0));
+        assert_eq!(db.find_cell(vec3(3.5, 0.0, 1.5)), Some(1));
+        assert_eq!(PathDatabase::empty().find_cell(vec3(0.0, 0.0, 0.0)), None);
+    }
+
+    #[test]
+    fn test_link_to_unknown_cell_is_an_error() {
+        assert!(read(two_cell_chunk(3)).is_err());
+        assert!(read(two_cell_chunk(0)).is_err());
+    }
+
+    #[test]
+    fn test_truncated_chunk_is_an_error() {
+        let mut bytes = two_cell_chunk(2);
+        bytes.truncate(bytes.len() - 4);
+        assert!(read(bytes).is_err());
+    }
+
+    #[test]
+    fn test_count_past_chunk_end_is_an_error() {
+        let mut bytes = two_cell_chunk(2);
+        // Claim u32::MAX planes - this must be rejected before trying to read them
+        let plane_count_offset = 4 + 3 * CELL_SIZE as usize;
+        bytes[plane_count_offset..plane_count_offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(read(bytes).is_err());
+
+        // A chunk length shorter than the data is also caught up front
+        let bytes = two_cell_chunk(2);
+        let short_len = bytes.len() as u64 - 4;
+        assert!(PathDatabase::read_chunk(&mut Cursor::new(bytes), short_len).is_err());
+    }
+}
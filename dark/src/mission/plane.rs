@@ -4,7 +4,7 @@ use cgmath::{InnerSpace, Vector3};
 
 use crate::{
     ss2_common::{read_single, read_vec3},
-    SCALE_FACTOR,
+    Result, SCALE_FACTOR,
 };
 
 #[derive(Clone, Debug)]
@@ -14,10 +14,10 @@ pub struct Plane {
 }
 
 impl Plane {
-    pub fn read<T: io::Read>(reader: &mut T) -> Plane {
-        let normal = read_vec3(reader).normalize();
-        let w = read_single(reader) / SCALE_FACTOR;
+    pub fn read<T: io::Read>(reader: &mut T) -> Result<Plane> {
+        let normal = read_vec3(reader)?.normalize();
+        let w = read_single(reader)? / SCALE_FACTOR;
 
-        Plane { normal, w }
+        Ok(Plane { normal, w })
     }
 }
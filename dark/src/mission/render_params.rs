@@ -1,5 +1,6 @@
-use crate::ss2_chunk_file_reader::ChunkFileTableOfContents;
+use crate::ss2_chunk_file_reader::{with_chunk, ChunkFileTableOfContents};
 use crate::ss2_common::read_single;
+use crate::Result;
 use cgmath::{vec3, Vector3};
 
 use std::f32;
@@ -17,17 +18,17 @@ impl RenderParams {
     pub fn read<T: io::Read + io::Seek>(
         table_of_contents: &ChunkFileTableOfContents,
         reader: &mut T,
-    ) -> RenderParams {
-        let chunk = table_of_contents
-            .get_chunk("RENDPARAMS".to_string())
-            .unwrap();
-        reader.seek(SeekFrom::Start(chunk.offset)).unwrap();
+    ) -> Result<RenderParams> {
+        let chunk = table_of_contents.require_chunk("RENDPARAMS")?;
+        with_chunk(reader, "RENDPARAMS", |reader| {
+            reader.seek(SeekFrom::Start(chunk.offset))?;
 
-        let _palette = read_string_with_size(reader, 16);
-        let ambient = read_single(reader);
+            let _palette = read_string_with_size(reader, 16)?;
+            let ambient = read_single(reader)?;
 
-        RenderParams {
-            ambient_color: vec3(ambient, ambient, ambient),
-        }
+            Ok(RenderParams {
+                ambient_color: vec3(ambient, ambient, ambient),
+            })
+        })
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     ss2_common::{read_i16, read_i32, read_plane, read_single, read_u32, read_vec3},
-    SCALE_FACTOR,
+    Result, SCALE_FACTOR,
 };
 
 use cgmath::{Point3, Vector3};
@@ -20,16 +20,16 @@ pub struct Room {
 
 impl Room {
     // Read the ROOM_DB chunk to get a list of rooms
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T) -> Room {
-        let obj_id = read_i32(reader);
-        let room_id = read_i16(reader);
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T) -> Result<Room> {
+        let obj_id = read_i32(reader)?;
+        let room_id = read_i16(reader)?;
 
-        let center = read_vec3(reader) / SCALE_FACTOR;
+        let center = read_vec3(reader)? / SCALE_FACTOR;
 
         let mut planes = Vec::new();
 
         for _ in 0..6 {
-            let plane = read_plane(reader);
+            let plane = read_plane(reader)?;
 
             planes.push(Plane {
                 n: plane.n,
@@ -37,13 +37,13 @@ impl Room {
             })
         }
 
-        let portal_count = read_u32(reader);
+        let portal_count = read_u32(reader)?;
 
         // TODO: https://github.com/Kernvirus/SystemShock2VR/blob/5f0f7d054e79c2e36d9661f4ca62ab95ae69de0b/Assets/Scripts/Editor/DarkEngine/Rooms/Room.cs
 
         let mut portals = Vec::new();
         for _ in 0..portal_count {
-            portals.push(RoomPortal::read(reader));
+            portals.push(RoomPortal::read(reader)?);
         }
 
         let bounding_box = bounding_box_from_planes(&planes);
@@ -52,27 +52,27 @@ impl Room {
 
         let mut portal_distances = Vec::new();
         for _ in 0..portal_distance_count {
-            portal_distances.push(read_single(reader));
+            portal_distances.push(read_single(reader)?);
         }
 
-        let num_lists = read_u32(reader);
+        let num_lists = read_u32(reader)?;
 
         for _ in 0..num_lists {
-            let count = read_u32(reader);
+            let count = read_u32(reader)?;
 
             for _0 in 0..count {
-                let _id = read_i32(reader);
+                let _id = read_i32(reader)?;
             }
         }
 
-        Room {
+        Ok(Room {
             obj_id,
             room_id,
             center,
             planes,
             portals,
             bounding_box,
-        }
+        })
     }
 }
 
@@ -88,24 +88,24 @@ pub struct RoomPortal {
 }
 
 impl RoomPortal {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T) -> RoomPortal {
-        let id = read_i32(reader);
-        let index = read_u32(reader);
-        let plane = read_plane(reader);
-        let edge_count = read_u32(reader);
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T) -> Result<RoomPortal> {
+        let id = read_i32(reader)?;
+        let index = read_u32(reader)?;
+        let plane = read_plane(reader)?;
+        let edge_count = read_u32(reader)?;
 
         //let mut _edges = Vec::new();
         for _ in 0..edge_count {
             //   edges.push(read_plane(reader))
-            let _0 = read_plane(reader);
+            let _0 = read_plane(reader)?;
         }
 
-        let src_room = read_i32(reader);
-        let dest_room = read_i32(reader);
+        let src_room = read_i32(reader)?;
+        let dest_room = read_i32(reader)?;
 
-        let center = read_vec3(reader);
-        let dest_portal = read_i32(reader);
-        RoomPortal {
+        let center = read_vec3(reader)?;
+        let dest_portal = read_i32(reader)?;
+        Ok(RoomPortal {
             id,
             index,
             plane,
@@ -113,7 +113,7 @@ impl RoomPortal {
             dest_room,
             center,
             dest_portal,
-        }
+        })
     }
 }
 
@@ -1,10 +1,10 @@
 use crate::{
     mission::room::Room,
-    ss2_chunk_file_reader::ChunkFileTableOfContents,
+    ss2_chunk_file_reader::{with_chunk, ChunkFileTableOfContents},
+    Error, Result,
 };
 use byteorder::ReadBytesExt;
 
-
 use tracing::trace;
 
 use std::io;
@@ -20,24 +20,25 @@ impl RoomDatabase {
     pub fn read<T: io::Read + io::Seek>(
         table_of_contents: &ChunkFileTableOfContents,
         reader: &mut T,
-    ) -> RoomDatabase {
+    ) -> Result<RoomDatabase> {
         let mut rooms = Vec::new();
-        let txlist = table_of_contents
-            .get_chunk("ROOM_DB".to_string())
-            .unwrap()
-            .offset;
-        reader.seek(SeekFrom::Start(txlist)).unwrap();
+        let txlist = table_of_contents.require_chunk("ROOM_DB")?.offset;
+        with_chunk(reader, "ROOM_DB", |reader| {
+            reader.seek(SeekFrom::Start(txlist))?;
 
-        let _unk = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-        assert!(_unk != 0);
+            let _unk = reader.read_u32::<byteorder::LittleEndian>()?;
+            if _unk == 0 {
+                return Err(Error::invalid_data("unexpected room database header"));
+            }
 
-        let count = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-        trace!("room db - count: {}", count);
+            let count = reader.read_u32::<byteorder::LittleEndian>()?;
+            trace!("room db - count: {}", count);
 
-        for _ in 0..count {
-            rooms.push(Room::read(reader));
-        }
+            for _ in 0..count {
+                rooms.push(Room::read(reader)?);
+            }
 
-        RoomDatabase { rooms }
+            Ok(RoomDatabase { rooms })
+        })
     }
 }
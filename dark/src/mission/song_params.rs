@@ -1,6 +1,5 @@
-use crate::ss2_chunk_file_reader::ChunkFileTableOfContents;
-
-
+use crate::ss2_chunk_file_reader::{with_chunk, ChunkFileTableOfContents};
+use crate::Result;
 
 use std::io;
 use std::io::SeekFrom;
@@ -16,14 +15,14 @@ impl SongParams {
     pub fn read<T: io::Read + io::Seek>(
         table_of_contents: &ChunkFileTableOfContents,
         reader: &mut T,
-    ) -> SongParams {
-        let chunk = table_of_contents
-            .get_chunk("SONGPARAMS".to_string())
-            .unwrap();
-        reader.seek(SeekFrom::Start(chunk.offset)).unwrap();
+    ) -> Result<SongParams> {
+        let chunk = table_of_contents.require_chunk("SONGPARAMS")?;
+        with_chunk(reader, "SONGPARAMS", |reader| {
+            reader.seek(SeekFrom::Start(chunk.offset))?;
 
-        let song = read_string_with_size(reader, 32);
+            let song = read_string_with_size(reader, 32)?;
 
-        SongParams { song }
+            Ok(SongParams { song })
+        })
     }
 }
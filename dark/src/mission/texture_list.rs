@@ -1,7 +1,7 @@
 use crate::properties::{AnimTexFlags, PropAnimTex, PropRenderType, RenderType};
-use crate::ss2_chunk_file_reader::ChunkFileTableOfContents;
+use crate::ss2_chunk_file_reader::{with_chunk, ChunkFileTableOfContents};
 use crate::ss2_entity_info::{self, SystemShock2EntityInfo};
-use crate::Gamesys;
+use crate::{Gamesys, Result};
 use byteorder::ReadBytesExt;
 use shipyard::{Get, View, World};
 use tracing::{info, warn};
@@ -36,7 +36,7 @@ impl TextureList {
         entity_info: &SystemShock2EntityInfo,
         obj_texture_families: Vec<(String, i32)>,
         reader: &mut T,
-    ) -> TextureList {
+    ) -> Result<TextureList> {
         // First, let's prepare by reading the texture archetypes from the world definition
         // This give us information that is stored in archetypes, like Render Type (ie, RenderType 2 is FullBright/Unlit)
         let name_to_info = read_texture_archetypes(obj_texture_families, entity_info, gamesys);
@@ -50,58 +50,57 @@ fn read_txlist_chunk<T: io::Read + io::Seek>(
     table_of_contents: &ChunkFileTableOfContents,
     reader: &mut T,
     name_to_info: HashMap<String, (RenderType, Option<TextureAnimationInfo>)>,
-) -> TextureList {
-    let txlist = table_of_contents
-        .get_chunk("TXLIST".to_string())
-        .unwrap()
-        .offset;
-    reader.seek(SeekFrom::Start(txlist)).unwrap();
-
-    let _txt_length = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-    let txt_count = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-    let fam_count = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-
-    let mut texture_fams = Vec::new();
-    let mut textures = Vec::new();
-
-    // Texture families are top-level folders in the res/fam zip file,
-    // and share the same palette (which is of no consequence here...)
-    for _ in 0..fam_count {
-        let fam = read_string_with_size(reader, 16);
-        texture_fams.push(fam);
-    }
-
-    for _ in 0..txt_count {
-        let _one = reader.read_u8().unwrap();
-        let fam = reader.read_u8().unwrap();
-        let _zero = reader.read_u16::<byteorder::LittleEndian>().unwrap();
-        let name = read_string_with_size(reader, 16);
-
-        let mut family = "".to_owned();
-        if fam > 0 && fam <= (texture_fams.len() as u8) {
-            family = texture_fams[(fam - 1) as usize].to_owned();
+) -> Result<TextureList> {
+    let txlist = table_of_contents.require_chunk("TXLIST")?.offset;
+    with_chunk(reader, "TXLIST", |reader| {
+        reader.seek(SeekFrom::Start(txlist))?;
+
+        let _txt_length = reader.read_u32::<byteorder::LittleEndian>()?;
+        let txt_count = reader.read_u32::<byteorder::LittleEndian>()?;
+        let fam_count = reader.read_u32::<byteorder::LittleEndian>()?;
+
+        let mut texture_fams = Vec::new();
+        let mut textures = Vec::new();
+
+        // Texture families are top-level folders in the res/fam zip file,
+        // and share the same palette (which is of no consequence here...)
+        for _ in 0..fam_count {
+            let fam = read_string_with_size(reader, 16)?;
+            texture_fams.push(fam);
         }
 
-        let entity_name = format!("t_fam/{}/{}", family, name);
+        for _ in 0..txt_count {
+            let _one = reader.read_u8()?;
+            let fam = reader.read_u8()?;
+            let _zero = reader.read_u16::<byteorder::LittleEndian>()?;
+            let name = read_string_with_size(reader, 16)?;
 
-        let (render_type, maybe_animation_info) = {
-            if let Some(info) = name_to_info.get(&entity_name) {
-                info!("texture info for: {} is {:?}", entity_name, info);
-                (info.0.clone(), info.1.clone())
-            } else {
-                warn!("no texture info for: {}", entity_name);
-                (RenderType::Normal, None)
+            let mut family = "".to_owned();
+            if fam > 0 && fam <= (texture_fams.len() as u8) {
+                family = texture_fams[(fam - 1) as usize].to_owned();
             }
-        };
-
-        textures.push(SystemShock2Texture {
-            family,
-            texture_filename: name,
-            render_type,
-            animation_info: maybe_animation_info,
-        })
-    }
-    TextureList(textures)
+
+            let entity_name = format!("t_fam/{}/{}", family, name);
+
+            let (render_type, maybe_animation_info) = {
+                if let Some(info) = name_to_info.get(&entity_name) {
+                    info!("texture info for: {} is {:?}", entity_name, info);
+                    (info.0.clone(), info.1.clone())
+                } else {
+                    warn!("no texture info for: {}", entity_name);
+                    (RenderType::Normal, None)
+                }
+            };
+
+            textures.push(SystemShock2Texture {
+                family,
+                texture_filename: name,
+                render_type,
+                animation_info: maybe_animation_info,
+            })
+        }
+        Ok(TextureList(textures))
+    })
 }
 
 fn read_texture_archetypes(
@@ -11,15 +11,15 @@ pub use motion_clip::*;
 pub use motion_info::*;
 pub use motion_query::*;
 pub use motion_schema::*;
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
 use crate::{
-    ss2_chunk_file_reader,
+    ss2_chunk_file_reader::{self, with_chunk},
     ss2_common::{
         self, read_bool, read_bytes, read_i32, read_single, read_string_with_size, read_u32,
         read_u8,
     },
-    NameMap, TagDatabase, SCALE_FACTOR,
+    Error, NameMap, Result, TagDatabase, SCALE_FACTOR,
 };
 use cgmath::{Deg, Transform3, Vector3};
 
@@ -62,118 +62,115 @@ pub struct MotionStuff {
 }
 
 impl MotionDB {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T) -> MotionDB {
-        let table_of_contents = ss2_chunk_file_reader::read_table_of_contents(reader);
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T) -> Result<MotionDB> {
+        let table_of_contents = ss2_chunk_file_reader::read_table_of_contents(reader)?;
 
-        let mot_chunk = table_of_contents.get_chunk("MotDBase".to_owned()).unwrap();
+        let mot_chunk = table_of_contents.require_chunk("MotDBase")?;
+        with_chunk(reader, "MotDBase", |reader| {
+            reader.seek(io::SeekFrom::Start(mot_chunk.offset))?;
 
-        reader.seek(io::SeekFrom::Start(mot_chunk.offset));
+            // Load Namemap
+            let (animation_name_to_index, index_to_animation_name) = load_name_map(reader)?;
 
-        // Load Namemap
-        let (animation_name_to_index, index_to_animation_name) = load_name_map(reader);
+            // Read motstuff
+            let motstuff_size = ss2_common::read_u32(reader)?;
+            trace!("motstuff_size: {motstuff_size}");
 
-        // Read motstuff
-        let motstuff_size = ss2_common::read_u32(reader);
-        trace!("motstuff_size: {motstuff_size}");
-
-        let mut motion_stuffs = Vec::new();
-        for i in 0..motstuff_size {
-            let motstuff = read_motion_stuff(reader);
-            motion_stuffs.push(motstuff);
-        }
+            let mut motion_stuffs = Vec::new();
+            for i in 0..motstuff_size {
+                let motstuff = read_motion_stuff(reader)?;
+                motion_stuffs.push(motstuff);
+            }
 
-        let mps_motion_size = ss2_common::read_u32(reader);
+            let mps_motion_size = ss2_common::read_u32(reader)?;
 
-        trace!("all sizes. motstuff_size: {motstuff_size} mps_motion_size: {mps_motion_size}");
+            trace!("all sizes. motstuff_size: {motstuff_size} mps_motion_size: {mps_motion_size}");
 
-        // Read mps motions
-        let mut mps_motions = Vec::new();
-        for _i in 0..mps_motion_size {
-            let mps_motion = read_mps_motion(reader);
-            mps_motions.push(mps_motion);
-        }
+            // Read mps motions
+            let mut mps_motions = Vec::new();
+            for _i in 0..mps_motion_size {
+                let mps_motion = read_mps_motion(reader)?;
+                mps_motions.push(mps_motion);
+            }
 
-        // Load tags
-        let _name_map = NameMap::read(reader);
-        let name_map = NameMap::read(reader);
-        //let (tag_map, key_to_tag_name) = load_name_map(reader);
-        //trace!("animation name map: {:#?}", animation_name_to_index);
-        //trace!("name map: {:#?}", name_map);
-        //trace!("tag map: {:#?}", tag_map);
+            // Load tags
+            let _name_map = NameMap::read(reader)?;
+            let name_map = NameMap::read(reader)?;
+            //let (tag_map, key_to_tag_name) = load_name_map(reader)?;
+            //trace!("animation name map: {:#?}", animation_name_to_index);
+            //trace!("name map: {:#?}", name_map);
+            //trace!("tag map: {:#?}", tag_map);
 
-        let num_actors = read_u32(reader);
-        trace!("num actors: {}", num_actors);
-        let num_tag_sets = read_u32(reader);
+            let num_actors = read_u32(reader)?;
+            trace!("num actors: {}", num_actors);
+            let num_tag_sets = read_u32(reader)?;
 
-        trace!("num_tag_sets: {num_tag_sets}");
+            trace!("num_tag_sets: {num_tag_sets}");
 
-        for _ in 0..num_tag_sets {
-            let _is_mandatory = read_bool(reader);
-            let _weight = read_single(reader);
-        }
+            for _ in 0..num_tag_sets {
+                let _is_mandatory = read_bool(reader)?;
+                let _weight = read_single(reader)?;
+            }
 
-        // // load tag databases
-        let n_cat = read_u32(reader);
-        trace!("ncat: {n_cat}");
+            // // load tag databases
+            let n_cat = read_u32(reader)?;
+            trace!("ncat: {n_cat}");
 
-        let mut tag_databases = Vec::new();
-        for _i in 0..n_cat {
-            let tag_database = TagDatabase::read(reader);
-            tag_databases.push(tag_database);
-        }
+            let mut tag_databases = Vec::new();
+            for _i in 0..n_cat {
+                let tag_database = TagDatabase::read(reader)?;
+                tag_databases.push(tag_database);
+            }
 
-        let mut tag_value_to_animation_name = HashMap::new();
-        let mut tag_value_to_animations = HashMap::new();
-        let mut tag_value_to_motion_schema = HashMap::new();
-        let schemas = read_u32(reader);
-        trace!("motion_schema_count: {schemas}");
-        for i in 0..schemas {
-            let schema = MotionSchema::read(reader);
-            let animations = schema
-                .motion_index_list
-                .iter()
-                .map(|id| {
-                    let str = index_to_animation_name.get(id).unwrap();
-                    str.to_owned()
-                })
-                .collect::<Vec<String>>();
-
-            let summary_str = schema
-                .motion_index_list
-                .iter()
-                .map(|id| {
-                    let str = index_to_animation_name.get(id).unwrap();
-                    str.to_owned()
-                })
-                .collect::<Vec<String>>()
-                .join(", ");
-
-            tag_value_to_animation_name.insert(i as i32, summary_str);
-            tag_value_to_motion_schema.insert(i as i32, schema);
-            tag_value_to_animations.insert(i as i32, animations);
-        }
+            let mut tag_value_to_animation_name = HashMap::new();
+            let mut tag_value_to_animations = HashMap::new();
+            let mut tag_value_to_motion_schema = HashMap::new();
+            let schemas = read_u32(reader)?;
+            trace!("motion_schema_count: {schemas}");
+            for i in 0..schemas {
+                let schema = MotionSchema::read(reader)?;
+                let animations = schema
+                    .motion_index_list
+                    .iter()
+                    .map(|id| {
+                        index_to_animation_name.get(id).cloned().ok_or_else(|| {
+                            Error::invalid_data(format!(
+                                "motion schema {} uses unknown motion {}",
+                                i, id
+                            ))
+                        })
+                    })
+                    .collect::<Result<Vec<String>>>()?;
+
+                let summary_str = animations.join(", ");
+
+                tag_value_to_animation_name.insert(i as i32, summary_str);
+                tag_value_to_motion_schema.insert(i as i32, schema);
+                tag_value_to_animations.insert(i as i32, animations);
+            }
 
-        // GENERATION: Output 'friendly' version of animation db
-        // tag_databases[0].debug_print(
-        //     &key_to_tag_name,
-        //     &tag_value_to_animation_name,
-        //     &HashMap::new(), /* no enum values */
-        // );
-
-        MotionDB {
-            animation_name_to_index,
-            mps_motions,
-            motion_stuffs,
-            tag_databases,
-            tag_name_map: name_map,
-            tag_value_to_animations,
-        }
+            // GENERATION: Output 'friendly' version of animation db
+            // tag_databases[0].debug_print(
+            //     &key_to_tag_name,
+            //     &tag_value_to_animation_name,
+            //     &HashMap::new(), /* no enum values */
+            // );
+
+            Ok(MotionDB {
+                animation_name_to_index,
+                mps_motions,
+                motion_stuffs,
+                tag_databases,
+                tag_name_map: name_map,
+                tag_value_to_animations,
+            })
+        })
     }
     ///
     /// query the motion database
     ///
     /// Returns a string containing the name of the animation
-    pub fn query(&self, query: MotionQuery) -> Option<String> {
+    pub fn query(&self, query: MotionQuery, rng: &mut impl Rng) -> Option<String> {
         info!("motion_query: {:?}", query);
         let creature_type = query.creature_type;
 
@@ -205,7 +202,6 @@ impl MotionDB {
 
         match query.selection_strategy {
             MotionQuerySelectionStrategy::Random => {
-                let mut rng = thread_rng();
                 let idx = rng.gen_range(0..options.len());
 
                 let opt = options[idx];
@@ -223,25 +219,25 @@ impl MotionDB {
 
 fn load_name_map<T: io::Read + io::Seek>(
     reader: &mut T,
-) -> (HashMap<String, u32>, HashMap<u32, String>) {
-    let _upper_bound = ss2_common::read_i32(reader);
-    let _lower_bound = ss2_common::read_i32(reader);
-    let size = ss2_common::read_u32(reader);
+) -> Result<(HashMap<String, u32>, HashMap<u32, String>)> {
+    let _upper_bound = ss2_common::read_i32(reader)?;
+    let _lower_bound = ss2_common::read_i32(reader)?;
+    let size = ss2_common::read_u32(reader)?;
 
     let mut animation_name_to_index = HashMap::new();
     let mut index_to_animation_name = HashMap::new();
 
     // TODO: What is the name map used for?
     for i in 0..size {
-        let char = ss2_common::read_char(reader);
+        let char = ss2_common::read_char(reader)?;
 
         if char == '+' {
-            let name = ss2_common::read_string_with_size(reader, 16);
+            let name = ss2_common::read_string_with_size(reader, 16)?;
             animation_name_to_index.insert(name.to_ascii_lowercase().to_owned(), i);
             index_to_animation_name.insert(i, name.to_ascii_lowercase().to_owned());
         }
     }
-    (animation_name_to_index, index_to_animation_name)
+    Ok((animation_name_to_index, index_to_animation_name))
 }
 
 pub type JointId = u32;
@@ -303,27 +299,27 @@ impl MpsMotion {
     }
 }
 
-fn read_mps_motion<T: io::Read + io::Seek>(reader: &mut T) -> MpsMotion {
+fn read_mps_motion<T: io::Read + io::Seek>(reader: &mut T) -> Result<MpsMotion> {
     // Motion Info
-    let motion_type = read_u32(reader);
-    let sig = read_u32(reader);
-    let frame_count = read_single(reader);
-    let frame_rate = read_i32(reader);
-    let mot_num = read_i32(reader);
-    let name = read_string_with_size(reader, 12);
-    let _app_type = read_u8(reader);
-    let _app_data = read_bytes(reader, 63);
-
-    let num_components = read_i32(reader);
-    let _unk1 = read_i32(reader);
-    let num_flags = read_i32(reader);
-    let _unk2 = read_i32(reader);
+    let motion_type = read_u32(reader)?;
+    let sig = read_u32(reader)?;
+    let frame_count = read_single(reader)?;
+    let frame_rate = read_i32(reader)?;
+    let mot_num = read_i32(reader)?;
+    let name = read_string_with_size(reader, 12)?;
+    let _app_type = read_u8(reader)?;
+    let _app_data = read_bytes(reader, 63)?;
+
+    let num_components = read_i32(reader)?;
+    let _unk1 = read_i32(reader)?;
+    let num_flags = read_i32(reader)?;
+    let _unk2 = read_i32(reader)?;
 
     let mut motion_components = Vec::new();
     for _i in 0..num_components {
-        let motion_type = read_i32(reader);
-        let joint_id = read_u32(reader) as JointId;
-        let handle = read_u32(reader);
+        let motion_type = read_i32(reader)?;
+        let joint_id = read_u32(reader)? as JointId;
+        let handle = read_u32(reader)?;
         let motion_component = MotionComponent {
             motion_type,
             joint_id,
@@ -334,14 +330,15 @@ fn read_mps_motion<T: io::Read + io::Seek>(reader: &mut T) -> MpsMotion {
 
     let mut motion_flags = Vec::new();
     for _i in 0..num_flags {
-        let frame = read_u32(reader);
-        let flag_u32 = read_u32(reader);
-        let flags = MotionFlags::from_bits(flag_u32).unwrap();
+        let frame = read_u32(reader)?;
+        let flag_u32 = read_u32(reader)?;
+        let flags = MotionFlags::from_bits(flag_u32)
+            .ok_or_else(|| Error::invalid_data(format!("unknown MotionFlags: {}", flag_u32)))?;
         let motion_flag = FrameFlags { frame, flags };
         motion_flags.push(motion_flag);
     }
 
-    MpsMotion {
+    Ok(MpsMotion {
         motion_type,
         motion_components,
         sig,
@@ -350,14 +347,14 @@ fn read_mps_motion<T: io::Read + io::Seek>(reader: &mut T) -> MpsMotion {
         mot_num,
         name,
         motion_flags,
-    }
+    })
 }
 
-pub fn read_motion_stuff<T: io::Read + io::Seek>(reader: &mut T) -> MotionStuff {
-    let flags = ss2_common::read_u32(reader);
-    let blend_length = ss2_common::read_u16(reader);
-    let end_direction = ss2_common::read_u16_angle(reader);
-    let translation = ss2_common::read_vec3(reader) / SCALE_FACTOR;
+pub fn read_motion_stuff<T: io::Read + io::Seek>(reader: &mut T) -> Result<MotionStuff> {
+    let flags = ss2_common::read_u32(reader)?;
+    let blend_length = ss2_common::read_u16(reader)?;
+    let end_direction = ss2_common::read_u16_angle(reader)?;
+    let translation = ss2_common::read_vec3(reader)? / SCALE_FACTOR;
 
     // Have to correct the transform - it seems the animation translation is in a different coordinate space
     // then the model?
@@ -366,13 +363,13 @@ pub fn read_motion_stuff<T: io::Read + io::Seek>(reader: &mut T) -> MotionStuff
     //     initial_translation.y,
     //     -initial_translation.x,
     // );
-    let duration = ss2_common::read_single(reader);
+    let duration = ss2_common::read_single(reader)?;
 
-    MotionStuff {
+    Ok(MotionStuff {
         flags,
         blend_length,
         end_direction,
         translation,
         duration,
-    }
+    })
 }
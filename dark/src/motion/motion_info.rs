@@ -1,6 +1,7 @@
 use std::io;
 
 use crate::ss2_common::{read_i32, read_single, read_string_with_size, read_u32};
+use crate::Result;
 
 #[derive(Debug)]
 pub struct MotionInfo {
@@ -13,20 +14,20 @@ pub struct MotionInfo {
 }
 
 impl MotionInfo {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T) -> MotionInfo {
-        let motion_type = read_u32(reader);
-        let sig = read_u32(reader);
-        let frame_count = read_single(reader);
-        let frame_rate = read_i32(reader);
-        let mot_num = read_i32(reader);
-        let name = read_string_with_size(reader, 12);
-        MotionInfo {
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T) -> Result<MotionInfo> {
+        let motion_type = read_u32(reader)?;
+        let sig = read_u32(reader)?;
+        let frame_count = read_single(reader)?;
+        let frame_rate = read_i32(reader)?;
+        let mot_num = read_i32(reader)?;
+        let name = read_string_with_size(reader, 12)?;
+        Ok(MotionInfo {
             motion_type,
             sig,
             frame_count,
             frame_rate,
             mot_num,
             name,
-        }
+        })
     }
 }
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, io};
 
-use crate::ss2_common;
+use crate::{ss2_common, Result};
 
 #[derive(Clone, Debug)]
 pub struct NameMap {
@@ -21,27 +21,27 @@ impl NameMap {
         self.name_to_index.len()
     }
 
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T) -> NameMap {
-        let _upper_bound = ss2_common::read_i32(reader);
-        let _lower_bound = ss2_common::read_i32(reader);
-        let size = ss2_common::read_u32(reader);
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T) -> Result<NameMap> {
+        let _upper_bound = ss2_common::read_i32(reader)?;
+        let _lower_bound = ss2_common::read_i32(reader)?;
+        let size = ss2_common::read_u32(reader)?;
 
         let mut name_to_index = HashMap::new();
         let mut index_to_name = HashMap::new();
 
         for i in 0..size {
-            let char = ss2_common::read_char(reader);
+            let char = ss2_common::read_char(reader)?;
 
             if char == '+' {
-                let name = ss2_common::read_string_with_size(reader, 16);
+                let name = ss2_common::read_string_with_size(reader, 16)?;
                 name_to_index.insert(name.to_ascii_lowercase().to_owned(), i);
                 index_to_name.insert(i, name.to_ascii_lowercase().to_owned());
             }
         }
 
-        NameMap {
+        Ok(NameMap {
             name_to_index,
             index_to_name,
-        }
+        })
     }
 }
@@ -8,6 +8,7 @@ mod prop_creature_pose;
 mod prop_frame_anim_config;
 mod prop_frame_anim_state;
 mod prop_frob_info;
+mod prop_gun_state;
 mod prop_hit_points;
 mod prop_key;
 mod prop_log;
@@ -33,6 +34,7 @@ pub use prop_creature_pose::*;
 pub use prop_frame_anim_config::*;
 pub use prop_frame_anim_state::*;
 pub use prop_frob_info::*;
+pub use prop_gun_state::*;
 pub use prop_hit_points::*;
 pub use prop_key::*;
 pub use prop_log::*;
@@ -62,10 +64,11 @@ use std::{
     time::Duration,
 };
 
-use crate::{ss2_common::*, ss2_entity_info::new, SCALE_FACTOR};
+use crate::{ss2_common::*, ss2_entity_info::new, Result, SCALE_FACTOR};
 use cgmath::{vec3, Deg, Point3, Quaternion, Rotation3, Vector3};
 use shipyard::{
-    Component, EntityId, Get, IntoIter, IntoWithId, TupleAddComponent, View, ViewMut, World,
+    Component, EntityId, Get, IntoIter, IntoWithId, TupleAddComponent, TupleRemove, View, ViewMut,
+    World,
 };
 
 // Properties
@@ -164,10 +167,10 @@ pub struct PropMotionActorTags {
 }
 
 impl PropMotionActorTags {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, len: u32) -> PropMotionActorTags {
-        let str = read_prop_string(reader, len);
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, len: u32) -> Result<PropMotionActorTags> {
+        let str = read_prop_string(reader, len)?;
         let tags = str.split(',').map(|s| s.trim().to_owned()).collect();
-        PropMotionActorTags { tags }
+        Ok(PropMotionActorTags { tags })
     }
 }
 
@@ -181,10 +184,13 @@ pub struct PropInventoryDimensions {
 }
 
 impl PropInventoryDimensions {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropInventoryDimensions {
-        let width = read_u32(reader);
-        let height = read_u32(reader);
-        PropInventoryDimensions { width, height }
+    pub fn read<T: io::Read + io::Seek>(
+        reader: &mut T,
+        _len: u32,
+    ) -> Result<PropInventoryDimensions> {
+        let width = read_u32(reader)?;
+        let height = read_u32(reader)?;
+        Ok(PropInventoryDimensions { width, height })
     }
 }
 
@@ -209,12 +215,28 @@ pub struct PropPhysState {
 pub struct PropLocked(pub bool);
 
 impl PropLocked {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropLocked {
-        let is_locked = read_bool(reader);
-        PropLocked(is_locked)
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropLocked> {
+        let is_locked = read_bool(reader)?;
+        Ok(PropLocked(is_locked))
     }
 }
 
+// Whether the object has been hacked - the gamesys doesn't set this, but it's saved with the
+// entity, so hacks stay in effect
+#[derive(Debug, Component, Clone, Serialize, Deserialize)]
+pub struct PropHacked(pub bool);
+
+impl PropHacked {
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropHacked> {
+        let is_hacked = read_bool(reader)?;
+        Ok(PropHacked(is_hacked))
+    }
+}
+
+// Number of items in a stack - ie, how many nanites are in a pile
+#[derive(Debug, Component, Clone, Serialize, Deserialize)]
+pub struct PropStackCount(pub u32);
+
 #[derive(Debug, Component, Clone, Serialize, Deserialize)]
 pub struct PropModelName(pub String);
 
@@ -270,6 +292,7 @@ pub struct ToTemplateLinkInfo {
 
 #[derive(Debug, Component, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Link {
+    AIPatrol,
     AIProjectile(AIProjectileOptions),
     AIRangedWeapon,
     AIWatchObj(AIWatchOptions),
@@ -286,6 +309,26 @@ pub enum Link {
     TPath(TPathData),
 }
 
+impl Link {
+    ///
+    /// from_name
+    ///
+    /// The link for a link flavor name, ie, from an AI scripted action. Only links without data can
+    /// be created this way.
+    pub fn from_name(name: &str) -> Option<Link> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "aipatrol" => Some(Link::AIPatrol),
+            "airangedweapon" => Some(Link::AIRangedWeapon),
+            "landingpoint" => Some(Link::LandingPoint),
+            "replicator" => Some(Link::Replicator),
+            "switchlink" => Some(Link::SwitchLink),
+            "tpathinit" => Some(Link::TPathInit),
+            "miss spang" => Some(Link::MissSpang),
+            _ => None,
+        }
+    }
+}
+
 #[derive(
     FromPrimitive,
     ToPrimitive,
@@ -320,32 +363,34 @@ pub struct AIProjectileOptions {
 }
 
 impl AIProjectileOptions {
-    pub fn read(reader: &mut Box<dyn ReadAndSeek>, _len: u32) -> AIProjectileOptions {
-        let _unk = read_u32(reader);
-        let _unk = read_u32(reader);
+    pub fn read(reader: &mut Box<dyn ReadAndSeek>, _len: u32) -> Result<AIProjectileOptions> {
+        let _unk = read_u32(reader)?;
+        let _unk = read_u32(reader)?;
 
-        let targeting_method_u32 = read_u32(reader);
-        let targeting_method = AITargetMethod::from_u32(targeting_method_u32).unwrap();
+        let targeting_method_u32 = read_u32(reader)?;
+        let targeting_method = AITargetMethod::from_u32(targeting_method_u32).ok_or_else(|| {
+            crate::Error::invalid_data(format!("unknown AITargetMethod: {}", targeting_method_u32))
+        })?;
 
-        let _unk = read_u32(reader);
-        let delay = read_single(reader);
-        let should_lead_target = read_bool(reader);
-        let ammo = read_u32(reader);
+        let _unk = read_u32(reader)?;
+        let delay = read_single(reader)?;
+        let should_lead_target = read_bool(reader)?;
+        let ammo = read_u32(reader)?;
 
-        let _unk = read_u32(reader);
-        let accuracy = read_u32(reader);
+        let _unk = read_u32(reader)?;
+        let accuracy = read_u32(reader)?;
 
-        let joint = read_u32(reader);
-        let vhot = read_u32(reader);
+        let joint = read_u32(reader)?;
+        let vhot = read_u32(reader)?;
 
-        let select_time = read_single(reader);
-        let _unk = read_u32(reader);
+        let select_time = read_single(reader)?;
+        let _unk = read_u32(reader)?;
 
         // let speed = read_single(reader) / SCALE_FACTOR;
         // let time = read_single(reader);
         // let limit = read_bool(reader);
         // let paused = read_u32(reader);
-        AIProjectileOptions {
+        Ok(AIProjectileOptions {
             targeting_method,
             delay,
             should_lead_target,
@@ -354,7 +399,7 @@ impl AIProjectileOptions {
             select_time,
             joint,
             vhot,
-        }
+        })
     }
 }
 
@@ -364,12 +409,12 @@ pub struct TPathData {
 }
 
 impl TPathData {
-    pub fn read(reader: &mut Box<dyn ReadAndSeek>, _len: u32) -> TPathData {
-        let speed = read_single(reader) / SCALE_FACTOR;
-        let _time = read_single(reader);
-        let _limit = read_bool(reader);
-        let _paused = read_u32(reader);
-        TPathData { speed }
+    pub fn read(reader: &mut Box<dyn ReadAndSeek>, _len: u32) -> Result<TPathData> {
+        let speed = read_single(reader)? / SCALE_FACTOR;
+        let _time = read_single(reader)?;
+        let _limit = read_bool(reader)?;
+        let _paused = read_u32(reader)?;
+        Ok(TPathData { speed })
     }
 }
 
@@ -380,10 +425,10 @@ pub struct ProjectileOptions {
 }
 
 impl ProjectileOptions {
-    pub fn read(reader: &mut Box<dyn ReadAndSeek>, _len: u32) -> ProjectileOptions {
-        let order = read_i32(reader);
-        let setting = read_i32(reader);
-        ProjectileOptions { order, setting }
+    pub fn read(reader: &mut Box<dyn ReadAndSeek>, _len: u32) -> Result<ProjectileOptions> {
+        let order = read_i32(reader)?;
+        let setting = read_i32(reader)?;
+        Ok(ProjectileOptions { order, setting })
     }
 }
 
@@ -393,9 +438,9 @@ pub struct CorpseOptions {
 }
 
 impl CorpseOptions {
-    pub fn read(reader: &mut Box<dyn ReadAndSeek>, _len: u32) -> CorpseOptions {
-        let propagate_scale = read_bool(reader);
-        CorpseOptions { propagate_scale }
+    pub fn read(reader: &mut Box<dyn ReadAndSeek>, _len: u32) -> Result<CorpseOptions> {
+        let propagate_scale = read_bool(reader)?;
+        Ok(CorpseOptions { propagate_scale })
     }
 }
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -405,10 +450,10 @@ pub struct GunFlashOptions {
 }
 
 impl GunFlashOptions {
-    pub fn read(reader: &mut Box<dyn ReadAndSeek>, _len: u32) -> GunFlashOptions {
-        let vhot = read_u32(reader);
-        let flags = read_u32(reader);
-        GunFlashOptions { vhot, flags }
+    pub fn read(reader: &mut Box<dyn ReadAndSeek>, _len: u32) -> Result<GunFlashOptions> {
+        let vhot = read_u32(reader)?;
+        let flags = read_u32(reader)?;
+        Ok(GunFlashOptions { vhot, flags })
     }
 }
 
@@ -421,17 +466,17 @@ pub struct FlinderizeOptions {
 }
 
 impl FlinderizeOptions {
-    pub fn read(reader: &mut Box<dyn ReadAndSeek>, _len: u32) -> FlinderizeOptions {
-        let count = read_u32(reader);
-        let impulse = read_single(reader);
-        let scatter = read_bool(reader);
-        let offset = read_vec3(reader) / SCALE_FACTOR;
-        FlinderizeOptions {
+    pub fn read(reader: &mut Box<dyn ReadAndSeek>, _len: u32) -> Result<FlinderizeOptions> {
+        let count = read_u32(reader)?;
+        let impulse = read_single(reader)?;
+        let scatter = read_bool(reader)?;
+        let offset = read_vec3(reader)? / SCALE_FACTOR;
+        Ok(FlinderizeOptions {
             count,
             impulse,
             scatter,
             offset,
-        }
+        })
     }
 }
 
@@ -443,35 +488,35 @@ pub struct AIWatchOptions {
 }
 
 impl AIWatchOptions {
-    pub fn read(reader: &mut Box<dyn ReadAndSeek>, _len: u32) -> AIWatchOptions {
-        let _unknown = read_bytes(reader, 60);
-
-        let _trigger = read_u32(reader);
-        let _awareness = read_u32(reader);
-        let _ai_watch_visibility = read_u32(reader);
-        let _unknown2 = read_i32(reader);
-        let _ai_watch_kill_condition = read_u32(reader);
-        let _kill_like_links = read_bool(reader);
-        let _once_only = read_bool(reader);
-        let _reuse_time = read_i32(reader);
-        let _reset_time = read_i32(reader);
-        let _min_alertness = read_u32(reader);
-        let _max_alertness = read_u32(reader);
-        let _ai_priority = read_u32(reader);
-        let radius = read_i32(reader) as f32 / SCALE_FACTOR;
-        let height = read_i32(reader) as f32 / SCALE_FACTOR;
+    pub fn read(reader: &mut Box<dyn ReadAndSeek>, _len: u32) -> Result<AIWatchOptions> {
+        let _unknown = read_bytes(reader, 60)?;
+
+        let _trigger = read_u32(reader)?;
+        let _awareness = read_u32(reader)?;
+        let _ai_watch_visibility = read_u32(reader)?;
+        let _unknown2 = read_i32(reader)?;
+        let _ai_watch_kill_condition = read_u32(reader)?;
+        let _kill_like_links = read_bool(reader)?;
+        let _once_only = read_bool(reader)?;
+        let _reuse_time = read_i32(reader)?;
+        let _reset_time = read_i32(reader)?;
+        let _min_alertness = read_u32(reader)?;
+        let _max_alertness = read_u32(reader)?;
+        let _ai_priority = read_u32(reader)?;
+        let radius = read_i32(reader)? as f32 / SCALE_FACTOR;
+        let height = read_i32(reader)? as f32 / SCALE_FACTOR;
 
         let mut scripted_actions = Vec::new();
         for _ in 0..8 {
-            let action = AIScriptedAction::read(reader);
+            let action = AIScriptedAction::read(reader)?;
             scripted_actions.push(action);
         }
 
-        AIWatchOptions {
+        Ok(AIWatchOptions {
             radius,
             height,
             scripted_actions,
-        }
+        })
     }
 }
 
@@ -623,6 +668,7 @@ pub fn get<R: io::Read + io::Seek + 'static>() -> (
 ) {
     // Links
     let links = vec![
+        define_link("L$AIPatrol", |_| Link::AIPatrol),
         define_link("L$AIRangedW", |_| Link::AIRangedWeapon),
         // TODO: Why is the data not available for some of these links?
         define_link("L$Corpse", |_| {
@@ -690,10 +736,31 @@ pub fn get<R: io::Read + io::Seek + 'static>() -> (
             accumulator::latest,
         ),
         define_prop(
+            "P$AI_Aggres",
+            AIRating::read,
+            PropAIAggression,
+            accumulator::latest,
+        ),
+        define_prop(
+            "P$AI_Defens",
+            |reader, _len| read_bool(reader),
+            PropAIDefensive,
+            accumulator::latest,
+        ),
+        define_prop("P$AI_Mode", PropAIMode::read, identity, accumulator::latest),
+        define_prop(
+            "P$AI_Patrol",
+            |reader, _len| read_bool(reader),
+            PropAIPatrol,
+            accumulator::latest,
+        ),
+        define_prop_with_migration(
             "P$AI_SigRsp",
             PropAISignalResponse::read,
             identity,
             accumulator::latest,
+            1,
+            Some(PropAISignalResponse::migrate),
         ),
         define_prop(
             "P$AmbientHa",
@@ -818,6 +885,13 @@ pub fn get<R: io::Read + io::Seek + 'static>() -> (
             accumulator::latest,
         ),
         define_prop("P$Locked", PropLocked::read, identity, accumulator::latest),
+        define_prop("P$Hacked", PropHacked::read, identity, accumulator::latest),
+        define_prop(
+            "P$StackCoun",
+            |reader, _len| read_u32(reader),
+            PropStackCount,
+            accumulator::latest,
+        ),
         define_prop(
             "P$Logs1",
             PropLog::read_deck1,
@@ -974,6 +1048,12 @@ pub fn get<R: io::Read + io::Seek + 'static>() -> (
             identity,
             accumulator::latest,
         ),
+        define_prop(
+            "P$GunState",
+            PropGunState::read,
+            identity,
+            accumulator::latest,
+        ),
         define_prop(
             "P$PGLaunchI",
             PropParticleLaunchInfo::read,
@@ -983,8 +1063,10 @@ pub fn get<R: io::Read + io::Seek + 'static>() -> (
         define_prop(
             "P$RenderTyp",
             |reader, _len| {
-                let val = read_u32(reader);
-                num_traits::FromPrimitive::from_u32(val).unwrap()
+                let val = read_u32(reader)?;
+                num_traits::FromPrimitive::from_u32(val).ok_or_else(|| {
+                    crate::Error::invalid_data(format!("unknown RenderType: {}", val))
+                })
             },
             PropRenderType,
             accumulator::latest,
@@ -1049,6 +1131,12 @@ pub fn get<R: io::Read + io::Seek + 'static>() -> (
             identity,
             accumulator::latest,
         ),
+        define_prop(
+            "P$RepHacked",
+            PropReplicatorContents::read,
+            PropReplicatorHackedContents,
+            accumulator::latest,
+        ),
         define_prop(
             "P$StartLoc",
             |reader, _len| read_i32(reader),
@@ -1151,15 +1239,15 @@ fn merge_ancestor_scripts(ancestor_scripts: PropScripts, new_scripts: PropScript
     ret
 }
 
-fn read_prop_scripts<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropScripts {
-    let script1 = read_string_with_size(reader, 32);
-    let script2 = read_string_with_size(reader, 32);
-    let script3 = read_string_with_size(reader, 32);
-    let script4 = read_string_with_size(reader, 32);
+fn read_prop_scripts<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropScripts> {
+    let script1 = read_string_with_size(reader, 32)?;
+    let script2 = read_string_with_size(reader, 32)?;
+    let script3 = read_string_with_size(reader, 32)?;
+    let script4 = read_string_with_size(reader, 32)?;
 
     // The prop is actually `dont_inherits` - so if it is false, that means to inherit.
     // Just removing the double negative
-    let inherits = read_u32(reader) == 0;
+    let inherits = read_u32(reader)? == 0;
 
     let ret = PropScripts {
         scripts: vec![script1, script2, script3, script4]
@@ -1170,37 +1258,37 @@ fn read_prop_scripts<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropS
         inherits,
     };
 
-    ret
+    Ok(ret)
 }
 
 fn read_prop_translating_door<T: io::Read + io::Seek>(
     reader: &mut T,
     _len: u32,
-) -> PropTranslatingDoor {
-    let door_type = read_i32(reader);
-    let closed = read_single(reader);
-    let open = read_single(reader);
-    let speed = read_single(reader) / SCALE_FACTOR;
-    let axis = read_i32(reader);
-    let _state = read_i32(reader);
-    let _hard_limits = read_bool(reader);
-    let _sound_blocking = read_single(reader);
-    let _vision_blocking = read_single(reader);
-    let _push_mass = read_single(reader);
-    let base_closed_location = read_vec3(reader) / SCALE_FACTOR;
-    let base_open_location = read_vec3(reader) / SCALE_FACTOR;
-    let base_location = read_vec3(reader) / SCALE_FACTOR;
-    let _base_angle = read_u16_vec3(reader);
-    let _base = read_single(reader);
-    let _room1 = read_i32(reader);
-    let _room2 = read_i32(reader);
+) -> Result<PropTranslatingDoor> {
+    let door_type = read_i32(reader)?;
+    let closed = read_single(reader)?;
+    let open = read_single(reader)?;
+    let speed = read_single(reader)? / SCALE_FACTOR;
+    let axis = read_i32(reader)?;
+    let _state = read_i32(reader)?;
+    let _hard_limits = read_bool(reader)?;
+    let _sound_blocking = read_single(reader)?;
+    let _vision_blocking = read_single(reader)?;
+    let _push_mass = read_single(reader)?;
+    let base_closed_location = read_vec3(reader)? / SCALE_FACTOR;
+    let base_open_location = read_vec3(reader)? / SCALE_FACTOR;
+    let base_location = read_vec3(reader)? / SCALE_FACTOR;
+    let _base_angle = read_u16_vec3(reader)?;
+    let _base = read_single(reader)?;
+    let _room1 = read_i32(reader)?;
+    let _room2 = read_i32(reader)?;
 
     let delta = _len - 94;
     if delta > 0 {
-        let _unk = read_bytes(reader, delta as usize);
+        let _unk = read_bytes(reader, delta as usize)?;
     }
 
-    PropTranslatingDoor {
+    Ok(PropTranslatingDoor {
         door_type,
         closed,
         open,
@@ -1209,22 +1297,22 @@ fn read_prop_translating_door<T: io::Read + io::Seek>(
         base_location,
         axis,
         speed,
-    }
+    })
 }
 
 fn read_prop_phys_dimensions<T: io::Read + io::Seek>(
     reader: &mut T,
     _len: u32,
-) -> PropPhysDimensions {
-    let radius0 = read_single(reader) / SCALE_FACTOR / 2.0;
-    let radius1 = read_single(reader) / SCALE_FACTOR / 2.0;
-    let offset0 = read_vec3(reader) / SCALE_FACTOR;
-    let offset1 = read_vec3(reader) / SCALE_FACTOR;
-    let size = read_vec3(reader) / SCALE_FACTOR;
-    let unk1 = read_u32(reader);
-    let unk2 = read_u32(reader);
-
-    PropPhysDimensions {
+) -> Result<PropPhysDimensions> {
+    let radius0 = read_single(reader)? / SCALE_FACTOR / 2.0;
+    let radius1 = read_single(reader)? / SCALE_FACTOR / 2.0;
+    let offset0 = read_vec3(reader)? / SCALE_FACTOR;
+    let offset1 = read_vec3(reader)? / SCALE_FACTOR;
+    let size = read_vec3(reader)? / SCALE_FACTOR;
+    let unk1 = read_u32(reader)?;
+    let unk2 = read_u32(reader)?;
+
+    Ok(PropPhysDimensions {
         radius0,
         radius1,
         offset0,
@@ -1232,37 +1320,40 @@ fn read_prop_phys_dimensions<T: io::Read + io::Seek>(
         size,
         unk1,
         unk2,
-    }
+    })
 }
 
-fn read_prop_phys_state<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropPhysState {
-    let position = read_vec3(reader) / SCALE_FACTOR;
-    let facing = read_vec3(reader);
-    let velocity = read_vec3(reader);
-    let rot_velocity = read_vec3(reader);
+fn read_prop_phys_state<T: io::Read + io::Seek>(
+    reader: &mut T,
+    _len: u32,
+) -> Result<PropPhysState> {
+    let position = read_vec3(reader)? / SCALE_FACTOR;
+    let facing = read_vec3(reader)?;
+    let velocity = read_vec3(reader)?;
+    let rot_velocity = read_vec3(reader)?;
 
     let rotation = quat_from_facing_vector(vec3(Deg(facing.x), Deg(facing.y), Deg(facing.z)));
 
-    PropPhysState {
+    Ok(PropPhysState {
         position,
         rotation,
         velocity,
         rot_velocity,
-    }
+    })
 }
 
-fn read_prop_position<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropPosition {
-    let position = read_vec3(reader) / SCALE_FACTOR;
-    let cell = read_u16(reader);
-    let _unknown = read_i16(reader);
-    let facing = read_u16_vec3(reader);
+fn read_prop_position<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropPosition> {
+    let position = read_vec3(reader)? / SCALE_FACTOR;
+    let cell = read_u16(reader)?;
+    let _unknown = read_i16(reader)?;
+    let facing = read_u16_vec3(reader)?;
     let rotation = quat_from_facing_vector(facing);
 
-    PropPosition {
+    Ok(PropPosition {
         position,
         cell,
         rotation,
-    }
+    })
 }
 
 /// Returns a quaternion from a vector based on euler angles
@@ -1272,12 +1363,15 @@ fn quat_from_facing_vector(facing: Vector3<Deg<f32>>) -> Quaternion<f32> {
         * Quaternion::from_angle_x(facing.x)
 }
 
-fn read_prop_string<T: io::Read + io::Seek>(reader: &mut T, prop_len: u32) -> String {
+fn read_prop_string<T: io::Read + io::Seek>(reader: &mut T, prop_len: u32) -> Result<String> {
     read_string_with_size(reader, prop_len as usize)
 }
 
-fn read_variable_length_string<T: io::Read + io::Seek>(reader: &mut T, prop_len: u32) -> String {
-    let _ignored = read_u32(reader);
+fn read_variable_length_string<T: io::Read + io::Seek>(
+    reader: &mut T,
+    prop_len: u32,
+) -> Result<String> {
+    let _ignored = read_u32(reader)?;
     read_string_with_size(reader, prop_len as usize - 4usize)
 }
 
@@ -1286,6 +1380,7 @@ impl<C> Property for C
 where
     C: Component
         + TupleAddComponent
+        + TupleRemove
         + fmt::Debug
         + std::marker::Sync
         + Clone
@@ -1295,6 +1390,15 @@ where
     fn initialize(&self, world: &mut World, entity: EntityId) {
         world.add_component(entity, self.clone());
     }
+
+    fn is_set(&self, world: &World, entity: EntityId) -> bool {
+        let view: View<C> = world.borrow().unwrap();
+        view.get(entity).is_ok()
+    }
+
+    fn remove(&self, world: &mut World, entity: EntityId) {
+        world.remove::<C>(entity);
+    }
 }
 
 #[derive(Debug)]
@@ -1307,6 +1411,7 @@ impl<C> Property for WrappedProperty<C>
 where
     C: Component
         + TupleAddComponent
+        + TupleRemove
         + fmt::Debug
         + std::marker::Sync
         + Clone
@@ -1330,22 +1435,51 @@ where
         drop(view);
         world.add_component(entity, value_to_set);
     }
+
+    fn is_set(&self, world: &World, entity: EntityId) -> bool {
+        let view: View<C> = world.borrow().unwrap();
+        view.get(entity).is_ok()
+    }
+
+    fn remove(&self, world: &mut World, entity: EntityId) {
+        world.remove::<C>(entity);
+    }
 }
 
 pub trait Property: fmt::Debug {
     fn initialize(&self, world: &mut World, entity: EntityId);
+
+    ///
+    /// is_set
+    ///
+    /// Whether the entity has a value for this property
+    fn is_set(&self, world: &World, entity: EntityId) -> bool;
+
+    ///
+    /// remove
+    ///
+    /// Remove this property from the entity, ie, when a metaproperty is taken away at runtime
+    fn remove(&self, world: &mut World, entity: EntityId);
 }
 
 pub trait PropertyDefinition<R: io::Read + io::Seek> {
     fn name(&self) -> String;
 
-    fn read(&self, reader: &mut R, prop_len: u32) -> Box<dyn Property>;
+    fn read(&self, reader: &mut R, prop_len: u32) -> Result<Box<dyn Property>>;
+
+    ///
+    /// version
+    ///
+    /// Version of the serialized form of this property. Bump this, and provide a migration, when the
+    /// component changes in a way that older save payloads can no longer be deserialized.
+    fn version(&self) -> u32;
 
     fn serialize(&self, world: &World) -> HashMap<u64, serde_json::Value>;
 
     fn deserialize(
         &self,
         val: &HashMap<u64, serde_json::Value>,
+        saved_version: u32,
         world: &mut World,
         entity_id_map: &HashMap<EntityId, EntityId>,
     );
@@ -1366,7 +1500,12 @@ pub trait LinkDefinitionWithData {
     fn link_chunk_name(&self) -> String;
     fn link_data_chunk_name(&self) -> String;
 
-    fn convert(&self, data: Vec<u8>, prop_len: u32, link: ToTemplateLinkInfo) -> ToTemplateLink;
+    fn convert(
+        &self,
+        data: Vec<u8>,
+        prop_len: u32,
+        link: ToTemplateLinkInfo,
+    ) -> Result<ToTemplateLink>;
 }
 
 struct LinkDefinitionWithDataStruct<TData> {
@@ -1393,14 +1532,14 @@ impl<TData> LinkDefinitionWithData for LinkDefinitionWithDataStruct<TData> {
         data: Vec<u8>,
         prop_len: u32,
         link_info: ToTemplateLinkInfo,
-    ) -> ToTemplateLink {
+    ) -> Result<ToTemplateLink> {
         let mut cursor: Box<dyn ReadAndSeek> = Box::new(Cursor::new(data));
-        let data = (self.reader)(&mut cursor, prop_len);
+        let data = (self.reader)(&mut cursor, prop_len)?;
         let link = (self.converter)(data);
-        ToTemplateLink {
+        Ok(ToTemplateLink {
             to_template_id: link_info.dest_template_id,
             link,
-        }
+        })
     }
 }
 
@@ -1417,7 +1556,7 @@ impl LinkDefinition for LinkDefinitionStruct {
     }
 }
 
-type Reader<R, ROutput> = fn(&mut R, u32) -> ROutput;
+type Reader<R, ROutput> = fn(&mut R, u32) -> Result<ROutput>;
 
 type Converter<RIntermediate, ROutput> = fn(RIntermediate) -> ROutput;
 
@@ -1425,11 +1564,19 @@ type LinkDataConverter<TData> = fn(TData, ToTemplateLinkInfo) -> ToTemplateLink;
 
 type Accumulator<T> = fn(T, T) -> T;
 
+///
+/// PropertyMigration
+///
+/// Upgrades a serialized property payload, saved at the given version, to the current version
+pub type PropertyMigration = fn(u32, serde_json::Value) -> serde_json::Value;
+
 struct PropertyDefinitionStruct<R: io::Read + io::Seek, RIntermediate, ROutput: Component> {
     name: String,
     reader: Reader<R, RIntermediate>,
     converter: Converter<RIntermediate, ROutput>,
     accumulator: Accumulator<ROutput>,
+    version: u32,
+    migration: Option<PropertyMigration>,
 }
 
 impl<R, RIntermediate, ROutput> PropertyDefinition<R>
@@ -1438,6 +1585,7 @@ where
     R: io::Read + io::Seek,
     ROutput: Component
         + TupleAddComponent
+        + TupleRemove
         + fmt::Debug
         + std::marker::Sync
         + Clone
@@ -1449,13 +1597,17 @@ where
         self.name.to_owned()
     }
 
-    fn read(&self, reader: &mut R, prop_len: u32) -> Box<dyn Property> {
-        let intermediate = (self.reader)(reader, prop_len);
+    fn read(&self, reader: &mut R, prop_len: u32) -> Result<Box<dyn Property>> {
+        let intermediate = (self.reader)(reader, prop_len)?;
         let output = (self.converter)(intermediate);
-        Box::new(WrappedProperty {
+        Ok(Box::new(WrappedProperty {
             inner_property: output,
             accumulator: self.accumulator,
-        })
+        }))
+    }
+
+    fn version(&self) -> u32 {
+        self.version
     }
 
     fn serialize(&self, world: &World) -> HashMap<u64, serde_json::Value> {
@@ -1471,14 +1623,31 @@ where
     fn deserialize(
         &self,
         map: &HashMap<u64, serde_json::Value>,
+        saved_version: u32,
         world: &mut World,
         entity_id_map: &HashMap<EntityId, EntityId>,
     ) {
         for (old_ent_id, json) in map {
-            if let Some(new_ent_id) = entity_id_map.get(&EntityId::from_inner(*old_ent_id).unwrap())
-            {
-                let prop: ROutput = serde_json::from_value(json.clone()).unwrap();
-                world.add_component(*new_ent_id, prop);
+            let maybe_new_ent_id = EntityId::from_inner(*old_ent_id)
+                .and_then(|old_ent_id| entity_id_map.get(&old_ent_id));
+            if let Some(new_ent_id) = maybe_new_ent_id {
+                let json = match self.migration {
+                    Some(migrate) if saved_version < self.version => {
+                        migrate(saved_version, json.clone())
+                    }
+                    _ => json.clone(),
+                };
+
+                match serde_json::from_value::<ROutput>(json) {
+                    Ok(prop) => world.add_component(*new_ent_id, prop),
+                    Err(err) => tracing::warn!(
+                        "Unable to deserialize property {} (saved at version {}) for entity {}: {}",
+                        self.name,
+                        saved_version,
+                        old_ent_id,
+                        err
+                    ),
+                }
             }
         }
     }
@@ -1493,12 +1662,34 @@ pub fn define_prop<
     reader: Reader<R, RIntermediate>,
     converter: Converter<RIntermediate, ROutput>,
     accumulator: Accumulator<ROutput>,
+) -> Box<dyn PropertyDefinition<R>> {
+    define_prop_with_migration(name, reader, converter, accumulator, 0, None)
+}
+
+///
+/// define_prop_with_migration
+///
+/// Like define_prop, but for properties whose serialized form has changed. Payloads saved with an
+/// older version are passed through the migration before being deserialized.
+pub fn define_prop_with_migration<
+    R: io::Read + io::Seek + 'static,
+    RIntermediate: 'static,
+    ROutput: 'static + fmt::Debug + Send + Sync + Clone + Component + Serialize + DeserializeOwned,
+>(
+    name: &str,
+    reader: Reader<R, RIntermediate>,
+    converter: Converter<RIntermediate, ROutput>,
+    accumulator: Accumulator<ROutput>,
+    version: u32,
+    migration: Option<PropertyMigration>,
 ) -> Box<dyn PropertyDefinition<R>> {
     Box::new(PropertyDefinitionStruct {
         name: name.to_string(),
         reader,
         converter,
         accumulator,
+        version,
+        migration,
     })
 }
 
@@ -6,12 +6,70 @@ use num_traits::FromPrimitive;
 use shipyard::Component;
 
 use crate::ss2_common::{read_bytes, read_i32, read_string_with_size, read_u32};
+use crate::{Error, Result};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Component, Clone, Deserialize, Serialize)]
 pub struct PropAI(pub String);
 
-#[derive(Debug, FromPrimitive, Clone, Serialize, Deserialize)]
+#[derive(Debug, FromPrimitive, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AIMode {
+    Asleep = 0,
+    SuperEfficient = 1,
+    Efficient = 2,
+    Normal = 3,
+    Combat = 4,
+    Dead = 5,
+}
+
+#[derive(Debug, Component, Clone, Deserialize, Serialize)]
+pub struct PropAIMode(pub AIMode);
+
+impl PropAIMode {
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropAIMode> {
+        let mode_u32 = read_u32(reader)?;
+        let mode = AIMode::from_u32(mode_u32)
+            .ok_or_else(|| Error::invalid_data(format!("unknown AIMode: {}", mode_u32)))?;
+        Ok(PropAIMode(mode))
+    }
+}
+
+// Whether the AI walks its patrol route (AIPatrol links between patrol points) when idle
+#[derive(Debug, Component, Clone, Deserialize, Serialize)]
+pub struct PropAIPatrol(pub bool);
+
+// The scale the AI ability settings are rated on
+#[derive(
+    Debug, FromPrimitive, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum AIRating {
+    Null = 0,
+    WellBelowAverage = 1,
+    BelowAverage = 2,
+    Average = 3,
+    AboveAverage = 4,
+    WellAboveAverage = 5,
+}
+
+impl AIRating {
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<AIRating> {
+        let rating_u32 = read_u32(reader)?;
+        AIRating::from_u32(rating_u32)
+            .ok_or_else(|| Error::invalid_data(format!("unknown AIRating: {}", rating_u32)))
+    }
+}
+
+// Ability setting for how readily the AI stands and fights
+#[derive(Debug, Component, Clone, Deserialize, Serialize)]
+pub struct PropAIAggression(pub AIRating);
+
+// Ability setting for AI that hold their post, rather than going to look into disturbances
+#[derive(Debug, Component, Clone, Deserialize, Serialize)]
+pub struct PropAIDefensive(pub bool);
+
+#[derive(
+    Debug, FromPrimitive, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
 pub enum AIPriority {
     None = 0,
     VeryLow = 1,
@@ -25,8 +83,11 @@ pub enum AIPriority {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AIScriptedActionType {
     Nothing,               // 0
-    ScriptMessage(String), // 1
-    Play(String),          // 2 - Sound or motion?
+    ScriptMessage(String), // 1 - Sent to the AI's own scripts
+    Play {
+        sound: String,  // Sound schema, or empty
+        motion: String, // Motion tags, or empty
+    }, // 2
     Alert,                 // 3
     BecomeHostile,         // 4
     EnableInvestigate,     // 5
@@ -57,7 +118,7 @@ pub enum AIScriptedActionType {
         entity_name: String,
         signal: String,
     }, // 14
-    DestScript,            // 15
+    DestScript(String),    // 15 - Script message sent to the destination of the last Goto
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -66,25 +127,32 @@ pub struct AIScriptedAction {
 }
 
 impl AIScriptedAction {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T) -> AIScriptedAction {
-        let action_type_u32 = read_u32(reader);
-        let sz0 = read_string_with_size(reader, 64);
-        let sz1 = read_string_with_size(reader, 64);
-        let sz2 = read_string_with_size(reader, 64);
-        let sz3 = read_string_with_size(reader, 64);
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T) -> Result<AIScriptedAction> {
+        let action_type_u32 = read_u32(reader)?;
+        let sz0 = read_string_with_size(reader, 64)?;
+        let sz1 = read_string_with_size(reader, 64)?;
+        let sz2 = read_string_with_size(reader, 64)?;
+        let sz3 = read_string_with_size(reader, 64)?;
 
         let action_type = match action_type_u32 {
             0 => AIScriptedActionType::Nothing,
             1 => AIScriptedActionType::ScriptMessage(sz0),
-            2 => AIScriptedActionType::Play(sz2),
+            2 => AIScriptedActionType::Play {
+                sound: sz0,
+                motion: sz2,
+            },
+            3 => AIScriptedActionType::Alert,
             4 => AIScriptedActionType::BecomeHostile,
+            5 => AIScriptedActionType::EnableInvestigate,
             6 => AIScriptedActionType::Goto {
                 waypoint_name: sz0,
                 speed: sz1,
             },
             7 => AIScriptedActionType::Frob(sz0),
             8 => {
-                let milliseconds = u64::from_str_radix(&sz0, 10).unwrap();
+                let milliseconds = u64::from_str_radix(&sz0, 10).map_err(|_| {
+                    Error::invalid_data(format!("invalid wait time in scripted action: {}", sz0))
+                })?;
                 AIScriptedActionType::Wait(Duration::from_millis(milliseconds))
             }
             9 => AIScriptedActionType::Mprint(sz0),
@@ -106,13 +174,37 @@ impl AIScriptedAction {
                 signal: sz0,
                 entity_name: sz1,
             },
-            _ => panic!(
-                "Unhandled action type: {} |{}|{}|{}|{}",
-                action_type_u32, &sz0, &sz1, &sz2, &sz3
-            ),
+            15 => AIScriptedActionType::DestScript(sz0),
+            _ => {
+                // Skip over it, rather than losing the rest of the sequence
+                tracing::warn!(
+                    "Unhandled action type: {} |{}|{}|{}|{}",
+                    action_type_u32,
+                    &sz0,
+                    &sz1,
+                    &sz2,
+                    &sz3
+                );
+                AIScriptedActionType::Nothing
+            }
         };
 
-        AIScriptedAction { action_type }
+        Ok(AIScriptedAction { action_type })
+    }
+}
+
+///
+/// migrate_play_actions
+///
+/// Play actions used to keep only their motion, serialized as `{"Play": motion}`. Rewrites those in
+/// a saved list of scripted actions to the current form.
+pub fn migrate_play_actions(actions: &mut serde_json::Value) {
+    for action in actions.as_array_mut().into_iter().flatten() {
+        if let Some(play) = action.pointer_mut("/action_type/Play") {
+            if let Some(motion) = play.as_str().map(str::to_owned) {
+                *play = serde_json::json!({ "sound": "", "motion": motion });
+            }
+        }
     }
 }
 
@@ -124,11 +216,12 @@ pub struct PropAISignalResponse {
 }
 
 impl PropAISignalResponse {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, len: u32) -> PropAISignalResponse {
-        let signal = read_string_with_size(reader, 32);
-        let priority_u32 = read_u32(reader);
-        let priority = AIPriority::from_u32(priority_u32).unwrap();
-        let _unk = read_bytes(reader, 16);
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, len: u32) -> Result<PropAISignalResponse> {
+        let signal = read_string_with_size(reader, 32)?;
+        let priority_u32 = read_u32(reader)?;
+        let priority = AIPriority::from_u32(priority_u32)
+            .ok_or_else(|| Error::invalid_data(format!("unknown AIPriority: {}", priority_u32)))?;
+        let _unk = read_bytes(reader, 16)?;
 
         // There can be a variable number of actions here, but we don't know how many (up to 16)
         // Use the length field to figure it out...
@@ -140,12 +233,167 @@ impl PropAISignalResponse {
 
         let mut actions = Vec::new();
         for _i in 0..num_actions {
-            actions.push(AIScriptedAction::read(reader));
+            actions.push(AIScriptedAction::read(reader)?);
         }
-        PropAISignalResponse {
+        Ok(PropAISignalResponse {
             signal,
             priority,
             actions,
+        })
+    }
+
+    ///
+    /// migrate
+    ///
+    /// Version 1 gave Play actions a sound
+    pub fn migrate(saved_version: u32, mut json: serde_json::Value) -> serde_json::Value {
+        if saved_version < 1 {
+            if let Some(actions) = json.get_mut("actions") {
+                migrate_play_actions(actions);
+            }
+        }
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use byteorder::{LittleEndian, WriteBytesExt};
+    use shipyard::{Get, View, World};
+    use std::{collections::HashMap, io::Cursor};
+
+    fn action_bytes(action_type: u32, args: [&str; 4]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.write_u32::<LittleEndian>(action_type).unwrap();
+        for arg in args {
+            let mut arg_bytes = arg.as_bytes().to_vec();
+            arg_bytes.resize(64, 0);
+            bytes.extend(arg_bytes);
+        }
+        bytes
+    }
+
+    fn read_action(action_type: u32, args: [&str; 4]) -> AIScriptedActionType {
+        let mut reader = Cursor::new(action_bytes(action_type, args));
+        AIScriptedAction::read(&mut reader).unwrap().action_type
+    }
+
+    #[test]
+    fn test_read_all_action_types() {
+        assert_eq!(
+            read_action(3, ["", "", "", ""]),
+            AIScriptedActionType::Alert
+        );
+        assert_eq!(
+            read_action(5, ["", "", "", ""]),
+            AIScriptedActionType::EnableInvestigate
+        );
+        assert_eq!(
+            read_action(15, ["TurnOn", "", "", ""]),
+            AIScriptedActionType::DestScript("TurnOn".to_owned())
+        );
+        assert_eq!(
+            read_action(2, ["hgrunt", "", "crumple", ""]),
+            AIScriptedActionType::Play {
+                sound: "hgrunt".to_owned(),
+                motion: "crumple".to_owned(),
+            }
+        );
+        assert_eq!(
+            read_action(8, ["1500", "", "", ""]),
+            AIScriptedActionType::Wait(Duration::from_millis(1500))
+        );
+    }
+
+    #[test]
+    fn test_signal_response_migration() {
+        let (properties, _, _) = crate::properties::get::<Cursor<Vec<u8>>>();
+        let definition = properties
+            .iter()
+            .find(|prop| prop.name() == "P$AI_SigRsp")
+            .unwrap();
+        assert_eq!(definition.version(), 1);
+
+        let mut world = World::new();
+        let entity_id = world.add_entity(());
+        let old_payload = serde_json::json!({
+            "signal": "Open",
+            "priority": "Normal",
+            "actions": [{ "action_type": { "Play": "crumple" } }],
+        });
+        definition.deserialize(
+            &HashMap::from([(entity_id.inner(), old_payload)]),
+            0,
+            &mut world,
+            &HashMap::from([(entity_id, entity_id)]),
+        );
+
+        let expected = AIScriptedActionType::Play {
+            sound: "".to_owned(),
+            motion: "crumple".to_owned(),
+        };
+        {
+            let v_signal_response = world.borrow::<View<PropAISignalResponse>>().unwrap();
+            let signal_response = v_signal_response.get(entity_id).unwrap();
+            assert_eq!(signal_response.actions[0].action_type, expected);
         }
+
+        // Saving again writes the current form, which is read back without a migration
+        let serialized = definition.serialize(&world);
+        let mut reloaded_world = World::new();
+        let reloaded_id = reloaded_world.add_entity(());
+        definition.deserialize(
+            &serialized,
+            definition.version(),
+            &mut reloaded_world,
+            &HashMap::from([(entity_id, reloaded_id)]),
+        );
+        let v_signal_response = reloaded_world
+            .borrow::<View<PropAISignalResponse>>()
+            .unwrap();
+        assert_eq!(
+            v_signal_response.get(reloaded_id).unwrap().actions[0].action_type,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_signal_response_migration_is_scoped() {
+        let old_payload = serde_json::json!({
+            "signal": "Play",
+            "actions": [{ "action_type": { "Play": "crumple" } }],
+            "other": { "Play": "untouched" },
+        });
+
+        let migrated = PropAISignalResponse::migrate(0, old_payload.clone());
+        assert_eq!(
+            migrated["actions"][0]["action_type"]["Play"],
+            serde_json::json!({ "sound": "", "motion": "crumple" })
+        );
+        assert_eq!(migrated["signal"], "Play");
+        assert_eq!(migrated["other"]["Play"], "untouched");
+
+        // Payloads already at version 1 are left alone
+        assert_eq!(
+            PropAISignalResponse::migrate(1, old_payload.clone()),
+            old_payload
+        );
+    }
+
+    #[test]
+    fn test_unknown_action_type_is_skipped() {
+        let mut bytes = action_bytes(42, ["", "", "", ""]);
+        bytes.extend(action_bytes(9, ["hello", "", "", ""]));
+
+        let mut reader = Cursor::new(bytes);
+        assert_eq!(
+            AIScriptedAction::read(&mut reader).unwrap().action_type,
+            AIScriptedActionType::Nothing
+        );
+        assert_eq!(
+            AIScriptedAction::read(&mut reader).unwrap().action_type,
+            AIScriptedActionType::Mprint("hello".to_owned())
+        );
     }
 }
@@ -5,6 +5,7 @@ use num_traits::ToPrimitive;
 use shipyard::Component;
 
 use crate::ss2_common::{read_i32, read_string_with_size, read_u32};
+use crate::{Error, Result};
 use serde::{Deserialize, Serialize};
 
 bitflags! {
@@ -35,18 +36,20 @@ pub struct PropAmbientHacked {
 }
 
 impl PropAmbientHacked {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropAmbientHacked {
-        let radius = read_i32(reader);
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropAmbientHacked> {
+        let radius = read_i32(reader)?;
         let radius_f32: f32 = radius.to_f32().unwrap();
         let radius_squared: f32 = radius_f32 * radius_f32;
-        let volume = read_i32(reader);
-        let sound_flag_bits = read_u32(reader);
-        let sound_flags = AmbientSoundFlags::from_bits(sound_flag_bits).unwrap();
-        let schema = read_string_with_size(reader, 16);
-        let aux1 = read_string_with_size(reader, 16);
-        let aux2 = read_string_with_size(reader, 16);
+        let volume = read_i32(reader)?;
+        let sound_flag_bits = read_u32(reader)?;
+        let sound_flags = AmbientSoundFlags::from_bits(sound_flag_bits).ok_or_else(|| {
+            Error::invalid_data(format!("unknown AmbientSoundFlags: {}", sound_flag_bits))
+        })?;
+        let schema = read_string_with_size(reader, 16)?;
+        let aux1 = read_string_with_size(reader, 16)?;
+        let aux2 = read_string_with_size(reader, 16)?;
 
-        PropAmbientHacked {
+        Ok(PropAmbientHacked {
             radius,
             radius_squared,
             volume,
@@ -54,6 +57,6 @@ impl PropAmbientHacked {
             schema,
             aux1,
             aux2,
-        }
+        })
     }
 }
@@ -5,7 +5,7 @@ use num_traits::FromPrimitive;
 use shipyard::Component;
 
 use crate::ss2_common::*;
-
+use crate::{Error, Result};
 
 use serde::{Deserialize, Serialize};
 
@@ -24,15 +24,17 @@ pub struct PropAnimTex {
 }
 
 impl PropAnimTex {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropAnimTex {
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> Result<PropAnimTex> {
         // TODO: Look at the rotate state in earth, see if it works?
-        let rate_in_milliseconds = read_u32(reader);
-        let anim_flags_bits = read_u32(reader);
+        let rate_in_milliseconds = read_u32(reader)?;
+        let anim_flags_bits = read_u32(reader)?;
 
-        let anim_flags = AnimTexFlags::from_u32(anim_flags_bits).unwrap();
-        PropAnimTex {
+        let anim_flags = AnimTexFlags::from_u32(anim_flags_bits).ok_or_else(|| {
+            Error::invalid_data(format!("unknown AnimTexFlags: {}", anim_flags_bits))
+        })?;
+        Ok(PropAnimTex {
             rate_in_milliseconds,
             anim_flags,
-        }
+        })
     }
 }
@@ -2,6 +2,7 @@ use shipyard::Component;
 use std::io;
 
 use crate::ss2_common::*;
+use crate::Result;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Component, Clone, Deserialize, Serialize)]
@@ -10,8 +11,8 @@ pub struct PropBitmapAnimation {
 }
 
 impl PropBitmapAnimation {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropBitmapAnimation {
-        let kill_on_completion = read_bool(reader);
-        PropBitmapAnimation { kill_on_completion }
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> Result<PropBitmapAnimation> {
+        let kill_on_completion = read_bool(reader)?;
+        Ok(PropBitmapAnimation { kill_on_completion })
     }
 }
@@ -3,6 +3,7 @@ use std::io;
 use shipyard::Component;
 
 use crate::ss2_common::read_u32;
+use crate::{Error, Result};
 
 use bitflags::bitflags;
 
@@ -26,9 +27,11 @@ pub struct PropCollisionType {
 }
 
 impl PropCollisionType {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropCollisionType {
-        let collision_type_u32 = read_u32(reader);
-        let collision_type = CollisionType::from_bits(collision_type_u32).unwrap();
-        PropCollisionType { collision_type }
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropCollisionType> {
+        let collision_type_u32 = read_u32(reader)?;
+        let collision_type = CollisionType::from_bits(collision_type_u32).ok_or_else(|| {
+            Error::invalid_data(format!("unknown CollisionType: {}", collision_type_u32))
+        })?;
+        Ok(PropCollisionType { collision_type })
     }
 }
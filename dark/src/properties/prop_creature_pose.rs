@@ -3,6 +3,7 @@ use std::io;
 use shipyard::Component;
 
 use crate::ss2_common::*;
+use crate::Result;
 use bitflags::bitflags;
 
 use serde::{Deserialize, Serialize};
@@ -27,18 +28,18 @@ pub struct PropCreaturePose {
 }
 
 impl PropCreaturePose {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropCreaturePose {
-        let pose_type_bits = read_u32(reader);
-        let motion_name = read_string_with_size(reader, 80);
-        let _unk = read_single(reader);
-        let scale = read_single(reader);
-        let ballistic = read_bool(reader);
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropCreaturePose> {
+        let pose_type_bits = read_u32(reader)?;
+        let motion_name = read_string_with_size(reader, 80)?;
+        let _unk = read_single(reader)?;
+        let scale = read_single(reader)?;
+        let ballistic = read_bool(reader)?;
 
-        PropCreaturePose {
+        Ok(PropCreaturePose {
             pose_type: PoseType::from_bits(pose_type_bits).unwrap_or(PoseType::Invalid),
             motion_or_tag_name: motion_name,
             scale,
             ballistic,
-        }
+        })
     }
 }
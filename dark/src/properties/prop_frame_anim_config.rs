@@ -2,6 +2,7 @@ use shipyard::Component;
 use std::io;
 
 use crate::ss2_common::*;
+use crate::Result;
 
 use serde::{Deserialize, Serialize};
 
@@ -15,18 +16,18 @@ pub struct PropFrameAnimConfig {
 }
 
 impl PropFrameAnimConfig {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropFrameAnimConfig {
-        let frames_per_second = read_single(reader);
-        let clamp = read_bool_u8(reader);
-        let bounce = read_bool_u8(reader);
-        let frame_limit = read_bool_u8(reader);
-        let unk = read_u8(reader);
-        PropFrameAnimConfig {
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> Result<PropFrameAnimConfig> {
+        let frames_per_second = read_single(reader)?;
+        let clamp = read_bool_u8(reader)?;
+        let bounce = read_bool_u8(reader)?;
+        let frame_limit = read_bool_u8(reader)?;
+        let unk = read_u8(reader)?;
+        Ok(PropFrameAnimConfig {
             frames_per_second,
             clamp,
             bounce,
             frame_limit,
             unk,
-        }
+        })
     }
 }
@@ -2,6 +2,7 @@ use shipyard::Component;
 use std::io;
 
 use crate::ss2_common::*;
+use crate::Result;
 
 use serde::{Deserialize, Serialize};
 
@@ -11,11 +12,11 @@ pub struct PropFrameAnimState {
 }
 
 impl PropFrameAnimState {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropFrameAnimState {
-        let _unk1 = read_u32(reader);
-        let _unk2 = read_u32(reader);
-        let current_frame = read_u32(reader);
-        let _unk3 = read_u32(reader);
-        PropFrameAnimState { current_frame }
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> Result<PropFrameAnimState> {
+        let _unk1 = read_u32(reader)?;
+        let _unk2 = read_u32(reader)?;
+        let current_frame = read_u32(reader)?;
+        let _unk3 = read_u32(reader)?;
+        Ok(PropFrameAnimState { current_frame })
     }
 }
@@ -3,6 +3,7 @@ use std::io;
 use shipyard::Component;
 
 use crate::ss2_common::*;
+use crate::{Error, Result};
 use bitflags::bitflags;
 
 use serde::{Deserialize, Serialize};
@@ -30,17 +31,27 @@ pub struct PropFrobInfo {
 }
 
 impl PropFrobInfo {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropFrobInfo {
-        let world_action = read_u32(reader);
-        let inventory_action = read_u32(reader);
-        let tool_action = read_u32(reader);
-        let _zero = read_u32(reader);
-        assert_eq!(_zero, 0);
-
-        PropFrobInfo {
-            world_action: FrobFlag::from_bits(world_action).unwrap(),
-            inventory_action: FrobFlag::from_bits(inventory_action).unwrap(),
-            tool_action: FrobFlag::from_bits(tool_action).unwrap(),
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropFrobInfo> {
+        let world_action = read_u32(reader)?;
+        let inventory_action = read_u32(reader)?;
+        let tool_action = read_u32(reader)?;
+        let _zero = read_u32(reader)?;
+        if _zero != 0 {
+            return Err(Error::invalid_data(format!(
+                "expected padding after frob flags, got {}",
+                _zero
+            )));
         }
+
+        Ok(PropFrobInfo {
+            world_action: FrobFlag::from_bits(world_action).ok_or_else(|| {
+                Error::invalid_data(format!("unknown FrobFlag: {}", world_action))
+            })?,
+            inventory_action: FrobFlag::from_bits(inventory_action).ok_or_else(|| {
+                Error::invalid_data(format!("unknown FrobFlag: {}", inventory_action))
+            })?,
+            tool_action: FrobFlag::from_bits(tool_action)
+                .ok_or_else(|| Error::invalid_data(format!("unknown FrobFlag: {}", tool_action)))?,
+        })
     }
 }
@@ -0,0 +1,57 @@
+use std::io;
+
+use shipyard::Component;
+
+use crate::ss2_common::{read_i32, read_single, read_u32};
+use crate::Result;
+
+use serde::{Deserialize, Serialize};
+
+// Condition of a weapon in perfect repair
+pub const MAX_GUN_CONDITION: f32 = 100.0;
+
+#[derive(Debug, Component, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropGunState {
+    // Rounds currently loaded
+    pub ammo_count: i32,
+    // 0 (broken) to MAX_GUN_CONDITION
+    pub condition: f32,
+    // Which projectile (ammo kind) is loaded
+    pub setting: u32,
+    // Number of modifications applied to the weapon
+    pub modification: u32,
+    // Not part of the original property - a jammed gun won't fire until it's reloaded
+    #[serde(default)]
+    pub jammed: bool,
+}
+
+impl PropGunState {
+    pub fn new(ammo_count: i32) -> PropGunState {
+        PropGunState {
+            ammo_count,
+            condition: MAX_GUN_CONDITION,
+            setting: 0,
+            modification: 0,
+            jammed: false,
+        }
+    }
+
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropGunState> {
+        let ammo_count = read_i32(reader)?;
+        let condition = read_single(reader)?;
+        let setting = read_u32(reader)?;
+        let modification = read_u32(reader)?;
+
+        Ok(PropGunState {
+            ammo_count,
+            condition,
+            setting,
+            modification,
+            jammed: false,
+        })
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.condition <= 0.0
+    }
+}
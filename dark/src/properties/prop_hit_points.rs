@@ -3,6 +3,7 @@ use std::io;
 use shipyard::Component;
 
 use crate::ss2_common::read_u32;
+use crate::Result;
 
 use serde::{Deserialize, Serialize};
 
@@ -12,9 +13,9 @@ pub struct PropHitPoints {
 }
 
 impl PropHitPoints {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropHitPoints {
-        let hit_points = read_u32(reader) as i32;
-        PropHitPoints { hit_points }
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropHitPoints> {
+        let hit_points = read_u32(reader)? as i32;
+        Ok(PropHitPoints { hit_points })
     }
 }
 
@@ -24,8 +25,8 @@ pub struct PropMaxHitPoints {
 }
 
 impl PropMaxHitPoints {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropMaxHitPoints {
-        let hit_points = read_u32(reader);
-        PropMaxHitPoints { hit_points }
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropMaxHitPoints> {
+        let hit_points = read_u32(reader)?;
+        Ok(PropMaxHitPoints { hit_points })
     }
 }
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use shipyard::Component;
 
 use crate::ss2_common::*;
-
+use crate::Result;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct KeyCard {
@@ -24,15 +24,15 @@ impl KeyCard {
 
         region_matches && lock_id_matches
     }
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> KeyCard {
-        let is_master = read_bool_u8(reader);
-        let region_id = read_u32(reader);
-        let lock_id = read_u8(reader);
-        KeyCard {
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<KeyCard> {
+        let is_master = read_bool_u8(reader)?;
+        let region_id = read_u32(reader)?;
+        let lock_id = read_u8(reader)?;
+        Ok(KeyCard {
             is_master,
             region_id,
             lock_id,
-        }
+        })
     }
 }
 
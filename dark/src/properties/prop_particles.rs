@@ -1,12 +1,11 @@
-use std::{io};
+use std::io;
 
 use cgmath::Vector3;
 
-
 use shipyard::Component;
 
 use crate::ss2_common::*;
-
+use crate::Result;
 
 use serde::{Deserialize, Serialize};
 
@@ -24,25 +23,28 @@ pub struct PropParticleLaunchInfo {
 }
 
 impl PropParticleLaunchInfo {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropParticleLaunchInfo {
-        let launch_type = read_u32(reader);
-        let loc_min = read_vec3(reader);
-        let loc_max = read_vec3(reader);
+    pub fn read<T: io::Seek + io::Read>(
+        reader: &mut T,
+        _len: u32,
+    ) -> Result<PropParticleLaunchInfo> {
+        let launch_type = read_u32(reader)?;
+        let loc_min = read_vec3(reader)?;
+        let loc_max = read_vec3(reader)?;
 
-        let vel_min = read_vec3(reader);
-        let vel_max = read_vec3(reader);
+        let vel_min = read_vec3(reader)?;
+        let vel_max = read_vec3(reader)?;
 
-        let min_radius = read_single(reader);
-        let max_radius = read_single(reader);
+        let min_radius = read_single(reader)?;
+        let max_radius = read_single(reader)?;
 
-        let min_time = read_single(reader);
-        let max_time = read_single(reader);
+        let min_time = read_single(reader)?;
+        let max_time = read_single(reader)?;
 
-        let _unk1 = read_u32(reader);
-        let _unk2 = read_u32(reader);
+        let _unk1 = read_u32(reader)?;
+        let _unk2 = read_u32(reader)?;
 
-        let _unk3 = read_bytes(reader, 64);
-        PropParticleLaunchInfo {
+        let _unk3 = read_bytes(reader, 64)?;
+        Ok(PropParticleLaunchInfo {
             launch_type,
             loc_min,
             loc_max,
@@ -52,7 +54,7 @@ impl PropParticleLaunchInfo {
             max_radius,
             min_time,
             max_time,
-        }
+        })
     }
 }
 
@@ -85,86 +87,86 @@ pub struct PropParticleGroup {
 }
 
 impl PropParticleGroup {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, len: u32) -> PropParticleGroup {
-        let _unk1 = read_bytes(reader, 36);
-        let _unk2 = read_u32(reader);
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T, len: u32) -> Result<PropParticleGroup> {
+        let _unk1 = read_bytes(reader, 36)?;
+        let _unk2 = read_u32(reader)?;
 
         // let active = read_u32(reader);
-        let render_type = read_u32(reader);
-        let motion_type = read_u32(reader);
-        let animation_type = read_u32(reader);
+        let render_type = read_u32(reader)?;
+        let motion_type = read_u32(reader)?;
+        let animation_type = read_u32(reader)?;
 
-        let _unk3 = read_u32(reader);
-        let _unk4 = read_u32(reader);
+        let _unk3 = read_u32(reader)?;
+        let _unk4 = read_u32(reader)?;
 
-        let num = read_u32(reader);
+        let num = read_u32(reader)?;
 
-        let _unk5 = read_bytes(reader, 24);
+        let _unk5 = read_bytes(reader, 24)?;
 
-        let velocity = read_vec3(reader);
-        let gravity = read_vec3(reader);
+        let velocity = read_vec3(reader)?;
+        let gravity = read_vec3(reader)?;
 
-        let r = read_u8(reader);
-        let g = read_u8(reader);
-        let b = read_u8(reader);
-        let a = read_u8(reader);
+        let r = read_u8(reader)?;
+        let g = read_u8(reader)?;
+        let b = read_u8(reader)?;
+        let a = read_u8(reader)?;
 
-        let _always_simulate = read_bool_u8(reader);
-        let _unk = read_bool_u8(reader);
-        let _unk = read_bool_u8(reader);
-        let _unk = read_bool_u8(reader);
+        let _always_simulate = read_bool_u8(reader)?;
+        let _unk = read_bool_u8(reader)?;
+        let _unk = read_bool_u8(reader)?;
+        let _unk = read_bool_u8(reader)?;
 
-        let _terrain_collide = read_bool_u8(reader);
-        let _unk = read_bool_u8(reader);
-        let _ignore_attach_refs = read_bool_u8(reader);
-        let _unk = read_bool_u8(reader);
+        let _terrain_collide = read_bool_u8(reader)?;
+        let _unk = read_bool_u8(reader)?;
+        let _ignore_attach_refs = read_bool_u8(reader)?;
+        let _unk = read_bool_u8(reader)?;
 
-        let _unk_launch_info = read_u32(reader);
-        let spin = read_vec3(reader);
-        let _pulse_period = read_u32(reader);
-        let _unk = read_u32(reader);
-        let _unk = read_u32(reader);
+        let _unk_launch_info = read_u32(reader)?;
+        let spin = read_vec3(reader)?;
+        let _pulse_period = read_u32(reader)?;
+        let _unk = read_u32(reader)?;
+        let _unk = read_u32(reader)?;
 
-        let _unk = read_u32(reader);
+        let _unk = read_u32(reader)?;
 
-        let _unk = read_bool_u8(reader);
-        let is_worldspace = read_bool_u8(reader);
-        let _unk = read_bool_u8(reader);
-        let is_active = read_bool_u8(reader);
+        let _unk = read_bool_u8(reader)?;
+        let is_worldspace = read_bool_u8(reader)?;
+        let _unk = read_bool_u8(reader)?;
+        let is_active = read_bool_u8(reader)?;
 
-        let _ms_offset = read_u32(reader);
-        let size = read_single(reader);
-        let _unk = read_u32(reader);
-        let _unk = read_u32(reader);
+        let _ms_offset = read_u32(reader)?;
+        let size = read_single(reader)?;
+        let _unk = read_u32(reader)?;
+        let _unk = read_u32(reader)?;
 
-        let prev_loc = read_vec3(reader);
-        let scale_vel = read_single(reader);
+        let prev_loc = read_vec3(reader)?;
+        let scale_vel = read_single(reader)?;
 
-        let _unk = read_u32(reader);
-        let bbox_min = read_vec3(reader);
-        let bbox_max = read_vec3(reader);
-        let radius = read_single(reader);
+        let _unk = read_u32(reader)?;
+        let bbox_min = read_vec3(reader)?;
+        let bbox_max = read_vec3(reader)?;
+        let radius = read_single(reader)?;
 
         // Runtime metadata?
-        let _unk = read_u32(reader);
-        let _unk = read_u32(reader);
-        let _unk = read_u32(reader);
+        let _unk = read_u32(reader)?;
+        let _unk = read_u32(reader)?;
+        let _unk = read_u32(reader)?;
 
         // More runtime data?
-        let _unk = read_u8(reader);
-        let _unk = read_u8(reader);
-        let _unk = read_u8(reader);
-        let _unk = read_u8(reader);
+        let _unk = read_u8(reader)?;
+        let _unk = read_u8(reader)?;
+        let _unk = read_u8(reader)?;
+        let _unk = read_u8(reader)?;
 
         // Even mor eruntime data?
-        let _unk = read_u32(reader);
-        let _unk = read_u32(reader);
-        let maybe_launch_time1 = read_fixed(reader);
-        let maybe_launch_time2 = read_fixed(reader);
-
-        let model_name = read_string_with_size(reader, 16);
-        let _unk = read_u32(reader);
-        let fade_time = read_fixed(reader);
+        let _unk = read_u32(reader)?;
+        let _unk = read_u32(reader)?;
+        let maybe_launch_time1 = read_fixed(reader)?;
+        let maybe_launch_time2 = read_fixed(reader)?;
+
+        let model_name = read_string_with_size(reader, 16)?;
+        let _unk = read_u32(reader)?;
+        let fade_time = read_fixed(reader)?;
         // panic!(
         //     "launch time: {} or {} or {}",
         //     maybe_launch_time1, maybe_launch_time2, model_name
@@ -177,8 +179,8 @@ impl PropParticleGroup {
 
         let consumed = 64 + 24 + 12 + 12 + 4 + 44 + 32 + 32 + 48 + 8;
 
-        let _rem = read_bytes(reader, (len - consumed) as usize);
-        PropParticleGroup {
+        let _rem = read_bytes(reader, (len - consumed) as usize)?;
+        Ok(PropParticleGroup {
             render_type,
             motion_type,
             animation_type,
@@ -201,6 +203,6 @@ impl PropParticleGroup {
             launch_time: maybe_launch_time2.max(maybe_launch_time1),
             fade_time,
             model_name,
-        }
+        })
     }
 }
@@ -4,6 +4,7 @@ use cgmath::Vector3;
 use shipyard::Component;
 
 use crate::ss2_common::*;
+use crate::Result;
 
 use serde::{Deserialize, Serialize};
 
@@ -22,18 +23,18 @@ pub struct PropPhysAttr {
 }
 
 impl PropPhysAttr {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropPhysAttr {
-        let gravity_scale = read_single(reader) / 100.0;
-        let mass = read_single(reader);
-        let density = read_single(reader);
-        let elasticity = read_single(reader);
-        let friction = read_single(reader);
-        let cog = read_vec3(reader);
-
-        let rotation_axes = read_u32(reader);
-        let rest_axes = read_u32(reader);
-        let climbable = read_u32(reader);
-        let edge_trigger = read_bool(reader);
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropPhysAttr> {
+        let gravity_scale = read_single(reader)? / 100.0;
+        let mass = read_single(reader)?;
+        let density = read_single(reader)?;
+        let elasticity = read_single(reader)?;
+        let friction = read_single(reader)?;
+        let cog = read_vec3(reader)?;
+
+        let rotation_axes = read_u32(reader)?;
+        let rest_axes = read_u32(reader)?;
+        let climbable = read_u32(reader)?;
+        let edge_trigger = read_bool(reader)?;
 
         let size = 48;
         let remainder = _len - size;
@@ -42,10 +43,10 @@ impl PropPhysAttr {
         // as 48, and others as 52. To handle this - we'll eat up the remaining bytes. But we could be
         // missing an interesting property.
         if remainder > 0 {
-            read_bytes(reader, remainder as usize);
+            read_bytes(reader, remainder as usize)?;
         }
 
-        PropPhysAttr {
+        Ok(PropPhysAttr {
             gravity_scale,
             mass,
             density,
@@ -56,6 +57,6 @@ impl PropPhysAttr {
             rest_axes,
             climbable,
             edge_trigger,
-        }
+        })
     }
 }
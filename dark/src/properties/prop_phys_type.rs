@@ -3,6 +3,7 @@ use std::io;
 use shipyard::Component;
 
 use crate::ss2_common::read_u32;
+use crate::Result;
 use bitflags::bitflags;
 
 use serde::{Deserialize, Serialize};
@@ -26,18 +27,18 @@ pub struct PropPhysType {
 }
 
 impl PropPhysType {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropPhysType {
-        let phys_type_bits = read_u32(reader);
-        let num_submodels = read_u32(reader);
-        let remove_on_sleep = read_u32(reader) != 0;
-        let is_special = read_u32(reader) != 0;
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropPhysType> {
+        let phys_type_bits = read_u32(reader)?;
+        let num_submodels = read_u32(reader)?;
+        let remove_on_sleep = read_u32(reader)? != 0;
+        let is_special = read_u32(reader)? != 0;
 
-        PropPhysType {
+        Ok(PropPhysType {
             phys_type: PhysicsModelType::from_bits(phys_type_bits)
                 .unwrap_or(PhysicsModelType::OrientedBoundingBox),
             num_submodels,
             remove_on_sleep,
             is_special,
-        }
+        })
     }
 }
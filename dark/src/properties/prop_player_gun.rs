@@ -4,6 +4,7 @@ use cgmath::Vector3;
 use shipyard::Component;
 
 use crate::ss2_common::{read_string_with_size, read_u16, read_u32, read_vec3};
+use crate::Result;
 
 use serde::{Deserialize, Serialize};
 
@@ -21,18 +22,18 @@ pub struct PropPlayerGun {
 }
 
 impl PropPlayerGun {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropPlayerGun {
-        let flags = read_u32(reader);
-        let hand_model = read_string_with_size(reader, 16);
-        let icon_file = read_string_with_size(reader, 16);
-        let model_offset = read_vec3(reader);
-        let fire_offset = read_vec3(reader);
-        let heading = read_u16(reader);
-        let reload_pitch = read_u16(reader);
-        let reload_rate = read_u16(reader);
-        let gun_type = read_u32(reader);
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropPlayerGun> {
+        let flags = read_u32(reader)?;
+        let hand_model = read_string_with_size(reader, 16)?;
+        let icon_file = read_string_with_size(reader, 16)?;
+        let model_offset = read_vec3(reader)?;
+        let fire_offset = read_vec3(reader)?;
+        let heading = read_u16(reader)?;
+        let reload_pitch = read_u16(reader)?;
+        let reload_rate = read_u16(reader)?;
+        let gun_type = read_u32(reader)?;
 
-        PropPlayerGun {
+        Ok(PropPlayerGun {
             flags,
             hand_model,
             icon_file,
@@ -42,6 +43,6 @@ impl PropPlayerGun {
             reload_pitch,
             reload_rate,
             gun_type,
-        }
+        })
     }
 }
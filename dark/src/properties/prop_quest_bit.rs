@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use shipyard::Component;
 
 use crate::ss2_common::read_u32;
+use crate::{Error, Result};
 
 bitflags! {
     #[derive(Deserialize, Serialize)]
@@ -22,9 +23,10 @@ pub struct PropQuestBitName(pub String);
 pub struct PropQuestBitValue(pub QuestBitValue);
 
 impl PropQuestBitValue {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropQuestBitValue {
-        let v = read_u32(reader);
-        let qb_val = QuestBitValue::from_bits(v).unwrap();
-        PropQuestBitValue(qb_val)
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> Result<PropQuestBitValue> {
+        let v = read_u32(reader)?;
+        let qb_val = QuestBitValue::from_bits(v)
+            .ok_or_else(|| Error::invalid_data(format!("unknown QuestBitValue: {}", v)))?;
+        Ok(PropQuestBitValue(qb_val))
     }
 }
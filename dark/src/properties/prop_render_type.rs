@@ -5,6 +5,7 @@ use num_traits::FromPrimitive;
 use shipyard::Component;
 
 use crate::ss2_common::read_u32;
+use crate::{Error, Result};
 
 use serde::{Deserialize, Serialize};
 
@@ -20,9 +21,10 @@ pub enum RenderType {
 pub struct PropRenderType(pub RenderType);
 
 impl PropRenderType {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropRenderType {
-        let val = read_u32(reader);
-        let render_type = RenderType::from_u32(val).unwrap();
-        PropRenderType(render_type)
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> Result<PropRenderType> {
+        let val = read_u32(reader)?;
+        let render_type = RenderType::from_u32(val)
+            .ok_or_else(|| Error::invalid_data(format!("unknown RenderType: {}", val)))?;
+        Ok(PropRenderType(render_type))
     }
 }
@@ -1,10 +1,9 @@
 use std::{ascii::AsciiExt, io};
 
-
-
 use shipyard::Component;
 
 use crate::ss2_common::{read_string_with_size, read_u32};
+use crate::Result;
 use serde::{Deserialize, Serialize};
 
 const NUM_REPLICATOR_ITEMS: usize = 6;
@@ -16,28 +15,35 @@ pub struct PropReplicatorContents {
 }
 
 impl PropReplicatorContents {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropReplicatorContents {
+    pub fn read<T: io::Seek + io::Read>(
+        reader: &mut T,
+        _len: u32,
+    ) -> Result<PropReplicatorContents> {
         let object_names = [
-            read_string_with_size(reader, 64).to_ascii_lowercase(),
-            read_string_with_size(reader, 64).to_ascii_lowercase(),
-            read_string_with_size(reader, 64).to_ascii_lowercase(),
-            read_string_with_size(reader, 64).to_ascii_lowercase(),
-            read_string_with_size(reader, 64).to_ascii_lowercase(),
-            read_string_with_size(reader, 64).to_ascii_lowercase(),
+            read_string_with_size(reader, 64)?.to_ascii_lowercase(),
+            read_string_with_size(reader, 64)?.to_ascii_lowercase(),
+            read_string_with_size(reader, 64)?.to_ascii_lowercase(),
+            read_string_with_size(reader, 64)?.to_ascii_lowercase(),
+            read_string_with_size(reader, 64)?.to_ascii_lowercase(),
+            read_string_with_size(reader, 64)?.to_ascii_lowercase(),
         ];
 
         let costs = [
-            read_u32(reader),
-            read_u32(reader),
-            read_u32(reader),
-            read_u32(reader),
-            read_u32(reader),
-            read_u32(reader),
+            read_u32(reader)?,
+            read_u32(reader)?,
+            read_u32(reader)?,
+            read_u32(reader)?,
+            read_u32(reader)?,
+            read_u32(reader)?,
         ];
 
-        PropReplicatorContents {
+        Ok(PropReplicatorContents {
             costs,
             object_names,
-        }
+        })
     }
 }
+
+// The items (and prices) a replicator offers once it has been hacked
+#[derive(Debug, Component, Clone, Deserialize, Serialize)]
+pub struct PropReplicatorHackedContents(pub PropReplicatorContents);
@@ -3,6 +3,7 @@ use std::io;
 use shipyard::Component;
 
 use crate::ss2_common::*;
+use crate::Result;
 
 use serde::{Deserialize, Serialize};
 
@@ -16,8 +17,8 @@ pub enum Gravity {
 pub struct PropRoomGravity(pub Gravity);
 
 impl PropRoomGravity {
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropRoomGravity {
-        let gravity_int = read_i32(reader);
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropRoomGravity> {
+        let gravity_int = read_i32(reader)?;
         // if gravity_int == 0 {
         //     PropRoomGravity(Gravity::Reset)
         // } else {
@@ -29,7 +30,7 @@ impl PropRoomGravity {
             gravity *= 3.0;
         }
 
-        PropRoomGravity(Gravity::Set(gravity))
+        Ok(PropRoomGravity(Gravity::Set(gravity)))
         // }
     }
 }
@@ -6,6 +6,7 @@ use shipyard::Component;
 use serde::{Deserialize, Serialize};
 
 use crate::ss2_common::read_u32;
+use crate::Result;
 bitflags! {
     #[derive(Serialize, Deserialize)]
     pub struct TripFlags: u32 {
@@ -35,9 +36,9 @@ impl PropTripFlags {
         }
     }
 
-    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> PropTripFlags {
-        let trip_flags = read_u32(reader);
+    pub fn read<T: io::Read + io::Seek>(reader: &mut T, _len: u32) -> Result<PropTripFlags> {
+        let trip_flags = read_u32(reader)?;
         let t = TripFlags::from_bits(trip_flags).unwrap_or(TripFlags::Default);
-        PropTripFlags { trip_flags: t }
+        Ok(PropTripFlags { trip_flags: t })
     }
 }
@@ -5,6 +5,7 @@ use num_derive::FromPrimitive;
 use shipyard::Component;
 
 use crate::ss2_common::*;
+use crate::{Error, Result};
 use bitflags::bitflags;
 
 use serde::{Deserialize, Serialize};
@@ -48,29 +49,50 @@ pub struct PropTweqRotateState {
 }
 
 impl PropTweqRotateState {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropTweqRotateState {
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> Result<PropTweqRotateState> {
         // TODO: Look at the rotate state in earth, see if it works?
-        let animation_state_bits = read_u16(reader);
-        let animation_state = TweqAnimationState::from_bits(animation_state_bits.into()).unwrap();
-        let _unk2 = read_u16(reader); // misc state, is this used?
-
-        let axis1_animation_state_bits = read_u32(reader);
-        let axis2_animation_state_bits = read_u32(reader);
-        let axis3_animation_state_bits = read_u32(reader);
-
-        let axis1_animation_state =
-            TweqAnimationState::from_bits(axis1_animation_state_bits).unwrap();
-        let axis2_animation_state =
-            TweqAnimationState::from_bits(axis2_animation_state_bits).unwrap();
-        let axis3_animation_state =
-            TweqAnimationState::from_bits(axis3_animation_state_bits).unwrap();
-
-        PropTweqRotateState {
+        let animation_state_bits = read_u16(reader)?;
+        let animation_state = TweqAnimationState::from_bits(animation_state_bits.into())
+            .ok_or_else(|| {
+                Error::invalid_data(format!(
+                    "unknown TweqAnimationState: {}",
+                    animation_state_bits
+                ))
+            })?;
+        let _unk2 = read_u16(reader)?; // misc state, is this used?
+
+        let axis1_animation_state_bits = read_u32(reader)?;
+        let axis2_animation_state_bits = read_u32(reader)?;
+        let axis3_animation_state_bits = read_u32(reader)?;
+
+        let axis1_animation_state = TweqAnimationState::from_bits(axis1_animation_state_bits)
+            .ok_or_else(|| {
+                Error::invalid_data(format!(
+                    "unknown TweqAnimationState: {}",
+                    axis1_animation_state_bits
+                ))
+            })?;
+        let axis2_animation_state = TweqAnimationState::from_bits(axis2_animation_state_bits)
+            .ok_or_else(|| {
+                Error::invalid_data(format!(
+                    "unknown TweqAnimationState: {}",
+                    axis2_animation_state_bits
+                ))
+            })?;
+        let axis3_animation_state = TweqAnimationState::from_bits(axis3_animation_state_bits)
+            .ok_or_else(|| {
+                Error::invalid_data(format!(
+                    "unknown TweqAnimationState: {}",
+                    axis3_animation_state_bits
+                ))
+            })?;
+
+        Ok(PropTweqRotateState {
             animation_state,
             axis1_animation_state,
             axis2_animation_state,
             axis3_animation_state,
-        }
+        })
     }
 }
 
@@ -80,14 +102,20 @@ pub struct PropTweqModelState {
 }
 
 impl PropTweqModelState {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropTweqModelState {
-        let animation_state_bits = read_u16(reader);
-        let animation_state = TweqAnimationState::from_bits(animation_state_bits.into()).unwrap();
-        let _misc = read_u16(reader); // misc state, is this used?
-        let _time = read_u16(reader); // misc state, is this used?
-        let _frame = read_u16(reader); // misc state, is this used?
-
-        PropTweqModelState { animation_state }
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> Result<PropTweqModelState> {
+        let animation_state_bits = read_u16(reader)?;
+        let animation_state = TweqAnimationState::from_bits(animation_state_bits.into())
+            .ok_or_else(|| {
+                Error::invalid_data(format!(
+                    "unknown TweqAnimationState: {}",
+                    animation_state_bits
+                ))
+            })?;
+        let _misc = read_u16(reader)?; // misc state, is this used?
+        let _time = read_u16(reader)?; // misc state, is this used?
+        let _frame = read_u16(reader)?; // misc state, is this used?
+
+        Ok(PropTweqModelState { animation_state })
     }
 }
 
@@ -99,18 +127,24 @@ pub struct PropTweqEmitterState {
 }
 
 impl PropTweqEmitterState {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropTweqEmitterState {
-        let animation_state_bits = read_u16(reader);
-        let animation_state = TweqAnimationState::from_bits(animation_state_bits.into()).unwrap();
-        let _misc = read_u16(reader); // misc state, is this used?
-        let _time = read_u16(reader); // misc state, is this used?
-        let _frame = read_u16(reader); // misc state, is this used?
-
-        PropTweqEmitterState {
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> Result<PropTweqEmitterState> {
+        let animation_state_bits = read_u16(reader)?;
+        let animation_state = TweqAnimationState::from_bits(animation_state_bits.into())
+            .ok_or_else(|| {
+                Error::invalid_data(format!(
+                    "unknown TweqAnimationState: {}",
+                    animation_state_bits
+                ))
+            })?;
+        let _misc = read_u16(reader)?; // misc state, is this used?
+        let _time = read_u16(reader)?; // misc state, is this used?
+        let _frame = read_u16(reader)?; // misc state, is this used?
+
+        Ok(PropTweqEmitterState {
             animation_state,
             time_since_last_event: Duration::from_secs(0),
             num_iterations: 0,
-        }
+        })
     }
 }
 
@@ -122,18 +156,24 @@ pub struct PropTweqDeleteState {
 }
 
 impl PropTweqDeleteState {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropTweqDeleteState {
-        let animation_state_bits = read_u16(reader);
-        let animation_state = TweqAnimationState::from_bits(animation_state_bits.into()).unwrap();
-        let _misc = read_u16(reader); // misc state, is this used?
-        let _time = read_u16(reader); // misc state, is this used?
-        let _frame = read_u16(reader); // misc state, is this used?
-
-        PropTweqDeleteState {
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> Result<PropTweqDeleteState> {
+        let animation_state_bits = read_u16(reader)?;
+        let animation_state = TweqAnimationState::from_bits(animation_state_bits.into())
+            .ok_or_else(|| {
+                Error::invalid_data(format!(
+                    "unknown TweqAnimationState: {}",
+                    animation_state_bits
+                ))
+            })?;
+        let _misc = read_u16(reader)?; // misc state, is this used?
+        let _time = read_u16(reader)?; // misc state, is this used?
+        let _frame = read_u16(reader)?; // misc state, is this used?
+
+        Ok(PropTweqDeleteState {
             animation_state,
             time_since_last_event: Duration::from_secs(0),
             num_iterations: 0,
-        }
+        })
     }
 }
 
@@ -146,31 +186,37 @@ pub struct PropTweqModelConfig {
 }
 
 impl PropTweqModelConfig {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropTweqModelConfig {
-        let _unk = read_u8(reader);
-        let _curve = read_u8(reader);
-        let animation_config_bits = read_u8(reader);
-        let animation_config =
-            TweqAnimationConfig::from_bits(animation_config_bits.into()).unwrap();
-        let halt_bits = read_u8(reader);
-        let halt: TweqHalt = num_traits::FromPrimitive::from_u8(halt_bits).unwrap();
-
-        let _misc = read_u16(reader);
-        let _rate = read_u16(reader);
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> Result<PropTweqModelConfig> {
+        let _unk = read_u8(reader)?;
+        let _curve = read_u8(reader)?;
+        let animation_config_bits = read_u8(reader)?;
+        let animation_config = TweqAnimationConfig::from_bits(animation_config_bits.into())
+            .ok_or_else(|| {
+                Error::invalid_data(format!(
+                    "unknown TweqAnimationConfig: {}",
+                    animation_config_bits
+                ))
+            })?;
+        let halt_bits = read_u8(reader)?;
+        let halt: TweqHalt = num_traits::FromPrimitive::from_u8(halt_bits)
+            .ok_or_else(|| Error::invalid_data(format!("unknown TweqHalt: {}", halt_bits)))?;
+
+        let _misc = read_u16(reader)?;
+        let _rate = read_u16(reader)?;
 
         let mut model_names = Vec::new();
         for _ in 0..6 {
-            let model_name = read_string_with_size(reader, 16);
+            let model_name = read_string_with_size(reader, 16)?;
             if !model_name.is_empty() {
                 model_names.push(model_name)
             }
         }
 
-        PropTweqModelConfig {
+        Ok(PropTweqModelConfig {
             animation_config,
             halt,
             model_names,
-        }
+        })
     }
 }
 
@@ -187,24 +233,33 @@ pub struct PropTweqEmitterConfig {
 }
 
 impl PropTweqEmitterConfig {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropTweqEmitterConfig {
-        let _unk = read_u8(reader);
-        let _curve = read_u8(reader);
-        let animation_config_bits = read_u8(reader);
-        let animation_config =
-            TweqAnimationConfig::from_bits(animation_config_bits.into()).unwrap();
-        let halt_bits = read_u8(reader);
-        let halt: TweqHalt = num_traits::FromPrimitive::from_u8(halt_bits).unwrap();
-
-        let _misc = read_u16(reader);
-        let rate = read_u16(reader);
-
-        let max_frames = read_u32(reader);
-        let emit_what = read_string_with_size(reader, 16);
-        let velocity = read_vec3(reader);
-        let angle_random = read_vec3(reader);
-
-        PropTweqEmitterConfig {
+    pub fn read<T: io::Seek + io::Read>(
+        reader: &mut T,
+        _len: u32,
+    ) -> Result<PropTweqEmitterConfig> {
+        let _unk = read_u8(reader)?;
+        let _curve = read_u8(reader)?;
+        let animation_config_bits = read_u8(reader)?;
+        let animation_config = TweqAnimationConfig::from_bits(animation_config_bits.into())
+            .ok_or_else(|| {
+                Error::invalid_data(format!(
+                    "unknown TweqAnimationConfig: {}",
+                    animation_config_bits
+                ))
+            })?;
+        let halt_bits = read_u8(reader)?;
+        let halt: TweqHalt = num_traits::FromPrimitive::from_u8(halt_bits)
+            .ok_or_else(|| Error::invalid_data(format!("unknown TweqHalt: {}", halt_bits)))?;
+
+        let _misc = read_u16(reader)?;
+        let rate = read_u16(reader)?;
+
+        let max_frames = read_u32(reader)?;
+        let emit_what = read_string_with_size(reader, 16)?;
+        let velocity = read_vec3(reader)?;
+        let angle_random = read_vec3(reader)?;
+
+        Ok(PropTweqEmitterConfig {
             animation_config,
             halt,
             rate: Duration::from_millis(rate.into()),
@@ -212,7 +267,7 @@ impl PropTweqEmitterConfig {
             emit_what,
             velocity,
             angle_random,
-        }
+        })
     }
 }
 
@@ -226,22 +281,28 @@ pub struct PropTweqDeleteConfig {
 }
 
 impl PropTweqDeleteConfig {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> PropTweqDeleteConfig {
-        let _unk = read_u8(reader);
-        let _curve = read_u8(reader);
-        let animation_config_bits = read_u8(reader);
-        let animation_config =
-            TweqAnimationConfig::from_bits(animation_config_bits.into()).unwrap();
-        let halt_bits = read_u8(reader);
-        let halt: TweqHalt = num_traits::FromPrimitive::from_u8(halt_bits).unwrap();
-
-        let _misc = read_u16(reader);
-        let rate = read_u16(reader);
-
-        PropTweqDeleteConfig {
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T, _len: u32) -> Result<PropTweqDeleteConfig> {
+        let _unk = read_u8(reader)?;
+        let _curve = read_u8(reader)?;
+        let animation_config_bits = read_u8(reader)?;
+        let animation_config = TweqAnimationConfig::from_bits(animation_config_bits.into())
+            .ok_or_else(|| {
+                Error::invalid_data(format!(
+                    "unknown TweqAnimationConfig: {}",
+                    animation_config_bits
+                ))
+            })?;
+        let halt_bits = read_u8(reader)?;
+        let halt: TweqHalt = num_traits::FromPrimitive::from_u8(halt_bits)
+            .ok_or_else(|| Error::invalid_data(format!("unknown TweqHalt: {}", halt_bits)))?;
+
+        let _misc = read_u16(reader)?;
+        let rate = read_u16(reader)?;
+
+        Ok(PropTweqDeleteConfig {
             animation_config,
             halt,
             rate: Duration::from_millis(rate.into()),
-        }
+        })
     }
 }
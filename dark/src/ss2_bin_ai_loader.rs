@@ -26,7 +26,7 @@ use crate::{
     },
     ss2_skeleton::Skeleton,
     util::load_multiple_textures_for_model,
-    SCALE_FACTOR,
+    Result, SCALE_FACTOR,
 };
 
 #[derive(Clone)]
@@ -60,37 +60,37 @@ pub struct AIMeshHeader {
     num_weights: u16,
 }
 
-pub fn read_header<T: Read + Seek>(reader: &mut T) -> AIMeshHeader {
-    let _zero0 = read_u32(reader); // radius
-    let _zero1 = read_u32(reader); // flags
-    let _zero2 = read_u32(reader); // app data
-
-    let _unk1 = read_u8(reader); // layout
-                                 // segs: https://github.com/infernuslord/DarkEngine/blob/c8542d03825bc650bfd6944dc03da5b793c92c19/tech/libsrc/mm/mms.h#L28
-                                 // mm_segment_list:
-                                 // - https://github.com/infernuslord/DarkEngine/blob/c8542d03825bc650bfd6944dc03da5b793c92c19/tech/libsrc/mp/mpupdate.c
-    let num_mappers = read_u8(reader);
-    let num_mats = read_u8(reader);
-    let num_joints = read_u8(reader);
-
-    let num_triangles = read_u16(reader);
-    let num_vertices = read_u16(reader);
-    let num_weights = read_u16(reader);
-    let _unk = read_u16(reader);
-
-    let offset_joint_remap = read_u32(reader);
-    let offset_mappers = read_u32(reader);
-    let offset_mats = read_u32(reader);
-
-    let offset_joints = read_u32(reader);
-    let offset_triangles = read_u32(reader);
-    let offset_normals = read_u32(reader);
-
-    let offset_vertices = read_u32(reader);
-    let offset_uvs = read_u32(reader);
-    let offset_weights = read_u32(reader);
-
-    AIMeshHeader {
+pub fn read_header<T: Read + Seek>(reader: &mut T) -> Result<AIMeshHeader> {
+    let _zero0 = read_u32(reader)?; // radius
+    let _zero1 = read_u32(reader)?; // flags
+    let _zero2 = read_u32(reader)?; // app data
+
+    let _unk1 = read_u8(reader)?; // layout
+                                  // segs: https://github.com/infernuslord/DarkEngine/blob/c8542d03825bc650bfd6944dc03da5b793c92c19/tech/libsrc/mm/mms.h#L28
+                                  // mm_segment_list:
+                                  // - https://github.com/infernuslord/DarkEngine/blob/c8542d03825bc650bfd6944dc03da5b793c92c19/tech/libsrc/mp/mpupdate.c
+    let num_mappers = read_u8(reader)?;
+    let num_mats = read_u8(reader)?;
+    let num_joints = read_u8(reader)?;
+
+    let num_triangles = read_u16(reader)?;
+    let num_vertices = read_u16(reader)?;
+    let num_weights = read_u16(reader)?;
+    let _unk = read_u16(reader)?;
+
+    let offset_joint_remap = read_u32(reader)?;
+    let offset_mappers = read_u32(reader)?;
+    let offset_mats = read_u32(reader)?;
+
+    let offset_joints = read_u32(reader)?;
+    let offset_triangles = read_u32(reader)?;
+    let offset_normals = read_u32(reader)?;
+
+    let offset_vertices = read_u32(reader)?;
+    let offset_uvs = read_u32(reader)?;
+    let offset_weights = read_u32(reader)?;
+
+    Ok(AIMeshHeader {
         offset_joint_remap,
         offset_joints,
         offset_mappers,
@@ -107,91 +107,91 @@ pub fn read_header<T: Read + Seek>(reader: &mut T) -> AIMeshHeader {
         num_triangles,
         num_vertices,
         num_weights,
-    }
+    })
 }
 
 pub fn read<T: Read + Seek>(
     reader: &mut T,
     common_header: &SystemShock2BinHeader,
-) -> SystemShock2AIMesh {
-    let header = read_header(reader);
+) -> Result<SystemShock2AIMesh> {
+    let header = read_header(reader)?;
 
-    reader.seek(SeekFrom::Start(header.offset_joint_remap as u64));
+    reader.seek(SeekFrom::Start(header.offset_joint_remap as u64))?;
 
-    let _joints_in = read_bytes(reader, header.num_joints as usize);
-    let _joints_out = read_bytes(reader, header.num_joints as usize);
+    let _joints_in = read_bytes(reader, header.num_joints as usize)?;
+    let _joints_out = read_bytes(reader, header.num_joints as usize)?;
 
     // Read joint map
     let mut joint_map = Vec::new();
-    reader.seek(SeekFrom::Start(header.offset_mappers as u64));
+    reader.seek(SeekFrom::Start(header.offset_mappers as u64))?;
 
     for _ in 0..header.num_mappers {
-        let joint_map_entry = read_joint_map_entry(reader);
+        let joint_map_entry = read_joint_map_entry(reader)?;
         joint_map.push(joint_map_entry);
     }
 
     // Read materials
     let mut materials = Vec::new();
-    reader.seek(SeekFrom::Start(header.offset_mats as u64));
+    reader.seek(SeekFrom::Start(header.offset_mats as u64))?;
 
     for _ in 0..header.num_mats {
-        let material = read_material(reader, common_header.version);
+        let material = read_material(reader, common_header.version)?;
         materials.push(material);
     }
 
     // Read joints
     let mut joints = Vec::new();
-    reader.seek(SeekFrom::Start(header.offset_joints as u64));
+    reader.seek(SeekFrom::Start(header.offset_joints as u64))?;
     for _ in 0..header.num_joints {
-        let joint = read_joint(reader);
+        let joint = read_joint(reader)?;
         joints.push(joint);
     }
 
     // Read triangles
     let mut triangles = Vec::new();
-    reader.seek(SeekFrom::Start(header.offset_triangles as u64));
+    reader.seek(SeekFrom::Start(header.offset_triangles as u64))?;
 
     for _ in 0..header.num_triangles {
-        let triangle = read_triangle(reader);
+        let triangle = read_triangle(reader)?;
         triangles.push(triangle);
     }
 
     // Read vertices
     let mut vertices = Vec::new();
-    reader.seek(SeekFrom::Start(header.offset_vertices as u64));
+    reader.seek(SeekFrom::Start(header.offset_vertices as u64))?;
 
     for _ in 0..header.num_vertices {
-        let vert = read_point3(reader) / SCALE_FACTOR;
+        let vert = read_point3(reader)? / SCALE_FACTOR;
         vertices.push(vert);
     }
 
     // Read uvs
     let mut uvs = Vec::new();
-    reader.seek(SeekFrom::Start(header.offset_uvs as u64));
+    reader.seek(SeekFrom::Start(header.offset_uvs as u64))?;
 
     for _ in 0..header.num_vertices {
-        let uv = read_uv(reader);
+        let uv = read_uv(reader)?;
         uvs.push(uv);
     }
 
     // Read normals
     let mut normals = Vec::new();
-    reader.seek(SeekFrom::Start(header.offset_normals as u64));
+    reader.seek(SeekFrom::Start(header.offset_normals as u64))?;
 
     for _ in 0..header.num_triangles {
-        let normal = read_vec3(reader);
+        let normal = read_vec3(reader)?;
         normals.push(normal);
     }
 
     // Read weights
     let mut weights = Vec::new();
-    reader.seek(SeekFrom::Start(header.offset_weights as u64));
+    reader.seek(SeekFrom::Start(header.offset_weights as u64))?;
     for _ in 0..header.num_weights {
-        let weight = read_single(reader);
+        let weight = read_single(reader)?;
         weights.push(weight);
     }
 
-    SystemShock2AIMesh {
+    Ok(SystemShock2AIMesh {
         joint_map,
         joints,
         materials,
@@ -203,7 +203,7 @@ pub fn read<T: Read + Seek>(
         //     polygons,
         //     header: header,
         //     uvs,
-    }
+    })
 }
 
 #[derive(Debug, Clone)]
@@ -217,16 +217,16 @@ pub struct AIJointMapEntry {
     // rotation: Vector3<f32>,
 }
 
-pub fn read_joint_map_entry<T: Read + Seek>(reader: &mut T) -> AIJointMapEntry {
+pub fn read_joint_map_entry<T: Read + Seek>(reader: &mut T) -> Result<AIJointMapEntry> {
     // Not convinced this is a 100% accurate, should revisit?
-    let _bbox = read_i32(reader);
-    let joint = read_i8(reader);
-    let num_of_material_segments = read_i8(reader);
-    let map_start = read_i8(reader);
-    let _en2 = read_i8(reader);
-    let _rotation = read_vec3(reader);
-
-    AIJointMapEntry {
+    let _bbox = read_i32(reader)?;
+    let joint = read_i8(reader)?;
+    let num_of_material_segments = read_i8(reader)?;
+    let map_start = read_i8(reader)?;
+    let _en2 = read_i8(reader)?;
+    let _rotation = read_vec3(reader)?;
+
+    Ok(AIJointMapEntry {
         joint,
         num_of_material_segments,
         map_start,
@@ -234,7 +234,7 @@ pub fn read_joint_map_entry<T: Read + Seek>(reader: &mut T) -> AIJointMapEntry {
         // jother,
         // en2,
         // rotation,
-    }
+    })
 }
 
 #[derive(Debug, Clone)]
@@ -260,38 +260,38 @@ pub struct AIMaterial {
     weight_start: u16,
 }
 
-pub fn read_material<T: Read + Seek>(reader: &mut T, version: u32) -> AIMaterial {
-    let name = read_string_with_size(reader, 16);
+pub fn read_material<T: Read + Seek>(reader: &mut T, version: u32) -> Result<AIMaterial> {
+    let name = read_string_with_size(reader, 16)?;
     let mut dw_caps = 0;
     let mut transparency = 0.0;
     let mut illumination = 0.0;
     let mut dw_for_rent = 0;
 
     if version > 1 {
-        dw_caps = read_u32(reader);
-        transparency = read_single(reader);
-        illumination = read_single(reader);
-        dw_for_rent = read_u32(reader)
+        dw_caps = read_u32(reader)?;
+        transparency = read_single(reader)?;
+        illumination = read_single(reader)?;
+        dw_for_rent = read_u32(reader)?
     }
 
-    let handle = read_u32(reader);
-    let uv = read_single(reader);
-    let material_type = read_u8(reader);
-    let smatsegs = read_u8(reader);
-    let map_start = read_u8(reader);
-    let flags = read_u8(reader);
+    let handle = read_u32(reader)?;
+    let uv = read_single(reader)?;
+    let material_type = read_u8(reader)?;
+    let smatsegs = read_u8(reader)?;
+    let map_start = read_u8(reader)?;
+    let flags = read_u8(reader)?;
 
-    let polygons = read_u16(reader);
-    let polygon_start = read_u16(reader);
+    let polygons = read_u16(reader)?;
+    let polygon_start = read_u16(reader)?;
 
-    let vertices = read_u16(reader);
-    let vertices_start = read_u16(reader);
+    let vertices = read_u16(reader)?;
+    let vertices_start = read_u16(reader)?;
 
-    let weight_start = read_u16(reader);
+    let weight_start = read_u16(reader)?;
 
-    let _pad = read_u16(reader);
+    let _pad = read_u16(reader)?;
 
-    AIMaterial {
+    Ok(AIMaterial {
         name,
         dw_caps,
         transparency,
@@ -311,7 +311,7 @@ pub fn read_material<T: Read + Seek>(reader: &mut T, version: u32) -> AIMaterial
         vertices_start,
 
         weight_start,
-    }
+    })
 }
 
 #[derive(Debug, Clone)]
@@ -325,17 +325,17 @@ pub struct AIJointInfo {
     mapper_id: i16,
 }
 
-pub fn read_joint<T: Read + Seek>(reader: &mut T) -> AIJointInfo {
-    let num_polys = read_i16(reader);
-    let start_poly = read_i16(reader);
-    let num_vertices = read_i16(reader);
-    let start_vertex = read_i16(reader);
-    let weight = read_u16(reader);
-    let _pad = read_u16(reader);
-    let flag = read_i16(reader);
-    let mapper_id = read_i16(reader);
-
-    AIJointInfo {
+pub fn read_joint<T: Read + Seek>(reader: &mut T) -> Result<AIJointInfo> {
+    let num_polys = read_i16(reader)?;
+    let start_poly = read_i16(reader)?;
+    let num_vertices = read_i16(reader)?;
+    let start_vertex = read_i16(reader)?;
+    let weight = read_u16(reader)?;
+    let _pad = read_u16(reader)?;
+    let flag = read_i16(reader)?;
+    let mapper_id = read_i16(reader)?;
+
+    Ok(AIJointInfo {
         num_polys,
         start_poly,
         num_vertices,
@@ -343,7 +343,7 @@ pub fn read_joint<T: Read + Seek>(reader: &mut T) -> AIJointInfo {
         weight_index: weight,
         flag,
         mapper_id,
-    }
+    })
 }
 
 #[derive(Debug, Clone)]
@@ -357,15 +357,15 @@ pub struct AITriangle {
     flags: u16,
 }
 
-pub fn read_triangle<T: Read + Seek>(reader: &mut T) -> AITriangle {
-    let vert_index0 = read_u16(reader);
-    let vert_index1 = read_u16(reader);
-    let vert_index2 = read_u16(reader);
-    let material_id = read_u16(reader);
-    let plane_coefficient = read_single(reader);
-    let normal_index = read_u16(reader);
-    let flags = read_u16(reader);
-    AITriangle {
+pub fn read_triangle<T: Read + Seek>(reader: &mut T) -> Result<AITriangle> {
+    let vert_index0 = read_u16(reader)?;
+    let vert_index1 = read_u16(reader)?;
+    let vert_index2 = read_u16(reader)?;
+    let material_id = read_u16(reader)?;
+    let plane_coefficient = read_single(reader)?;
+    let normal_index = read_u16(reader)?;
+    let flags = read_u16(reader)?;
+    Ok(AITriangle {
         vert_index0,
         vert_index1,
         vert_index2,
@@ -373,7 +373,7 @@ pub fn read_triangle<T: Read + Seek>(reader: &mut T) -> AITriangle {
         plane_coefficient,
         normal_index,
         flags,
-    }
+    })
 }
 
 #[derive(Clone, Debug)]
@@ -381,14 +381,14 @@ pub struct AIUv {
     uv: Vector2<f32>,
 }
 
-pub fn read_uv<T: Read + Seek>(reader: &mut T) -> AIUv {
-    let uv = read_vec2(reader);
+pub fn read_uv<T: Read + Seek>(reader: &mut T) -> Result<AIUv> {
+    let uv = read_vec2(reader)?;
 
     // TODO: Properly read normal
     // Idea here: https://github.com/Kernvirus/SystemShock2VR/blob/5f0f7d054e79c2e36d9661f4ca62ab95ae69de0b/Assets/Scripts/Editor/DarkEngine/DarkDataConverter.cs#L12
-    let _packed_normal = read_u32(reader);
+    let _packed_normal = read_u32(reader)?;
 
-    AIUv { uv }
+    Ok(AIUv { uv })
 }
 
 // Converter
@@ -1,18 +1,8 @@
 // ss2_bin_header.rs
 // Common header for ai/obj bin files
-use std::{
-    io::{prelude::*},
-};
+use std::io::prelude::*;
 
-
-
-
-
-use crate::{
-    ss2_common::{
-        self,
-    },
-};
+use crate::{ss2_common, Error, Result};
 
 pub enum BinFileType {
     Mesh, // Animated AI Mesh
@@ -24,15 +14,15 @@ pub struct SystemShock2BinHeader {
     pub version: u32,
 }
 
-pub fn read<T: Read + Seek>(reader: &mut T) -> SystemShock2BinHeader {
-    let header = ss2_common::read_string_with_size(reader, 4);
+pub fn read<T: Read + Seek>(reader: &mut T) -> Result<SystemShock2BinHeader> {
+    let header = ss2_common::read_string_with_size(reader, 4)?;
 
     let bin_type = match header.as_str() {
         "LGMD" => BinFileType::Obj,
         "LGMM" => BinFileType::Mesh,
-        _ => panic!("Unexpected bin_type {header}"),
+        _ => return Err(Error::invalid_data(format!("Unexpected bin_type {header}"))),
     };
 
-    let version = ss2_common::read_u32(reader);
-    SystemShock2BinHeader { bin_type, version }
+    let version = ss2_common::read_u32(reader)?;
+    Ok(SystemShock2BinHeader { bin_type, version })
 }
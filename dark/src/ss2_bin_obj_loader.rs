@@ -27,7 +27,7 @@ use crate::{
     },
     ss2_skeleton::{Bone, Skeleton},
     util::load_multiple_textures_for_model,
-    SCALE_FACTOR,
+    Error, Result, SCALE_FACTOR,
 };
 
 #[derive(FromPrimitive, ToPrimitive, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -50,12 +50,13 @@ pub struct Vhot {
 }
 
 impl Vhot {
-    pub fn read<T: Read + Seek>(reader: &mut T) -> Vhot {
-        let vhot_type_num = read_u32(reader);
-        let vhot_type = VhotType::from_u32(vhot_type_num).unwrap();
+    pub fn read<T: Read + Seek>(reader: &mut T) -> Result<Vhot> {
+        let vhot_type_num = read_u32(reader)?;
+        let vhot_type = VhotType::from_u32(vhot_type_num)
+            .ok_or_else(|| Error::invalid_data(format!("unknown vhot type: {}", vhot_type_num)))?;
 
-        let point = read_point3(reader) / SCALE_FACTOR;
-        Vhot { vhot_type, point }
+        let point = read_point3(reader)? / SCALE_FACTOR;
+        Ok(Vhot { vhot_type, point })
     }
 }
 
@@ -75,27 +76,27 @@ pub struct SystemShock2ObjectMesh {
 pub fn read<T: Read + Seek>(
     reader: &mut T,
     common_header: &SystemShock2BinHeader,
-) -> SystemShock2ObjectMesh {
-    let header = read_header(reader, common_header);
+) -> Result<SystemShock2ObjectMesh> {
+    let header = read_header(reader, common_header)?;
 
-    let vertices = read_vertices(&header, reader);
+    let vertices = read_vertices(&header, reader)?;
 
     let polygons: Vec<SystemShock2ObjectPolygon> =
-        read_polygons(&header, reader, common_header.version);
+        read_polygons(&header, reader, common_header.version)?;
 
-    let uvs = read_uvs(&header, reader);
+    let uvs = read_uvs(&header, reader)?;
 
-    let mut materials = read_materials(&header, reader);
+    let mut materials = read_materials(&header, reader)?;
 
-    read_extended_materials(&header, &mut materials, reader, common_header.version);
+    read_extended_materials(&header, &mut materials, reader, common_header.version)?;
 
-    let objs = read_sub_objects(&header, reader);
+    let objs = read_sub_objects(&header, reader)?;
 
-    let vhots = read_vhots(&header, reader);
+    let vhots = read_vhots(&header, reader)?;
 
     let bounding_box = Aabb3::new(header.bbox_min, header.bbox_max);
 
-    SystemShock2ObjectMesh {
+    Ok(SystemShock2ObjectMesh {
         bounding_box,
         materials,
         vertices,
@@ -105,7 +106,7 @@ pub fn read<T: Read + Seek>(
         vhots,
         sub_objects: objs,
         version: common_header.version,
-    }
+    })
 }
 
 // Converter
@@ -242,10 +243,10 @@ pub struct SystemShock2MeshMaterial {
     pub emissivity: f32,
 }
 
-fn read_material<T: Read>(reader: &mut T) -> SystemShock2MeshMaterial {
-    let name = ss2_common::read_string_with_size(reader, 16);
-    let material_type = ss2_common::read_u8(reader);
-    let slot_num = ss2_common::read_u8(reader);
+fn read_material<T: Read>(reader: &mut T) -> Result<SystemShock2MeshMaterial> {
+    let name = ss2_common::read_string_with_size(reader, 16)?;
+    let material_type = ss2_common::read_u8(reader)?;
+    let slot_num = ss2_common::read_u8(reader)?;
 
     let mut color = vec4(0.0, 0.0, 0.0, 0.0);
     let mut ipal_index = 0;
@@ -254,10 +255,10 @@ fn read_material<T: Read>(reader: &mut T) -> SystemShock2MeshMaterial {
     if material_type == 1
     /* MD_MAT_COLOR */
     {
-        let r = ss2_common::read_u8(reader);
-        let g = ss2_common::read_u8(reader);
-        let b = ss2_common::read_u8(reader);
-        let a = ss2_common::read_u8(reader);
+        let r = ss2_common::read_u8(reader)?;
+        let g = ss2_common::read_u8(reader)?;
+        let b = ss2_common::read_u8(reader)?;
+        let a = ss2_common::read_u8(reader)?;
 
         color = vec4(
             r as f32 / 255.0,
@@ -265,18 +266,20 @@ fn read_material<T: Read>(reader: &mut T) -> SystemShock2MeshMaterial {
             b as f32 / 255.0,
             a as f32 / 255.0,
         );
-        ipal_index = ss2_common::read_u32(reader);
+        ipal_index = ss2_common::read_u32(reader)?;
     } else if material_type == 0
     /* MD_MAT_TMAP */
     {
-        handle = ss2_common::read_u32(reader);
-        uv_scale = ss2_common::read_single(reader);
+        handle = ss2_common::read_u32(reader)?;
+        uv_scale = ss2_common::read_single(reader)?;
         color = vec4(1.0, 1.0, 1.0, 1.0);
     } else {
-        panic!("Unknown material type: {material_type}");
+        return Err(Error::invalid_data(format!(
+            "Unknown material type: {material_type}"
+        )));
     }
 
-    SystemShock2MeshMaterial {
+    Ok(SystemShock2MeshMaterial {
         name,
         material_type,
         slot_num,
@@ -286,26 +289,24 @@ fn read_material<T: Read>(reader: &mut T) -> SystemShock2MeshMaterial {
         uv_scale,
         emissivity: 0.0,
         transparency: 0.0,
-    }
+    })
 }
 
 pub fn read_materials<T: Read + Seek>(
     header: &ObjBinHeader,
     reader: &mut T,
-) -> Vec<SystemShock2MeshMaterial> {
-    reader
-        .seek(SeekFrom::Start((header.offset_mats) as u64))
-        .unwrap();
+) -> Result<Vec<SystemShock2MeshMaterial>> {
+    reader.seek(SeekFrom::Start((header.offset_mats) as u64))?;
 
     let mut materials = Vec::new();
     let len = header.num_mats;
 
     for _idx in 0..len {
-        let material = read_material(reader);
+        let material = read_material(reader)?;
         materials.push(material);
     }
 
-    materials
+    Ok(materials)
 }
 
 fn build_vertex(
@@ -429,93 +430,90 @@ fn read_polygon<T: Read>(
     _header: &ObjBinHeader,
     reader: &mut T,
     version: u32,
-) -> SystemShock2ObjectPolygon {
-    let _index = ss2_common::read_u16(reader);
-    let slot_index = ss2_common::read_u16(reader);
+) -> Result<SystemShock2ObjectPolygon> {
+    let _index = ss2_common::read_u16(reader)?;
+    let slot_index = ss2_common::read_u16(reader)?;
 
-    let poly_type = ss2_common::read_u8(reader);
-    let num_verts = ss2_common::read_u8(reader);
+    let poly_type = ss2_common::read_u8(reader)?;
+    let num_verts = ss2_common::read_u8(reader)?;
 
     // Plane info?
-    let _norm = ss2_common::read_u16(reader);
-    let _d = ss2_common::read_single(reader);
+    let _norm = ss2_common::read_u16(reader)?;
+    let _d = ss2_common::read_single(reader)?;
 
     // Read vert indices
-    let vertex_indices = read_array_u16(reader, num_verts as u32);
+    let vertex_indices = read_array_u16(reader, num_verts as u32)?;
 
     // Read normal indices
-    let _normal_indices = read_array_u16(reader, num_verts as u32);
+    let _normal_indices = read_array_u16(reader, num_verts as u32)?;
 
     // Read uv indices, maybe
     let mut uvs = vec![];
     if (poly_type & 3) == 3 {
-        uvs = read_array_u16(reader, num_verts as u32);
+        uvs = read_array_u16(reader, num_verts as u32)?;
     }
 
     if version == 4 {
-        let _unknown = read_u8(reader);
+        let _unknown = read_u8(reader)?;
     }
 
-    SystemShock2ObjectPolygon {
+    Ok(SystemShock2ObjectPolygon {
         vertex_indices,
         uv_indices: uvs,
         slot_index,
-    }
+    })
 }
 
 pub fn read_polygons<T: Read + Seek>(
     header: &ObjBinHeader,
     reader: &mut T,
     version: u32,
-) -> Vec<SystemShock2ObjectPolygon> {
+) -> Result<Vec<SystemShock2ObjectPolygon>> {
     let mut ret = Vec::new();
 
-    reader
-        .seek(SeekFrom::Start((header.offset_polygons) as u64))
-        .unwrap();
+    reader.seek(SeekFrom::Start((header.offset_polygons) as u64))?;
 
     for _idx in 0..header.num_polygons {
-        let polygon = read_polygon(header, reader, version);
+        let polygon = read_polygon(header, reader, version)?;
         ret.push(polygon);
     }
 
-    ret
+    Ok(ret)
 }
 
-pub fn read_vhots<T: Read + Seek>(header: &ObjBinHeader, reader: &mut T) -> Vec<Vhot> {
+pub fn read_vhots<T: Read + Seek>(header: &ObjBinHeader, reader: &mut T) -> Result<Vec<Vhot>> {
     let mut vhots = Vec::new();
 
     if header.num_vhots > 0 {
-        reader
-            .seek(SeekFrom::Start((header.offset_vhots) as u64))
-            .unwrap();
+        reader.seek(SeekFrom::Start((header.offset_vhots) as u64))?;
 
         for _ in 0..header.num_vhots {
-            vhots.push(Vhot::read(reader));
+            vhots.push(Vhot::read(reader)?);
         }
     }
     vhots.sort_by(|a, b| a.vhot_type.cmp(&b.vhot_type));
-    vhots
+    Ok(vhots)
 }
 
-pub fn read_uvs<T: Read + Seek>(header: &ObjBinHeader, reader: &mut T) -> Vec<Vector2<f32>> {
+pub fn read_uvs<T: Read + Seek>(
+    header: &ObjBinHeader,
+    reader: &mut T,
+) -> Result<Vec<Vector2<f32>>> {
     let mut uvs = Vec::new();
 
     let space = header.offset_vhots - header.offset_uvs;
     let num_uvs = space / (4 /* size of float */ * 2/* 2 floats in vector2 */);
 
     if num_uvs > 0 {
-        reader
-            .seek(SeekFrom::Start((header.offset_uvs) as u64))
-            .unwrap();
+        reader.seek(SeekFrom::Start((header.offset_uvs) as u64))?;
 
         for _idx in 0..num_uvs {
-            let uv = ss2_common::read_vec2(reader);
+            let uv = ss2_common::read_vec2(reader)?;
             uvs.push(uv);
         }
     }
 
-    uvs
+    Ok(uvs)
 }
 
 fn read_extended_materials<T: Read + Seek>(
@@ -523,42 +521,47 @@ fn read_extended_materials<T: Read + Seek>(
     materials: &mut Vec<SystemShock2MeshMaterial>,
     reader: &mut T,
     version: u32,
-) {
-    assert!(version > 3 && header.size_mat_extra >= 8);
+) -> Result<()> {
+    if version <= 3 || header.size_mat_extra < 8 {
+        return Err(Error::invalid_data(format!(
+            "unsupported extended materials: version {}, size {}",
+            version, header.size_mat_extra
+        )));
+    }
     if version > 3 && header.size_mat_extra >= 8 {
-        reader
-            .seek(SeekFrom::Start((header.offset_mat_extra) as u64))
-            .unwrap();
+        reader.seek(SeekFrom::Start((header.offset_mat_extra) as u64))?;
         let remaining_size = (header.size_mat_extra - 8) as usize;
 
         let len = materials.len();
         for i in 0..len {
-            let transparency = read_single(reader);
-            let emissivity = read_single(reader);
+            let transparency = read_single(reader)?;
+            let emissivity = read_single(reader)?;
             materials[i].transparency = transparency;
             materials[i].emissivity = emissivity;
         }
 
         if remaining_size > 0 {
-            let _unk = read_bytes(reader, remaining_size);
+            let _unk = read_bytes(reader, remaining_size)?;
         }
     }
+    Ok(())
 }
 
-fn read_vertices<T: Read + Seek>(header: &ObjBinHeader, reader: &mut T) -> Vec<Vector3<f32>> {
-    reader
-        .seek(SeekFrom::Start((header.offset_verts) as u64))
-        .unwrap();
+fn read_vertices<T: Read + Seek>(
+    header: &ObjBinHeader,
+    reader: &mut T,
+) -> Result<Vec<Vector3<f32>>> {
+    reader.seek(SeekFrom::Start((header.offset_verts) as u64))?;
 
     let mut vertices = Vec::new();
 
     let len = header.num_verts;
     for _idx in 0..len {
-        let vertex_position = read_vec3(reader) / SCALE_FACTOR;
+        let vertex_position = read_vec3(reader)? / SCALE_FACTOR;
         vertices.push(vertex_position);
     }
 
-    vertices
+    Ok(vertices)
 }
 
 #[derive(Debug)]
@@ -575,35 +578,36 @@ pub struct SubObjectHeader {
     point_stop: u16,
 }
 
-fn read_sub_objects<T: Read + Seek>(header: &ObjBinHeader, reader: &mut T) -> Vec<SubObjectHeader> {
-    reader
-        .seek(SeekFrom::Start((header.offset_objs) as u64))
-        .unwrap();
+fn read_sub_objects<T: Read + Seek>(
+    header: &ObjBinHeader,
+    reader: &mut T,
+) -> Result<Vec<SubObjectHeader>> {
+    reader.seek(SeekFrom::Start((header.offset_objs) as u64))?;
 
     let _obj_size = (header.offset_mats - header.offset_objs) / (header.num_objs as u32);
 
     let mut objs = Vec::new();
     for i in 0..header.num_objs {
-        let name = read_string_with_size(reader, 8);
-        let _obj_type = read_u8(reader);
-        let parent_idx = read_i32(reader);
-        let min_range = read_single(reader);
-        let max_range = read_single(reader);
+        let name = read_string_with_size(reader, 8)?;
+        let _obj_type = read_u8(reader)?;
+        let parent_idx = read_i32(reader)?;
+        let min_range = read_single(reader)?;
+        let max_range = read_single(reader)?;
 
         // Transform
-        let mut decomposed = read_matrix(reader);
+        let mut decomposed = read_matrix(reader)?;
         decomposed.disp /= SCALE_FACTOR;
         let transform: Matrix4<f32> = decomposed.into();
 
-        let child_sub_obj_idx = read_i16(reader);
-        let next_sub_obj_idx = read_i16(reader);
-        let _vhot_start = read_i16(reader);
-        let _num_vhots = read_i16(reader);
-        let point_start = read_u16(reader);
-        let sub_num_points = read_u16(reader);
+        let child_sub_obj_idx = read_i16(reader)?;
+        let next_sub_obj_idx = read_i16(reader)?;
+        let _vhot_start = read_i16(reader)?;
+        let _num_vhots = read_i16(reader)?;
+        let point_start = read_u16(reader)?;
+        let sub_num_points = read_u16(reader)?;
 
         // Not sure what this is
-        let _ = read_bytes(reader, 12);
+        let _ = read_bytes(reader, 12)?;
 
         let soh = SubObjectHeader {
             idx: i as u32,
@@ -619,7 +623,7 @@ fn read_sub_objects<T: Read + Seek>(header: &ObjBinHeader, reader: &mut T) -> Ve
         };
         objs.push(soh);
     }
-    objs
+    Ok(objs)
 }
 
 pub struct ObjBinHeader {
@@ -643,15 +647,18 @@ pub struct ObjBinHeader {
     offset_uvs: u32,
 }
 
-pub fn read_header<T: Read>(reader: &mut T, common_header: &SystemShock2BinHeader) -> ObjBinHeader {
+pub fn read_header<T: Read>(
+    reader: &mut T,
+    common_header: &SystemShock2BinHeader,
+) -> Result<ObjBinHeader> {
     let version = common_header.version;
-    let obj_name = ss2_common::read_string_with_size(reader, 8);
+    let obj_name = ss2_common::read_string_with_size(reader, 8)?;
 
-    let _sphere_rad = ss2_common::read_single(reader) / SCALE_FACTOR;
-    let _max_poly_rad: f32 = ss2_common::read_single(reader) / SCALE_FACTOR;
+    let _sphere_rad = ss2_common::read_single(reader)? / SCALE_FACTOR;
+    let _max_poly_rad: f32 = ss2_common::read_single(reader)? / SCALE_FACTOR;
 
-    let bbox_max_initial = ss2_common::read_point3(reader) / SCALE_FACTOR;
-    let bbox_min_initial = ss2_common::read_point3(reader) / SCALE_FACTOR;
+    let bbox_max_initial = ss2_common::read_point3(reader)? / SCALE_FACTOR;
+    let bbox_min_initial = ss2_common::read_point3(reader)? / SCALE_FACTOR;
 
     // Because of the tweaks to the coordinate system, there is no guarantee that the
     // provided min/max are actually the min/max - so we need to normalize them.
@@ -666,40 +673,45 @@ pub fn read_header<T: Read>(reader: &mut T, common_header: &SystemShock2BinHeade
         bbox_min_initial.y.max(bbox_max_initial.y),
         bbox_min_initial.z.max(bbox_max_initial.z),
     );
-    let _parent_center = ss2_common::read_vec3(reader) / SCALE_FACTOR;
-
-    let num_polygons = ss2_common::read_u16(reader);
-    let num_verts = ss2_common::read_u16(reader);
-    let _num_params = ss2_common::read_u16(reader);
-
-    let num_mats = ss2_common::read_u8(reader);
-    let _num_vcalls = ss2_common::read_u8(reader);
-    let num_vhots = ss2_common::read_u8(reader);
-    let num_objs = ss2_common::read_u8(reader);
-
-    let offset_objs = ss2_common::read_u32(reader);
-    let offset_mats = ss2_common::read_u32(reader);
-    let offset_uvs = ss2_common::read_u32(reader);
-    let offset_vhots = ss2_common::read_u32(reader);
-    let offset_verts = ss2_common::read_u32(reader);
-    let _offset_lights = ss2_common::read_u32(reader);
-    let _offset_normals = ss2_common::read_u32(reader);
-    let offset_polygons = ss2_common::read_u32(reader);
-    let _offset_nodes = ss2_common::read_u32(reader);
-    let _model_size = ss2_common::read_u32(reader);
+    let _parent_center = ss2_common::read_vec3(reader)? / SCALE_FACTOR;
+
+    let num_polygons = ss2_common::read_u16(reader)?;
+    let num_verts = ss2_common::read_u16(reader)?;
+    let _num_params = ss2_common::read_u16(reader)?;
+
+    let num_mats = ss2_common::read_u8(reader)?;
+    let _num_vcalls = ss2_common::read_u8(reader)?;
+    let num_vhots = ss2_common::read_u8(reader)?;
+    let num_objs = ss2_common::read_u8(reader)?;
+
+    let offset_objs = ss2_common::read_u32(reader)?;
+    let offset_mats = ss2_common::read_u32(reader)?;
+    let offset_uvs = ss2_common::read_u32(reader)?;
+    let offset_vhots = ss2_common::read_u32(reader)?;
+    let offset_verts = ss2_common::read_u32(reader)?;
+    let _offset_lights = ss2_common::read_u32(reader)?;
+    let _offset_normals = ss2_common::read_u32(reader)?;
+    let offset_polygons = ss2_common::read_u32(reader)?;
+    let _offset_nodes = ss2_common::read_u32(reader)?;
+    let _model_size = ss2_common::read_u32(reader)?;
 
     let mut offset_mat_extra = 0;
     let mut size_mat_extra = 0;
     let mut mat_flags = 0;
 
     if version > 3 {
-        mat_flags = read_u32(reader);
-        offset_mat_extra = read_u32(reader);
-        size_mat_extra = read_u32(reader);
-        assert!(size_mat_extra >= 8);
+        mat_flags = read_u32(reader)?;
+        offset_mat_extra = read_u32(reader)?;
+        size_mat_extra = read_u32(reader)?;
+        if size_mat_extra < 8 {
+            return Err(Error::invalid_data(format!(
+                "unexpected extended material size: {}",
+                size_mat_extra
+            )));
+        }
     }
 
-    ObjBinHeader {
+    Ok(ObjBinHeader {
         bbox_min,
         bbox_max,
         obj_name,
@@ -717,7 +729,7 @@ pub fn read_header<T: Read>(reader: &mut T, common_header: &SystemShock2BinHeade
         offset_mat_extra,
         size_mat_extra,
         mat_flags,
-    }
+    })
 }
 
 fn convert_skinned_vertices_to_static_vertices(
@@ -2,7 +2,10 @@ use std::io::{Read, Seek};
 
 use cgmath::Vector3;
 
-use crate::ss2_common::{read_i32, read_single, read_u16, read_u32, read_vec3};
+use crate::{
+    ss2_common::{read_i32, read_single, read_u16, read_u32, read_vec3},
+    Result,
+};
 
 // What does CAL stand for?
 pub struct SystemShock2Cal {
@@ -14,30 +17,30 @@ pub struct SystemShock2Cal {
     pub limbs: Vec<Limb>,
 }
 
-pub fn read<T: Read + Seek>(reader: &mut T) -> SystemShock2Cal {
-    let version = read_i32(reader);
-    let num_torsos = read_u32(reader);
-    let num_limbs = read_u32(reader);
+pub fn read<T: Read + Seek>(reader: &mut T) -> Result<SystemShock2Cal> {
+    let version = read_i32(reader)?;
+    let num_torsos = read_u32(reader)?;
+    let num_limbs = read_u32(reader)?;
 
     let mut torsos = Vec::new();
     for _ in 0..num_torsos {
-        let torso = read_torso(reader);
+        let torso = read_torso(reader)?;
         torsos.push(torso);
     }
 
     let mut limbs = Vec::new();
     for _ in 0..num_limbs {
-        let limb = read_limb(reader);
+        let limb = read_limb(reader)?;
         limbs.push(limb)
     }
 
-    SystemShock2Cal {
+    Ok(SystemShock2Cal {
         version,
         num_torsos,
         num_limbs,
         torsos,
         limbs,
-    }
+    })
 }
 
 #[derive(Debug)]
@@ -51,30 +54,30 @@ pub struct Torso {
 
 const NUM_TORSO_JOINTS: usize = 16;
 
-pub fn read_torso<T: Read + Seek>(reader: &mut T) -> Torso {
-    let joint = read_u32(reader);
-    let parent = read_i32(reader);
-    let fixed_count = read_i32(reader);
+pub fn read_torso<T: Read + Seek>(reader: &mut T) -> Result<Torso> {
+    let joint = read_u32(reader)?;
+    let parent = read_i32(reader)?;
+    let fixed_count = read_i32(reader)?;
 
     let mut fixed_joints = Vec::new();
     for _ in 0..16 {
-        let id = read_u32(reader);
+        let id = read_u32(reader)?;
         fixed_joints.push(id);
     }
 
     let mut fixed_joint_offset = Vec::new();
     for _ in 0..16 {
-        let offset = read_vec3(reader);
+        let offset = read_vec3(reader)?;
         fixed_joint_offset.push(offset);
     }
 
-    Torso {
+    Ok(Torso {
         joint,
         parent,
         fixed_count,
         fixed_joints,
         fixed_joint_offset,
-    }
+    })
 }
 
 #[derive(Debug)]
@@ -91,28 +94,28 @@ pub struct Limb {
 
 const NUM_LIMB_JOINTS: usize = 16;
 
-pub fn read_limb<T: Read + Seek>(reader: &mut T) -> Limb {
-    let torso_index = read_u32(reader);
-    let bend = read_i32(reader);
-    let num_segments = read_i32(reader);
-    let attachment_joint = read_u16(reader);
+pub fn read_limb<T: Read + Seek>(reader: &mut T) -> Result<Limb> {
+    let torso_index = read_u32(reader)?;
+    let bend = read_i32(reader)?;
+    let num_segments = read_i32(reader)?;
+    let attachment_joint = read_u16(reader)?;
 
     let mut segments = Vec::new();
     for _ in 0..NUM_LIMB_JOINTS {
-        segments.push(read_u16(reader))
+        segments.push(read_u16(reader)?)
     }
 
     let mut segment_directions = Vec::new();
     for _ in 0..NUM_LIMB_JOINTS {
-        segment_directions.push(read_vec3(reader))
+        segment_directions.push(read_vec3(reader)?)
     }
 
     let mut segment_lengths = Vec::new();
     for _ in 0..NUM_LIMB_JOINTS {
-        segment_lengths.push(read_single(reader))
+        segment_lengths.push(read_single(reader)?)
     }
 
-    Limb {
+    Ok(Limb {
         torso_index,
         bend,
         num_segments,
@@ -120,5 +123,5 @@ pub fn read_limb<T: Read + Seek>(reader: &mut T) -> Limb {
         segments,
         segment_directions,
         segment_lengths,
-    }
+    })
 }
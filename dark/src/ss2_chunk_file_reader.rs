@@ -14,13 +14,15 @@ pub const CHUNK_HEADER_SIZE: u32 = 24;
 use byteorder::ReadBytesExt;
 use tracing::info;
 
-use crate::ss2_common::read_string_with_size;
+use crate::{ss2_common::read_string_with_size, Error, Result};
 
 // Chunk describes a chunk of data for the consumer
 #[derive(Debug, Clone)]
 pub struct Chunk {
     pub offset: u64, // Offset of the chunk, in bytes
     pub length: u64, // Length of chunk, in bytes
+    pub version_high: u32,
+    pub version_low: u32,
 }
 
 #[derive(Debug)]
@@ -37,48 +39,104 @@ impl ChunkFileTableOfContents {
     pub fn get_chunk(&self, chunk_name: String) -> Option<Chunk> {
         self.table_of_contents.get(&chunk_name).cloned()
     }
-}
 
-pub fn read_table_of_contents<T: io::Read + io::Seek>(reader: &mut T) -> ChunkFileTableOfContents {
-    let inv_offset = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-    let zero = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-    let one = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-    let mut buf: [u8; 256] = [0; 256];
-    reader.read_exact(&mut buf).unwrap();
-    let dead_beef = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-
-    info!(
-        "reading chunk table of contents - inv_offset: {} zero: {}, one: {}, zeros: {}, dead_beef: {}, debug: {}",
-        inv_offset,
-        zero,
-        one,
-        buf.len(),
-        dead_beef,
-        0x0EFBEADDE_u32
-    );
-
-    reader.seek(SeekFrom::Start(inv_offset as u64)).unwrap();
-
-    let chunk_count = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-
-    let mut dictionary = HashMap::new();
-    for _ in 0..chunk_count {
-        let chunk_name = read_string_with_size(reader, 12);
-
-        let offset = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-        let length = reader.read_u32::<byteorder::LittleEndian>().unwrap();
-
-        dictionary.insert(
-            chunk_name,
-            Chunk {
-                // Always skip the header
-                offset: (offset + CHUNK_HEADER_SIZE) as u64,
-                length: length as u64,
-            },
-        );
+    ///
+    /// require_chunk
+    ///
+    /// Like get_chunk, but for chunks that must be present in the file
+    pub fn require_chunk(&self, chunk_name: &str) -> Result<Chunk> {
+        self.table_of_contents
+            .get(chunk_name)
+            .cloned()
+            .ok_or_else(|| Error::missing_chunk(chunk_name))
     }
 
-    ChunkFileTableOfContents {
-        table_of_contents: dictionary,
+    ///
+    /// chunks
+    ///
+    /// All chunks in the file, along with their names, in the order they appear in the file
+    pub fn chunks(&self) -> Vec<(String, Chunk)> {
+        let mut chunks = self
+            .table_of_contents
+            .iter()
+            .map(|(name, chunk)| (name.to_owned(), chunk.clone()))
+            .collect::<Vec<(String, Chunk)>>();
+        chunks.sort_by_key(|(_, chunk)| chunk.offset);
+        chunks
     }
 }
+
+pub fn read_table_of_contents<T: io::Read + io::Seek>(
+    reader: &mut T,
+) -> Result<ChunkFileTableOfContents> {
+    with_chunk(reader, "<table of contents>", |reader| {
+        let inv_offset = reader.read_u32::<byteorder::LittleEndian>()?;
+        let zero = reader.read_u32::<byteorder::LittleEndian>()?;
+        let one = reader.read_u32::<byteorder::LittleEndian>()?;
+        let mut buf: [u8; 256] = [0; 256];
+        reader.read_exact(&mut buf)?;
+        let dead_beef = reader.read_u32::<byteorder::LittleEndian>()?;
+
+        info!(
+            "reading chunk table of contents - inv_offset: {} zero: {}, one: {}, zeros: {}, dead_beef: {}, debug: {}",
+            inv_offset,
+            zero,
+            one,
+            buf.len(),
+            dead_beef,
+            0x0EFBEADDE_u32
+        );
+
+        reader.seek(SeekFrom::Start(inv_offset as u64))?;
+
+        let chunk_count = reader.read_u32::<byteorder::LittleEndian>()?;
+
+        let mut entries = Vec::new();
+        for _ in 0..chunk_count {
+            let chunk_name = read_string_with_size(reader, 12)?;
+
+            let offset = reader.read_u32::<byteorder::LittleEndian>()?;
+            let length = reader.read_u32::<byteorder::LittleEndian>()?;
+
+            entries.push((chunk_name, offset, length));
+        }
+
+        // The version lives in the header of each chunk, rather than in the table of contents
+        let mut dictionary = HashMap::new();
+        for (chunk_name, offset, length) in entries {
+            reader.seek(SeekFrom::Start(offset as u64 + 12))?;
+            let version_high = reader.read_u32::<byteorder::LittleEndian>()?;
+            let version_low = reader.read_u32::<byteorder::LittleEndian>()?;
+
+            dictionary.insert(
+                chunk_name,
+                Chunk {
+                    // Always skip the header
+                    offset: offset as u64 + CHUNK_HEADER_SIZE as u64,
+                    length: length as u64,
+                    version_high,
+                    version_low,
+                },
+            );
+        }
+
+        Ok(ChunkFileTableOfContents {
+            table_of_contents: dictionary,
+        })
+    })
+}
+
+///
+/// with_chunk
+///
+/// Run a reader for a chunk, tagging any error with the chunk name and the offset where reading stopped
+pub fn with_chunk<R: io::Read + io::Seek, T>(
+    reader: &mut R,
+    chunk_name: &str,
+    read: impl FnOnce(&mut R) -> Result<T>,
+) -> Result<T> {
+    read(reader).map_err(|err| {
+        let offset = reader.stream_position().unwrap_or(0);
+        err.in_chunk(chunk_name, offset)
+    })
+}
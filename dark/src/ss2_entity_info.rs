@@ -12,10 +12,10 @@ use crate::{
         LinkDefinition, LinkDefinitionWithData, PropSymName, PropTemplateId, Property,
         PropertyDefinition, TemplateLinks, ToTemplateLinkInfo,
     },
-    ss2_chunk_file_reader::ChunkFileTableOfContents,
+    ss2_chunk_file_reader::{with_chunk, ChunkFileTableOfContents},
     ss2_common::{read_bytes, read_i32, read_u16, read_u32},
     util::merge_maps,
-    Gamesys,
+    Error, Gamesys, Result,
 };
 
 #[derive(Debug)]
@@ -211,26 +211,26 @@ pub fn new<R: io::Read + io::Seek>(
     links_with_data: &Vec<Box<dyn LinkDefinitionWithData>>,
     properties: &Vec<Box<dyn PropertyDefinition<R>>>,
     reader: &mut R,
-) -> SystemShock2EntityInfo {
-    let entity_to_properties = read_all_properties(toc, properties, reader);
+) -> Result<SystemShock2EntityInfo> {
+    let entity_to_properties = read_all_properties(toc, properties, reader)?;
 
-    let link_metaprops = read_link("L$MetaProp", reader, toc);
+    let link_metaprops = read_link("L$MetaProp", reader, toc)?;
 
-    let link_playerfactories = read_link("L$PlayerFac", reader, toc);
+    let link_playerfactories = read_link("L$PlayerFac", reader, toc)?;
     println!("player factory? {link_playerfactories:#?}");
 
-    let mut template_to_links = read_all_links(toc, links, reader);
-    read_all_data_links(toc, &mut template_to_links, links_with_data, reader);
+    let mut template_to_links = read_all_links(toc, links, reader)?;
+    read_all_data_links(toc, &mut template_to_links, links_with_data, reader)?;
 
     let hierarchy = calculate_hierarchy(&link_metaprops);
 
-    SystemShock2EntityInfo {
+    Ok(SystemShock2EntityInfo {
         template_to_links,
         link_playerfactories,
         entity_to_properties,
         link_metaprops,
         hierarchy,
-    }
+    })
 }
 #[derive(Debug, Clone)]
 pub struct Link {
@@ -245,33 +245,36 @@ pub fn read_link<T: io::Read + io::Seek>(
     link_chunk_name: &str,
     reader: &mut T,
     toc: &ChunkFileTableOfContents,
-) -> Vec<Link> {
+) -> Result<Vec<Link>> {
     let mut ret = vec![];
 
     if let Some(chunk_pos) = toc.get_chunk(link_chunk_name.to_owned()) {
         trace!("reading chunk: {}", link_chunk_name);
-        reader.seek(SeekFrom::Start(chunk_pos.offset)).unwrap();
-
-        let end_pos = chunk_pos.offset + chunk_pos.length;
-        while reader.stream_position().unwrap() < end_pos {
-            let id = read_i32(reader);
-            let src = read_i32(reader);
-            let dest = read_i32(reader);
-            let flavor = read_u16(reader);
-
-            let link = Link {
-                id,
-                src,
-                dest,
-                flavor,
-                name: link_chunk_name.to_owned(),
-            };
+        with_chunk(reader, link_chunk_name, |reader| {
+            reader.seek(SeekFrom::Start(chunk_pos.offset))?;
 
-            ret.push(link);
-        }
+            let end_pos = chunk_pos.offset + chunk_pos.length;
+            while reader.stream_position()? < end_pos {
+                let id = read_i32(reader)?;
+                let src = read_i32(reader)?;
+                let dest = read_i32(reader)?;
+                let flavor = read_u16(reader)?;
+
+                let link = Link {
+                    id,
+                    src,
+                    dest,
+                    flavor,
+                    name: link_chunk_name.to_owned(),
+                };
+
+                ret.push(link);
+            }
+            Ok(())
+        })?;
     }
 
-    ret
+    Ok(ret)
 }
 
 fn read_all_data_links<R1: io::Read + io::Seek>(
@@ -279,13 +282,13 @@ fn read_all_data_links<R1: io::Read + io::Seek>(
     ent_to_links: &mut HashMap<i32, TemplateLinks>,
     links: &Vec<Box<dyn LinkDefinitionWithData>>,
     ref_reader: &mut R1,
-) {
+) -> Result<()> {
     for link in links {
         let chunk_name = link.link_chunk_name();
         let data_chunk_name = link.link_data_chunk_name();
 
-        let link_infos = read_link(&chunk_name, ref_reader, toc);
-        let link_data = read_link_data(&data_chunk_name, ref_reader, toc, link_infos.len() as u32);
+        let link_infos = read_link(&chunk_name, ref_reader, toc)?;
+        let link_data = read_link_data(&data_chunk_name, ref_reader, toc, link_infos.len() as u32)?;
 
         for link_info in link_infos {
             let to_link = ToTemplateLinkInfo {
@@ -295,7 +298,7 @@ fn read_all_data_links<R1: io::Read + io::Seek>(
             };
 
             if let Some(data) = link_data.get(&link_info.id) {
-                let component_link = link.convert(data.clone(), data.len() as u32, to_link);
+                let component_link = link.convert(data.clone(), data.len() as u32, to_link)?;
 
                 ent_to_links
                     .entry(link_info.src)
@@ -306,6 +309,7 @@ fn read_all_data_links<R1: io::Read + io::Seek>(
             }
         }
     }
+    Ok(())
 }
 
 pub fn read_link_data<T: io::Read + io::Seek>(
@@ -313,7 +317,7 @@ pub fn read_link_data<T: io::Read + io::Seek>(
     reader: &mut T,
     toc: &ChunkFileTableOfContents,
     count: u32,
-) -> HashMap<i32, Vec<u8>> {
+) -> Result<HashMap<i32, Vec<u8>>> {
     let mut data = HashMap::new();
 
     if count > 0 {
@@ -324,39 +328,42 @@ pub fn read_link_data<T: io::Read + io::Seek>(
                 chunk_pos.length,
                 count
             );
-            reader.seek(SeekFrom::Start(chunk_pos.offset)).unwrap();
-
-            // Figure out the size for each individual link entry
-            let _link_data_size = (chunk_pos.length / count as u64) - 4;
-
-            let end_pos = chunk_pos.offset + chunk_pos.length;
-            let data_len = read_u32(reader) as u64;
-            // trace!("data_len: {} link_data_size: {}", data_len, link_data_size);
-            // assert!(data_len == link_data_size);
-            while reader.stream_position().unwrap() < end_pos {
-                let id = read_i32(reader);
-                // println!("link id: {}", id);
-                let bytes = read_bytes(reader, data_len as usize);
-                data.insert(id, bytes);
-            }
-            // panic!("link data size: {}", link_data_size);
+            with_chunk(reader, link_data_chunk_name, |reader| {
+                reader.seek(SeekFrom::Start(chunk_pos.offset))?;
+
+                // Figure out the size for each individual link entry
+                let _link_data_size = (chunk_pos.length / count as u64) - 4;
+
+                let end_pos = chunk_pos.offset + chunk_pos.length;
+                let data_len = read_u32(reader)? as u64;
+                // trace!("data_len: {} link_data_size: {}", data_len, link_data_size);
+                // assert!(data_len == link_data_size);
+                while reader.stream_position()? < end_pos {
+                    let id = read_i32(reader)?;
+                    // println!("link id: {}", id);
+                    let bytes = read_bytes(reader, data_len as usize)?;
+                    data.insert(id, bytes);
+                }
+                // panic!("link data size: {}", link_data_size);
+                Ok(())
+            })?;
         }
     }
 
-    data
+    Ok(data)
 }
 
 fn read_all_links<R: io::Read + io::Seek>(
     toc: &ChunkFileTableOfContents,
     links: &Vec<Box<dyn LinkDefinition>>,
     ref_reader: &mut R,
-) -> HashMap<i32, TemplateLinks> {
+) -> Result<HashMap<i32, TemplateLinks>> {
     let mut ent_to_links: HashMap<i32, TemplateLinks> = HashMap::new();
 
     for link in links {
         let name = link.name();
 
-        let link_infos = read_link(&name.to_owned(), ref_reader, toc);
+        let link_infos = read_link(&name.to_owned(), ref_reader, toc)?;
 
         for link_info in link_infos {
             let to_link = ToTemplateLinkInfo {
@@ -376,14 +383,14 @@ fn read_all_links<R: io::Read + io::Seek>(
         }
     }
 
-    ent_to_links
+    Ok(ent_to_links)
 }
 
 fn read_all_properties<R: io::Read + io::Seek>(
     toc: &ChunkFileTableOfContents,
     properties: &Vec<Box<dyn PropertyDefinition<R>>>,
     ref_reader: &mut R,
-) -> HashMap<i32, Vec<Rc<Box<dyn Property>>>> {
+) -> Result<HashMap<i32, Vec<Rc<Box<dyn Property>>>>> {
     let mut ent_to_props = HashMap::new();
     for prop in properties {
         let name = prop.name();
@@ -394,33 +401,35 @@ fn read_all_properties<R: io::Read + io::Seek>(
         } else {
             let chunk = toc.get_chunk(name.to_owned()).unwrap();
             trace!("Chunk: {chunk:?}");
-            ref_reader.seek(SeekFrom::Start(chunk.offset)).unwrap();
-
-            let end_pos = chunk.offset + chunk.length;
-            while ref_reader.stream_position().unwrap() < end_pos {
-                let obj_id = read_i32(ref_reader);
-                let prop_len = read_u32(ref_reader);
-
-                let expected_pos = ref_reader.stream_position().unwrap() + prop_len as u64;
-                let prop = prop.read(ref_reader, prop_len);
-
-                ent_to_props.entry(obj_id).or_insert_with(Vec::new);
-
-                let props = ent_to_props.get_mut(&obj_id).unwrap();
-                assert!(
-                    expected_pos == ref_reader.stream_position().unwrap(),
-                    "prop name: {} len: {}, expected_pos: {}, actual_pos: {}",
-                    name,
-                    prop_len,
-                    expected_pos,
-                    ref_reader.stream_position().unwrap()
-                );
+            with_chunk(ref_reader, &name, |ref_reader| {
+                ref_reader.seek(SeekFrom::Start(chunk.offset))?;
+
+                let end_pos = chunk.offset + chunk.length;
+                while ref_reader.stream_position()? < end_pos {
+                    let obj_id = read_i32(ref_reader)?;
+                    let prop_len = read_u32(ref_reader)?;
+
+                    let expected_pos = ref_reader.stream_position()? + prop_len as u64;
+                    let prop = prop.read(ref_reader, prop_len)?;
+
+                    ent_to_props.entry(obj_id).or_insert_with(Vec::new);
+
+                    let props = ent_to_props.get_mut(&obj_id).unwrap();
+                    let actual_pos = ref_reader.stream_position()?;
+                    if expected_pos != actual_pos {
+                        return Err(Error::invalid_data(format!(
+                            "prop name: {} len: {}, expected_pos: {}, actual_pos: {}",
+                            name, prop_len, expected_pos, actual_pos
+                        )));
+                    }
 
-                props.push(Rc::new(prop));
+                    props.push(Rc::new(prop));
 
-                ref_reader.seek(SeekFrom::Start(expected_pos)).unwrap();
-            }
+                    ref_reader.seek(SeekFrom::Start(expected_pos))?;
+                }
+                Ok(())
+            })?;
         }
     }
-    ent_to_props
+    Ok(ent_to_props)
 }
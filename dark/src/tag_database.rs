@@ -3,6 +3,7 @@ use std::{collections::HashMap, io, rc::Rc};
 use crate::{
     properties::PropertyDefinition,
     ss2_common::{read_i32, read_single, read_u32},
+    Result,
 };
 
 #[derive(Debug, Clone)]
@@ -58,30 +59,30 @@ impl TagQuery {
 }
 
 impl TagDatabase {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T) -> TagDatabase {
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T) -> Result<TagDatabase> {
         // TAG DATABASE
         let mut data = Vec::new();
-        let size = read_u32(reader);
+        let size = read_u32(reader)?;
 
         for _ in 0..size {
-            data.push(TagDatabaseData::read(reader));
+            data.push(TagDatabaseData::read(reader)?);
         }
 
         // Data
 
         // Key
-        let key_size = read_u32(reader);
+        let key_size = read_u32(reader)?;
 
         let mut branches = HashMap::new();
 
         for _ in 0..key_size {
-            let key = TagDatabaseKey::read(reader);
-            let db = TagDatabase::read(reader);
+            let key = TagDatabaseKey::read(reader)?;
+            let db = TagDatabase::read(reader)?;
 
             branches.insert(key, Rc::new(db));
         }
 
-        TagDatabase { data, branches }
+        Ok(TagDatabase { data, branches })
     }
 
     pub fn query_match_all(&self, query: &TagQuery) -> Vec<i32> {
@@ -222,10 +223,10 @@ pub struct TagDatabaseData {
 }
 
 impl TagDatabaseData {
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T) -> TagDatabaseData {
-        let data = read_i32(reader);
-        let weight = read_single(reader);
-        TagDatabaseData { data, weight }
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T) -> Result<TagDatabaseData> {
+        let data = read_i32(reader)?;
+        let weight = read_single(reader)?;
+        Ok(TagDatabaseData { data, weight })
     }
 }
 
@@ -258,10 +259,10 @@ impl TagDatabaseKey {
         }
     }
 
-    pub fn read<T: io::Seek + io::Read>(reader: &mut T) -> TagDatabaseKey {
-        let key_type = read_u32(reader);
-        let min = read_i32(reader);
-        let max = read_i32(reader);
+    pub fn read<T: io::Seek + io::Read>(reader: &mut T) -> Result<TagDatabaseKey> {
+        let key_type = read_u32(reader)?;
+        let min = read_i32(reader)?;
+        let max = read_i32(reader)?;
 
         let mut enum_values = Vec::new();
         let bytes0 = min.to_le_bytes();
@@ -277,11 +278,11 @@ impl TagDatabaseKey {
             }
         }
 
-        TagDatabaseKey {
+        Ok(TagDatabaseKey {
             key_type,
             min,
             max,
             enum_values,
-        }
+        })
     }
 }
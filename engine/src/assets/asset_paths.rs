@@ -66,13 +66,12 @@ pub struct AssetPath {
 
 impl AbstractAssetPath for AssetPath {
     fn exists(&self, base_path: String, asset_name: String) -> bool {
-        let path = base_path.to_owned()
-            + "/"
-            + &self.folder_name.to_owned()
-            + "/"
-            + &asset_name.to_string();
-        let exists = Path::new(&path).exists();
-        trace!("Checking exists [{}]:{}", path, exists);
+        // Absolute folders (ie, overlays outside the data root) are used as-is
+        let path = Path::new(&base_path)
+            .join(&self.folder_name)
+            .join(&asset_name);
+        let exists = path.exists();
+        trace!("Checking exists [{}]:{}", path.display(), exists);
         exists
     }
 
@@ -81,12 +80,10 @@ impl AbstractAssetPath for AssetPath {
         base_path: String,
         asset_name: String,
     ) -> Option<RefCell<Box<dyn ReadableAndSeekable>>> {
-        let path = base_path.to_owned()
-            + "/"
-            + &self.folder_name.to_owned()
-            + "/"
-            + &asset_name.to_string();
-        trace!(" -- reading from path: {}", path);
+        let path = Path::new(&base_path)
+            .join(&self.folder_name)
+            .join(&asset_name);
+        trace!(" -- reading from path: {}", path.display());
 
         let file = File::open(path).unwrap();
         let reader = BufReader::new(file);
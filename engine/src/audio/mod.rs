@@ -17,12 +17,6 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 use crate::assets::asset_paths::ReadableAndSeekable;
 
-#[cfg(target_os = "android")]
-const BASE_PATH: &str = "/mnt/sdcard/shock2quest";
-
-#[cfg(not(target_os = "android"))]
-const BASE_PATH: &str = "../../Data";
-
 static NEXT_HANDLE_ID: AtomicU64 = AtomicU64::new(0);
 
 const SOUND_SCALE_FACTOR: f32 = 5.0;
@@ -102,8 +96,9 @@ where
     TCue: Clone,
     TAmbientKey: Hash + Eq + Copy,
 {
-    stream: OutputStream,
-    handle: OutputStreamHandle,
+    // Both are None for a silent context, which plays nothing
+    stream: Option<OutputStream>,
+    handle: Option<OutputStreamHandle>,
     sinks: Vec<Sink>,
     channel_to_last_handle: HashMap<String, u64>,
     handle_to_sink: HashMap<u64, SinkAdapter>,
@@ -130,6 +125,26 @@ where
 {
     pub fn new() -> AudioContext<TAmbientKey, TCue> {
         let (stream, handle) = rodio::OutputStream::try_default().unwrap();
+        Self::create(Some(stream), Some(handle))
+    }
+
+    ///
+    /// silent
+    ///
+    /// Create an audio context that does not open an output device. All playback
+    /// requests are dropped, which is useful for headless runs and tests.
+    pub fn silent() -> AudioContext<TAmbientKey, TCue> {
+        Self::create(None, None)
+    }
+
+    pub fn is_silent(&self) -> bool {
+        self.handle.is_none()
+    }
+
+    fn create(
+        stream: Option<OutputStream>,
+        handle: Option<OutputStreamHandle>,
+    ) -> AudioContext<TAmbientKey, TCue> {
         AudioContext {
             stream,
             handle,
@@ -168,7 +183,11 @@ where
     }
 
     pub fn set_environmental_sound(&mut self, clip: Rc<AudioClip>) -> () {
-        let sink = rodio::Sink::try_new(&self.handle).unwrap();
+        let handle = match &self.handle {
+            Some(handle) => handle,
+            None => return,
+        };
+        let sink = rodio::Sink::try_new(handle).unwrap();
         clip.add_to_sink(&sink);
         sink.set_volume(0.2);
         sink.play();
@@ -181,6 +200,10 @@ where
         current_ambient_sounds: Vec<(TAmbientKey, Vector3<f32>, Rc<AudioClip>)>,
     ) {
         println!("!!debug - audio update");
+        if self.is_silent() {
+            return;
+        }
+
         self.update_background_music();
         self.update_environmental_sounds();
 
@@ -257,7 +280,7 @@ where
         for (key, pos, clip) in &current_ambient_sounds {
             if !self.ambient_sounds.contains_key(key) {
                 let sink = rodio::SpatialSink::try_new(
-                    &self.handle,
+                    self.handle.as_ref().unwrap(),
                     [
                         pos.x / SOUND_SCALE_FACTOR,
                         pos.y / SOUND_SCALE_FACTOR,
@@ -287,7 +310,7 @@ where
                 .unwrap()
                 .next_clip(self.next_music_cue.clone());
             if let Some(next_song) = maybe_next {
-                let sink = rodio::Sink::try_new(&self.handle).unwrap();
+                let sink = rodio::Sink::try_new(self.handle.as_ref().unwrap()).unwrap();
                 next_song.add_to_sink(&sink);
                 sink.play();
                 self.next_music_cue = None;
@@ -299,7 +322,7 @@ where
     fn update_environmental_sounds(&mut self) {
         if let Some((current_sink, clip)) = &self.environmental_sink {
             if current_sink.len() == 0 {
-                let sink = rodio::Sink::try_new(&self.handle).unwrap();
+                let sink = rodio::Sink::try_new(self.handle.as_ref().unwrap()).unwrap();
                 clip.add_to_sink(&sink);
                 sink.set_volume(0.2);
                 sink.play();
@@ -369,9 +392,11 @@ pub fn test_audio<TAmbientKey: Hash + Eq + Copy, TCue: Clone>(
     let position = (context.last_left_ear_position + context.last_right_ear_position) / 2.0;
 
     let id = handle.id.clone();
-    let sink = play_audio_core(context, position, handle, maybe_channel, audio_clip);
+    let maybe_sink = play_audio_core(context, position, handle, maybe_channel, audio_clip);
 
-    context.handle_to_sink.insert(id, SinkAdapter::fixed(sink));
+    if let Some(sink) = maybe_sink {
+        context.handle_to_sink.insert(id, SinkAdapter::fixed(sink));
+    }
 }
 
 pub fn play_spatial_audio<TAmbientKey: Hash + Eq + Copy, TCue: Clone>(
@@ -383,11 +408,13 @@ pub fn play_spatial_audio<TAmbientKey: Hash + Eq + Copy, TCue: Clone>(
 ) {
     let id = handle.id.clone();
     let scaled_position = position / SOUND_SCALE_FACTOR;
-    let sink = play_audio_core(context, scaled_position, handle, maybe_channel, audio_clip);
+    let maybe_sink = play_audio_core(context, scaled_position, handle, maybe_channel, audio_clip);
 
-    context
-        .handle_to_sink
-        .insert(id, SinkAdapter::positional(sink));
+    if let Some(sink) = maybe_sink {
+        context
+            .handle_to_sink
+            .insert(id, SinkAdapter::positional(sink));
+    }
 }
 
 pub fn play_audio_core<TAmbientKey: Hash + Eq + Copy, TCue: Clone>(
@@ -396,7 +423,9 @@ pub fn play_audio_core<TAmbientKey: Hash + Eq + Copy, TCue: Clone>(
     handle: AudioHandle,
     maybe_channel: Option<AudioChannel>,
     audio_clip: Rc<AudioClip>,
-) -> SpatialSink {
+) -> Option<SpatialSink> {
+    let output_handle = context.handle.as_ref()?;
+
     if let Some(channel) = maybe_channel {
         let maybe_previous_audio = context.channel_to_last_handle.get(&channel.name);
         if let Some(audio) = maybe_previous_audio {
@@ -434,12 +463,12 @@ pub fn play_audio_core<TAmbientKey: Hash + Eq + Copy, TCue: Clone>(
         [left_ear.x, left_ear.y, left_ear.z],
         [right_ear.x, right_ear.y, right_ear.z],
     );
-    let sink = rodio::SpatialSink::try_new(&context.handle, positions.0, positions.1, positions.2)
+    let sink = rodio::SpatialSink::try_new(output_handle, positions.0, positions.1, positions.2)
         .unwrap();
     audio_clip.add_to_spatial_sink(&sink);
 
     //context.handle_to_sink.insert(handle.id, sink);
-    sink
+    Some(sink)
 
     //context.spatial_sinks.push(sink);
 }
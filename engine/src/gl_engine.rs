@@ -156,7 +156,7 @@ pub fn init_gles() -> OpenGLEngine {
     init(true, storage)
 }
 
-fn create_desktop_storage() -> Box<dyn crate::file_system::Storage> {
+pub(crate) fn create_desktop_storage() -> Box<dyn crate::file_system::Storage> {
     let bundle_file_system = Box::new(crate::file_system::DefaultFileSystem {
         root_path: Box::new(std::path::Path::new("../assets/")),
     });
@@ -0,0 +1,31 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::engine::Engine;
+use crate::engine::EngineRenderContext;
+use crate::scene::scene::Scene;
+
+// When set, GPU resources (meshes, textures) are created as empty handles,
+// so assets can be imported and scenes built without an OpenGL context.
+static IS_HEADLESS: AtomicBool = AtomicBool::new(false);
+
+pub fn is_headless() -> bool {
+    IS_HEADLESS.load(Ordering::Relaxed)
+}
+
+pub struct HeadlessEngine {
+    pub storage: Box<dyn crate::file_system::Storage>,
+}
+
+impl Engine for HeadlessEngine {
+    fn get_storage(&self) -> &Box<dyn crate::file_system::Storage> {
+        &self.storage
+    }
+
+    fn render(&self, _render_context: &EngineRenderContext, _scene: &Scene) {}
+}
+
+pub fn init_headless() -> HeadlessEngine {
+    IS_HEADLESS.store(true, Ordering::Relaxed);
+    let storage = crate::gl_engine::create_desktop_storage();
+    HeadlessEngine { storage }
+}
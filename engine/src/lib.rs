@@ -4,6 +4,7 @@ mod engine;
 pub mod file_system;
 mod font;
 mod gl_engine;
+mod headless_engine;
 pub mod importers;
 pub mod macros;
 pub mod materials;
@@ -19,12 +20,18 @@ pub mod util;
 pub use crate::engine::Engine;
 pub use crate::engine::EngineRenderContext;
 pub use crate::font::{Font, FontCharacterInfo};
+pub use crate::headless_engine::is_headless;
 
 pub fn opengl() -> Box<dyn Engine> {
     let engine = gl_engine::init_gl();
     Box::new(engine)
 }
 
+pub fn headless() -> Box<dyn Engine> {
+    let engine = headless_engine::init_headless();
+    Box::new(engine)
+}
+
 pub fn opengles() -> Box<dyn Engine> {
     let engine = gl_engine::init_gles();
     Box::new(engine)
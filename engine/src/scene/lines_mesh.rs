@@ -18,6 +18,15 @@ pub struct LinesMesh {
 pub fn create(raw_vertices: Vec<VertexPosition>) -> LinesMesh {
     let index_count = (raw_vertices.len()) as i32;
 
+    if crate::is_headless() {
+        return LinesMesh {
+            index_count,
+            vao: 0,
+            vbo: 0,
+            ebo: 0,
+        };
+    }
+
     let (mut VBO, mut VAO, mut EBO) = (0, 0, 0);
     unsafe {
         gl::GenVertexArrays(1, &mut VAO);
@@ -72,6 +81,10 @@ impl Geometry for LinesMesh {
 
 impl Drop for LinesMesh {
     fn drop(&mut self) {
+        if crate::is_headless() {
+            return;
+        }
+
         unsafe {
             gl::DeleteBuffers(1, &self.ebo);
             gl::DeleteBuffers(1, &self.vbo);
@@ -24,6 +24,15 @@ pub struct Mesh {
 pub fn create<T: Vertex>(raw_vertices: Vec<T>) -> Mesh {
     let triangle_count = (raw_vertices.len()) as i32;
 
+    if crate::is_headless() {
+        return Mesh {
+            triangle_count,
+            vao: 0,
+            vbo: 0,
+            ebo: 0,
+        };
+    }
+
     // let vertices: Vec<f32> = raw_vertices
     //     .into_iter()
     //     .flat_map(|v| {
@@ -132,6 +141,10 @@ impl Geometry for Mesh {
 
 impl Drop for Mesh {
     fn drop(&mut self) {
+        if crate::is_headless() {
+            return;
+        }
+
         unsafe {
             gl::DeleteBuffers(1, &self.ebo);
             gl::DeleteBuffers(1, &self.vbo);
@@ -70,6 +70,10 @@ impl Texture {
 
 impl Drop for Texture {
     fn drop(&mut self) {
+        if crate::is_headless() {
+            return;
+        }
+
         unsafe {
             gl::DeleteTextures(1, &self.gl_id);
         }
@@ -110,6 +114,14 @@ pub fn init_from_memory(raw_texture_data: RawTextureData) -> Texture {
 }
 
 pub fn init_from_memory2(raw_texture_data: RawTextureData, options: &TextureOptions) -> Texture {
+    if crate::is_headless() {
+        return Texture {
+            gl_id: 0,
+            width: raw_texture_data.width,
+            height: raw_texture_data.height,
+        };
+    }
+
     let mut texture = 0;
     unsafe {
         gl::GenTextures(1, &mut texture);
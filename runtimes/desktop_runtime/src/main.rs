@@ -10,11 +10,15 @@ use engine::profile;
 
 use engine::util::compute_view_matrix_from_render_context;
 use glfw::Modifiers;
+use shock2vr::command::DamagePlayerCommand;
 use shock2vr::command::LoadCommand;
 use shock2vr::command::MoveInventoryCommand;
 
 use shock2vr::command::SaveCommand;
 use shock2vr::command::SpawnItemCommand;
+use shock2vr::game_rng::random_seed;
+use shock2vr::replay::{InputPlayer, InputRecorder, ReplayHeader};
+use shock2vr::save_load::SaveThumbnail;
 
 use std::time::Instant;
 
@@ -33,6 +37,7 @@ use glfw::MouseButton;
 use shock2vr::input_context::InputContext;
 use shock2vr::time::Time;
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::mpsc::Receiver;
 use std::time::Duration;
 
@@ -113,6 +118,30 @@ struct Args {
     // count: u8,
     #[arg(short, long, default_value = None)]
     experimental: Option<Vec<String>>,
+
+    /// Record all input to the given replay file
+    #[arg(long = "record-input", default_value = None)]
+    record_input: Option<String>,
+
+    /// Play back input from the given replay file, instead of the keyboard and mouse
+    #[arg(long = "replay-input", default_value = None, conflicts_with = "record_input")]
+    replay_input: Option<String>,
+
+    /// Seed for gameplay randomness (sounds, music, AI)
+    #[arg(long, default_value = None)]
+    seed: Option<u64>,
+
+    /// Folder containing shock2.gam and the res folder. Detected automatically when not set.
+    #[arg(long = "data-root", default_value = None)]
+    data_root: Option<PathBuf>,
+
+    /// Folder to check for game files before the data root. Can be repeated, first match wins.
+    #[arg(long = "overlay")]
+    overlays: Vec<PathBuf>,
+
+    /// Folder to keep save slots in. Defaults to a `saves` folder in the data root.
+    #[arg(long = "save-folder", default_value = None)]
+    save_folder: Option<PathBuf>,
 }
 struct MouseUpdateResult {
     delta_x: f32,
@@ -229,12 +258,49 @@ pub fn main() {
     let experimental_features: HashSet<String> =
         args.experimental.unwrap_or(vec![]).into_iter().collect();
 
-    let (mission, spawn_location) = parse_mission(&args.mission);
+    let mut maybe_input_player = args
+        .replay_input
+        .map(|path| InputPlayer::open(&path).expect("Unable to open input replay"));
+
+    let (mission, spawn_location, save_file, seed) = match &maybe_input_player {
+        Some(player) => {
+            let header = player.header();
+            (
+                header.mission.clone(),
+                header.spawn_location.clone(),
+                header.save_file.clone(),
+                Some(header.seed),
+            )
+        }
+        None => {
+            let (mission, spawn_location) = parse_mission(&args.mission);
+            // A recording needs a known seed to be replayable
+            let seed = match (&args.record_input, args.seed) {
+                (Some(_), None) => Some(random_seed()),
+                (_, seed) => seed,
+            };
+            (mission, spawn_location, args.save_file, seed)
+        }
+    };
+
+    let mut maybe_input_recorder = args.record_input.map(|path| {
+        let header = ReplayHeader::new(
+            mission.clone(),
+            spawn_location.clone(),
+            save_file.clone(),
+            seed.unwrap(),
+        );
+        InputRecorder::create(&path, &header).expect("Unable to create input recording")
+    });
 
     let options = GameOptions {
+        data_root: args.data_root,
+        data_overlays: args.overlays,
         mission,
         spawn_location,
-        save_file: args.save_file,
+        save_file,
+        save_folder: args.save_folder,
+        seed,
         debug_draw: args.debug_draw,
         debug_physics: args.debug_physics,
         debug_portals: args.debug_portals,
@@ -242,7 +308,13 @@ pub fn main() {
         experimental_features,
         ..GameOptions::default()
     };
-    let mut game = shock2vr::Game::init(file_system, options);
+    let mut game = match shock2vr::Game::init(file_system, options) {
+        Ok(game) => game,
+        Err(err) => {
+            eprintln!("Unable to start game: {}", err);
+            std::process::exit(1);
+        }
+    };
     // FOR SCREENSHOT
     // let mut camera_context = CameraContext {
     //     camera_offset: cgmath::Vector3::new(1.25, -14.0, -24.0),
@@ -289,6 +361,24 @@ pub fn main() {
             total: Duration::from_secs_f32(time - start_time),
         };
 
+        let (time, input_context, commands) = match maybe_input_player.as_mut() {
+            Some(player) => match player.next_frame() {
+                Some(frame) => frame,
+                None => {
+                    println!("Input replay finished");
+                    window.set_should_close(true);
+                    continue;
+                }
+            },
+            None => (time, input_context, commands),
+        };
+
+        if let Some(recorder) = maybe_input_recorder.as_mut() {
+            recorder
+                .record(&time, &input_context, &commands)
+                .expect("Unable to write input recording");
+        }
+
         profile!("game.update", game.update(&time, &input_context, commands));
 
         let screen_size = vec2(SCR_WIDTH as f32, SCR_HEIGHT as f32);
@@ -326,11 +416,39 @@ pub fn main() {
 
         profile!("engine.render", engine.render(&render_context, &scene));
 
+        // Grab the frame for a save that was just made, before it is swapped out
+        if game.wants_save_thumbnail() {
+            let (width, height) = window.get_framebuffer_size();
+            game.set_save_thumbnail(capture_save_thumbnail(width as u32, height as u32));
+        }
+
         // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
         // -------------------------------------------------------------------------------
         window.swap_buffers();
         glfw.poll_events();
     }
+
+    if let Some(mut recorder) = maybe_input_recorder {
+        recorder.flush().expect("Unable to write input recording");
+        println!("Recorded {} frames of input", recorder.frame_count());
+    }
+}
+
+fn capture_save_thumbnail(width: u32, height: u32) -> Option<SaveThumbnail> {
+    let mut pixels = vec![0; (width as usize) * (height as usize) * 3];
+    unsafe {
+        gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+        gl::ReadPixels(
+            0,
+            0,
+            width as i32,
+            height as i32,
+            gl::RGB,
+            gl::UNSIGNED_BYTE,
+            pixels.as_mut_ptr() as *mut std::ffi::c_void,
+        );
+    }
+    SaveThumbnail::from_frame(width, height, &pixels)
 }
 
 fn parse_mission(mission: &str) -> (String, SpawnLocation) {
@@ -368,6 +486,7 @@ fn parse_mission(mission: &str) -> (String, SpawnLocation) {
 }
 
 struct InputState {
+    damage_player_pressed: bool,
     quick_load_pressed: bool,
     quick_save_pressed: bool,
     space_pressed: bool,
@@ -376,6 +495,7 @@ struct InputState {
 impl InputState {
     pub fn new() -> Self {
         Self {
+            damage_player_pressed: false,
             quick_load_pressed: false,
             quick_save_pressed: false,
             space_pressed: false,
@@ -583,6 +703,13 @@ fn process_events(
         }
     }
 
+    if window.get_key(Key::K) == Action::Press && is_alt_pressed {
+        input_state.damage_player_pressed = true;
+        if !last_input_state.damage_player_pressed {
+            commands.push(Box::new(DamagePlayerCommand::new(10.0)));
+        }
+    }
+
     input_state.is_crouching = window.get_key(Key::LeftControl) == Action::Press;
 
     if window.get_key(Key::I) == Action::Press {
@@ -0,0 +1,107 @@
+// Headless runtime - drives the game loop without a window, GPU, or audio device.
+// This is useful for running missions on CI, and for asserting on the state of the world
+// after simulating a number of frames.
+
+use std::time::Duration;
+
+use engine::Engine;
+use shock2vr::command::Command;
+use shock2vr::input_context::InputContext;
+use shock2vr::replay::InputPlayer;
+use shock2vr::time::Time;
+use shock2vr::{Game, GameInitError, GameOptions};
+
+pub const DEFAULT_TIMESTEP: Duration = Duration::from_micros(16_667);
+
+pub struct HeadlessGame {
+    // Kept alive for the lifetime of the game, as it owns the storage
+    _engine: Box<dyn Engine>,
+    game: Game,
+    timestep: Duration,
+    time: Time,
+    frame: u64,
+}
+
+impl HeadlessGame {
+    pub fn new(options: GameOptions, timestep: Duration) -> Result<HeadlessGame, GameInitError> {
+        let engine = engine::headless();
+        let file_system = engine.get_storage().external_filesystem();
+        // Keep saves (ie, autosaves from level transitions) out of the data folder
+        let save_folder = options
+            .save_folder
+            .clone()
+            .or_else(|| Some(std::env::temp_dir().join("shock2quest-headless-saves")));
+        let options = GameOptions {
+            headless: true,
+            save_folder,
+            ..options
+        };
+        let game = Game::init(file_system, options)?;
+
+        Ok(HeadlessGame {
+            _engine: engine,
+            game,
+            timestep,
+            time: Time::default(),
+            frame: 0,
+        })
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn game_mut(&mut self) -> &mut Game {
+        &mut self.game
+    }
+
+    pub fn time(&self) -> &Time {
+        &self.time
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    ///
+    /// step
+    ///
+    /// Advance the simulation by a single fixed timestep, using the provided input
+    pub fn step(&mut self, input_context: &InputContext, commands: Vec<Box<dyn Command>>) {
+        self.time = Time {
+            elapsed: self.timestep,
+            total: self.time.total + self.timestep,
+        };
+        self.game.update(&self.time, input_context, commands);
+        self.frame += 1;
+    }
+
+    ///
+    /// run_frames
+    ///
+    /// Advance the simulation by `frames` timesteps, with no input from the player
+    pub fn run_frames(&mut self, frames: u64) {
+        let input_context = InputContext::default();
+        for _ in 0..frames {
+            self.step(&input_context, vec![]);
+        }
+    }
+
+    ///
+    /// step_replay
+    ///
+    /// Advance the simulation using the next frame of a recorded input replay.
+    /// The recorded time step is used instead of the fixed timestep.
+    /// Returns false once the replay is finished.
+    pub fn step_replay(&mut self, player: &mut InputPlayer) -> bool {
+        match player.next_frame() {
+            Some((time, input_context, commands)) => {
+                self.game.update(&time, &input_context, commands);
+                self.time = time;
+                self.frame += 1;
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use dark::properties::PropPosition;
+use headless_runtime::HeadlessGame;
+use shipyard::{IntoIter, View};
+use shock2vr::replay::InputPlayer;
+use shock2vr::GameOptions;
+use shock2vr::SpawnLocation;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Mission file to load, ie earth.mis
+    #[arg(short, long, default_value = "earth.mis")]
+    mission: String,
+
+    /// Number of frames to simulate
+    #[arg(short, long, default_value_t = 600)]
+    frames: u64,
+
+    /// Simulation rate, in frames per second
+    #[arg(long = "tick-rate", default_value_t = 60.0)]
+    tick_rate: f32,
+
+    #[arg(short, long, default_value = None)]
+    save_file: Option<String>,
+
+    #[arg(short, long, default_value = None)]
+    experimental: Option<Vec<String>>,
+
+    /// Drive the simulation from a recorded input replay, instead of running idle frames
+    #[arg(short, long, default_value = None)]
+    replay: Option<String>,
+
+    /// Seed for gameplay randomness (sounds, music, AI)
+    #[arg(long, default_value = None)]
+    seed: Option<u64>,
+
+    /// Folder containing shock2.gam and the res folder. Detected automatically when not set.
+    #[arg(long = "data-root", default_value = None)]
+    data_root: Option<PathBuf>,
+
+    /// Folder to check for game files before the data root. Can be repeated, first match wins.
+    #[arg(long = "overlay")]
+    overlays: Vec<PathBuf>,
+}
+
+pub fn main() {
+    tracing_subscriber::fmt::init();
+    let args = Args::parse();
+
+    let experimental_features: HashSet<String> =
+        args.experimental.unwrap_or(vec![]).into_iter().collect();
+
+    let mut maybe_input_player = args
+        .replay
+        .map(|path| InputPlayer::open(&path).expect("Unable to open input replay"));
+
+    let options = match &maybe_input_player {
+        Some(player) => {
+            let header = player.header();
+            GameOptions {
+                mission: header.mission.clone(),
+                spawn_location: header.spawn_location.clone(),
+                save_file: header.save_file.clone(),
+                seed: Some(header.seed),
+                render_particles: false,
+                experimental_features,
+                ..GameOptions::default()
+            }
+        }
+        None => GameOptions {
+            mission: args.mission,
+            spawn_location: SpawnLocation::MapDefault,
+            save_file: args.save_file,
+            seed: args.seed,
+            render_particles: false,
+            experimental_features,
+            ..GameOptions::default()
+        },
+    };
+    let options = GameOptions {
+        data_root: args.data_root,
+        data_overlays: args.overlays,
+        ..options
+    };
+
+    let timestep = Duration::from_secs_f32(1.0 / args.tick_rate);
+
+    let start = Instant::now();
+    let mut game = match HeadlessGame::new(options, timestep) {
+        Ok(game) => game,
+        Err(err) => {
+            eprintln!("Unable to start game: {}", err);
+            std::process::exit(1);
+        }
+    };
+    println!(
+        "loaded {} in {:?}",
+        game.game().mission_name(),
+        start.elapsed()
+    );
+
+    let start = Instant::now();
+    match maybe_input_player.as_mut() {
+        Some(player) => while game.step_replay(player) {},
+        None => game.run_frames(args.frames),
+    }
+    println!(
+        "simulated {} frames ({:?} game time) in {:?}",
+        game.frame(),
+        game.time().total,
+        start.elapsed()
+    );
+
+    let v_position = game.game().world().borrow::<View<PropPosition>>().unwrap();
+    let positioned_entities = (&v_position).iter().count();
+    println!("entities with position: {}", positioned_entities);
+    println!("player position: {:?}", game.game().player_position());
+}
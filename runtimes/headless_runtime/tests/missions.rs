@@ -0,0 +1,234 @@
+// These tests load real mission data, so they are skipped when the Data folder is not available.
+
+use cgmath::{vec2, vec3, InnerSpace};
+use dark::properties::{PropHitPoints, PropPosition};
+use headless_runtime::{HeadlessGame, DEFAULT_TIMESTEP};
+use shipyard::{IntoIter, UniqueViewMut, View, ViewMut};
+use shock2vr::command::{DamagePlayerCommand, SaveCommand};
+use shock2vr::input_context::InputContext;
+use shock2vr::player_stats::{Currency, PlayerStats, RESURRECTION_NANITE_COST};
+use shock2vr::{DataRoot, GameInitError, GameOptions, MissionLoadError};
+
+fn has_game_data() -> bool {
+    let has_data = DataRoot::detect().is_some();
+    if !has_data {
+        eprintln!("Skipping test - game data not found");
+    }
+    has_data
+}
+
+fn load_mission(mission: &str) -> HeadlessGame {
+    let options = GameOptions {
+        mission: mission.to_owned(),
+        render_particles: false,
+        ..GameOptions::default()
+    };
+    HeadlessGame::new(options, DEFAULT_TIMESTEP).unwrap()
+}
+
+// Each test gets its own, empty save folder, so saves from other tests aren't picked up
+fn load_mission_with_empty_saves(mission: &str, test_name: &str) -> HeadlessGame {
+    let save_folder = std::env::temp_dir().join(format!("shock2quest-test-{test_name}"));
+    let _ = std::fs::remove_dir_all(&save_folder);
+
+    let options = GameOptions {
+        mission: mission.to_owned(),
+        render_particles: false,
+        save_folder: Some(save_folder),
+        ..GameOptions::default()
+    };
+    HeadlessGame::new(options, DEFAULT_TIMESTEP).unwrap()
+}
+
+fn walk_forward(game: &mut HeadlessGame, frames: u64) {
+    let mut input_context = InputContext::default();
+    input_context.right_hand.thumbstick = vec2(0.0, 1.0);
+    for _ in 0..frames {
+        game.step(&input_context, vec![]);
+    }
+}
+
+fn kill_player(game: &mut HeadlessGame) {
+    let max_hit_points = game.game().player_stats().max_hit_points();
+    game.step(
+        &InputContext::default(),
+        vec![Box::new(DamagePlayerCommand::new(
+            max_hit_points as f32 + 10.0,
+        ))],
+    );
+    game.run_frames(10);
+}
+
+#[test]
+fn earth_runs_headless() {
+    if !has_game_data() {
+        return;
+    }
+
+    let mut game = load_mission("earth.mis");
+    let start_position = game.game().player_position();
+    game.run_frames(120);
+
+    assert_eq!(game.frame(), 120);
+    assert_eq!(game.game().mission_name(), "earth.mis");
+
+    // With no input, the player should stay put (modulo settling onto the floor)
+    let end_position = game.game().player_position();
+    assert!((end_position - start_position).magnitude() < 1.0);
+
+    let v_position = game.game().world().borrow::<View<PropPosition>>().unwrap();
+    assert!((&v_position).iter().count() > 0);
+}
+
+#[test]
+fn medsci1_runs_headless() {
+    if !has_game_data() {
+        return;
+    }
+
+    let mut game = load_mission("medsci1.mis");
+    game.run_frames(120);
+
+    assert_eq!(game.frame(), 120);
+    assert!(game.game().player_position().x.is_finite());
+}
+
+#[test]
+fn missing_mission_is_an_error() {
+    if !has_game_data() {
+        return;
+    }
+
+    let options = GameOptions {
+        mission: "not_a_mission.mis".to_owned(),
+        ..GameOptions::default()
+    };
+    let result = HeadlessGame::new(options, DEFAULT_TIMESTEP);
+
+    assert!(matches!(
+        result,
+        Err(GameInitError::Mission(
+            MissionLoadError::MissingMission { .. }
+        ))
+    ));
+}
+
+#[test]
+fn missing_game_data_is_an_error() {
+    // Only meaningful when the Data folder isn't available
+    if DataRoot::detect().is_some() {
+        return;
+    }
+
+    let result = HeadlessGame::new(GameOptions::default(), DEFAULT_TIMESTEP);
+
+    assert!(matches!(
+        result,
+        Err(GameInitError::DataRootNotFound { .. })
+    ));
+}
+
+#[test]
+fn player_resurrects_at_activated_machine() {
+    if !has_game_data() {
+        return;
+    }
+
+    let mut game = load_mission_with_empty_saves("medsci1.mis", "resurrect-machine");
+    game.run_frames(10);
+
+    // Activate a machine where the player is standing, and make sure they can pay for it
+    let resurrection_point = game.game().player_position() + vec3(0.0, 0.1, 0.0);
+    {
+        let mut player_stats = game
+            .game()
+            .world()
+            .borrow::<UniqueViewMut<PlayerStats>>()
+            .unwrap();
+        player_stats.activate_resurrection_machine("medsci1.mis", resurrection_point);
+        player_stats.award_currency(Currency::Nanites, RESURRECTION_NANITE_COST);
+    }
+
+    walk_forward(&mut game, 60);
+    kill_player(&mut game);
+
+    let player_stats = game.game().player_stats();
+    assert!(!player_stats.is_dead());
+    assert_eq!(player_stats.hit_points(), player_stats.max_hit_points());
+    assert_eq!(player_stats.balance(Currency::Nanites), 0);
+    assert_eq!(game.game().mission_name(), "medsci1.mis");
+    assert!((game.game().player_position() - resurrection_point).magnitude() < 1.0);
+}
+
+#[test]
+fn player_without_machine_loads_latest_save() {
+    if !has_game_data() {
+        return;
+    }
+
+    let mut game = load_mission_with_empty_saves("medsci1.mis", "resurrect-load-save");
+    game.run_frames(10);
+
+    let saved_position = game.game().player_position();
+    game.step(&InputContext::default(), vec![Box::new(SaveCommand::new())]);
+    assert_eq!(game.game().save_slots().list().unwrap().len(), 1);
+
+    walk_forward(&mut game, 60);
+    kill_player(&mut game);
+
+    let player_stats = game.game().player_stats();
+    assert!(!player_stats.is_dead());
+    assert_eq!(game.game().mission_name(), "medsci1.mis");
+    assert!((game.game().player_position() - saved_position).magnitude() < 1.0);
+}
+
+#[test]
+fn player_without_machine_or_save_restarts_mission() {
+    if !has_game_data() {
+        return;
+    }
+
+    let mut game = load_mission_with_empty_saves("medsci1.mis", "resurrect-restart");
+    game.run_frames(10);
+    let start_position = game.game().player_position();
+
+    walk_forward(&mut game, 60);
+    kill_player(&mut game);
+
+    let player_stats = game.game().player_stats();
+    assert_eq!(player_stats.hit_points(), player_stats.max_hit_points());
+    assert_eq!(game.game().mission_name(), "medsci1.mis");
+    assert!((game.game().player_position() - start_position).magnitude() < 1.0);
+}
+
+#[test]
+fn restart_resets_the_mission_and_the_player() {
+    if !has_game_data() {
+        return;
+    }
+
+    let mut game = load_mission_with_empty_saves("medsci1.mis", "resurrect-restart-state");
+    game.run_frames(10);
+
+    // Change the world and the player, so there is something for the restart to undo
+    {
+        let world = game.game().world();
+        let mut v_hit_points = world.borrow::<ViewMut<PropHitPoints>>().unwrap();
+        for hit_points in (&mut v_hit_points).iter() {
+            hit_points.hit_points = 1;
+        }
+        let mut player_stats = world.borrow::<UniqueViewMut<PlayerStats>>().unwrap();
+        player_stats.award_currency(Currency::Nanites, 50);
+    }
+
+    kill_player(&mut game);
+
+    let v_hit_points = game.game().world().borrow::<View<PropHitPoints>>().unwrap();
+    assert!(v_hit_points
+        .iter()
+        .any(|hit_points| hit_points.hit_points > 1));
+    assert_eq!(game.game().player_stats().balance(Currency::Nanites), 0);
+
+    // Nothing new to go back to, so nothing is autosaved
+    assert!(game.game().save_slots().list().unwrap().is_empty());
+}
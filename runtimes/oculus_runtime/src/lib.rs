@@ -421,7 +421,8 @@ fn main() {
         experimental_features,
         ..GameOptions::default()
     };
-    let mut game = shock2vr::Game::init(&file_system, options);
+    let mut game = shock2vr::Game::init(&file_system, options)
+        .unwrap_or_else(|err| panic!("Unable to start game: {}", err));
 
     let mut camera_pos = vec3(0.0, 5.0, 10.0);
 
@@ -0,0 +1,237 @@
+///
+/// chunk_inspector.rs
+///
+/// Inspect the chunk files used by the dark engine (.mis, .gam, .sav): list the table of contents,
+/// dump the raw bytes of a chunk, or decode a chunk with the matching property / link definition.
+///
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+};
+
+use dark::{
+    properties::{PropertyDefinition, ToTemplateLinkInfo},
+    ss2_chunk_file_reader::{self, ChunkFileTableOfContents},
+    ss2_common::{read_i32, read_u32},
+    ss2_entity_info::{self, Link},
+};
+use serde_json::json;
+use shipyard::World;
+
+use crate::OutputFormat;
+
+pub fn list(file_name: &str, format: OutputFormat) {
+    let mut reader = open(file_name);
+    let toc = read_table_of_contents(&mut reader, file_name);
+    let chunks = toc.chunks();
+
+    match format {
+        OutputFormat::Table => {
+            println!(
+                "{:<12} {:>10} {:>10} {:>8}",
+                "chunk", "offset", "length", "version"
+            );
+            for (name, chunk) in chunks {
+                println!(
+                    "{:<12} {:>10} {:>10} {:>8}",
+                    name,
+                    chunk.offset,
+                    chunk.length,
+                    format!("{}.{}", chunk.version_high, chunk.version_low)
+                );
+            }
+        }
+        OutputFormat::Json => {
+            let entries = chunks
+                .iter()
+                .map(|(name, chunk)| {
+                    json!({
+                        "name": name,
+                        "offset": chunk.offset,
+                        "length": chunk.length,
+                        "version_high": chunk.version_high,
+                        "version_low": chunk.version_low,
+                    })
+                })
+                .collect::<Vec<serde_json::Value>>();
+            println!("{}", serde_json::to_string_pretty(&entries).unwrap());
+        }
+    }
+}
+
+pub fn hex_dump(file_name: &str, chunk_name: &str) {
+    let mut reader = open(file_name);
+    let toc = read_table_of_contents(&mut reader, file_name);
+    let bytes = read_chunk_bytes(&mut reader, &toc, chunk_name);
+
+    for (row, line) in bytes.chunks(16).enumerate() {
+        let hex = line
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<String>>()
+            .join(" ");
+        let ascii = line
+            .iter()
+            .map(|b| {
+                if b.is_ascii_graphic() || *b == b' ' {
+                    *b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+        println!("{:08x}  {:<47}  {}", row * 16, hex, ascii);
+    }
+}
+
+pub fn decode(file_name: &str, chunk_name: &str) {
+    let mut reader = open(file_name);
+    let toc = read_table_of_contents(&mut reader, file_name);
+
+    if !toc.has_chunk(chunk_name.to_owned()) {
+        panic!("chunk {} not found in {}", chunk_name, file_name);
+    }
+
+    let (properties, links, links_with_data) = dark::properties::get::<BufReader<File>>();
+
+    let decoded = if let Some(property) = properties.iter().find(|p| p.name() == chunk_name) {
+        decode_property(&mut reader, &toc, property.as_ref(), chunk_name)
+            .unwrap_or_else(|err| panic!("{}", err.in_file(file_name)))
+    } else if let Some(link) = links.iter().find(|l| l.name() == chunk_name) {
+        let raw_links = ss2_entity_info::read_link(chunk_name, &mut reader, &toc)
+            .unwrap_or_else(|err| panic!("{}", err.in_file(file_name)));
+        let decoded_links = raw_links
+            .iter()
+            .map(|raw_link| {
+                let converted = link.convert(to_link_info(raw_link));
+                link_to_json(raw_link, Some(json!(converted.link)))
+            })
+            .collect::<Vec<serde_json::Value>>();
+        json!(decoded_links)
+    } else if let Some(link) = links_with_data
+        .iter()
+        .find(|l| l.link_chunk_name() == chunk_name || l.link_data_chunk_name() == chunk_name)
+    {
+        let raw_links = ss2_entity_info::read_link(&link.link_chunk_name(), &mut reader, &toc)
+            .unwrap_or_else(|err| panic!("{}", err.in_file(file_name)));
+        let link_data = ss2_entity_info::read_link_data(
+            &link.link_data_chunk_name(),
+            &mut reader,
+            &toc,
+            raw_links.len() as u32,
+        )
+        .unwrap_or_else(|err| panic!("{}", err.in_file(file_name)));
+        let decoded_links = raw_links
+            .iter()
+            .map(|raw_link| {
+                let converted = link_data.get(&raw_link.id).map(|data| {
+                    let converted = link
+                        .convert(data.clone(), data.len() as u32, to_link_info(raw_link))
+                        .unwrap_or_else(|err| panic!("{}", err.in_file(file_name)));
+                    json!(converted.link)
+                });
+                link_to_json(raw_link, converted)
+            })
+            .collect::<Vec<serde_json::Value>>();
+        json!(decoded_links)
+    } else if chunk_name.starts_with("L$") {
+        // All link chunks share the same layout, so we can at least show the raw links
+        let raw_links = ss2_entity_info::read_link(chunk_name, &mut reader, &toc)
+            .unwrap_or_else(|err| panic!("{}", err.in_file(file_name)));
+        let decoded_links = raw_links
+            .iter()
+            .map(|raw_link| link_to_json(raw_link, None))
+            .collect::<Vec<serde_json::Value>>();
+        json!(decoded_links)
+    } else {
+        panic!(
+            "no property or link definition for chunk {} - try dumping it as hex instead",
+            chunk_name
+        );
+    };
+
+    println!("{}", serde_json::to_string_pretty(&decoded).unwrap());
+}
+
+fn open(file_name: &str) -> BufReader<File> {
+    let file = File::open(crate::resolve_data_file(file_name)).unwrap();
+    BufReader::new(file)
+}
+
+fn read_table_of_contents(
+    reader: &mut BufReader<File>,
+    file_name: &str,
+) -> ChunkFileTableOfContents {
+    ss2_chunk_file_reader::read_table_of_contents(reader)
+        .unwrap_or_else(|err| panic!("{}", err.in_file(file_name)))
+}
+
+fn read_chunk_bytes(
+    reader: &mut BufReader<File>,
+    toc: &ChunkFileTableOfContents,
+    chunk_name: &str,
+) -> Vec<u8> {
+    let chunk = toc
+        .get_chunk(chunk_name.to_owned())
+        .unwrap_or_else(|| panic!("chunk {} not found", chunk_name));
+    reader.seek(SeekFrom::Start(chunk.offset)).unwrap();
+
+    let mut bytes = vec![0; chunk.length as usize];
+    reader.read_exact(&mut bytes).unwrap();
+    bytes
+}
+
+// Read each (object id, property) entry in the chunk, and then use the property definition's
+// serializer to turn them into json, keyed by object id
+fn decode_property(
+    reader: &mut BufReader<File>,
+    toc: &ChunkFileTableOfContents,
+    property: &dyn PropertyDefinition<BufReader<File>>,
+    chunk_name: &str,
+) -> dark::Result<serde_json::Value> {
+    let chunk = toc.require_chunk(chunk_name)?;
+    reader.seek(SeekFrom::Start(chunk.offset))?;
+
+    let mut world = World::new();
+    let mut entity_to_obj_id = HashMap::new();
+    let end_pos = chunk.offset + chunk.length;
+    while reader.stream_position()? < end_pos {
+        let obj_id = read_i32(reader)?;
+        let prop_len = read_u32(reader)?;
+        let expected_pos = reader.stream_position()? + prop_len as u64;
+
+        let prop = property.read(reader, prop_len)?;
+        let entity = world.add_entity(());
+        prop.initialize(&mut world, entity);
+        entity_to_obj_id.insert(entity.inner(), obj_id);
+
+        reader.seek(SeekFrom::Start(expected_pos))?;
+    }
+
+    let obj_id_to_value = property
+        .serialize(&world)
+        .into_iter()
+        .filter_map(|(entity, value)| entity_to_obj_id.get(&entity).map(|id| (*id, value)))
+        .collect::<BTreeMap<i32, serde_json::Value>>();
+
+    Ok(json!(obj_id_to_value))
+}
+
+fn to_link_info(raw_link: &Link) -> ToTemplateLinkInfo {
+    ToTemplateLinkInfo {
+        id: raw_link.id,
+        dest_template_id: raw_link.dest,
+        flavor: raw_link.flavor,
+    }
+}
+
+fn link_to_json(raw_link: &Link, data: Option<serde_json::Value>) -> serde_json::Value {
+    json!({
+        "id": raw_link.id,
+        "src": raw_link.src,
+        "dest": raw_link.dest,
+        "flavor": raw_link.flavor,
+        "data": data,
+    })
+}
@@ -0,0 +1,111 @@
+///
+/// script_coverage.rs
+///
+/// Loads a mission (along with the gamesys), and reports which of the scripts referenced
+/// by its entities are implemented, no-ops, known-but-unimplemented, or missing entirely.
+///
+use std::{fs::File, io::BufReader};
+
+use dark::{
+    gamesys,
+    properties::{PropScripts, PropTemplateId},
+    ss2_entity_info::{self, SystemShock2EntityInfo},
+};
+use engine::assets::{asset_cache::AssetCache, asset_paths::AssetPath};
+use shipyard::{IntoIter, View, World};
+use shock2vr::{ScriptCoverageReport, ScriptRegistry, ScriptStatus, ZipAssetPath};
+
+use crate::{data_root, resource_path, OutputFormat};
+
+pub fn run(mission: &str, format: OutputFormat) {
+    // Mission loading touches textures, so make sure we don't need a GL context for them
+    let _engine = engine::headless();
+
+    let asset_paths = AssetPath::combine(vec![
+        ZipAssetPath::new(resource_path("res/fam.crf")).unwrap(),
+        AssetPath::folder("".to_owned()),
+    ]);
+    let mut asset_cache = AssetCache::new(
+        data_root().root().to_string_lossy().to_string(),
+        asset_paths,
+    );
+
+    let (properties, links, links_with_data) = dark::properties::get();
+
+    let game_file = File::open(resource_path("shock2.gam")).unwrap();
+    let mut game_reader = BufReader::new(game_file);
+    let gamesys = gamesys::read(&mut game_reader, &links, &links_with_data, &properties)
+        .unwrap_or_else(|err| panic!("{}", err.in_file("shock2.gam")));
+
+    let mission_file = File::open(resource_path(mission)).unwrap();
+    let mut mission_reader = BufReader::new(mission_file);
+    let level = dark::mission::read(
+        &mut asset_cache,
+        &mut mission_reader,
+        &gamesys,
+        &links,
+        &links_with_data,
+        &properties,
+    )
+    .unwrap_or_else(|err| panic!("{}", err.in_file(mission)));
+
+    let entity_info = ss2_entity_info::merge_with_gamesys(&level.entity_info, &gamesys);
+    let mut world = World::new();
+    for template_id in level.entity_info.entity_to_properties.keys() {
+        initialize_entity(&mut world, &entity_info, *template_id);
+    }
+
+    let registry = ScriptRegistry::with_builtin_scripts();
+    let report =
+        world.run(|v_scripts: View<PropScripts>| registry.coverage_report(v_scripts.iter()));
+
+    match format {
+        OutputFormat::Table => print_table(mission, &report),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+    }
+}
+
+// Apply the properties of the entity, and all its ancestors, in the same order the game does,
+// so that inherited scripts are picked up
+fn initialize_entity(world: &mut World, entity_info: &SystemShock2EntityInfo, template_id: i32) {
+    let hierarchy = ss2_entity_info::get_hierarchy(entity_info);
+    let mut ancestors = ss2_entity_info::get_ancestors(hierarchy, &template_id);
+    ancestors.push(template_id);
+
+    let entity = world.add_entity(PropTemplateId { template_id });
+    for ancestor_id in ancestors {
+        if let Some(props) = entity_info.entity_to_properties.get(&ancestor_id) {
+            for prop in props {
+                prop.initialize(world, entity);
+            }
+        }
+    }
+}
+
+fn print_table(mission: &str, report: &ScriptCoverageReport) {
+    println!("{:<14} {:>8}  {}", "status", "entities", "script");
+    for entry in &report.entries {
+        println!(
+            "{:<14} {:>8}  {}",
+            format!("{:?}", entry.status),
+            entry.entity_count,
+            entry.name
+        );
+    }
+
+    println!();
+    println!("{mission}:");
+    for status in [
+        ScriptStatus::Implemented,
+        ScriptStatus::Noop,
+        ScriptStatus::Unimplemented,
+        ScriptStatus::Missing,
+    ] {
+        println!(
+            "  {:<14} {} scripts, {} entities",
+            format!("{:?}", status),
+            report.script_count(status),
+            report.entity_count(status)
+        );
+    }
+}
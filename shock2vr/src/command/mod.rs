@@ -1,19 +1,29 @@
 mod move_inventory_command;
+mod recorded_command;
 mod spawn_item_command;
 
 pub use move_inventory_command::*;
-use shipyard::World;
+pub use recorded_command::*;
+use shipyard::{UniqueView, World};
 pub use spawn_item_command::*;
 
 use std::fmt;
 
-use crate::scripts::Effect;
+use crate::{
+    mission::PlayerInfo,
+    save_load::{SaveSlotKind, QUICKSAVE_SLOT_ID},
+    scripts::{Effect, Message, MessagePayload},
+};
 
 pub trait Command: fmt::Debug {
     fn execute(&self, world: &World) -> Effect;
+
+    // Serializable form of the command, used for input recording and replay
+    fn to_recorded(&self) -> RecordedCommand;
 }
 
 // SaveCommand
+// Quicksaves the game
 #[derive(Debug)]
 pub struct SaveCommand {}
 
@@ -26,12 +36,17 @@ impl SaveCommand {
 impl Command for SaveCommand {
     fn execute(&self, _world: &World) -> Effect {
         Effect::GlobalEffect(crate::scripts::GlobalEffect::Save {
-            file_name: "save1.sav".to_owned(),
+            kind: SaveSlotKind::Quick,
         })
     }
+
+    fn to_recorded(&self) -> RecordedCommand {
+        RecordedCommand::Save
+    }
 }
 
 // LoadCommand
+// Loads the quicksave
 #[derive(Debug)]
 pub struct LoadCommand {}
 
@@ -44,9 +59,13 @@ impl LoadCommand {
 impl Command for LoadCommand {
     fn execute(&self, _world: &World) -> Effect {
         Effect::GlobalEffect(crate::scripts::GlobalEffect::Load {
-            file_name: "save1.sav".to_owned(),
+            slot_id: QUICKSAVE_SLOT_ID.to_owned(),
         })
     }
+
+    fn to_recorded(&self) -> RecordedCommand {
+        RecordedCommand::Load
+    }
 }
 
 #[derive(Debug)]
@@ -65,4 +84,41 @@ impl Command for TransitionLevelCommand {
             loc: None,
         })
     }
+
+    fn to_recorded(&self) -> RecordedCommand {
+        RecordedCommand::TransitionLevel
+    }
+}
+
+// DamagePlayerCommand
+// Hurts the player, as if they were hit by an attack - useful for testing death and resurrection
+#[derive(Debug)]
+pub struct DamagePlayerCommand {
+    amount: f32,
+}
+
+impl DamagePlayerCommand {
+    pub fn new(amount: f32) -> DamagePlayerCommand {
+        DamagePlayerCommand { amount }
+    }
+}
+
+impl Command for DamagePlayerCommand {
+    fn execute(&self, world: &World) -> Effect {
+        let player_entity = world.borrow::<UniqueView<PlayerInfo>>().unwrap().entity_id;
+        Effect::Send {
+            msg: Message {
+                to: player_entity,
+                payload: MessagePayload::Damage {
+                    amount: self.amount,
+                },
+            },
+        }
+    }
+
+    fn to_recorded(&self) -> RecordedCommand {
+        RecordedCommand::DamagePlayer {
+            amount: self.amount,
+        }
+    }
 }
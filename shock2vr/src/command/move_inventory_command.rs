@@ -11,7 +11,7 @@ use crate::{
     PlayerInfo,
 };
 
-use super::Command;
+use super::{Command, RecordedCommand};
 // SpawnItemCommand
 #[derive(Debug)]
 pub struct MoveInventoryCommand {
@@ -38,4 +38,10 @@ impl Command for MoveInventoryCommand {
             rotation: Quaternion::from_angle_y(cgmath::Deg(180.0)) * rot,
         }
     }
+
+    fn to_recorded(&self) -> RecordedCommand {
+        RecordedCommand::MoveInventory {
+            head_rotation: self.head_rotation,
+        }
+    }
 }
@@ -0,0 +1,37 @@
+use cgmath::Quaternion;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    Command, DamagePlayerCommand, LoadCommand, MoveInventoryCommand, SaveCommand, SpawnItemCommand,
+    TransitionLevelCommand,
+};
+
+// RecordedCommand
+// Serializable representation of a command, so that it can be written
+// to an input recording and re-created on replay.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum RecordedCommand {
+    Save,
+    Load,
+    TransitionLevel,
+    SpawnItem { head_rotation: Quaternion<f32> },
+    MoveInventory { head_rotation: Quaternion<f32> },
+    DamagePlayer { amount: f32 },
+}
+
+impl RecordedCommand {
+    pub fn to_command(&self) -> Box<dyn Command> {
+        match self {
+            RecordedCommand::Save => Box::new(SaveCommand::new()),
+            RecordedCommand::Load => Box::new(LoadCommand::new()),
+            RecordedCommand::TransitionLevel => Box::new(TransitionLevelCommand::new()),
+            RecordedCommand::SpawnItem { head_rotation } => {
+                Box::new(SpawnItemCommand::new(*head_rotation))
+            }
+            RecordedCommand::MoveInventory { head_rotation } => {
+                Box::new(MoveInventoryCommand::new(*head_rotation))
+            }
+            RecordedCommand::DamagePlayer { amount } => Box::new(DamagePlayerCommand::new(*amount)),
+        }
+    }
+}
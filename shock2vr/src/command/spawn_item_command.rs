@@ -7,7 +7,7 @@ use crate::{
     mission::entity_creator::CreateEntityOptions, scripts::Effect, util::vec3_to_point3, PlayerInfo,
 };
 
-use super::Command;
+use super::{Command, RecordedCommand};
 // SpawnItemCommand
 #[derive(Debug)]
 pub struct SpawnItemCommand {
@@ -43,4 +43,10 @@ impl Command for SpawnItemCommand {
             options: CreateEntityOptions::default(),
         }
     }
+
+    fn to_recorded(&self) -> RecordedCommand {
+        RecordedCommand::SpawnItem {
+            head_rotation: self.head_rotation,
+        }
+    }
 }
@@ -28,6 +28,21 @@ pub enum ActorType {
     Arachnid = 4,
 }
 
+// The creature type of an entity (P$Creature) - an index into CREATURES
+#[derive(FromPrimitive, ToPrimitive, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CreatureType {
+    Human = 0,
+    PlayerLimb = 1,
+    Avatar = 2,
+    Rumbler = 3,
+    Droid = 4,
+    Overlord = 5,
+    Arachnid = 6,
+    Monkey = 7,
+    BabyArachnid = 8,
+    Shodan = 9,
+}
+
 pub struct CreatureDefinition {
     pub actor_type: ActorType,
     pub physics_offset_height: f32,
@@ -215,6 +230,7 @@ pub const SHODAN: Lazy<Arc<CreatureDefinition>> = Lazy::new(|| {
     })
 });
 
+// Ordered by CreatureType
 const CREATURES: [Lazy<Arc<CreatureDefinition>>; 10] = [
     HUMAN,
     PLAYER_LIMB,
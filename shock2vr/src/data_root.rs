@@ -0,0 +1,171 @@
+///
+/// data_root.rs
+///
+/// Locates the System Shock 2 game files. The data root is the folder containing `shock2.gam`,
+/// the mission files, and the `res` folder. Overlay folders are checked first, in order, so that
+/// patched or modded files can take precedence over the original data.
+///
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+// File that must be present for a folder to be considered a data root
+const MARKER_FILE: &str = "shock2.gam";
+
+// Environment variable that can be used to point at the data root, without any code changes
+pub const DATA_ROOT_ENV_VAR: &str = "SHOCK2QUEST_DATA";
+
+// Sub-folders that the game data might live in, relative to an install folder
+const DATA_FOLDER_NAMES: [&str; 3] = ["Data", "DATA", "data"];
+
+#[derive(Clone, Debug)]
+pub struct DataRoot {
+    root: PathBuf,
+    overlays: Vec<PathBuf>,
+}
+
+impl DataRoot {
+    pub fn new(root: PathBuf) -> DataRoot {
+        DataRoot {
+            root,
+            overlays: Vec::new(),
+        }
+    }
+
+    pub fn with_overlays(self, overlays: Vec<PathBuf>) -> DataRoot {
+        DataRoot { overlays, ..self }
+    }
+
+    ///
+    /// detect
+    ///
+    /// Find the data root by checking, in order: the environment variable, the Data folder next to
+    /// the executable or working directory (or any of their parents), and then the default GOG and
+    /// Steam install locations.
+    pub fn detect() -> Option<DataRoot> {
+        candidate_folders()
+            .into_iter()
+            .find(|folder| is_data_root(folder))
+            .map(DataRoot::new)
+    }
+
+    ///
+    /// search_paths
+    ///
+    /// All the folders that `detect` checks - useful for reporting where we looked when nothing is found
+    pub fn search_paths() -> Vec<PathBuf> {
+        candidate_folders()
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn overlays(&self) -> &[PathBuf] {
+        &self.overlays
+    }
+
+    ///
+    /// resolve
+    ///
+    /// Get the path to a resource, relative to the data root. The first overlay containing the
+    /// resource wins - otherwise, the path in the data root is returned, whether or not it exists.
+    pub fn resolve(&self, resource: &str) -> PathBuf {
+        self.overlays
+            .iter()
+            .map(|overlay| overlay.join(resource))
+            .find(|path| path.exists())
+            .unwrap_or_else(|| self.root.join(resource))
+    }
+
+    pub fn resource_path(&self, resource: &str) -> String {
+        self.resolve(resource).to_string_lossy().to_string()
+    }
+}
+
+fn is_data_root(folder: &Path) -> bool {
+    folder.join(MARKER_FILE).exists()
+}
+
+fn candidate_folders() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    if let Some(path) = env::var_os(DATA_ROOT_ENV_VAR) {
+        candidates.push(PathBuf::from(path));
+    }
+
+    if cfg!(target_os = "android") {
+        candidates.push(PathBuf::from("/mnt/sdcard/shock2quest"));
+    }
+
+    // The Data folder in the repository, when running from a runtime folder or target folder
+    let exe_folder = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    let working_folder = env::current_dir().ok();
+    for start in [working_folder, exe_folder].into_iter().flatten() {
+        for ancestor in start.ancestors() {
+            candidates.push(ancestor.join("Data"));
+        }
+    }
+
+    for install_folder in install_folders() {
+        for data_folder in DATA_FOLDER_NAMES {
+            candidates.push(install_folder.join(data_folder));
+        }
+        candidates.push(install_folder);
+    }
+
+    candidates
+}
+
+// Default install locations for the GOG and Steam releases
+fn install_folders() -> Vec<PathBuf> {
+    let mut folders = Vec::new();
+
+    if cfg!(target_os = "windows") {
+        for program_files in ["C:\\Program Files (x86)", "C:\\Program Files"] {
+            let program_files = PathBuf::from(program_files);
+            folders.push(program_files.join("Steam\\steamapps\\common\\SS2"));
+            folders.push(program_files.join("GOG Galaxy\\Games\\System Shock 2"));
+        }
+        folders.push(PathBuf::from("C:\\GOG Games\\System Shock 2"));
+    }
+
+    if let Some(home) = env::var_os("HOME").map(PathBuf::from) {
+        if cfg!(target_os = "macos") {
+            folders.push(home.join("Library/Application Support/Steam/steamapps/common/SS2"));
+        } else {
+            folders.push(home.join(".steam/steam/steamapps/common/SS2"));
+            folders.push(home.join(".local/share/Steam/steamapps/common/SS2"));
+            folders.push(home.join("GOG Games/System Shock 2/game"));
+        }
+    }
+
+    folders
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_overlays_take_precedence() {
+        let base = env::temp_dir().join(format!("shock2quest-data-root-{}", std::process::id()));
+        let root = base.join("Data");
+        let overlay = base.join("Overlay");
+        fs::create_dir_all(&root).unwrap();
+        fs::create_dir_all(&overlay).unwrap();
+        fs::write(overlay.join("earth.mis"), b"").unwrap();
+
+        let data_root = DataRoot::new(root.clone()).with_overlays(vec![overlay.clone()]);
+
+        assert_eq!(data_root.resolve("earth.mis"), overlay.join("earth.mis"));
+        assert_eq!(data_root.resolve("shock2.gam"), root.join("shock2.gam"));
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+}
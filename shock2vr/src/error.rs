@@ -0,0 +1,141 @@
+///
+/// error.rs
+///
+/// Errors that can occur while starting the game or loading a mission - most commonly, because the
+/// Data folder is missing or incomplete. These are surfaced to the runtimes so they can show
+/// something more helpful than a panic.
+///
+use std::{error, fmt, io, path::PathBuf};
+
+use zip::result::ZipError;
+
+use crate::save_load::SaveError;
+
+#[derive(Debug)]
+pub enum MissionLoadError {
+    MissingMission { path: String, source: io::Error },
+    CorruptMission(dark::Error),
+}
+
+impl fmt::Display for MissionLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MissionLoadError::MissingMission { path, source } => {
+                write!(f, "unable to open mission {}: {}", path, source)
+            }
+            MissionLoadError::CorruptMission(err) => write!(f, "unable to read mission: {}", err),
+        }
+    }
+}
+
+impl error::Error for MissionLoadError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            MissionLoadError::MissingMission { source, .. } => Some(source),
+            MissionLoadError::CorruptMission(err) => Some(err),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum GameInitError {
+    DataRootNotFound { searched: Vec<PathBuf> },
+    MissingResource { path: String, source: io::Error },
+    CorruptArchive { path: String, source: ZipError },
+    CorruptResource(dark::Error),
+    CorruptSave { path: String, source: SaveError },
+    Mission(MissionLoadError),
+}
+
+impl GameInitError {
+    ///
+    /// from_archive_error
+    ///
+    /// Opening an archive fails with the same error type whether the file is missing or unreadable,
+    /// so split those out - a missing file usually means the Data folder isn't set up.
+    pub fn from_archive_error(path: &str, err: ZipError) -> GameInitError {
+        match err {
+            ZipError::Io(source) if source.kind() == io::ErrorKind::NotFound => {
+                GameInitError::MissingResource {
+                    path: path.to_owned(),
+                    source,
+                }
+            }
+            source => GameInitError::CorruptArchive {
+                path: path.to_owned(),
+                source,
+            },
+        }
+    }
+}
+
+impl From<MissionLoadError> for GameInitError {
+    fn from(err: MissionLoadError) -> GameInitError {
+        GameInitError::Mission(err)
+    }
+}
+
+impl fmt::Display for GameInitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameInitError::DataRootNotFound { searched } => {
+                write!(
+                    f,
+                    "unable to find the System Shock 2 data folder. Searched:"
+                )?;
+                for path in searched {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            }
+            GameInitError::MissingResource { path, source } => {
+                write!(f, "missing resource {}: {}", path, source)
+            }
+            GameInitError::CorruptArchive { path, source } => {
+                write!(f, "unable to read archive {}: {}", path, source)
+            }
+            GameInitError::CorruptResource(err) => write!(f, "unable to read resource: {}", err),
+            GameInitError::CorruptSave { path, source } => {
+                write!(f, "unable to load save {}: {}", path, source)
+            }
+            GameInitError::Mission(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl error::Error for GameInitError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            GameInitError::DataRootNotFound { .. } => None,
+            GameInitError::MissingResource { source, .. } => Some(source),
+            GameInitError::CorruptArchive { source, .. } => Some(source),
+            GameInitError::CorruptResource(err) => Some(err),
+            GameInitError::CorruptSave { source, .. } => Some(source),
+            GameInitError::Mission(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_archive_is_missing_resource() {
+        let err = GameInitError::from_archive_error(
+            "res/obj.crf",
+            ZipError::Io(io::Error::from(io::ErrorKind::NotFound)),
+        );
+        assert!(matches!(err, GameInitError::MissingResource { .. }));
+        assert!(err.to_string().contains("res/obj.crf"));
+    }
+
+    #[test]
+    fn test_invalid_archive_is_corrupt_archive() {
+        let err = GameInitError::from_archive_error(
+            "res/obj.crf",
+            ZipError::InvalidArchive("Could not find central directory end"),
+        );
+        assert!(matches!(err, GameInitError::CorruptArchive { .. }));
+    }
+}
@@ -92,10 +92,23 @@ where
         msg: &MessagePayload,
     ) -> Effect {
         match msg {
-            MessagePayload::ProvideForConsumption { entity } => Effect::DropEntityInfo {
-                parent_entity_id: entity_id,
-                dropped_entity_id: *entity,
-            },
+            // The GUI gets the first chance at an item given to it (ie, an ICE pick given to a
+            // hackable object) - otherwise, the item is put into the entity
+            MessagePayload::ProvideForConsumption { entity } => {
+                match self
+                    .gui
+                    .handle_script_msg(entity_id, world, &self.state, msg)
+                {
+                    Some((state, effect)) => {
+                        self.state = state;
+                        effect
+                    }
+                    None => Effect::DropEntityInfo {
+                        parent_entity_id: entity_id,
+                        dropped_entity_id: *entity,
+                    },
+                }
+            }
             MessagePayload::GUIHover {
                 held_entity_id,
                 screen_coordinates,
@@ -154,7 +167,16 @@ where
                     Effect::NoEffect
                 }
             }
-            _ => Effect::NoEffect,
+            _ => match self
+                .gui
+                .handle_script_msg(entity_id, world, &self.state, msg)
+            {
+                Some((state, effect)) => {
+                    self.state = state;
+                    effect
+                }
+                None => Effect::NoEffect,
+            },
         }
     }
 }
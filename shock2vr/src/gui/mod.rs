@@ -3,6 +3,8 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use cgmath::{Point2, Vector2, Vector3};
 use shipyard::{EntityId, World};
 
+use crate::scripts::MessagePayload;
+
 mod gui_component;
 mod gui_manager;
 mod gui_script;
@@ -58,4 +60,19 @@ where
         state: &TState,
         msg: &TMsg,
     ) -> (TState, crate::Effect);
+
+    ///
+    /// handle_script_msg
+    ///
+    /// Handle a message sent to the entity, outside of the GUI itself. Returns None if the GUI
+    /// doesn't handle the message.
+    fn handle_script_msg(
+        &self,
+        _entity_id: EntityId,
+        _world: &World,
+        _state: &TState,
+        _msg: &MessagePayload,
+    ) -> Option<(TState, crate::Effect)> {
+        None
+    }
 }
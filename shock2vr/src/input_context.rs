@@ -4,8 +4,9 @@
 // For desktop / PC runtime, the mapping is a bit more interesting..
 
 use cgmath::{Quaternion, Vector2, Vector3, Zero};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct InputContext {
     // Information about the head position
     pub head: Head,
@@ -26,7 +27,7 @@ impl InputContext {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Head {
     pub rotation: Quaternion<f32>,
 }
@@ -43,7 +44,7 @@ impl Head {
 }
 
 // Context for an individual hand (motion controller)
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Hand {
     pub position: Vector3<f32>,
     pub rotation: Quaternion<f32>,
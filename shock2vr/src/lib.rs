@@ -1,10 +1,16 @@
 pub mod command;
+pub mod game_rng;
 pub mod input_context;
 pub mod inventory;
+pub mod player_stats;
+pub mod psi_powers;
+pub mod replay;
 pub mod save_load;
 pub mod time;
 
 mod creature;
+mod data_root;
+mod error;
 mod gui;
 mod hud;
 mod mission;
@@ -12,23 +18,33 @@ mod physics;
 mod quest_info;
 mod runtime_props;
 mod scripts;
+mod security_system;
 mod systems;
 mod util;
 mod virtual_hand;
 mod vr_config;
 mod zip_asset_path;
 
+pub use data_root::{DataRoot, DATA_ROOT_ENV_VAR};
+pub use error::{GameInitError, MissionLoadError};
 pub use mission::visibility_engine::CullingInfo;
 pub use mission::SpawnLocation;
+pub use scripts::{
+    Script, ScriptCoverageEntry, ScriptCoverageReport, ScriptRegistry, ScriptStatus,
+};
+pub use zip_asset_path::ZipAssetPath;
 
 use std::{
     collections::{HashMap, HashSet},
     fs::{File, OpenOptions},
     io::{BufReader, Read},
+    path::PathBuf,
     rc::Rc,
 };
 
-use cgmath::{vec3, InnerSpace, Matrix4, Quaternion, Rad, Rotation, Rotation3, Vector2, Vector3};
+use cgmath::{
+    vec3, InnerSpace, Matrix4, Quaternion, Rad, Rotation, Rotation3, Vector2, Vector3, VectorSpace,
+};
 use command::Command;
 use dark::{
     gamesys,
@@ -47,58 +63,161 @@ use engine::{
     profile,
     scene::SceneObject,
 };
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use mission::entity_populator::{EntityPopulator, MissionEntityPopulator, SaveFileEntityPopulator};
+use game_rng::GameRng;
+use player_stats::PlayerStats;
 use quest_info::QuestInfo;
 
-use save_load::{EntitySaveData, GlobalData, HeldItemSaveData, SaveData};
+use save_load::{
+    EntitySaveData, GlobalData, HeldItemSaveData, SaveData, SaveError, SaveSlot, SaveSlotKind,
+    SaveSlotManager, SaveThumbnail,
+};
 use scripts::GlobalEffect;
 use shipyard::*;
 use shipyard::{self, View};
 use time::Time;
-use tracing::{info, span, trace, warn, Level};
-
-use zip_asset_path::ZipAssetPath;
+use tracing::{error, info, span, trace, warn, Level};
 
 use crate::{
     mission::{GlobalContext, Mission, PlayerInfo},
+    physics::{FixedTimestep, DEFAULT_PHYSICS_TICK_RATE},
     scripts::{Effect, Message, MessagePayload},
     util::log_entities_with_link,
 };
 
-#[cfg(target_os = "android")]
-const BASE_PATH: &str = "/mnt/sdcard/shock2quest";
-
-#[cfg(not(target_os = "android"))]
-const BASE_PATH: &str = "../../Data";
+fn open_resource(data_root: &DataRoot, path: &str) -> Result<BufReader<File>, GameInitError> {
+    let file =
+        File::open(data_root.resolve(path)).map_err(|source| GameInitError::MissingResource {
+            path: data_root.resource_path(path),
+            source,
+        })?;
+    Ok(BufReader::new(file))
+}
 
-pub fn resource_path(str: &str) -> String {
-    format!("{BASE_PATH}/{str}")
+fn open_archive(
+    data_root: &DataRoot,
+    path: &str,
+    collapse_paths: bool,
+) -> Result<Box<ZipAssetPath>, GameInitError> {
+    let full_path = data_root.resource_path(path);
+    ZipAssetPath::new2(full_path.clone(), collapse_paths)
+        .map_err(|err| GameInitError::from_archive_error(&full_path, err))
 }
 
 pub struct GameOptions {
+    // Folder containing shock2.gam, the missions, and the res folder.
+    // When not set, it is detected from the working directory, executable, and GOG / Steam installs.
+    pub data_root: Option<PathBuf>,
+    // Folders that are checked before the data root, in order - ie, for patched or modded files
+    pub data_overlays: Vec<PathBuf>,
     pub mission: String,
     pub spawn_location: SpawnLocation,
     pub save_file: Option<String>,
+    // Folder that save slots are kept in. When not set, a `saves` folder in the data root is used.
+    pub save_folder: Option<PathBuf>,
     pub render_particles: bool,
     pub debug_physics: bool,
     pub debug_draw: bool,
     pub debug_portals: bool,
     pub experimental_features: HashSet<String>,
+    // Rate, in steps per second, that physics is simulated at - regardless of the frame rate
+    pub physics_tick_rate: f32,
+    // Seed for gameplay randomness (sounds, music, AI). When not set, a random seed is picked.
+    pub seed: Option<u64>,
+    // Run without an audio device, and skip building per-eye scene objects.
+    // Used by the headless runtime, where there is no GPU or sound card.
+    pub headless: bool,
+    // Scripts that can be attached to entities. Defaults to the built-in scripts,
+    // but tests or mods can register additional ones.
+    pub script_registry: Rc<ScriptRegistry>,
 }
 
 impl Default for GameOptions {
     fn default() -> Self {
         Self {
+            data_root: None,
+            data_overlays: Vec::new(),
             mission: "earth.mis".to_owned(),
             spawn_location: SpawnLocation::MapDefault,
             save_file: None,
+            save_folder: None,
             debug_draw: false,
             debug_portals: false,
             debug_physics: false,
             render_particles: true,
             experimental_features: HashSet::new(),
+            physics_tick_rate: DEFAULT_PHYSICS_TICK_RATE,
+            seed: None,
+            headless: false,
+            script_registry: Rc::new(ScriptRegistry::with_builtin_scripts()),
+        }
+    }
+}
+
+// What happens to the player after they die
+#[derive(Debug, PartialEq)]
+enum DeathOutcome {
+    // Brought back by the mission's Quantum Bio-Reconstruction machine, at this position
+    Resurrect(Vector3<f32>),
+    // Pick up from the most recent save
+    LoadSave(String),
+    // Nothing to go back to, so start the mission over
+    Restart,
+}
+
+///
+/// choose_death_outcome
+///
+/// Resurrect the player if they've activated a machine in the mission and can pay for it,
+/// otherwise go back to the most recent save, and failing that, restart the mission.
+fn choose_death_outcome(
+    player_stats: &mut PlayerStats,
+    level_name: &str,
+    save_slots: &SaveSlotManager,
+) -> DeathOutcome {
+    if let Some(position) = player_stats.resurrect(level_name) {
+        return DeathOutcome::Resurrect(position);
+    }
+
+    match save_slots.list() {
+        Ok(slots) => match slots.first() {
+            Some(slot) => DeathOutcome::LoadSave(slot.id.clone()),
+            None => DeathOutcome::Restart,
+        },
+        Err(err) => {
+            error!("unable to list saves: {}", err);
+            DeathOutcome::Restart
+        }
+    }
+}
+
+// What the player brought into the current mission, so that restarting it can put it back
+#[derive(Clone)]
+struct MissionEntry {
+    spawn_loc: SpawnLocation,
+    quest_info: QuestInfo,
+    player_stats: PlayerStats,
+    held_items: HeldItemSaveData,
+}
+
+impl MissionEntry {
+    fn new(spawn_loc: SpawnLocation) -> MissionEntry {
+        MissionEntry {
+            spawn_loc,
+            quest_info: QuestInfo::new(),
+            player_stats: PlayerStats::new(),
+            held_items: HeldItemSaveData::empty(),
+        }
+    }
+
+    fn from_global_data(global_data: &GlobalData) -> MissionEntry {
+        MissionEntry {
+            spawn_loc: SpawnLocation::PositionRotation(global_data.position, global_data.rotation),
+            quest_info: global_data.quest_info.clone(),
+            player_stats: global_data.player_stats.clone(),
+            held_items: global_data.held_items.clone(),
         }
     }
 }
@@ -118,19 +237,29 @@ pub struct Game {
     last_music_cue: Option<String>,
     last_env_sound: Option<String>,
 
+    physics_timestep: FixedTimestep,
+    // Where the player was before and after the latest physics step, to interpolate the camera
+    player_step: Option<(Vector3<f32>, Vector3<f32>)>,
+
     mission_to_save_data: HashMap<String, EntitySaveData>,
+    mission_entry: MissionEntry,
+
+    save_slots: SaveSlotManager,
+    // Slot that was just saved, waiting for the runtime to capture a frame for its thumbnail
+    pending_thumbnail_slot: Option<String>,
+    // Total time spent in game, across all missions and saves
+    play_time: Duration,
 }
 
 impl Game {
     fn switch_mission(&mut self, level_name: String, spawn_loc: SpawnLocation) {
-        let current_quest_info = self
-            .active_mission
-            .world
-            .borrow::<UniqueView<QuestInfo>>()
-            .unwrap()
-            .clone();
-
         let (current_save_data, held_data) = save_load::to_save_data(&self.active_mission.world);
+        let entry = MissionEntry {
+            spawn_loc,
+            quest_info: self.active_mission.quest_info(),
+            player_stats: self.player_stats(),
+            held_items: held_data,
+        };
         println!("ALL ENTITIES: {}", &current_save_data.all_entities.len());
 
         self.mission_to_save_data.insert(
@@ -151,35 +280,117 @@ impl Game {
             }
         };
 
-        let active_mission = Mission::load(
-            level_name,
+        if self.enter_mission(level_name, entry, populator) {
+            if let Err(err) = self.save_to_slot(SaveSlotKind::Auto) {
+                error!("unable to autosave: {}", err);
+            }
+        }
+    }
+
+    ///
+    /// restart_mission
+    ///
+    /// Start the current mission over - the level as it is in the mission file, and the player as
+    /// they were when they entered it. Nothing is saved, since there is nothing new to go back to.
+    fn restart_mission(&mut self) {
+        let level_name = self.active_mission.level_name.clone();
+        let entry = self.mission_entry.clone();
+
+        if self.enter_mission(
+            level_name.clone(),
+            entry,
+            Box::new(MissionEntityPopulator::create()),
+        ) {
+            // Forget the state of the level the player died in
+            self.mission_to_save_data
+                .remove(&level_name.to_ascii_lowercase());
+        }
+    }
+
+    ///
+    /// enter_mission
+    ///
+    /// Load a mission and make it the active one. Returns whether it loaded - if not, the current
+    /// mission keeps running, rather than bringing down the whole game.
+    fn enter_mission(
+        &mut self,
+        level_name: String,
+        entry: MissionEntry,
+        populator: Box<dyn EntityPopulator>,
+    ) -> bool {
+        let current_rng = self
+            .active_mission
+            .world
+            .borrow::<UniqueView<GameRng>>()
+            .unwrap()
+            .clone();
+
+        let maybe_mission = Mission::load(
+            level_name.clone(),
             &mut self.asset_cache,
             &mut self.audio_context,
             &self.global_context,
-            spawn_loc,
-            current_quest_info,
+            entry.spawn_loc.clone(),
+            entry.quest_info.clone(),
+            entry.player_stats.clone(),
+            current_rng,
             populator,
-            held_data,
+            entry.held_items.clone(),
         );
-        self.active_mission = active_mission;
-    }
-    pub fn init(_file_system: &Box<dyn FileSystem>, options: GameOptions) -> Game {
-        let asset_paths = AssetPath::combine(vec![
-            AssetPath::folder(resource_path("res/mesh")),
-            // AssetPath::folder(resource_path("res/mesh/txt16")),
-            AssetPath::folder(resource_path("res/obj")),
-            // AssetPath::folder(resource_path("res/obj/txt16")),
-            ZipAssetPath::new(resource_path("res/obj.crf")),
-            ZipAssetPath::new(resource_path("res/bitmap.crf")),
-            ZipAssetPath::new(resource_path("res/fam.crf")),
-            ZipAssetPath::new(resource_path("res/iface.crf")),
-            ZipAssetPath::new(resource_path("res/mesh.crf")),
-            ZipAssetPath::new(resource_path("res/motions.crf")),
-            ZipAssetPath::new(resource_path("res/objicon.crf")),
-            ZipAssetPath::new(resource_path("res/snd.crf")),
-            ZipAssetPath::new(resource_path("res/snd2.crf")),
-            ZipAssetPath::new(resource_path("res/song.crf")),
-            ZipAssetPath::new2(resource_path("res/strings.crf"), false),
+
+        match maybe_mission {
+            Ok(active_mission) => {
+                self.active_mission = active_mission;
+                self.mission_entry = entry;
+                self.physics_timestep.reset();
+                self.player_step = None;
+                true
+            }
+            Err(err) => {
+                error!("unable to switch to mission {}: {}", level_name, err);
+                false
+            }
+        }
+    }
+    pub fn init(
+        _file_system: &Box<dyn FileSystem>,
+        options: GameOptions,
+    ) -> Result<Game, GameInitError> {
+        let data_root = match &options.data_root {
+            Some(root) => DataRoot::new(root.clone()),
+            None => DataRoot::detect().ok_or_else(|| GameInitError::DataRootNotFound {
+                searched: DataRoot::search_paths(),
+            })?,
+        }
+        .with_overlays(options.data_overlays.clone());
+        info!("using data root: {}", data_root.root().display());
+
+        // Loose files in the overlays take precedence over anything in the data root
+        let mut asset_paths = Vec::new();
+        for overlay in data_root.overlays() {
+            for folder in ["", "res/mesh", "res/obj"] {
+                asset_paths.push(AssetPath::folder(
+                    overlay.join(folder).to_string_lossy().to_string(),
+                ));
+            }
+        }
+
+        asset_paths.extend(vec![
+            AssetPath::folder("res/mesh".to_owned()),
+            // AssetPath::folder("res/mesh/txt16".to_owned()),
+            AssetPath::folder("res/obj".to_owned()),
+            // AssetPath::folder("res/obj/txt16".to_owned()),
+            open_archive(&data_root, "res/obj.crf", true)?,
+            open_archive(&data_root, "res/bitmap.crf", true)?,
+            open_archive(&data_root, "res/fam.crf", true)?,
+            open_archive(&data_root, "res/iface.crf", true)?,
+            open_archive(&data_root, "res/mesh.crf", true)?,
+            open_archive(&data_root, "res/motions.crf", true)?,
+            open_archive(&data_root, "res/objicon.crf", true)?,
+            open_archive(&data_root, "res/snd.crf", true)?,
+            open_archive(&data_root, "res/snd2.crf", true)?,
+            open_archive(&data_root, "res/song.crf", true)?,
+            open_archive(&data_root, "res/strings.crf", false)?,
             //AssetPath::folder("../assets/"),
             // Textures
             // AssetPath::folder("res/bitmap".to_owned()),
@@ -214,13 +425,14 @@ impl Game {
             // AssetPath::folder("res/snd2/vLogs/english".to_owned()),
             // AssetPath::folder("res/snd2/vTriggers/english".to_owned()),
         ]);
+        let asset_paths = AssetPath::combine(asset_paths);
         // Global items
-        let mut asset_cache = AssetCache::new(BASE_PATH.to_owned(), asset_paths);
+        let mut asset_cache =
+            AssetCache::new(data_root.root().to_string_lossy().to_string(), asset_paths);
 
         let (properties, links, links_with_data) = dark::properties::get();
 
-        let game_file = File::open(resource_path("shock2.gam")).unwrap();
-        let mut game_reader = BufReader::new(game_file);
+        let mut game_reader = open_resource(&data_root, "shock2.gam")?;
 
         let _strings = asset_cache.get(&STRINGS_IMPORTER, "objname.str");
 
@@ -230,13 +442,18 @@ impl Game {
         // let header = ss2_bin_header::read(&mut atek_reader);
         // let obj = ss2_bin_obj_loader::read(&mut atek_reader, &header);
 
-        let gamesys = gamesys::read(&mut game_reader, &links, &links_with_data, &properties);
+        let gamesys = gamesys::read(&mut game_reader, &links, &links_with_data, &properties)
+            .map_err(|err| GameInitError::CorruptResource(err.in_file("shock2.gam")))?;
 
-        let motiondb_file = File::open(resource_path("motiondb.bin")).unwrap();
-        let mut motiondb_reader = BufReader::new(motiondb_file);
-        let motiondb = MotionDB::read(&mut motiondb_reader);
+        let mut motiondb_reader = open_resource(&data_root, "motiondb.bin")?;
+        let motiondb = MotionDB::read(&mut motiondb_reader)
+            .map_err(|err| GameInitError::CorruptResource(err.in_file("motiondb.bin")))?;
 
-        let mut audio_context = AudioContext::new();
+        let mut audio_context = if options.headless {
+            AudioContext::silent()
+        } else {
+            AudioContext::new()
+        };
 
         let global_context = GlobalContext {
             links,
@@ -244,6 +461,8 @@ impl Game {
             properties,
             motiondb,
             gamesys,
+            script_registry: options.script_registry.clone(),
+            data_root,
         };
 
         // TEST: Load all missions
@@ -289,32 +508,69 @@ impl Game {
         //     }
         // }
 
-        let (active_mission, mission_to_save_data) =
+        let save_slots = SaveSlotManager::new(
+            options
+                .save_folder
+                .clone()
+                .unwrap_or_else(|| data_root.root().join("saves")),
+        );
+
+        let (active_mission, mission_to_save_data, mission_entry, play_time) =
             if let Some(save_file_path) = &options.save_file {
-                let mut file = OpenOptions::new().read(true).open(save_file_path).unwrap();
-                let save_data = SaveData::read(&mut file);
-                Self::load_from_save_data(
-                    save_data,
-                    &mut asset_cache,
-                    &mut audio_context,
-                    &global_context,
+                let mut file =
+                    OpenOptions::new()
+                        .read(true)
+                        .open(save_file_path)
+                        .map_err(|source| GameInitError::MissingResource {
+                            path: save_file_path.to_owned(),
+                            source,
+                        })?;
+                let save_data =
+                    SaveData::read(&mut file).map_err(|source| GameInitError::CorruptSave {
+                        path: save_file_path.to_owned(),
+                        source,
+                    })?;
+                let play_time = save_data.global_data.play_time;
+                let (active_mission, mission_to_save_data, mission_entry) =
+                    Self::load_from_save_data(
+                        save_data,
+                        &mut asset_cache,
+                        &mut audio_context,
+                        &global_context,
+                    )?;
+                (
+                    active_mission,
+                    mission_to_save_data,
+                    mission_entry,
+                    play_time,
                 )
             } else {
                 // Level specific items
                 let mission_to_save_data = HashMap::new();
+                let mission_entry = MissionEntry::new(options.spawn_location.clone());
                 let active_mission = Mission::load(
                     options.mission.to_owned(),
                     //"medsci2.mis".to_owned(),
                     &mut asset_cache,
                     &mut audio_context,
                     &global_context,
-                    options.spawn_location.clone(),
-                    QuestInfo::new(),
+                    mission_entry.spawn_loc.clone(),
+                    mission_entry.quest_info.clone(),
+                    mission_entry.player_stats.clone(),
+                    options
+                        .seed
+                        .map(GameRng::from_seed)
+                        .unwrap_or_else(GameRng::from_entropy),
                     //Box::new(MissionEntityPopulator::create()),
                     Box::new(MissionEntityPopulator::create()),
-                    HeldItemSaveData::empty(),
-                );
-                (active_mission, mission_to_save_data)
+                    mission_entry.held_items.clone(),
+                )?;
+                (
+                    active_mission,
+                    mission_to_save_data,
+                    mission_entry,
+                    Duration::ZERO,
+                )
             };
 
         // log_entities_with_link(&active_mission.world, |link| {
@@ -331,16 +587,22 @@ impl Game {
         // );
         // panic!();
 
-        Game {
+        Ok(Game {
             asset_cache,
             audio_context,
             active_mission,
             global_context,
             last_music_cue: None,
             last_env_sound: None,
+            physics_timestep: FixedTimestep::new(options.physics_tick_rate),
+            player_step: None,
             options,
             mission_to_save_data,
-        }
+            mission_entry,
+            save_slots,
+            pending_thumbnail_slot: None,
+            play_time,
+        })
     }
 
     pub fn update(
@@ -353,6 +615,7 @@ impl Game {
         let _enter = span.enter();
         let delta_time = time.elapsed.as_secs_f32();
         trace!("delta_time: {}", delta_time);
+        self.play_time += time.elapsed;
 
         let mut command_effects = Vec::new();
         for command in commands {
@@ -375,26 +638,42 @@ impl Game {
 
         let new_rotation = player.rotation * additional_rotation;
 
+        // Physics runs at a fixed rate, independent of the frame rate - so movement is
+        // applied per physics step, rather than per frame.
+        let physics_steps = self.physics_timestep.advance(time.elapsed);
+        let step_time = self.physics_timestep.step_duration().as_secs_f32();
+
         let dir = new_rotation * input_context.head.rotation;
         let move_thumbstick_value = input_context.right_hand.thumbstick;
         let forward = dir.rotate_vector(vec3(
-            -delta_time * move_thumbstick_value.x * 25. / SCALE_FACTOR,
+            -step_time * move_thumbstick_value.x * 25. / SCALE_FACTOR,
             0.0,
-            -delta_time * move_thumbstick_value.y * 25. / SCALE_FACTOR,
+            -step_time * move_thumbstick_value.y * 25. / SCALE_FACTOR,
         ));
 
-        let up_value = input_context.left_hand.thumbstick.y / SCALE_FACTOR;
-
-        let (new_character_pos, collision_events) = profile!(
-            "shock2.update.physics",
-            self.active_mission.physics.update(
-                forward + vec3(0.0, up_value, 0.0),
-                &mut self.active_mission.player_handle,
-            )
-        );
+        let up_value = step_time * input_context.left_hand.thumbstick.y * 60. / SCALE_FACTOR;
+
+        let mut new_character_pos = player.pos;
+        let mut collision_events = Vec::new();
+        for _ in 0..physics_steps {
+            let previous_character_pos = new_character_pos;
+            let (character_pos, mut step_collision_events) = profile!(
+                "shock2.update.physics",
+                self.active_mission.physics.update(
+                    step_time,
+                    forward + vec3(0.0, up_value, 0.0),
+                    &mut self.active_mission.player_handle,
+                )
+            );
+            new_character_pos = character_pos;
+            collision_events.append(&mut step_collision_events);
+            self.player_step = Some((previous_character_pos, new_character_pos));
+        }
 
-        // Clear forces
-        self.active_mission.physics.clear_forces();
+        // Clear forces, once they've had a chance to act
+        if physics_steps > 0 {
+            self.active_mission.physics.clear_forces();
+        }
 
         let mut player_info = self
             .active_mission
@@ -540,30 +819,93 @@ impl Game {
         for effect in global_effects {
             self.handle_global_effect(effect);
         }
+
+        if self.player_stats().is_dead() {
+            self.handle_player_death();
+        }
+    }
+
+    pub fn world(&self) -> &World {
+        &self.active_mission.world
+    }
+
+    pub fn mission_name(&self) -> &str {
+        &self.active_mission.level_name
+    }
+
+    pub fn player_position(&self) -> Vector3<f32> {
+        self.active_mission
+            .world
+            .borrow::<UniqueView<PlayerInfo>>()
+            .unwrap()
+            .pos
+    }
+
+    pub fn player_stats(&self) -> PlayerStats {
+        self.active_mission
+            .world
+            .borrow::<UniqueView<PlayerStats>>()
+            .unwrap()
+            .clone()
+    }
+
+    pub fn script_coverage(&self) -> ScriptCoverageReport {
+        self.active_mission.script_coverage()
+    }
+
+    pub fn save_slots(&self) -> &SaveSlotManager {
+        &self.save_slots
+    }
+
+    ///
+    /// wants_save_thumbnail
+    ///
+    /// Whether a save was just made - runtimes that can capture the rendered frame should do so
+    /// after rendering, and hand it to set_save_thumbnail.
+    pub fn wants_save_thumbnail(&self) -> bool {
+        self.pending_thumbnail_slot.is_some()
+    }
+
+    ///
+    /// set_save_thumbnail
+    ///
+    /// Use the captured frame as the thumbnail for the save that was just made - or, when the
+    /// frame couldn't be captured, leave the save without one.
+    pub fn set_save_thumbnail(&mut self, thumbnail: Option<SaveThumbnail>) {
+        let maybe_slot_id = self.pending_thumbnail_slot.take();
+        if let (Some(slot_id), Some(thumbnail)) = (maybe_slot_id, thumbnail) {
+            if let Err(err) = self.save_slots.set_thumbnail(&slot_id, &thumbnail) {
+                error!("unable to save thumbnail for slot {}: {}", slot_id, err);
+            }
+        }
     }
 
-    fn save_to_file(&self, file_name: String) {
+    pub fn save_to_slot(&mut self, kind: SaveSlotKind) -> Result<SaveSlot, SaveError> {
         let save_data = self.build_save_data();
-        let mut zip_file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(file_name)
-            .unwrap();
-        save_data.write(&mut zip_file);
+        let slot = self.save_slots.create(kind, &save_data, None)?;
+        info!("saved to slot {}", slot.id);
+        self.pending_thumbnail_slot = Some(slot.id.clone());
+        Ok(slot)
     }
 
-    fn load_from_file(&mut self, file_name: String) {
-        let mut file = OpenOptions::new().read(true).open(file_name).unwrap();
-        let save_data = SaveData::read(&mut file);
-        let (mission, level_map) = Self::load_from_save_data(
+    pub fn load_from_slot(&mut self, slot_id: &str) -> Result<(), SaveError> {
+        let save_data = self.save_slots.load(slot_id)?;
+        let play_time = save_data.global_data.play_time;
+
+        // On failure the current mission keeps running, so the caller can decide what to do
+        let (mission, level_map, mission_entry) = Self::load_from_save_data(
             save_data,
             &mut self.asset_cache,
             &mut self.audio_context,
             &mut self.global_context,
-        );
+        )?;
         self.active_mission = mission;
         self.mission_to_save_data = level_map;
+        self.mission_entry = mission_entry;
+        self.play_time = play_time;
+        self.physics_timestep.reset();
+        self.player_step = None;
+        Ok(())
     }
 
     fn load_from_save_data(
@@ -571,8 +913,10 @@ impl Game {
         asset_cache: &mut AssetCache,
         audio_context: &mut AudioContext<EntityId, String>,
         global_context: &GlobalContext,
-    ) -> (Mission, HashMap<String, EntitySaveData>) {
+    ) -> Result<(Mission, HashMap<String, EntitySaveData>, MissionEntry), MissionLoadError> {
         let current_mission = save_data.global_data.active_mission.clone();
+        // The save is the furthest back a restart can go
+        let mission_entry = MissionEntry::from_global_data(&save_data.global_data);
         //self.mission_to_save_data = save_data.level_data;
 
         let populator: Box<dyn EntityPopulator> = {
@@ -600,12 +944,14 @@ impl Game {
             global_context,
             spawn_loc,
             save_data.global_data.quest_info,
+            save_data.global_data.player_stats,
+            save_data.global_data.rng,
             populator,
             save_data.global_data.held_items,
-        );
+        )?;
 
         //self.active_mission = active_mission;
-        (active_mission, save_data.level_data)
+        Ok((active_mission, save_data.level_data, mission_entry))
     }
 
     fn build_save_data(&self) -> SaveData {
@@ -624,10 +970,14 @@ impl Game {
             (player_info.pos, player_info.rotation)
         };
 
-        let quest_info = self
+        let quest_info = self.active_mission.quest_info();
+
+        let player_stats = self.player_stats();
+
+        let rng = self
             .active_mission
             .world
-            .borrow::<UniqueView<QuestInfo>>()
+            .borrow::<UniqueView<GameRng>>()
             .unwrap()
             .clone();
 
@@ -636,7 +986,10 @@ impl Game {
             position,
             rotation,
             quest_info,
+            rng,
             active_mission: self.active_mission.level_name.clone(),
+            play_time: self.play_time,
+            player_stats,
         };
 
         SaveData {
@@ -645,10 +998,69 @@ impl Game {
         }
     }
 
+    ///
+    /// handle_player_death
+    ///
+    /// Bring the player back once their hit points run out - at the resurrection machine
+    /// activated in this mission if they can pay for it, otherwise from the most recent save.
+    fn handle_player_death(&mut self) {
+        let level_name = self.active_mission.level_name.clone();
+        let outcome = choose_death_outcome(
+            &mut self
+                .active_mission
+                .world
+                .borrow::<UniqueViewMut<PlayerStats>>()
+                .unwrap(),
+            &level_name,
+            &self.save_slots,
+        );
+
+        match outcome {
+            DeathOutcome::Resurrect(position) => {
+                info!("player died - resurrecting at {:?}", position);
+                let global_effects = self.active_mission.handle_effects(
+                    vec![Effect::SetPlayerPosition {
+                        position,
+                        is_teleport: true,
+                    }],
+                    &self.global_context,
+                    &self.options,
+                    &mut self.asset_cache,
+                    &mut self.audio_context,
+                );
+                for effect in global_effects {
+                    self.handle_global_effect(effect);
+                }
+                return;
+            }
+            DeathOutcome::LoadSave(slot_id) => {
+                info!("player died - loading save {}", slot_id);
+                match self.load_from_slot(&slot_id) {
+                    Ok(()) if !self.player_stats().is_dead() => return,
+                    Ok(()) => warn!("player is dead in save {}", slot_id),
+                    Err(err) => error!("unable to load save {}: {}", slot_id, err),
+                }
+            }
+            DeathOutcome::Restart => (),
+        }
+
+        // Nothing we could go back to, so start the mission over
+        info!("player died - restarting {}", level_name);
+        self.restart_mission();
+    }
+
     fn handle_global_effect(&mut self, global_effect: GlobalEffect) {
         match global_effect {
-            GlobalEffect::Save { file_name } => self.save_to_file(file_name),
-            GlobalEffect::Load { file_name } => self.load_from_file(file_name),
+            GlobalEffect::Save { kind } => {
+                if let Err(err) = self.save_to_slot(kind) {
+                    error!("unable to save: {}", err);
+                }
+            }
+            GlobalEffect::Load { slot_id } => {
+                if let Err(err) = self.load_from_slot(&slot_id) {
+                    error!("unable to load save {}: {}", slot_id, err);
+                }
+            }
             GlobalEffect::TransitionLevel { level_file, loc } => {
                 let spawn_loc = match loc {
                     None => SpawnLocation::MapDefault,
@@ -675,9 +1087,17 @@ impl Game {
     }
 
     pub fn render(&mut self) -> (Vec<SceneObject>, Vector3<f32>, Quaternion<f32>) {
-        let (scene, pos, rot) = self
-            .active_mission
-            .render(&mut self.asset_cache, &self.options);
+        let alpha = self.physics_timestep.alpha();
+        let (scene, pos, rot) =
+            self.active_mission
+                .render(&mut self.asset_cache, &self.options, alpha);
+
+        // Blend the player between steps, like the bodies are - unless the player has since been
+        // moved outside of physics (teleported, respawned, etc)
+        let pos = match self.player_step {
+            Some((previous, current)) if current == pos => previous.lerp(current, alpha),
+            _ => pos,
+        };
 
         // let font = File::open(resource_path("res/fonts/mainfont.FON")).unwrap();
         // let mut font_reader = BufReader::new(font);
@@ -696,6 +1116,10 @@ impl Game {
         projection: Matrix4<f32>,
         screen_size: Vector2<f32>,
     ) -> Vec<SceneObject> {
+        if self.options.headless {
+            return vec![];
+        }
+
         let hand_material = engine::scene::color_material::create(vec3(1.0, 0.0, 0.0));
         let transform = Matrix4::from_scale(0.25) * Matrix4::from_translation(vec3(0.0, 4.0, 0.0));
         let mut hand_obj = SceneObject::new(hand_material, Box::new(engine::scene::cube::create()));
@@ -779,10 +1203,108 @@ impl Game {
 
     fn resolve_schema(&self, name: &str) -> String {
         let sound_schema = &self.global_context.gamesys.sound_schema;
+        let mut rng = self
+            .active_mission
+            .world
+            .borrow::<UniqueViewMut<GameRng>>()
+            .unwrap();
         let ret = sound_schema
-            .get_random_sample(name)
+            .get_random_sample(name, &mut *rng)
             .unwrap_or_else(|| name.to_owned());
         trace!("resolved sound schema {} to {}", name, ret);
         ret
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player_stats::{Currency, RESURRECTION_NANITE_COST};
+    use cgmath::Zero;
+
+    fn save_data(mission: &str) -> SaveData {
+        SaveData {
+            global_data: GlobalData {
+                position: Vector3::zero(),
+                rotation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+                quest_info: QuestInfo::new(),
+                held_items: HeldItemSaveData::empty(),
+                active_mission: mission.to_owned(),
+                rng: GameRng::from_seed(42),
+                play_time: Duration::ZERO,
+                player_stats: PlayerStats::new(),
+            },
+            level_data: HashMap::new(),
+        }
+    }
+
+    fn save_slots(name: &str) -> SaveSlotManager {
+        let folder = std::env::temp_dir().join(format!(
+            "shock2quest-death-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&folder);
+        SaveSlotManager::new(folder)
+    }
+
+    fn dead_player() -> PlayerStats {
+        let mut player_stats = PlayerStats::new();
+        let max_hit_points = player_stats.max_hit_points();
+        player_stats.adjust_hit_points(-max_hit_points);
+        assert!(player_stats.is_dead());
+        player_stats
+    }
+
+    #[test]
+    fn test_death_resurrects_at_machine() {
+        let save_slots = save_slots("resurrect");
+        save_slots
+            .create(SaveSlotKind::Manual, &save_data("medsci1.mis"), None)
+            .unwrap();
+
+        let mut player_stats = dead_player();
+        let machine = vec3(1.0, 2.0, 3.0);
+        player_stats.activate_resurrection_machine("medsci1.mis", machine);
+        player_stats.award_currency(Currency::Nanites, RESURRECTION_NANITE_COST);
+
+        assert_eq!(
+            choose_death_outcome(&mut player_stats, "medsci1.mis", &save_slots),
+            DeathOutcome::Resurrect(machine)
+        );
+        assert!(!player_stats.is_dead());
+    }
+
+    #[test]
+    fn test_death_loads_last_save() {
+        let save_slots = save_slots("load");
+        let slot = save_slots
+            .create(SaveSlotKind::Manual, &save_data("medsci1.mis"), None)
+            .unwrap();
+
+        // A machine in another mission, or one the player can't pay for, doesn't help
+        let mut player_stats = dead_player();
+        player_stats.activate_resurrection_machine("medsci2.mis", vec3(1.0, 2.0, 3.0));
+        assert_eq!(
+            choose_death_outcome(&mut player_stats, "medsci1.mis", &save_slots),
+            DeathOutcome::LoadSave(slot.id.clone())
+        );
+
+        player_stats.activate_resurrection_machine("medsci1.mis", vec3(1.0, 2.0, 3.0));
+        assert_eq!(
+            choose_death_outcome(&mut player_stats, "medsci1.mis", &save_slots),
+            DeathOutcome::LoadSave(slot.id)
+        );
+        assert!(player_stats.is_dead());
+    }
+
+    #[test]
+    fn test_death_restarts_without_saves() {
+        let save_slots = save_slots("restart");
+        let mut player_stats = dead_player();
+        assert_eq!(
+            choose_death_outcome(&mut player_stats, "medsci1.mis", &save_slots),
+            DeathOutcome::Restart
+        );
+    }
+}
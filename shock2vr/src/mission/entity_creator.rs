@@ -782,12 +782,15 @@ pub fn create_physics_representation(
 #[derive(Clone, Debug)]
 pub struct CreateEntityOptions {
     pub force_visible: bool,
+    // For projectiles, the entity that fired it - so a shot doesn't hit its own shooter
+    pub launched_by: Option<EntityId>,
 }
 
 impl Default for CreateEntityOptions {
     fn default() -> Self {
         CreateEntityOptions {
             force_visible: false,
+            launched_by: None,
         }
     }
 }
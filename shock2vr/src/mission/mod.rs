@@ -30,10 +30,11 @@ use dark::{
     motion::{AnimationEvent, AnimationPlayer, MotionDB, MotionQuery, MotionQueryItem},
     properties::{
         Link, LinkDefinition, LinkDefinitionWithData, Links, PhysicsModelType, PropCreature,
-        PropFrameAnimState, PropHasRefs, PropLocalPlayer, PropModelName, PropMotionActorTags,
-        PropParticleGroup, PropParticleLaunchInfo, PropPhysDimensions, PropPhysInitialVelocity,
-        PropPhysState, PropPhysType, PropPosition, PropRenderType, PropScripts, PropTeleported,
-        PropTripFlags, PropertyDefinition, RenderType, ToLink, TripFlags, WrappedEntityId,
+        PropFrameAnimState, PropHacked, PropHasRefs, PropLocalPlayer, PropLocked, PropModelName,
+        PropMotionActorTags, PropObjName, PropParticleGroup, PropParticleLaunchInfo,
+        PropPhysDimensions, PropPhysInitialVelocity, PropPhysState, PropPhysType, PropPosition,
+        PropRenderType, PropScripts, PropStackCount, PropTeleported, PropTripFlags, Property,
+        PropertyDefinition, RenderType, ToLink, TripFlags, WrappedEntityId,
     },
     ss2_entity_info::{self, SystemShock2EntityInfo},
     BitmapAnimation, SCALE_FACTOR,
@@ -47,25 +48,36 @@ use engine::{
 };
 use physics::PhysicsWorld;
 use rapier3d::prelude::RigidBodyHandle;
-use scripts::ScriptWorld;
+use scripts::{
+    ai::{
+        awareness::{NoiseEvents, ENVIRONMENTAL_NOISE_RADIUS, PLAYER_NOISE_RADIUS},
+        navigation::NavigationGraph,
+    },
+    ScriptCoverageReport, ScriptRegistry, ScriptStatus, ScriptWorld,
+};
 use std::time::Instant;
 
+use rand::Rng;
 use shipyard::*;
 use shipyard::{self, View, World};
-use tracing::{info, trace, warn};
+use tracing::{info, trace};
 
 use crate::{
     creature::{get_creature_definition, HitBoxManager},
+    data_root::DataRoot,
+    error::MissionLoadError,
+    game_rng::GameRng,
     gui::GuiManager,
     hud::{draw_item_name, draw_item_outline},
     input_context::{self},
     inventory::PlayerInventoryEntity,
     mission::entity_populator::EntityPopulator,
     physics::{self, PlayerHandle},
+    player_stats::{Currency, PlayerStats},
     quest_info::QuestInfo,
     runtime_props::{
-        RuntimePropDoNotSerialize, RuntimePropJointTransforms, RuntimePropProxyEntity,
-        RuntimePropTransform, RuntimePropVhots,
+        RuntimePropDoNotSerialize, RuntimePropJointTransforms, RuntimePropLaunchedBy,
+        RuntimePropProxyEntity, RuntimePropTransform, RuntimePropVhots,
     },
     save_load::HeldItemSaveData,
     scripts::{
@@ -74,10 +86,11 @@ use crate::{
         script_util::{get_all_links_with_template, get_environmental_sound_query},
         Effect, GlobalEffect, Message, MessagePayload,
     },
+    security_system::{choose_reinforcement_spawn, SecuritySystem, ALARM_REINFORCEMENTS},
     systems::{run_bitmap_animation, run_tweq, turn_off_tweqs, turn_on_tweqs},
     time::Time,
     util::{get_email_sound_file, has_refs, resolve_proxy_entity, vec3_to_point3},
-    virtual_hand::{VirtualHand, VirtualHandEffect},
+    virtual_hand::{can_grab_item, VirtualHand, VirtualHandEffect},
     vr_config, GameOptions,
 };
 
@@ -85,16 +98,6 @@ use self::{
     entity_creator::{CreateEntityOptions, EntityCreationInfo},
     visibility_engine::VisibilityEngine,
 };
-#[cfg(target_os = "android")]
-const BASE_PATH: &str = "/mnt/sdcard/shock2quest";
-
-#[cfg(not(target_os = "android"))]
-const BASE_PATH: &str = "../../Data";
-
-pub fn resource_path(str: &str) -> String {
-    format!("{BASE_PATH}/{str}")
-}
-
 #[derive(Unique, Clone)]
 pub struct PlayerInfo {
     pub pos: Vector3<f32>,
@@ -174,6 +177,8 @@ pub struct GlobalContext {
     pub links_with_data: Vec<Box<dyn LinkDefinitionWithData>>,
     pub gamesys: Gamesys,
     pub motiondb: MotionDB,
+    pub script_registry: Rc<ScriptRegistry>,
+    pub data_root: DataRoot,
 }
 
 impl Mission {
@@ -184,9 +189,11 @@ impl Mission {
         global_context: &GlobalContext,
         spawn_loc: SpawnLocation,
         quest_info: QuestInfo,
+        player_stats: PlayerStats,
+        mut rng: GameRng,
         entity_populator: Box<dyn EntityPopulator>,
         held_item_save_data: HeldItemSaveData,
-    ) -> Mission {
+    ) -> Result<Mission, MissionLoadError> {
         let properties = &global_context.properties;
         let links = &global_context.links;
         let links_with_data = &global_context.links_with_data;
@@ -194,7 +201,12 @@ impl Mission {
         let _motiondb = &global_context.motiondb;
 
         let mut world = World::new();
-        let f = File::open(resource_path(&mission)).unwrap();
+        let f = File::open(global_context.data_root.resolve(&mission)).map_err(|source| {
+            MissionLoadError::MissingMission {
+                path: global_context.data_root.resource_path(&mission),
+                source,
+            }
+        })?;
         let mut reader = BufReader::new(f);
         let start = SystemTime::now();
         info!("starting level load");
@@ -205,7 +217,8 @@ impl Mission {
             links,
             links_with_data,
             properties,
-        );
+        )
+        .map_err(|err| MissionLoadError::CorruptMission(err.in_file(&mission)))?;
         let mut scene = dark::mission::to_scene(&level, asset_cache);
         let duration: Duration = start.elapsed().unwrap();
         info!("loading level took {}s", duration.as_secs_f32());
@@ -251,9 +264,13 @@ impl Mission {
         world.add_component(inventory, PlayerInventoryEntity {});
 
         world.add_unique(GlobalTemplateIdMap(template_to_entity_id.clone()));
+        world.add_unique(NavigationGraph::from_level(
+            &level.path_database,
+            &level.room_database,
+        ));
 
         // Start background music
-        initialize_background_music(&level, asset_cache, audio_context);
+        initialize_background_music(&level, asset_cache, audio_context, rng.fork());
 
         let mut entities_to_instantiate = HashSet::new();
 
@@ -278,7 +295,7 @@ impl Mission {
         let mut physics = PhysicsWorld::new();
         let mut id_to_physics = HashMap::new();
         let mut id_to_bitmap = HashMap::new();
-        let mut script_world = ScriptWorld::new();
+        let mut script_world = ScriptWorld::new(global_context.script_registry.clone());
 
         let world_entity_id = world.add_entity(RuntimePropDoNotSerialize {});
         physics.add_level_geometry(world_entity_id, &level);
@@ -309,6 +326,7 @@ impl Mission {
                 &mut script_world,
                 created_entity,
                 Matrix4::identity(),
+                None,
             );
         }
 
@@ -330,6 +348,7 @@ impl Mission {
             spawn_loc.calculate_start_position(&world, &level.entity_info, &template_to_entity_id);
 
         let player_handle = physics.create_player(start_pos, player_entity);
+        script_world.add_entity(player_entity, "internal_player");
 
         world.add_unique(PlayerInfo {
             rotation: start_rotation,
@@ -340,13 +359,18 @@ impl Mission {
             inventory_entity_id: inventory,
         });
 
+        world.add_unique(SecuritySystem::new(quest_info.alarm(&mission)));
         world.add_unique(quest_info);
+        world.add_unique(player_stats);
+        world.add_unique(rng);
+
+        world.add_unique(NoiseEvents::default());
 
         world.add_unique(EffectQueue {
             effects: Vec::new(),
         });
 
-        Mission {
+        let loaded_mission = Mission {
             level,
             left_hand,
             right_hand,
@@ -368,7 +392,51 @@ impl Mission {
             gui: GuiManager::new(),
             hit_boxes: HitBoxManager::new(),
             visibility_engine: Box::new(PortalVisibilityEngine::new()),
-        }
+        };
+
+        let coverage = loaded_mission.script_coverage();
+        info!(
+            "script coverage for {}: {} implemented, {} noop, {} unimplemented, {} missing",
+            loaded_mission.level_name,
+            coverage.script_count(ScriptStatus::Implemented),
+            coverage.script_count(ScriptStatus::Noop),
+            coverage.script_count(ScriptStatus::Unimplemented),
+            coverage.script_count(ScriptStatus::Missing),
+        );
+
+        Ok(loaded_mission)
+    }
+
+    ///
+    /// script_coverage
+    ///
+    /// Report which of the scripts used by entities in this mission are implemented
+    pub fn script_coverage(&self) -> ScriptCoverageReport {
+        let v_scripts = self.world.borrow::<View<PropScripts>>().unwrap();
+        self.script_world
+            .registry()
+            .coverage_report(v_scripts.iter())
+    }
+
+    ///
+    /// quest_info
+    ///
+    /// The player's quest state, with this mission's security alarm written into it - the alarm
+    /// counts down every frame, so it's only synced when the state leaves the mission
+    pub fn quest_info(&self) -> QuestInfo {
+        let alarm = self
+            .world
+            .borrow::<UniqueView<SecuritySystem>>()
+            .unwrap()
+            .alarm()
+            .cloned();
+        let mut quest_info = self
+            .world
+            .borrow::<UniqueView<QuestInfo>>()
+            .unwrap()
+            .clone();
+        quest_info.set_alarm(&self.level_name, alarm);
+        quest_info
     }
 
     pub fn update(
@@ -410,6 +478,12 @@ impl Mission {
         // from physics
         self.synchronize_physics_positions();
 
+        // Noises made last frame can be heard by AI this frame
+        self.world
+            .borrow::<UniqueViewMut<NoiseEvents>>()
+            .unwrap()
+            .advance();
+
         // Update scripts
         let mut script_effects = profile!(
             "game.mission.script_world.update",
@@ -417,6 +491,14 @@ impl Mission {
         );
         effects.append(&mut script_effects);
 
+        effects.append(&mut self.update_security_system(time));
+
+        self.world
+            .borrow::<UniqueViewMut<PlayerStats>>()
+            .unwrap()
+            .psi_mut()
+            .update(time.elapsed.as_secs_f32());
+
         self.world.run(run_tweq);
         self.world.run(run_bitmap_animation);
 
@@ -489,11 +571,7 @@ impl Mission {
                     .unwrap_or(vec3(1.0, 1.0, 1.0));
                 let position = self.physics.get_position(*handle).unwrap();
                 let rotation = self.physics.get_rotation(*handle).unwrap();
-                let scale_xform =
-                    Matrix4::from_nonuniform_scale(scale.x.abs(), scale.y.abs(), scale.z.abs());
-                let translation_xform = Matrix4::from_translation(position);
-                let rotation_xform = Matrix4::from(rotation);
-                let xform = translation_xform * rotation_xform * scale_xform;
+                let xform = physics_transform(position, rotation, scale);
                 v_entities.add_component(
                     *entity_id,
                     &mut v_prop_position,
@@ -508,6 +586,134 @@ impl Mission {
         };
     }
 
+    ///
+    /// interpolated_transform
+    ///
+    /// For entities driven by physics, blend the transform between the last two physics steps,
+    /// so movement stays smooth when the frame rate doesn't line up with the physics tick rate
+    fn interpolated_transform(
+        &self,
+        entity_id: EntityId,
+        v_scale: &View<dark::properties::PropScale>,
+        alpha: f32,
+    ) -> Option<Matrix4<f32>> {
+        let handle = self.id_to_physics.get(&entity_id)?;
+        let (position, rotation) = self
+            .physics
+            .get_interpolated_position_rotation(*handle, alpha)?;
+        let scale = v_scale
+            .get(entity_id)
+            .map(|p| p.0)
+            .unwrap_or(vec3(1.0, 1.0, 1.0));
+        Some(physics_transform(position, rotation, scale))
+    }
+
+    ///
+    /// update_security_system
+    ///
+    /// Counts down the security alarm, and sends in reinforcements while it is going.
+    fn update_security_system(&mut self, time: &Time) -> Vec<Effect> {
+        let maybe_spotted_position = self
+            .world
+            .borrow::<UniqueViewMut<SecuritySystem>>()
+            .unwrap()
+            .update(time.elapsed.as_secs_f32());
+
+        // Reinforcements come in from where the level's creatures are
+        let maybe_spawn_position = maybe_spotted_position.and_then(|spotted_position| {
+            let v_creature = self.world.borrow::<View<PropCreature>>().unwrap();
+            let v_position = self.world.borrow::<View<PropPosition>>().unwrap();
+            let creature_positions = (&v_creature, &v_position)
+                .iter()
+                .map(|(_, position)| position.position);
+            choose_reinforcement_spawn(spotted_position, creature_positions)
+        });
+
+        match maybe_spawn_position {
+            None => {
+                if maybe_spotted_position.is_some() {
+                    info!("alarm has nowhere to send reinforcements");
+                }
+                vec![]
+            }
+            Some(position) => {
+                let mut rng = self.world.borrow::<UniqueViewMut<GameRng>>().unwrap();
+                let template_name =
+                    ALARM_REINFORCEMENTS[rng.gen_range(0..ALARM_REINFORCEMENTS.len())];
+                info!("alarm sending reinforcement: {}", template_name);
+                vec![Effect::CreateEntityByTemplateName {
+                    template_name: template_name.to_owned(),
+                    position: Point3::from_vec(position),
+                    orientation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+                }]
+            }
+        }
+    }
+
+    ///
+    /// get_currency_pickup
+    ///
+    /// If the entity being put in the player's inventory is a stack of currency (nanites), returns
+    /// the currency and how much of it there is.
+    fn get_currency_pickup(
+        &self,
+        parent_entity_id: EntityId,
+        dropped_entity_id: EntityId,
+    ) -> Option<(Currency, u32)> {
+        let inventory_entity_id = self
+            .world
+            .borrow::<UniqueView<PlayerInfo>>()
+            .unwrap()
+            .inventory_entity_id;
+        if parent_entity_id != inventory_entity_id {
+            return None;
+        }
+
+        let v_obj_name = self.world.borrow::<View<PropObjName>>().unwrap();
+        let currency = Currency::from_obj_name(&v_obj_name.get(dropped_entity_id).ok()?.0)?;
+
+        let v_stack_count = self.world.borrow::<View<PropStackCount>>().unwrap();
+        let amount = v_stack_count
+            .get(dropped_entity_id)
+            .map(|stack_count| stack_count.0)
+            .unwrap_or(1);
+        Some((currency, amount))
+    }
+
+    ///
+    /// get_telekinesis_target
+    ///
+    /// The item the hand holding the psi amp is pointed at, and the free hand to pull it into
+    fn get_telekinesis_target(
+        &self,
+        amp_entity_id: EntityId,
+    ) -> Option<(EntityId, vr_config::Handedness)> {
+        let (amp_hand, free_hand, free_handedness) = if self.left_hand.is_holding(amp_entity_id) {
+            (
+                &self.left_hand,
+                &self.right_hand,
+                vr_config::Handedness::Right,
+            )
+        } else if self.right_hand.is_holding(amp_entity_id) {
+            (
+                &self.right_hand,
+                &self.left_hand,
+                vr_config::Handedness::Left,
+            )
+        } else {
+            return None;
+        };
+
+        if free_hand.get_held_entity().is_some() {
+            return None;
+        }
+
+        amp_hand
+            .get_raytraced_entity()
+            .filter(|entity_id| can_grab_item(&self.world, *entity_id))
+            .map(|entity_id| (entity_id, free_handedness))
+    }
+
     fn update_animations(&mut self, time: &Time) {
         for (id, player) in self.id_to_animation_player.iter_mut() {
             // self.id_to_animation_player.entry(*id).and_modify(|player| {
@@ -646,6 +852,73 @@ impl Mission {
         }
     }
 
+    ///
+    /// add_meta_property
+    ///
+    /// Apply the properties of a metaproperty to an entity at runtime
+    pub fn add_meta_property(&mut self, entity_id: EntityId, metaprop_name: &str) {
+        let maybe_template_id = self
+            .template_name_to_template_id
+            .get(&metaprop_name.to_ascii_lowercase())
+            .map(|metadata| metadata.template_id);
+
+        match maybe_template_id {
+            None => info!("unable to find metaproperty: {}", metaprop_name),
+            Some(template_id) => apply_meta_property(
+                &mut self.world,
+                &self.entity_info.entity_to_properties,
+                template_id,
+                entity_id,
+            ),
+        }
+    }
+
+    ///
+    /// remove_meta_property
+    ///
+    /// Take away the properties of a metaproperty. Properties the entity's own archetypes provide
+    /// go back to those values.
+    pub fn remove_meta_property(&mut self, entity_id: EntityId, metaprop_name: &str) {
+        let maybe_template_id = self
+            .template_name_to_template_id
+            .get(&metaprop_name.to_ascii_lowercase())
+            .map(|metadata| metadata.template_id);
+
+        let metaprop_template_id = match maybe_template_id {
+            None => {
+                info!("unable to find metaproperty: {}", metaprop_name);
+                return;
+            }
+            Some(template_id) => template_id,
+        };
+
+        let maybe_entity_template_id = self
+            .world
+            .borrow::<View<dark::properties::PropTemplateId>>()
+            .unwrap()
+            .get(entity_id)
+            .map(|prop| prop.template_id)
+            .ok();
+
+        let archetype_ids = match maybe_entity_template_id {
+            None => vec![],
+            Some(template_id) => {
+                let hierarchy = ss2_entity_info::get_hierarchy(&self.entity_info);
+                let mut ancestors = ss2_entity_info::get_ancestors(hierarchy, &template_id);
+                ancestors.push(template_id);
+                ancestors
+            }
+        };
+
+        strip_meta_property(
+            &mut self.world,
+            &self.entity_info.entity_to_properties,
+            metaprop_template_id,
+            &archetype_ids,
+            entity_id,
+        );
+    }
+
     pub fn make_un_physical(&mut self, entity_id: EntityId) {
         let current_entity = self.id_to_physics.get(&entity_id);
         if current_entity.is_none() {
@@ -722,6 +995,7 @@ impl Mission {
         root_transform: Matrix4<f32>,
         additional_options: CreateEntityOptions,
     ) -> EntityCreationInfo {
+        let launched_by = additional_options.launched_by;
         let created_entity = {
             entity_creator::create_entity_with_position(
                 template_id,
@@ -750,6 +1024,7 @@ impl Mission {
             &mut self.script_world,
             created_entity,
             root_transform,
+            launched_by,
         )
     }
 
@@ -763,6 +1038,7 @@ impl Mission {
         script_world: &mut ScriptWorld,
         created_entity: EntityCreationInfo,
         root_transform: Matrix4<f32>,
+        launched_by: Option<EntityId>,
     ) -> EntityCreationInfo {
         let ret = created_entity.clone();
 
@@ -778,6 +1054,10 @@ impl Mission {
             id_to_bitmap.insert(created_entity.entity_id, bitmap_animation);
         }
 
+        if let Some(launched_by) = launched_by {
+            world.add_component(created_entity.entity_id, RuntimePropLaunchedBy(launched_by));
+        }
+
         let v_initial_velocity = world.borrow::<View<PropPhysInitialVelocity>>().unwrap();
         if let Some(rigid_body) = created_entity.rigid_body {
             let initial_velocity = v_initial_velocity
@@ -793,7 +1073,7 @@ impl Mission {
                 // Use raycast strategy for fast moving objects
                 script_world.add_entity2(
                     created_entity.entity_id,
-                    Box::new(InternalFastProjectileScript::new(x_velocity)),
+                    Box::new(InternalFastProjectileScript::new(x_velocity, launched_by)),
                 );
                 // HACK: Don't use physics for these entities...
                 physics.remove(created_entity.entity_id);
@@ -847,6 +1127,18 @@ impl Mission {
                     drop(quests);
                 }
 
+                Effect::AdjustHitPoints { entity_id, delta } if entity_id == player_entity => {
+                    let mut player_stats =
+                        self.world.borrow::<UniqueViewMut<PlayerStats>>().unwrap();
+                    // Psi powers, like the Psycho-Reflective Screen, can soak up some of the damage
+                    let delta = if delta < 0 {
+                        (delta as f32 * player_stats.psi().damage_multiplier()).round() as i32
+                    } else {
+                        delta
+                    };
+                    player_stats.adjust_hit_points(delta);
+                }
+
                 Effect::AdjustHitPoints { entity_id, delta } => {
                     let mut v_hit_points = self
                         .world
@@ -859,7 +1151,178 @@ impl Mission {
                 }
 
                 Effect::AwardXP { amount } => {
-                    warn!("!! TODO !!: Award XP {}", amount);
+                    let mut player_stats =
+                        self.world.borrow::<UniqueViewMut<PlayerStats>>().unwrap();
+                    player_stats.award_cyber_modules(amount);
+                }
+
+                Effect::AdjustPsiPoints { delta } => {
+                    let mut player_stats =
+                        self.world.borrow::<UniqueViewMut<PlayerStats>>().unwrap();
+                    player_stats.adjust_psi_points(delta);
+                }
+
+                Effect::IncreasePlayerStat { stat } => {
+                    let mut player_stats =
+                        self.world.borrow::<UniqueViewMut<PlayerStats>>().unwrap();
+                    if !player_stats.increase_stat(stat) {
+                        info!("{:?} is already at the maximum level", stat);
+                    }
+                }
+
+                Effect::TogglePlayerImplant { name, stat } => {
+                    let mut player_stats =
+                        self.world.borrow::<UniqueViewMut<PlayerStats>>().unwrap();
+                    let is_active = player_stats.toggle_implant(&name, stat);
+                    info!("implant {} active: {}", name, is_active);
+                }
+
+                Effect::SetHacked { entity_id } => {
+                    self.world.add_component(entity_id, PropHacked(true));
+                }
+
+                Effect::Unlock { entity_id } => {
+                    self.world.add_component(entity_id, PropLocked(false));
+                }
+
+                Effect::TriggerAlarm { entity_id } => {
+                    let player_pos = self.world.borrow::<UniqueView<PlayerInfo>>().unwrap().pos;
+                    let mut security = self
+                        .world
+                        .borrow::<UniqueViewMut<SecuritySystem>>()
+                        .unwrap();
+                    if security.raise_alarm(player_pos, entity_id) {
+                        info!("alarm raised by {:?}", entity_id);
+                    }
+                }
+
+                Effect::DisableAlarm => {
+                    let mut security = self
+                        .world
+                        .borrow::<UniqueViewMut<SecuritySystem>>()
+                        .unwrap();
+                    if security.reset_alarm() {
+                        info!("alarm disabled");
+                    }
+                }
+
+                Effect::DisableAlarmRaisedBy { entity_id } => {
+                    let mut security = self
+                        .world
+                        .borrow::<UniqueViewMut<SecuritySystem>>()
+                        .unwrap();
+                    if security.reset_alarm_raised_by(entity_id) {
+                        info!("alarm disabled by {:?}", entity_id);
+                    }
+                }
+
+                Effect::Purchase { price, effect } => {
+                    let is_paid = self
+                        .world
+                        .borrow::<UniqueViewMut<PlayerStats>>()
+                        .unwrap()
+                        .spend_currency(Currency::Nanites, price);
+                    if is_paid {
+                        global_effects.extend(self.handle_effects(
+                            Effect::flatten(vec![*effect]),
+                            global_context,
+                            game_options,
+                            asset_cache,
+                            audio_context,
+                        ));
+                    } else {
+                        info!("not enough nanites to spend {}", price);
+                    }
+                }
+
+                Effect::TrainWeaponSkill { skill } => {
+                    let mut player_stats =
+                        self.world.borrow::<UniqueViewMut<PlayerStats>>().unwrap();
+                    if !player_stats.train_weapon_skill(skill) {
+                        info!("unable to train {:?}", skill);
+                    }
+                }
+
+                Effect::BeginResearch {
+                    name,
+                    duration_seconds,
+                } => {
+                    let mut player_stats =
+                        self.world.borrow::<UniqueViewMut<PlayerStats>>().unwrap();
+                    if player_stats.begin_research(&name, duration_seconds) {
+                        info!("researching {} for {}s", name, duration_seconds);
+                    }
+                }
+
+                Effect::AdvanceResearch { elapsed_seconds } => {
+                    let mut player_stats =
+                        self.world.borrow::<UniqueViewMut<PlayerStats>>().unwrap();
+                    if let Some(name) = player_stats.advance_research(elapsed_seconds) {
+                        info!("finished researching {}", name);
+                    }
+                }
+
+                Effect::UsePsiPower { power } => {
+                    let mut player_stats =
+                        self.world.borrow::<UniqueViewMut<PlayerStats>>().unwrap();
+                    if !player_stats.use_psi_power(power) {
+                        info!("unable to use psi power {}", power.name());
+                    }
+                }
+
+                Effect::LearnPsiPower { power } => {
+                    let mut player_stats =
+                        self.world.borrow::<UniqueViewMut<PlayerStats>>().unwrap();
+                    if player_stats.learn_psi_power(power) {
+                        info!("learned psi power {}", power.name());
+                    } else {
+                        info!("unable to learn psi power {}", power.name());
+                    }
+                }
+
+                Effect::SelectNextPsiPower => {
+                    let mut player_stats =
+                        self.world.borrow::<UniqueViewMut<PlayerStats>>().unwrap();
+                    if let Some(power) = player_stats.psi_mut().select_next() {
+                        info!("selected psi power {}", power.name());
+                    }
+                }
+
+                Effect::Telekinesis { entity_id } => {
+                    // Grab through the hand, so the item ends up held just like any other
+                    if let Some((target_entity_id, hand)) = self.get_telekinesis_target(entity_id) {
+                        let mut effect_queue =
+                            self.world.borrow::<UniqueViewMut<EffectQueue>>().unwrap();
+                        effect_queue.push(Effect::GrabEntity {
+                            entity_id: target_entity_id,
+                            hand,
+                            current_parent_id: None,
+                        });
+                    }
+                }
+
+                Effect::SetGunState {
+                    entity_id,
+                    gun_state,
+                } => {
+                    self.world.add_component(entity_id, gun_state);
+                }
+
+                Effect::ActivateResurrectionMachine { entity_id } => {
+                    // The machine itself is solid, so bring the player back where they
+                    // were standing when they activated it
+                    let position = self.world.borrow::<UniqueView<PlayerInfo>>().unwrap().pos;
+                    let mut player_stats =
+                        self.world.borrow::<UniqueViewMut<PlayerStats>>().unwrap();
+                    player_stats.activate_resurrection_machine(&self.level_name, position);
+                    info!(
+                        "activated resurrection machine {:?} at {:?}",
+                        entity_id, position
+                    );
+                }
+
+                Effect::Send { msg } => {
+                    self.script_world.dispatch(msg);
                 }
 
                 Effect::DrawDebugLines { lines } => {
@@ -908,6 +1371,22 @@ impl Mission {
                     parent_entity_id,
                     dropped_entity_id,
                 } => {
+                    if let Some((currency, amount)) =
+                        self.get_currency_pickup(parent_entity_id, dropped_entity_id)
+                    {
+                        // Nanites go to the player's balance, not the inventory
+                        self.world
+                            .borrow::<UniqueViewMut<PlayerStats>>()
+                            .unwrap()
+                            .award_currency(currency, amount);
+                        info!("picked up {} {:?}", amount, currency);
+
+                        self.left_hand = self.left_hand.destroy_entity(dropped_entity_id);
+                        self.right_hand = self.right_hand.destroy_entity(dropped_entity_id);
+                        self.remove_entity(dropped_entity_id);
+                        continue;
+                    }
+
                     let mut was_able_to_drop = false;
                     {
                         // First, remove any existing contains links for the dropped entity..
@@ -1026,7 +1505,11 @@ impl Mission {
                             let query = MotionQuery::new(actor_type, query_items)
                                 .with_selection_strategy(selection_strategy);
                             // let query_with_actor =
-                            let maybe_next_animation = global_context.motiondb.query(query.clone());
+                            let maybe_next_animation = {
+                                let mut rng =
+                                    self.world.borrow::<UniqueViewMut<GameRng>>().unwrap();
+                                global_context.motiondb.query(query.clone(), &mut *rng)
+                            };
                             if let Some(next_animation) = maybe_next_animation {
                                 let maybe_clip = asset_cache.get_opt(
                                     &ANIMATION_CLIP_IMPORTER,
@@ -1123,6 +1606,17 @@ impl Mission {
                     self.remove_entity(entity_id);
                 }
 
+                Effect::AddLink { from, to, link } => add_link(&self.world, from, to, link),
+                Effect::RemoveLink { from, to, link } => remove_link(&self.world, from, to, link),
+                Effect::AddMetaProperty {
+                    entity_id,
+                    metaprop_name,
+                } => self.add_meta_property(entity_id, &metaprop_name),
+                Effect::RemoveMetaProperty {
+                    entity_id,
+                    metaprop_name,
+                } => self.remove_meta_property(entity_id, &metaprop_name),
+
                 Effect::ChangeModel {
                     entity_id,
                     model_name,
@@ -1167,25 +1661,65 @@ impl Mission {
                     drop(quests);
                 }
                 Effect::PlaySound { handle, name } => {
-                    let audio_file = resolve_schema(global_context, &name.to_string());
+                    let audio_file = resolve_schema(global_context, &self.world, &name.to_string());
                     let audio_clip = asset_cache.get(&AUDIO_IMPORTER, &format!("{audio_file}.wav"));
                     info!("Playing clip: {} handle: {:?}", name, &handle);
                     engine::audio::test_audio(audio_context, handle, None, audio_clip);
+
+                    // Sounds without a position come from the player interacting with things
+                    let (player_entity, player_pos) = {
+                        let player = self.world.borrow::<UniqueView<PlayerInfo>>().unwrap();
+                        (player.entity_id, player.pos)
+                    };
+                    self.world
+                        .borrow::<UniqueViewMut<NoiseEvents>>()
+                        .unwrap()
+                        .emit(player_entity, player_pos, PLAYER_NOISE_RADIUS);
+                }
+                Effect::PlayPositionalSound {
+                    handle,
+                    name,
+                    position,
+                    source,
+                } => {
+                    let audio_file = resolve_schema(global_context, &self.world, &name);
+                    let audio_clip = asset_cache.get(&AUDIO_IMPORTER, &format!("{audio_file}.wav"));
+                    info!(
+                        "Playing clip: {} handle: {:?} position: {:?}",
+                        name, &handle, position
+                    );
+                    engine::audio::play_spatial_audio(
+                        audio_context,
+                        position,
+                        handle,
+                        None,
+                        audio_clip,
+                    );
+                    self.world
+                        .borrow::<UniqueViewMut<NoiseEvents>>()
+                        .unwrap()
+                        .emit(source, position, ENVIRONMENTAL_NOISE_RADIUS);
                 }
                 // TODO: Global effect
                 Effect::PlayEnvironmentalSound {
                     query,
                     position,
                     audio_handle,
+                    source,
                 } => {
                     play_environmental_sound(
                         &global_context.gamesys,
+                        &self.world,
                         asset_cache,
                         audio_context,
                         query,
                         audio_handle,
                         position,
                     );
+                    self.world
+                        .borrow::<UniqueViewMut<NoiseEvents>>()
+                        .unwrap()
+                        .emit(source, position, ENVIRONMENTAL_NOISE_RADIUS);
                 }
                 Effect::SlayEntity { entity_id } => {
                     let did_slay = self.slay_entity(entity_id, asset_cache);
@@ -1201,6 +1735,7 @@ impl Mission {
 
                             play_environmental_sound(
                                 &global_context.gamesys,
+                                &self.world,
                                 asset_cache,
                                 audio_context,
                                 env_sound_query,
@@ -1389,9 +1924,18 @@ impl Mission {
         &mut self,
         asset_cache: &mut AssetCache,
         options: &GameOptions,
+        physics_alpha: f32,
     ) -> (Vec<SceneObject>, Vector3<f32>, Quaternion<f32>) {
         let _v_position = self.world.borrow::<View<PropPosition>>().unwrap();
         let v_transform = self.world.borrow::<View<RuntimePropTransform>>().unwrap();
+        let v_scale = self
+            .world
+            .borrow::<View<dark::properties::PropScale>>()
+            .unwrap();
+        let get_render_transform = |entity_id: EntityId| {
+            self.interpolated_transform(entity_id, &v_scale, physics_alpha)
+                .or_else(|| v_transform.get(entity_id).map(|p| p.0).ok())
+        };
         let v_frame_state = self.world.borrow::<View<PropFrameAnimState>>().unwrap();
         let v_render_type = self.world.borrow::<View<PropRenderType>>().unwrap();
 
@@ -1430,7 +1974,7 @@ impl Mission {
                 }
             };
 
-            if let Ok(xform) = v_transform.get(*entity_id).map(|p| p.0) {
+            if let Some(xform) = get_render_transform(*entity_id) {
                 for obj in scene_objs {
                     let mut xformed_obj = obj.clone();
                     xformed_obj.set_transform(xform);
@@ -1448,7 +1992,7 @@ impl Mission {
                 continue;
             }
 
-            if let Ok(xform) = v_transform.get(*entity_id).map(|p| p.0) {
+            if let Some(xform) = get_render_transform(*entity_id) {
                 let current_frame = v_frame_state
                     .get(*entity_id)
                     .map(|c| c.current_frame)
@@ -1703,6 +2247,7 @@ fn initialize_background_music(
     level: &dark::mission::SystemShock2Level,
     asset_cache: &mut AssetCache,
     audio_context: &mut AudioContext<EntityId, String>,
+    rng: GameRng,
 ) {
     let song_file_name = &level.song_params.song;
     info!("loading music for level: {}", song_file_name);
@@ -1712,7 +2257,7 @@ fn initialize_background_music(
                 .get(&SONG_IMPORTER, &format!("{song_file_name}.snc"))
                 .clone()
         };
-        let background_music_player = SongPlayer::new(&song, asset_cache);
+        let background_music_player = SongPlayer::new(&song, asset_cache, Box::new(rng));
         audio_context.set_background_music(Box::new(background_music_player));
     } else {
         audio_context.stop_background_music();
@@ -1787,6 +2332,17 @@ fn create_room_entities(
     }
 }
 
+fn physics_transform(
+    position: Vector3<f32>,
+    rotation: Quaternion<f32>,
+    scale: Vector3<f32>,
+) -> Matrix4<f32> {
+    let scale_xform = Matrix4::from_nonuniform_scale(scale.x.abs(), scale.y.abs(), scale.z.abs());
+    let translation_xform = Matrix4::from_translation(position);
+    let rotation_xform = Matrix4::from(rotation);
+    translation_xform * rotation_xform * scale_xform
+}
+
 fn option_to_vec<T>(option: Option<T>) -> Vec<T> {
     match option {
         None => vec![],
@@ -1808,10 +2364,11 @@ pub fn make_un_physical2(
     id_to_physics.remove(&entity_id);
 }
 
-fn resolve_schema(global_context: &GlobalContext, name: &str) -> String {
+fn resolve_schema(global_context: &GlobalContext, world: &World, name: &str) -> String {
     let sound_schema = &global_context.gamesys.sound_schema;
+    let mut rng = world.borrow::<UniqueViewMut<GameRng>>().unwrap();
     let ret = sound_schema
-        .get_random_sample(name)
+        .get_random_sample(name, &mut *rng)
         .unwrap_or_else(|| name.to_owned());
     trace!("resolved sound schema {} to {}", name, ret);
     ret
@@ -1819,13 +2376,17 @@ fn resolve_schema(global_context: &GlobalContext, name: &str) -> String {
 
 fn play_environmental_sound(
     gamesys: &Gamesys,
+    world: &World,
     asset_cache: &mut AssetCache,
     audio_context: &mut AudioContext<EntityId, String>,
     query: dark::EnvSoundQuery,
     audio_handle: AudioHandle,
     position: Vector3<f32>,
 ) {
-    let maybe_audio_file = gamesys.get_random_environmental_sound(&query);
+    let maybe_audio_file = {
+        let mut rng = world.borrow::<UniqueViewMut<GameRng>>().unwrap();
+        gamesys.get_random_environmental_sound(&query, &mut *rng)
+    };
     if maybe_audio_file.is_some() {
         let audio_file = maybe_audio_file.unwrap();
         let audio_clip = asset_cache.get(&AUDIO_IMPORTER, &format!("{audio_file}.wav").to_owned());
@@ -1837,3 +2398,158 @@ fn play_environmental_sound(
         engine::audio::play_spatial_audio(audio_context, position, audio_handle, None, audio_clip);
     }
 }
+
+///
+/// apply_meta_property
+///
+/// Give the entity the properties of the metaproperty's template
+fn apply_meta_property(
+    world: &mut World,
+    entity_to_properties: &HashMap<i32, Vec<Rc<Box<dyn Property>>>>,
+    metaprop_template_id: i32,
+    entity_id: EntityId,
+) {
+    if let Some(props) = entity_to_properties.get(&metaprop_template_id) {
+        for prop in props {
+            prop.initialize(world, entity_id);
+        }
+    }
+}
+
+///
+/// strip_meta_property
+///
+/// Take the metaproperty's properties off the entity, then put back any that the entity's own
+/// archetypes (its template and ancestors) provide.
+fn strip_meta_property(
+    world: &mut World,
+    entity_to_properties: &HashMap<i32, Vec<Rc<Box<dyn Property>>>>,
+    metaprop_template_id: i32,
+    archetype_ids: &[i32],
+    entity_id: EntityId,
+) {
+    if let Some(props) = entity_to_properties.get(&metaprop_template_id) {
+        for prop in props {
+            prop.remove(world, entity_id);
+        }
+    }
+
+    let missing_props = archetype_ids
+        .iter()
+        .filter_map(|id| entity_to_properties.get(id))
+        .flatten()
+        .filter(|prop| !prop.is_set(world, entity_id))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    for prop in missing_props {
+        prop.initialize(world, entity_id);
+    }
+}
+
+fn add_link(world: &World, from: EntityId, to: EntityId, link: Link) {
+    let mut v_links = world.borrow::<ViewMut<Links>>().unwrap();
+    if let Ok(links) = (&mut v_links).get(from) {
+        links.to_links.push(ToLink {
+            link,
+            to_entity_id: Some(WrappedEntityId(to)),
+            to_template_id: 0,
+        });
+    }
+}
+
+fn remove_link(world: &World, from: EntityId, to: EntityId, link: Link) {
+    let mut v_links = world.borrow::<ViewMut<Links>>().unwrap();
+    if let Ok(links) = (&mut v_links).get(from) {
+        links.to_links.retain(|to_link| {
+            let is_matching_link = std::mem::discriminant(&to_link.link)
+                == std::mem::discriminant(&link)
+                && to_link.to_entity_id.map(|id| id.0) == Some(to);
+
+            !is_matching_link
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dark::properties::PropSymName;
+
+    fn property<T: Property + 'static>(prop: T) -> Rc<Box<dyn Property>> {
+        let prop: Box<dyn Property> = Box::new(prop);
+        Rc::new(prop)
+    }
+
+    #[test]
+    fn test_remove_meta_property_restores_archetype_value() {
+        const ARCHETYPE: i32 = -10;
+        const METAPROP: i32 = -20;
+        let entity_to_properties = HashMap::from([
+            (ARCHETYPE, vec![property(PropObjName("Hybrid".to_owned()))]),
+            (
+                METAPROP,
+                vec![
+                    property(PropObjName("Cowering Hybrid".to_owned())),
+                    property(PropSymName("Coward".to_owned())),
+                ],
+            ),
+        ]);
+
+        let mut world = World::new();
+        let entity_id = world.add_entity((PropObjName("Hybrid".to_owned()),));
+
+        apply_meta_property(&mut world, &entity_to_properties, METAPROP, entity_id);
+        assert_eq!(
+            world
+                .borrow::<View<PropObjName>>()
+                .unwrap()
+                .get(entity_id)
+                .unwrap()
+                .0,
+            "Cowering Hybrid"
+        );
+        assert!(world
+            .borrow::<View<PropSymName>>()
+            .unwrap()
+            .contains(entity_id));
+
+        strip_meta_property(
+            &mut world,
+            &entity_to_properties,
+            METAPROP,
+            &[ARCHETYPE],
+            entity_id,
+        );
+        assert_eq!(
+            world
+                .borrow::<View<PropObjName>>()
+                .unwrap()
+                .get(entity_id)
+                .unwrap()
+                .0,
+            "Hybrid"
+        );
+        assert!(!world
+            .borrow::<View<PropSymName>>()
+            .unwrap()
+            .contains(entity_id));
+    }
+
+    #[test]
+    fn test_add_and_remove_link() {
+        let mut world = World::new();
+        let from = world.add_entity((Links::empty(),));
+        let to = world.add_entity((Links::empty(),));
+        let other = world.add_entity((Links::empty(),));
+
+        add_link(&world, from, to, Link::SwitchLink);
+        add_link(&world, from, other, Link::SwitchLink);
+        remove_link(&world, from, to, Link::SwitchLink);
+
+        let v_links = world.borrow::<View<Links>>().unwrap();
+        let to_links = &v_links.get(from).unwrap().to_links;
+        assert_eq!(to_links.len(), 1);
+        assert_eq!(to_links[0].to_entity_id.map(|id| id.0), Some(other));
+    }
+}
@@ -6,11 +6,12 @@ use dark::{
     ss2_entity_info::SystemShock2EntityInfo,
 };
 use num_traits::Zero;
+use serde::{Deserialize, Serialize};
 use shipyard::{Get, IntoIter, IntoWithId, View, World};
 
 use crate::scripts::script_util::{get_all_links_of_type, get_first_link_of_type};
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum SpawnLocation {
     MapDefault,
     Marker(i32),
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+// If a frame takes much longer than expected (ie, a hitch while loading assets),
+// cap the number of steps so we don't spiral trying to catch up.
+const MAX_STEPS_PER_FRAME: u32 = 4;
+
+pub const DEFAULT_PHYSICS_TICK_RATE: f32 = 60.0;
+
+// Range of tick rates that are sensible to simulate at
+const MIN_PHYSICS_TICK_RATE: f32 = 1.0;
+const MAX_PHYSICS_TICK_RATE: f32 = 1000.0;
+
+///
+/// FixedTimestep
+///
+/// Accumulates variable frame times, and hands out a whole number of fixed-size steps to run.
+/// The leftover time is used to interpolate between the last two simulated states when rendering.
+pub struct FixedTimestep {
+    step: Duration,
+    accumulator: Duration,
+}
+
+impl FixedTimestep {
+    pub fn new(tick_rate: f32) -> FixedTimestep {
+        // NaN would slip through the clamp, so falls back to the default
+        let tick_rate = if tick_rate.is_nan() {
+            DEFAULT_PHYSICS_TICK_RATE
+        } else {
+            tick_rate.clamp(MIN_PHYSICS_TICK_RATE, MAX_PHYSICS_TICK_RATE)
+        };
+
+        FixedTimestep {
+            step: Duration::from_secs_f32(1.0 / tick_rate),
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    pub fn step_duration(&self) -> Duration {
+        self.step
+    }
+
+    ///
+    /// advance
+    ///
+    /// Add the elapsed frame time, and return the number of fixed steps that should be run
+    pub fn advance(&mut self, elapsed: Duration) -> u32 {
+        self.accumulator += elapsed;
+
+        let mut steps = 0;
+        while self.accumulator >= self.step && steps < MAX_STEPS_PER_FRAME {
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+
+        // Drop any time we couldn't catch up on
+        if self.accumulator >= self.step {
+            self.accumulator = Duration::ZERO;
+        }
+
+        steps
+    }
+
+    ///
+    /// alpha
+    ///
+    /// How far we are between the last step and the next one, from 0.0 to 1.0
+    pub fn alpha(&self) -> f32 {
+        self.accumulator.as_secs_f32() / self.step.as_secs_f32()
+    }
+
+    pub fn reset(&mut self) {
+        self.accumulator = Duration::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accumulates_partial_frames() {
+        let mut timestep = FixedTimestep::new(50.0);
+
+        // 15ms isn't enough for a 20ms step...
+        assert_eq!(timestep.advance(Duration::from_millis(15)), 0);
+        assert!((timestep.alpha() - 0.75).abs() < 0.001);
+
+        // ...but another 15ms is, with 10ms left over
+        assert_eq!(timestep.advance(Duration::from_millis(15)), 1);
+        assert!((timestep.alpha() - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_invalid_tick_rates() {
+        let step_duration = |tick_rate| FixedTimestep::new(tick_rate).step_duration();
+
+        assert_eq!(step_duration(0.0), Duration::from_secs(1));
+        assert_eq!(step_duration(-60.0), Duration::from_secs(1));
+        assert_eq!(step_duration(f32::INFINITY), Duration::from_millis(1));
+        assert_eq!(
+            step_duration(f32::NAN),
+            step_duration(DEFAULT_PHYSICS_TICK_RATE)
+        );
+    }
+
+    #[test]
+    fn test_long_frames_are_capped() {
+        let mut timestep = FixedTimestep::new(50.0);
+
+        assert_eq!(
+            timestep.advance(Duration::from_secs(1)),
+            MAX_STEPS_PER_FRAME
+        );
+        assert_eq!(timestep.alpha(), 0.0);
+    }
+}
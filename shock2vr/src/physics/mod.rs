@@ -1,7 +1,10 @@
 mod debug_render_pipeline;
+mod fixed_timestep;
 mod physics_events;
 mod util;
 
+pub use fixed_timestep::*;
+
 use collision::Aabb3;
 use engine::profile;
 use std::collections::{HashMap, HashSet};
@@ -172,6 +175,9 @@ pub struct PhysicsWorld {
 
     // Collision Events
     events: PhysicsEvents,
+
+    // Positions of the non-static bodies before the latest step, for render interpolation
+    previous_positions: HashMap<RigidBodyHandle, Isometry<Real>>,
 }
 
 impl PhysicsWorld {
@@ -232,6 +238,8 @@ impl PhysicsWorld {
                     rigid_body.set_position(xform, true);
                     rigid_body.reset_torques(true);
                     rigid_body.reset_forces(true);
+                    // Teleported - don't interpolate from the old position
+                    self.previous_positions.remove(handle);
                 }
             }
         }
@@ -264,6 +272,8 @@ impl PhysicsWorld {
                 rigid_body.set_position(xform, true);
                 rigid_body.reset_torques(true);
                 rigid_body.reset_forces(true);
+                // Teleported - don't interpolate from the old position
+                self.previous_positions.remove(&handle);
             }
         }
     }
@@ -394,6 +404,29 @@ impl PhysicsWorld {
         maybe_rigid_body.map(|rigid_body| nvec_to_cgmath(*rigid_body.translation()))
     }
 
+    ///
+    /// get_interpolated_position_rotation
+    ///
+    /// Get the position and rotation of a body, blended between the previous step (alpha = 0.0)
+    /// and the current step (alpha = 1.0)
+    pub fn get_interpolated_position_rotation(
+        &self,
+        handle: RigidBodyHandle,
+        alpha: f32,
+    ) -> Option<(Vector3<f32>, Quaternion<f32>)> {
+        let rigid_body = self.rigid_body_set.get(handle)?;
+        let current = rigid_body.position();
+        let position = match self.previous_positions.get(&handle) {
+            Some(previous) => previous.lerp_slerp(current, alpha),
+            None => *current,
+        };
+
+        Some((
+            nvec_to_cgmath(position.translation.vector),
+            nquat_to_quat(position.rotation),
+        ))
+    }
+
     pub fn get_angular_velocity(&self, handle: RigidBodyHandle) -> Option<Vector3<f32>> {
         let maybe_rigid_body = self.rigid_body_set.get(handle);
 
@@ -579,6 +612,7 @@ impl PhysicsWorld {
             }
         }
         for handle in bodies_to_remove {
+            self.previous_positions.remove(&handle);
             self.rigid_body_set.remove(
                 handle,
                 &mut self.island_manager,
@@ -685,6 +719,8 @@ impl PhysicsWorld {
             player_sensor_intersections: HashSet::new(),
 
             events: PhysicsEvents::new(),
+
+            previous_positions: HashMap::new(),
         }
     }
 
@@ -703,12 +739,28 @@ impl PhysicsWorld {
         debug_renderer.render()
     }
 
+    ///
+    /// update
+    ///
+    /// Step the simulation forward by a single fixed timestep (in seconds),
+    /// moving the player by `desired_movement` over the course of the step.
     pub fn update(
         &mut self,
+        timestep: f32,
         desired_movement: Vector3<f32>,
         player_handle: &mut PlayerHandle,
     ) -> (Vector3<f32>, Vec<CollisionEvent>) {
-        /* Run the game loop, stepping the simulation once per frame. */
+        self.integration_parameters.dt = timestep;
+
+        // Keep track of where everything was, so rendering can interpolate between steps
+        self.previous_positions.clear();
+        for (handle, rigid_body) in self.rigid_body_set.iter() {
+            if !rigid_body.is_fixed() {
+                self.previous_positions
+                    .insert(handle, *rigid_body.position());
+            }
+        }
+
         profile!(
             "physics.step",
             self.physics_pipeline.step(
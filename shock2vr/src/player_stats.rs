@@ -0,0 +1,652 @@
+///
+/// player_stats.rs
+///
+/// Module keeping track of the player character - hit points, psi points, stats, skills, cyber
+/// modules, nanites, OS upgrades, resurrection machines, research, and psi powers. Like QuestInfo, this is
+/// carried across missions and saved in the global save data.
+///
+use std::collections::{BTreeMap, BTreeSet};
+
+use cgmath::Vector3;
+use serde::{Deserialize, Serialize};
+use shipyard::Unique;
+
+use crate::psi_powers::{PsiPower, PsiState};
+
+// Stats and skills range from 1 to 6 - implants can temporarily push a stat past the maximum
+pub const MIN_LEVEL: u32 = 1;
+pub const MAX_LEVEL: u32 = 6;
+
+const BASE_MAX_HIT_POINTS: i32 = 30;
+const HIT_POINTS_PER_ENDURANCE: i32 = 5;
+const TANK_HIT_POINTS: i32 = 5;
+
+const BASE_MAX_PSI_POINTS: i32 = 10;
+const PSI_POINTS_PER_PSI: i32 = 5;
+
+// Hypos heal half again as much with the Pharmo-Friendly upgrade
+const PHARMO_FRIENDLY_MULTIPLIER: f32 = 1.5;
+
+// Cost, in nanites, of being brought back by a Quantum Bio-Reconstruction machine
+pub const RESURRECTION_NANITE_COST: u32 = 10;
+
+// Replicator Expert knocks this fraction off of replicator prices
+const REPLICATOR_EXPERT_DISCOUNT: f32 = 0.2;
+
+// Cyber modules needed to train a weapon skill, per level the skill is already at
+const WEAPON_TRAINING_COST_PER_LEVEL: u32 = 8;
+
+// Cyber modules needed to learn a psi power, per tier of the power
+const PSI_POWER_COST_PER_TIER: u32 = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Stat {
+    Strength,
+    Endurance,
+    Psi,
+    Agility,
+    Cyber,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum TechSkill {
+    Hacking,
+    Repair,
+    Modify,
+    Maintenance,
+    Research,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum WeaponSkill {
+    Standard,
+    Energy,
+    Heavy,
+    Exotic,
+}
+
+// Balances the player spends - only nanites are picked up by name here; cyber modules are awarded
+// by their own expcookie script, from the amount in PropExp
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Currency {
+    Nanites,
+    CyberModules,
+}
+
+impl Currency {
+    pub fn from_obj_name(obj_name: &str) -> Option<Currency> {
+        match obj_name.to_ascii_lowercase().as_str() {
+            "nanites" => Some(Currency::Nanites),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum OsUpgrade {
+    Tank,
+    LethalWeapon,
+    Sharpshooter,
+    PharmoFriendly,
+    PackRat,
+    SecurityExpert,
+    Smasher,
+    SpatiallyAware,
+    StrongMetabolism,
+    CyberneticallyEnhanced,
+    AutoRepair,
+    Speedy,
+    ReplicatorExpert,
+    PowerPsi,
+}
+
+#[derive(Deserialize, Serialize, Unique, Clone, Debug)]
+pub struct PlayerStats {
+    hit_points: i32,
+    psi_points: i32,
+    cyber_modules: u32,
+    // Balance of Currency::Nanites - only ever changed through award_currency / spend_currency
+    #[serde(default)]
+    nanites: u32,
+    stats: BTreeMap<Stat, u32>,
+    tech_skills: BTreeMap<TechSkill, u32>,
+    weapon_skills: BTreeMap<WeaponSkill, u32>,
+    os_upgrades: BTreeSet<OsUpgrade>,
+    // Implants that are currently active, by name, along with the stat they boost
+    active_implants: BTreeMap<String, Stat>,
+    // Where the player comes back after dying, by (lowercase) mission name -
+    // each mission has its own Quantum Bio-Reconstruction machine
+    #[serde(default)]
+    resurrection_points: BTreeMap<String, Vector3<f32>>,
+    // Objects that have been researched, by (lowercase) name
+    #[serde(default)]
+    researched: BTreeSet<String>,
+    #[serde(default)]
+    active_research: Option<ActiveResearch>,
+    #[serde(default)]
+    psi: PsiState,
+}
+
+// Research is done on one kind of object at a time, and is done once it has run long enough
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct ActiveResearch {
+    pub name: String,
+    pub remaining_seconds: f32,
+}
+
+impl PlayerStats {
+    pub fn new() -> PlayerStats {
+        let mut player_stats = PlayerStats {
+            hit_points: 0,
+            psi_points: 0,
+            cyber_modules: 0,
+            nanites: 0,
+            stats: BTreeMap::new(),
+            tech_skills: BTreeMap::new(),
+            weapon_skills: BTreeMap::new(),
+            os_upgrades: BTreeSet::new(),
+            active_implants: BTreeMap::new(),
+            resurrection_points: BTreeMap::new(),
+            researched: BTreeSet::new(),
+            active_research: None,
+            psi: PsiState::new(),
+        };
+        player_stats.hit_points = player_stats.max_hit_points();
+        player_stats.psi_points = player_stats.max_psi_points();
+        player_stats
+    }
+
+    pub fn hit_points(&self) -> i32 {
+        self.hit_points
+    }
+
+    pub fn max_hit_points(&self) -> i32 {
+        let tank_bonus = if self.has_os_upgrade(OsUpgrade::Tank) {
+            TANK_HIT_POINTS
+        } else {
+            0
+        };
+        BASE_MAX_HIT_POINTS
+            + (self.stat(Stat::Endurance) - MIN_LEVEL) as i32 * HIT_POINTS_PER_ENDURANCE
+            + tank_bonus
+    }
+
+    ///
+    /// adjust_hit_points
+    ///
+    /// Heal (positive delta) or hurt (negative delta) the player, keeping hit points
+    /// between 0 and the maximum. Returns the new hit points.
+    pub fn adjust_hit_points(&mut self, delta: i32) -> i32 {
+        self.hit_points = (self.hit_points + delta).clamp(0, self.max_hit_points());
+        self.hit_points
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.hit_points <= 0
+    }
+
+    pub fn psi_points(&self) -> i32 {
+        self.psi_points
+    }
+
+    pub fn max_psi_points(&self) -> i32 {
+        BASE_MAX_PSI_POINTS + (self.stat(Stat::Psi) - MIN_LEVEL) as i32 * PSI_POINTS_PER_PSI
+    }
+
+    pub fn adjust_psi_points(&mut self, delta: i32) -> i32 {
+        self.psi_points = (self.psi_points + delta).clamp(0, self.max_psi_points());
+        self.psi_points
+    }
+
+    pub fn psi(&self) -> &PsiState {
+        &self.psi
+    }
+
+    pub fn psi_mut(&mut self) -> &mut PsiState {
+        &mut self.psi
+    }
+
+    pub fn can_use_psi_power(&self, power: PsiPower) -> bool {
+        self.psi.is_ready(power) && self.psi_points >= power.psi_cost()
+    }
+
+    pub fn psi_power_cost(&self, power: PsiPower) -> u32 {
+        power.tier() * PSI_POWER_COST_PER_TIER
+    }
+
+    ///
+    /// learn_psi_power
+    ///
+    /// Spend cyber modules to learn a psi power, as long as the player's psi level is at least the
+    /// power's tier. Returns whether the power was learned.
+    pub fn learn_psi_power(&mut self, power: PsiPower) -> bool {
+        if self.psi.is_learned(power) || power.tier() > self.stat(Stat::Psi) {
+            return false;
+        }
+
+        if !self.spend_cyber_modules(self.psi_power_cost(power)) {
+            return false;
+        }
+
+        self.psi.learn(power);
+        true
+    }
+
+    ///
+    /// use_psi_power
+    ///
+    /// Spend the psi points for a power and start its cooldown. Returns false if the power can't
+    /// be used right now.
+    pub fn use_psi_power(&mut self, power: PsiPower) -> bool {
+        if !self.can_use_psi_power(power) {
+            return false;
+        }
+
+        self.psi_points -= power.psi_cost();
+        self.psi.use_power(power);
+        true
+    }
+
+    ///
+    /// hypo_amount
+    ///
+    /// How much a hypo (medical or psi) restores, taking OS upgrades into account
+    pub fn hypo_amount(&self, base_amount: i32) -> i32 {
+        if self.has_os_upgrade(OsUpgrade::PharmoFriendly) {
+            (base_amount as f32 * PHARMO_FRIENDLY_MULTIPLIER).round() as i32
+        } else {
+            base_amount
+        }
+    }
+
+    pub fn cyber_modules(&self) -> u32 {
+        self.cyber_modules
+    }
+
+    pub fn award_cyber_modules(&mut self, amount: i32) {
+        self.cyber_modules = self.cyber_modules.saturating_add_signed(amount);
+    }
+
+    ///
+    /// spend_cyber_modules
+    ///
+    /// Spend cyber modules (ie, at an upgrade station), if the player has enough.
+    /// Returns whether the modules were spent.
+    pub fn spend_cyber_modules(&mut self, amount: u32) -> bool {
+        if self.cyber_modules >= amount {
+            self.cyber_modules -= amount;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn balance(&self, currency: Currency) -> u32 {
+        match currency {
+            Currency::Nanites => self.nanites,
+            Currency::CyberModules => self.cyber_modules,
+        }
+    }
+
+    pub fn award_currency(&mut self, currency: Currency, amount: u32) {
+        match currency {
+            Currency::Nanites => self.nanites = self.nanites.saturating_add(amount),
+            Currency::CyberModules => self.award_cyber_modules(amount as i32),
+        }
+    }
+
+    ///
+    /// spend_currency
+    ///
+    /// Spend some of the currency, if the player has enough. Returns whether it was spent.
+    pub fn spend_currency(&mut self, currency: Currency, amount: u32) -> bool {
+        match currency {
+            Currency::Nanites if self.nanites >= amount => {
+                self.nanites -= amount;
+                true
+            }
+            Currency::Nanites => false,
+            Currency::CyberModules => self.spend_cyber_modules(amount),
+        }
+    }
+
+    ///
+    /// replicator_price
+    ///
+    /// The nanites the player pays for an item listed at base_price
+    pub fn replicator_price(&self, base_price: u32) -> u32 {
+        if self.has_os_upgrade(OsUpgrade::ReplicatorExpert) {
+            (base_price as f32 * (1.0 - REPLICATOR_EXPERT_DISCOUNT)).round() as u32
+        } else {
+            base_price
+        }
+    }
+
+    pub fn resurrection_point(&self, mission: &str) -> Option<Vector3<f32>> {
+        self.resurrection_points
+            .get(&mission.to_ascii_lowercase())
+            .copied()
+    }
+
+    pub fn activate_resurrection_machine(&mut self, mission: &str, position: Vector3<f32>) {
+        self.resurrection_points
+            .insert(mission.to_ascii_lowercase(), position);
+    }
+
+    ///
+    /// resurrect
+    ///
+    /// Bring the player back to life at the resurrection machine for the mission, if one has been
+    /// activated and the player can pay for it. Returns the position to respawn at.
+    pub fn resurrect(&mut self, mission: &str) -> Option<Vector3<f32>> {
+        let position = self.resurrection_point(mission)?;
+        if !self.spend_currency(Currency::Nanites, RESURRECTION_NANITE_COST) {
+            return None;
+        }
+        self.hit_points = self.max_hit_points();
+        Some(position)
+    }
+
+    ///
+    /// stat
+    ///
+    /// The effective level of a stat, including any active implants
+    pub fn stat(&self, stat: Stat) -> u32 {
+        let boost = self
+            .active_implants
+            .values()
+            .filter(|boosted| **boosted == stat)
+            .count() as u32;
+        self.base_stat(stat) + boost
+    }
+
+    pub fn base_stat(&self, stat: Stat) -> u32 {
+        *self.stats.get(&stat).unwrap_or(&MIN_LEVEL)
+    }
+
+    ///
+    /// increase_stat
+    ///
+    /// Permanently raise a stat by one level, up to the maximum. Returns whether the stat changed.
+    pub fn increase_stat(&mut self, stat: Stat) -> bool {
+        let changed = increase_level(&mut self.stats, stat);
+        self.clamp_points();
+        changed
+    }
+
+    pub fn tech_skill(&self, skill: TechSkill) -> u32 {
+        *self.tech_skills.get(&skill).unwrap_or(&MIN_LEVEL)
+    }
+
+    pub fn increase_tech_skill(&mut self, skill: TechSkill) -> bool {
+        increase_level(&mut self.tech_skills, skill)
+    }
+
+    pub fn weapon_skill(&self, skill: WeaponSkill) -> u32 {
+        *self.weapon_skills.get(&skill).unwrap_or(&MIN_LEVEL)
+    }
+
+    pub fn increase_weapon_skill(&mut self, skill: WeaponSkill) -> bool {
+        increase_level(&mut self.weapon_skills, skill)
+    }
+
+    pub fn weapon_training_cost(&self, skill: WeaponSkill) -> u32 {
+        self.weapon_skill(skill) * WEAPON_TRAINING_COST_PER_LEVEL
+    }
+
+    ///
+    /// train_weapon_skill
+    ///
+    /// Spend cyber modules to raise a weapon skill a level. Returns whether the skill changed.
+    pub fn train_weapon_skill(&mut self, skill: WeaponSkill) -> bool {
+        if self.weapon_skill(skill) >= MAX_LEVEL {
+            return false;
+        }
+        self.spend_cyber_modules(self.weapon_training_cost(skill))
+            && self.increase_weapon_skill(skill)
+    }
+
+    pub fn is_researched(&self, name: &str) -> bool {
+        self.researched.contains(&name.to_ascii_lowercase())
+    }
+
+    pub fn active_research(&self) -> Option<&ActiveResearch> {
+        self.active_research.as_ref()
+    }
+
+    ///
+    /// begin_research
+    ///
+    /// Start researching an object, replacing any research in progress. Returns false if the
+    /// object has already been researched, or is already being researched.
+    pub fn begin_research(&mut self, name: &str, duration_seconds: f32) -> bool {
+        let name = name.to_ascii_lowercase();
+        let is_active = self
+            .active_research
+            .as_ref()
+            .map(|research| research.name == name)
+            .unwrap_or(false);
+        if is_active || self.researched.contains(&name) {
+            return false;
+        }
+
+        self.active_research = Some(ActiveResearch {
+            name,
+            remaining_seconds: duration_seconds,
+        });
+        true
+    }
+
+    ///
+    /// advance_research
+    ///
+    /// Progress the active research. Returns the name of the object if the research completed.
+    pub fn advance_research(&mut self, elapsed_seconds: f32) -> Option<String> {
+        let research = self.active_research.as_mut()?;
+        research.remaining_seconds -= elapsed_seconds;
+        if research.remaining_seconds > 0.0 {
+            return None;
+        }
+
+        let completed = self.active_research.take()?;
+        self.researched.insert(completed.name.clone());
+        Some(completed.name)
+    }
+
+    pub fn has_os_upgrade(&self, upgrade: OsUpgrade) -> bool {
+        self.os_upgrades.contains(&upgrade)
+    }
+
+    pub fn add_os_upgrade(&mut self, upgrade: OsUpgrade) {
+        self.os_upgrades.insert(upgrade);
+    }
+
+    pub fn is_implant_active(&self, implant_name: &str) -> bool {
+        self.active_implants.contains_key(implant_name)
+    }
+
+    ///
+    /// toggle_implant
+    ///
+    /// Activate the implant if it isn't active, or deactivate it if it is.
+    /// Returns whether the implant is now active.
+    pub fn toggle_implant(&mut self, implant_name: &str, stat: Stat) -> bool {
+        let is_active = if self.active_implants.remove(implant_name).is_some() {
+            false
+        } else {
+            self.active_implants.insert(implant_name.to_owned(), stat);
+            true
+        };
+        self.clamp_points();
+        is_active
+    }
+
+    // Removing a boost can lower the maximums, so make sure the current points still fit
+    fn clamp_points(&mut self) {
+        self.hit_points = self.hit_points.min(self.max_hit_points());
+        self.psi_points = self.psi_points.min(self.max_psi_points());
+    }
+}
+
+fn increase_level<T: Ord>(levels: &mut BTreeMap<T, u32>, key: T) -> bool {
+    let level = levels.entry(key).or_insert(MIN_LEVEL);
+    if *level < MAX_LEVEL {
+        *level += 1;
+        true
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hit_points_are_clamped() {
+        let mut stats = PlayerStats::new();
+        let max = stats.max_hit_points();
+
+        assert_eq!(stats.adjust_hit_points(10), max);
+        assert_eq!(stats.adjust_hit_points(-(max + 10)), 0);
+        assert!(stats.is_dead());
+    }
+
+    #[test]
+    fn test_endurance_raises_max_hit_points() {
+        let mut stats = PlayerStats::new();
+        let max = stats.max_hit_points();
+
+        assert!(stats.increase_stat(Stat::Endurance));
+        assert_eq!(stats.max_hit_points(), max + HIT_POINTS_PER_ENDURANCE);
+    }
+
+    #[test]
+    fn test_stats_are_capped() {
+        let mut stats = PlayerStats::new();
+        for _ in MIN_LEVEL..MAX_LEVEL {
+            assert!(stats.increase_stat(Stat::Strength));
+        }
+        assert!(!stats.increase_stat(Stat::Strength));
+        assert_eq!(stats.stat(Stat::Strength), MAX_LEVEL);
+    }
+
+    #[test]
+    fn test_resurrection_costs_nanites() {
+        let mut stats = PlayerStats::new();
+        stats.adjust_hit_points(-stats.max_hit_points());
+        assert_eq!(stats.resurrect("medsci1.mis"), None);
+
+        let position = Vector3::new(1.0, 2.0, 3.0);
+        stats.activate_resurrection_machine("MEDSCI1.MIS", position);
+        assert_eq!(stats.resurrect("medsci1.mis"), None);
+        assert!(stats.is_dead());
+
+        stats.award_currency(Currency::Nanites, RESURRECTION_NANITE_COST + 5);
+        assert_eq!(stats.resurrect("medsci1.mis"), Some(position));
+        assert_eq!(stats.hit_points(), stats.max_hit_points());
+        assert_eq!(stats.balance(Currency::Nanites), 5);
+        assert_eq!(stats.resurrection_point("eng1.mis"), None);
+    }
+
+    #[test]
+    fn test_research_completes_over_time() {
+        let mut stats = PlayerStats::new();
+        assert!(stats.begin_research("Grub Organ", 10.0));
+        assert!(!stats.begin_research("grub organ", 10.0));
+
+        assert_eq!(stats.advance_research(6.0), None);
+        assert!(!stats.is_researched("grub organ"));
+        assert_eq!(stats.advance_research(6.0), Some("grub organ".to_owned()));
+        assert!(stats.is_researched("Grub Organ"));
+        assert_eq!(stats.active_research(), None);
+        assert!(!stats.begin_research("Grub Organ", 10.0));
+    }
+
+    #[test]
+    fn test_weapon_training_costs_cyber_modules() {
+        let mut stats = PlayerStats::new();
+        let cost = stats.weapon_training_cost(WeaponSkill::Standard);
+        assert!(!stats.train_weapon_skill(WeaponSkill::Standard));
+
+        stats.award_cyber_modules(cost as i32);
+        assert!(stats.train_weapon_skill(WeaponSkill::Standard));
+        assert_eq!(stats.weapon_skill(WeaponSkill::Standard), MIN_LEVEL + 1);
+        assert_eq!(stats.cyber_modules(), 0);
+    }
+
+    #[test]
+    fn test_implants_boost_stats_while_active() {
+        let mut stats = PlayerStats::new();
+        let max_psi = stats.max_psi_points();
+        stats.adjust_psi_points(max_psi);
+
+        assert!(stats.toggle_implant("WormMind Implant", Stat::Psi));
+        assert_eq!(stats.stat(Stat::Psi), MIN_LEVEL + 1);
+        assert_eq!(stats.base_stat(Stat::Psi), MIN_LEVEL);
+        stats.adjust_psi_points(PSI_POINTS_PER_PSI);
+
+        assert!(!stats.toggle_implant("WormMind Implant", Stat::Psi));
+        assert_eq!(stats.stat(Stat::Psi), MIN_LEVEL);
+        assert_eq!(stats.psi_points(), max_psi);
+    }
+
+    #[test]
+    fn test_currency_pickups() {
+        let mut stats = PlayerStats::new();
+        stats.award_currency(Currency::from_obj_name("Nanites").unwrap(), 20);
+        stats.award_currency(Currency::CyberModules, 4);
+        assert_eq!(Currency::from_obj_name("Hypo"), None);
+        // Picked up through the expcookie script instead
+        assert_eq!(Currency::from_obj_name("Cyber Modules"), None);
+
+        assert_eq!(stats.balance(Currency::Nanites), 20);
+        assert_eq!(stats.cyber_modules(), 4);
+    }
+
+    #[test]
+    fn test_replicator_expert_discount() {
+        let mut stats = PlayerStats::new();
+        assert_eq!(stats.replicator_price(50), 50);
+        stats.add_os_upgrade(OsUpgrade::ReplicatorExpert);
+        assert_eq!(stats.replicator_price(50), 40);
+    }
+
+    #[test]
+    fn test_psi_powers_cost_psi_points() {
+        let mut stats = PlayerStats::new();
+        let power = PsiPower::PsychoReflectiveScreen;
+        assert!(!stats.use_psi_power(power));
+
+        stats.award_cyber_modules(100);
+        assert!(stats.learn_psi_power(power));
+        assert!(stats.use_psi_power(power));
+        assert_eq!(
+            stats.psi_points(),
+            stats.max_psi_points() - power.psi_cost()
+        );
+        // Still cooling down
+        assert!(!stats.use_psi_power(power));
+    }
+
+    #[test]
+    fn test_learn_psi_power() {
+        let mut stats = PlayerStats::new();
+        let power = PsiPower::PsionicActionCloak;
+
+        // Not enough cyber modules
+        assert!(!stats.learn_psi_power(PsiPower::ProjectedCryokinesis));
+
+        stats.award_cyber_modules(100);
+        // Tier 2 needs a psi level of at least 2
+        assert!(!stats.learn_psi_power(power));
+
+        stats.increase_stat(Stat::Psi);
+        assert!(stats.learn_psi_power(power));
+        assert_eq!(stats.cyber_modules(), 100 - stats.psi_power_cost(power));
+        assert_eq!(stats.psi().selected(), Some(power));
+
+        // Already learned
+        assert!(!stats.learn_psi_power(power));
+    }
+}
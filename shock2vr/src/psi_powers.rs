@@ -0,0 +1,386 @@
+///
+/// psi_powers.rs
+///
+/// The psi powers the player can channel through the psi amp - the catalog of powers, by tier,
+/// along with the powers the player has learned, the one selected on the amp, cooldowns, and any
+/// buffs that are currently active. This is kept as part of PlayerStats.
+///
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+// Damage taken is scaled by this while the Psycho-Reflective Screen is up
+const SCREEN_DAMAGE_MULTIPLIER: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PsiPower {
+    // Tier 1
+    ProjectedCryokinesis,
+    PsychoReflectiveScreen,
+    KineticRedirection,
+    // Tier 2
+    PsionicActionCloak,
+    NeuralDecontamination,
+    // Tier 3
+    ElectronCascade,
+    RecursivePsionicAmplification,
+    // Tier 4
+    ProjectedPyrokinesis,
+    PsionicHypnogenesis,
+    // Tier 5
+    SomaTransference,
+    MetacreativeBarrier,
+}
+
+pub const ALL_PSI_POWERS: [PsiPower; 11] = [
+    PsiPower::ProjectedCryokinesis,
+    PsiPower::PsychoReflectiveScreen,
+    PsiPower::KineticRedirection,
+    PsiPower::PsionicActionCloak,
+    PsiPower::NeuralDecontamination,
+    PsiPower::ElectronCascade,
+    PsiPower::RecursivePsionicAmplification,
+    PsiPower::ProjectedPyrokinesis,
+    PsiPower::PsionicHypnogenesis,
+    PsiPower::SomaTransference,
+    PsiPower::MetacreativeBarrier,
+];
+
+// Lasting effects of a psi power on the player
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PsiBuff {
+    // Reduces damage taken
+    Screen,
+    // Hides the player from the AI
+    Cloak,
+}
+
+// What happens when a psi power is used
+#[derive(Clone, Debug, PartialEq)]
+pub enum PsiPowerEffect {
+    // Launch one of the psi amp's projectiles - the index into the Projectile links the amp
+    // inherits from its archetype, the same way a weapon's setting picks its projectile
+    Projectile {
+        index: usize,
+    },
+    Buff {
+        buff: PsiBuff,
+        duration_seconds: f32,
+    },
+    // Pull the object the amp is pointed at into the player's free hand
+    Telekinesis,
+    // Recharge energy weapons the player is holding
+    RechargeHeldItems,
+    AdjustHitPoints {
+        delta: i32,
+    },
+    AdjustPsiPoints {
+        delta: i32,
+    },
+}
+
+impl PsiPower {
+    pub fn tier(&self) -> u32 {
+        match self {
+            PsiPower::ProjectedCryokinesis
+            | PsiPower::PsychoReflectiveScreen
+            | PsiPower::KineticRedirection => 1,
+            PsiPower::PsionicActionCloak | PsiPower::NeuralDecontamination => 2,
+            PsiPower::ElectronCascade | PsiPower::RecursivePsionicAmplification => 3,
+            PsiPower::ProjectedPyrokinesis | PsiPower::PsionicHypnogenesis => 4,
+            PsiPower::SomaTransference | PsiPower::MetacreativeBarrier => 5,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PsiPower::ProjectedCryokinesis => "Projected Cryokinesis",
+            PsiPower::PsychoReflectiveScreen => "Psycho-Reflective Screen",
+            PsiPower::KineticRedirection => "Kinetic Redirection",
+            PsiPower::PsionicActionCloak => "Psionic Action Cloak",
+            PsiPower::NeuralDecontamination => "Neural Decontamination",
+            PsiPower::ElectronCascade => "Electron Cascade",
+            PsiPower::RecursivePsionicAmplification => "Recursive Psionic Amplification",
+            PsiPower::ProjectedPyrokinesis => "Projected Pyrokinesis",
+            PsiPower::PsionicHypnogenesis => "Psionic Hypnogenesis",
+            PsiPower::SomaTransference => "Soma Transference",
+            PsiPower::MetacreativeBarrier => "Metacreative Barrier",
+        }
+    }
+
+    ///
+    /// psi_cost
+    ///
+    /// Psi points spent each time the power is used - higher tier powers cost more
+    pub fn psi_cost(&self) -> i32 {
+        match self {
+            PsiPower::ProjectedCryokinesis | PsiPower::ProjectedPyrokinesis => {
+                self.tier() as i32 + 1
+            }
+            PsiPower::KineticRedirection => 1,
+            // Amplification trades health for psi, so it is free to use
+            PsiPower::RecursivePsionicAmplification => 0,
+            _ => self.tier() as i32 * 3,
+        }
+    }
+
+    pub fn cooldown_seconds(&self) -> f32 {
+        match self {
+            PsiPower::ProjectedCryokinesis
+            | PsiPower::ProjectedPyrokinesis
+            | PsiPower::KineticRedirection => 1.0,
+            PsiPower::PsionicHypnogenesis => 2.0,
+            _ => 5.0,
+        }
+    }
+
+    ///
+    /// effects
+    ///
+    /// What the power does when used - turned into game effects by the psi amp
+    pub fn effects(&self) -> Vec<PsiPowerEffect> {
+        match self {
+            PsiPower::ProjectedCryokinesis => vec![PsiPowerEffect::Projectile { index: 0 }],
+            PsiPower::PsychoReflectiveScreen => vec![PsiPowerEffect::Buff {
+                buff: PsiBuff::Screen,
+                duration_seconds: 30.0,
+            }],
+            PsiPower::KineticRedirection => vec![PsiPowerEffect::Telekinesis],
+            PsiPower::PsionicActionCloak => vec![PsiPowerEffect::Buff {
+                buff: PsiBuff::Cloak,
+                duration_seconds: 20.0,
+            }],
+            PsiPower::NeuralDecontamination => {
+                vec![PsiPowerEffect::AdjustHitPoints { delta: 10 }]
+            }
+            PsiPower::ElectronCascade => vec![PsiPowerEffect::RechargeHeldItems],
+            PsiPower::RecursivePsionicAmplification => vec![
+                PsiPowerEffect::AdjustHitPoints { delta: -5 },
+                PsiPowerEffect::AdjustPsiPoints { delta: 10 },
+            ],
+            PsiPower::ProjectedPyrokinesis => vec![PsiPowerEffect::Projectile { index: 1 }],
+            PsiPower::PsionicHypnogenesis => vec![PsiPowerEffect::Projectile { index: 2 }],
+            PsiPower::SomaTransference => vec![PsiPowerEffect::AdjustHitPoints { delta: 25 }],
+            PsiPower::MetacreativeBarrier => vec![
+                PsiPowerEffect::Buff {
+                    buff: PsiBuff::Screen,
+                    duration_seconds: 60.0,
+                },
+                PsiPowerEffect::Buff {
+                    buff: PsiBuff::Cloak,
+                    duration_seconds: 10.0,
+                },
+            ],
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct PsiState {
+    learned: BTreeSet<PsiPower>,
+    selected: Option<PsiPower>,
+    // Seconds left before each power can be used again
+    cooldowns: BTreeMap<PsiPower, f32>,
+    // Seconds left on each active buff
+    buffs: BTreeMap<PsiBuff, f32>,
+}
+
+impl PsiState {
+    pub fn new() -> PsiState {
+        PsiState::default()
+    }
+
+    pub fn is_learned(&self, power: PsiPower) -> bool {
+        self.learned.contains(&power)
+    }
+
+    ///
+    /// learn
+    ///
+    /// Learn a new power - the first power learned is selected on the amp
+    pub fn learn(&mut self, power: PsiPower) {
+        self.learned.insert(power);
+        if self.selected.is_none() {
+            self.selected = Some(power);
+        }
+    }
+
+    pub fn selected(&self) -> Option<PsiPower> {
+        self.selected
+    }
+
+    ///
+    /// select_next
+    ///
+    /// Cycle the amp to the next learned power, in catalog order
+    pub fn select_next(&mut self) -> Option<PsiPower> {
+        let next = match self.selected {
+            Some(selected) => self
+                .learned
+                .iter()
+                .find(|power| **power > selected)
+                .or_else(|| self.learned.iter().next()),
+            None => self.learned.iter().next(),
+        };
+        self.selected = next.copied();
+        self.selected
+    }
+
+    ///
+    /// next_to_learn
+    ///
+    /// The lowest tier power that hasn't been learned yet, if the player's psi level is high
+    /// enough for it
+    pub fn next_to_learn(&self, psi_level: u32) -> Option<PsiPower> {
+        ALL_PSI_POWERS
+            .iter()
+            .copied()
+            .find(|power| !self.is_learned(*power))
+            .filter(|power| power.tier() <= psi_level)
+    }
+
+    ///
+    /// select
+    ///
+    /// Select the power to use with the psi amp. Returns false if the power hasn't been learned.
+    pub fn select(&mut self, power: PsiPower) -> bool {
+        if self.is_learned(power) {
+            self.selected = Some(power);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn remaining_cooldown(&self, power: PsiPower) -> f32 {
+        self.cooldowns.get(&power).copied().unwrap_or(0.0)
+    }
+
+    pub fn is_ready(&self, power: PsiPower) -> bool {
+        self.is_learned(power) && self.remaining_cooldown(power) <= 0.0
+    }
+
+    ///
+    /// use_power
+    ///
+    /// Start the cooldown for the power and apply any buffs it gives. Psi points are handled by
+    /// PlayerStats.
+    pub fn use_power(&mut self, power: PsiPower) {
+        self.cooldowns.insert(power, power.cooldown_seconds());
+        for effect in power.effects() {
+            if let PsiPowerEffect::Buff {
+                buff,
+                duration_seconds,
+            } = effect
+            {
+                let remaining = self.buffs.entry(buff).or_insert(0.0);
+                *remaining = remaining.max(duration_seconds);
+            }
+        }
+    }
+
+    pub fn has_buff(&self, buff: PsiBuff) -> bool {
+        self.buffs.contains_key(&buff)
+    }
+
+    pub fn damage_multiplier(&self) -> f32 {
+        if self.has_buff(PsiBuff::Screen) {
+            SCREEN_DAMAGE_MULTIPLIER
+        } else {
+            1.0
+        }
+    }
+
+    ///
+    /// update
+    ///
+    /// Count down cooldowns and buffs
+    pub fn update(&mut self, elapsed_seconds: f32) {
+        self.cooldowns.retain(|_, remaining| {
+            *remaining -= elapsed_seconds;
+            *remaining > 0.0
+        });
+        self.buffs.retain(|_, remaining| {
+            *remaining -= elapsed_seconds;
+            *remaining > 0.0
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_covers_all_tiers() {
+        for tier in 1..=5 {
+            assert!(ALL_PSI_POWERS.iter().any(|power| power.tier() == tier));
+        }
+    }
+
+    #[test]
+    fn test_every_power_has_an_effect() {
+        for power in ALL_PSI_POWERS {
+            assert!(
+                !power.effects().is_empty(),
+                "{} has no effect",
+                power.name()
+            );
+            assert!(power.cooldown_seconds() > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_learn_and_select() {
+        let mut psi = PsiState::new();
+        assert!(!psi.select(PsiPower::KineticRedirection));
+
+        psi.learn(PsiPower::ProjectedCryokinesis);
+        psi.learn(PsiPower::KineticRedirection);
+        assert_eq!(psi.selected(), Some(PsiPower::ProjectedCryokinesis));
+
+        assert!(psi.select(PsiPower::KineticRedirection));
+        assert_eq!(psi.selected(), Some(PsiPower::KineticRedirection));
+
+        // Cycles back around to the first learned power
+        assert_eq!(psi.select_next(), Some(PsiPower::ProjectedCryokinesis));
+        assert_eq!(psi.select_next(), Some(PsiPower::KineticRedirection));
+    }
+
+    #[test]
+    fn test_next_to_learn() {
+        let mut psi = PsiState::new();
+        assert_eq!(psi.next_to_learn(1), Some(PsiPower::ProjectedCryokinesis));
+
+        for power in [
+            PsiPower::ProjectedCryokinesis,
+            PsiPower::PsychoReflectiveScreen,
+            PsiPower::KineticRedirection,
+        ] {
+            psi.learn(power);
+        }
+
+        // Tier 2 powers need a higher psi level
+        assert_eq!(psi.next_to_learn(1), None);
+        assert_eq!(psi.next_to_learn(2), Some(PsiPower::PsionicActionCloak));
+    }
+
+    #[test]
+    fn test_cooldowns_and_buffs_expire() {
+        let mut psi = PsiState::new();
+        psi.learn(PsiPower::PsychoReflectiveScreen);
+        assert!(psi.is_ready(PsiPower::PsychoReflectiveScreen));
+
+        psi.use_power(PsiPower::PsychoReflectiveScreen);
+        assert!(!psi.is_ready(PsiPower::PsychoReflectiveScreen));
+        assert_eq!(psi.damage_multiplier(), SCREEN_DAMAGE_MULTIPLIER);
+
+        psi.update(PsiPower::PsychoReflectiveScreen.cooldown_seconds());
+        assert!(psi.is_ready(PsiPower::PsychoReflectiveScreen));
+        assert!(psi.has_buff(PsiBuff::Screen));
+
+        psi.update(30.0);
+        assert!(!psi.has_buff(PsiBuff::Screen));
+        assert_eq!(psi.damage_multiplier(), 1.0);
+    }
+}
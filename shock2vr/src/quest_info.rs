@@ -9,11 +9,16 @@ use dark::properties::{KeyCard, QuestBitValue};
 use serde::{Deserialize, Serialize};
 use shipyard::Unique;
 
+use crate::security_system::Alarm;
+
 #[derive(Deserialize, Serialize, Unique, Clone, Debug)]
 pub struct QuestInfo {
     quest_bit_values: HashMap<String, QuestBitValue>,
     played_emails: HashSet<String>,
     key_cards: Vec<KeyCard>,
+    // Active security alarms, keyed by mission
+    #[serde(default)]
+    alarms: HashMap<String, Alarm>,
 }
 
 impl QuestInfo {
@@ -22,6 +27,7 @@ impl QuestInfo {
             quest_bit_values: HashMap::new(),
             played_emails: HashSet::new(),
             key_cards: Vec::new(),
+            alarms: HashMap::new(),
         }
     }
 
@@ -53,4 +59,16 @@ impl QuestInfo {
     pub fn mark_email_as_played(&mut self, email: &str) {
         self.played_emails.insert(email.to_owned());
     }
+
+    pub fn alarm(&self, mission: &str) -> Option<Alarm> {
+        self.alarms.get(&mission.to_ascii_lowercase()).cloned()
+    }
+
+    pub fn set_alarm(&mut self, mission: &str, alarm: Option<Alarm>) {
+        let mission = mission.to_ascii_lowercase();
+        match alarm {
+            Some(alarm) => self.alarms.insert(mission, alarm),
+            None => self.alarms.remove(&mission),
+        };
+    }
 }
@@ -0,0 +1,146 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+};
+
+use crate::{command::Command, input_context::InputContext, time::Time, Game};
+
+use super::{ReplayFrame, ReplayHeader, REPLAY_FORMAT_VERSION};
+
+pub struct InputPlayer {
+    header: ReplayHeader,
+    frames: Vec<ReplayFrame>,
+    next_frame: usize,
+}
+
+impl InputPlayer {
+    pub fn open(path: &str) -> io::Result<InputPlayer> {
+        let file = File::open(path)?;
+        InputPlayer::read(file)
+    }
+
+    pub fn read<R: Read>(reader: R) -> io::Result<InputPlayer> {
+        let mut lines = BufReader::new(reader).lines();
+
+        let header_line = lines
+            .next()
+            .ok_or_else(|| invalid_data("replay is missing a header".to_owned()))??;
+        let header: ReplayHeader = serde_json::from_str(&header_line)?;
+
+        if header.version != REPLAY_FORMAT_VERSION {
+            return Err(invalid_data(format!(
+                "unsupported replay version: {} (expected {})",
+                header.version, REPLAY_FORMAT_VERSION
+            )));
+        }
+
+        let mut frames: Vec<ReplayFrame> = Vec::new();
+        for line in lines {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            frames.push(serde_json::from_str(&line)?);
+        }
+
+        Ok(InputPlayer {
+            header,
+            frames,
+            next_frame: 0,
+        })
+    }
+
+    pub fn header(&self) -> &ReplayHeader {
+        &self.header
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_frame >= self.frames.len()
+    }
+
+    ///
+    /// next_frame
+    ///
+    /// Returns the inputs for the next call to Game::update, or None when the replay is finished
+    pub fn next_frame(&mut self) -> Option<(Time, InputContext, Vec<Box<dyn Command>>)> {
+        let frame = self.frames.get(self.next_frame)?;
+        self.next_frame += 1;
+
+        let commands = frame.commands.iter().map(|c| c.to_command()).collect();
+        Some((frame.time.clone(), frame.input.clone(), commands))
+    }
+
+    ///
+    /// step
+    ///
+    /// Feed the next recorded frame into the game. Returns false once the replay is finished.
+    pub fn step(&mut self, game: &mut Game) -> bool {
+        match self.next_frame() {
+            Some((time, input_context, commands)) => {
+                game.update(&time, &input_context, commands);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{command::SpawnItemCommand, replay::InputRecorder, SpawnLocation};
+    use cgmath::{vec2, Deg, Quaternion, Rotation3};
+    use std::time::Duration;
+
+    #[test]
+    fn test_recorded_frames_replay_in_order() {
+        // Given a recording with two frames, where the second issues a command
+        let header = ReplayHeader::new("earth.mis".to_owned(), SpawnLocation::MapDefault, None, 42);
+        let mut buffer = Vec::new();
+        let mut recorder = InputRecorder::new(&mut buffer, &header).unwrap();
+
+        let mut input = InputContext::default();
+        input.right_hand.thumbstick = vec2(0.0, 1.0);
+        let time = Time {
+            elapsed: Duration::from_millis(16),
+            total: Duration::from_millis(16),
+        };
+        recorder.record(&time, &input, &[]).unwrap();
+
+        let head_rotation = Quaternion::from_angle_y(Deg(90.0));
+        let commands: Vec<Box<dyn Command>> = vec![Box::new(SpawnItemCommand::new(head_rotation))];
+        recorder.record(&time, &input, &commands).unwrap();
+        drop(recorder);
+
+        // When we read it back
+        let mut player = InputPlayer::read(buffer.as_slice()).unwrap();
+
+        // Then the frames come back in order, with their inputs and commands intact
+        assert_eq!(player.header().mission, "earth.mis");
+        assert_eq!(player.header().seed, 42);
+        assert_eq!(player.frame_count(), 2);
+
+        let (first_time, first_input, first_commands) = player.next_frame().unwrap();
+        assert_eq!(first_time.elapsed, Duration::from_millis(16));
+        assert_eq!(first_input.right_hand.thumbstick, vec2(0.0, 1.0));
+        assert!(first_commands.is_empty());
+
+        let (_, _, second_commands) = player.next_frame().unwrap();
+        assert_eq!(second_commands.len(), 1);
+        assert!(matches!(
+            second_commands[0].to_recorded(),
+            crate::command::RecordedCommand::SpawnItem { head_rotation: rot } if rot == head_rotation
+        ));
+
+        assert!(player.is_finished());
+        assert!(player.next_frame().is_none());
+    }
+}
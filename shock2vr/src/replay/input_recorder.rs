@@ -0,0 +1,64 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+};
+
+use crate::{command::Command, input_context::InputContext, time::Time};
+
+use super::{ReplayFrame, ReplayHeader};
+
+pub struct InputRecorder<W: Write> {
+    writer: W,
+    frame_count: u64,
+}
+
+impl InputRecorder<BufWriter<File>> {
+    pub fn create(path: &str, header: &ReplayHeader) -> io::Result<Self> {
+        let file = File::create(path)?;
+        InputRecorder::new(BufWriter::new(file), header)
+    }
+}
+
+impl<W: Write> InputRecorder<W> {
+    pub fn new(mut writer: W, header: &ReplayHeader) -> io::Result<InputRecorder<W>> {
+        write_json_line(&mut writer, header)?;
+        Ok(InputRecorder {
+            writer,
+            frame_count: 0,
+        })
+    }
+
+    ///
+    /// record
+    ///
+    /// Record the inputs for a single call to Game::update. This should be called
+    /// with the same arguments, right before they are passed to the game.
+    pub fn record(
+        &mut self,
+        time: &Time,
+        input_context: &InputContext,
+        commands: &[Box<dyn Command>],
+    ) -> io::Result<()> {
+        let frame = ReplayFrame {
+            time: time.clone(),
+            input: input_context.clone(),
+            commands: commands.iter().map(|c| c.to_recorded()).collect(),
+        };
+        write_json_line(&mut self.writer, &frame)?;
+        self.frame_count += 1;
+        Ok(())
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+fn write_json_line<W: Write, T: serde::Serialize>(writer: &mut W, value: &T) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, value)?;
+    writer.write_all(b"\n")
+}
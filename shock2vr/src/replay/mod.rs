@@ -0,0 +1,17 @@
+///
+/// replay
+///
+/// Recording and playback of the inputs passed to Game::update. Each frame captures the
+/// time step, the input context (head + hands), and any commands issued, so that a
+/// session can be fed back into the game frame-by-frame.
+///
+/// The replay file is stored as JSON lines - a header, followed by one line per frame.
+/// This keeps recordings usable even if the game crashes mid-session.
+///
+mod input_player;
+mod input_recorder;
+mod replay_data;
+
+pub use input_player::*;
+pub use input_recorder::*;
+pub use replay_data::*;
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{command::RecordedCommand, input_context::InputContext, time::Time, SpawnLocation};
+
+pub const REPLAY_FORMAT_VERSION: u32 = 2;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayHeader {
+    pub version: u32,
+    // Starting point of the recording
+    pub mission: String,
+    pub spawn_location: SpawnLocation,
+    pub save_file: Option<String>,
+    // Seed for the game rng, so sounds, music and AI play out the same way on replay
+    pub seed: u64,
+}
+
+impl ReplayHeader {
+    pub fn new(
+        mission: String,
+        spawn_location: SpawnLocation,
+        save_file: Option<String>,
+        seed: u64,
+    ) -> ReplayHeader {
+        ReplayHeader {
+            version: REPLAY_FORMAT_VERSION,
+            mission,
+            spawn_location,
+            save_file,
+            seed,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    pub time: Time,
+    pub input: InputContext,
+    pub commands: Vec<RecordedCommand>,
+}
@@ -34,6 +34,10 @@ pub struct RuntimePropVhots(pub Vec<Vhot>);
 #[derive(Component)]
 pub struct RuntimePropDoNotSerialize;
 
+// RuntimePropLaunchedBy - the entity that fired a projectile
+#[derive(Component)]
+pub struct RuntimePropLaunchedBy(pub shipyard::EntityId);
+
 // RuntimePropProxyEntity - pointer to the parent entity (for example, hitboxes use this to point to the parent entity)
 #[derive(Component)]
 pub struct RuntimePropProxyEntity(pub shipyard::EntityId);
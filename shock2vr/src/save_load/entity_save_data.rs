@@ -11,6 +11,10 @@ pub struct EntitySaveData {
     pub properties:
         HashMap<String /* prop name */, HashMap<u64 /*entity id*/, serde_json::Value>>,
     pub links: HashMap<u64 /*entity_id */, serde_json::Value>,
+    // The PropertyDefinition version each property was serialized with - saves from before
+    // properties were versioned won't have this, so everything is treated as version 0
+    #[serde(default)]
+    pub property_versions: HashMap<String /* prop name */, u32>,
 }
 
 impl EntitySaveData {
@@ -20,6 +24,7 @@ impl EntitySaveData {
             template_id_to_entity_id: HashMap::new(),
             properties: HashMap::new(),
             links: HashMap::new(),
+            property_versions: HashMap::new(),
         }
     }
     pub fn instantiate(
@@ -49,7 +54,13 @@ impl EntitySaveData {
             let name = prop.name();
             if let Some(prop_info) = self.properties.get(&name) {
                 println!("deserializing: {}", name);
-                prop.deserialize(prop_info, world, &old_entity_id_to_new_entity_id);
+                let saved_version = self.property_versions.get(&name).copied().unwrap_or(0);
+                prop.deserialize(
+                    prop_info,
+                    saved_version,
+                    world,
+                    &old_entity_id_to_new_entity_id,
+                );
             }
         }
 
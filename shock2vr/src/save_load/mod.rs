@@ -1,10 +1,14 @@
 mod entity_save_data;
 mod held_item_save_data;
 mod save_data;
+mod save_slot_manager;
+mod save_thumbnail;
 
 pub use entity_save_data::*;
 pub use held_item_save_data::*;
 pub use save_data::*;
+pub use save_slot_manager::*;
+pub use save_thumbnail::*;
 
 use std::{
     collections::{HashMap, HashSet},
@@ -114,6 +118,7 @@ pub fn to_save_data(world: &World) -> (EntitySaveData, HeldItemSaveData) {
 
     let mut world_serialized_properties = HashMap::new();
     let mut held_serialized_properties = HashMap::new();
+    let mut property_versions = HashMap::new();
     for prop in all_properties {
         let raw_serialized = prop.serialize(world);
 
@@ -126,6 +131,7 @@ pub fn to_save_data(world: &World) -> (EntitySaveData, HeldItemSaveData) {
 
         world_serialized_properties.insert(prop.name(), world_serialized);
         held_serialized_properties.insert(prop.name(), held_serialized);
+        property_versions.insert(prop.name(), prop.version());
     }
 
     let mut world_serialized_links = HashMap::new();
@@ -145,6 +151,7 @@ pub fn to_save_data(world: &World) -> (EntitySaveData, HeldItemSaveData) {
         template_id_to_entity_id: template_id_to_entity_id.0.clone(),
         links: world_serialized_links,
         all_entities: all_world_entities,
+        property_versions: property_versions.clone(),
     };
 
     let held_entity_data = EntitySaveData {
@@ -152,6 +159,7 @@ pub fn to_save_data(world: &World) -> (EntitySaveData, HeldItemSaveData) {
         template_id_to_entity_id: HashMap::new(),
         links: held_serialized_links,
         properties: held_serialized_properties,
+        property_versions,
     };
 
     let held_metadata = HeldItemSaveData {
@@ -2,12 +2,173 @@
  * SaveData
  *
  * Data type for information we serialize to load/save the game
+ *
+ * On disk, a save is a small container:
+ *  - SAVE_MAGIC
+ *  - the length of the header, as a little-endian u32
+ *  - the header, as JSON (SaveHeader)
+ *  - the body - the SaveData as JSON, optionally deflate-compressed
+ *
+ * The header is cheap to read on its own, so save slots can be listed without loading the whole
+ * game state. Saves from before the container existed are just the bare JSON body - these are
+ * read as format version 0.
  */
 use super::{EntitySaveData, HeldItemSaveData};
-use crate::quest_info::QuestInfo;
-use cgmath::{Quaternion, Vector3};
+use crate::{
+    game_rng::GameRng, player_stats::PlayerStats, quest_info::QuestInfo, MissionLoadError,
+};
+use cgmath::{Quaternion, Vector3, Zero};
+use dark::properties::migrate_play_actions;
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    error, fmt,
+    io::{self, Read, Write},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const SAVE_MAGIC: &[u8; 8] = b"S2QSAVE\0";
+
+// Current version of the save container. Bump this, and add a migration to SAVE_MIGRATIONS,
+// whenever the shape of SaveData changes in a way that serde defaults can't handle.
+pub const SAVE_FORMAT_VERSION: u32 = 2;
+
+// Upgrades the JSON body of a save by a single version - the migration at index `i` takes a body
+// saved at version `i` to version `i + 1`.
+type SaveMigration = fn(serde_json::Value) -> serde_json::Value;
+
+const SAVE_MIGRATIONS: [SaveMigration; SAVE_FORMAT_VERSION as usize] =
+    [migrate_v0_to_v1, migrate_v1_to_v2];
+
+// Version 0 was the bare JSON body. The body itself is unchanged - missing property versions
+// default to 0, so the per-property migrations handle anything older.
+fn migrate_v0_to_v1(body: serde_json::Value) -> serde_json::Value {
+    body
+}
+
+// Version 2 gave AI Play actions a sound. Properties are migrated by their own definitions, but
+// links aren't versioned, so the actions of saved AIWatchObj links are upgraded here.
+fn migrate_v1_to_v2(mut body: serde_json::Value) -> serde_json::Value {
+    if let Some(level_data) = body
+        .get_mut("level_data")
+        .and_then(serde_json::Value::as_object_mut)
+    {
+        for entity_data in level_data.values_mut() {
+            migrate_links(Some(entity_data));
+        }
+    }
+    migrate_links(body.pointer_mut("/global_data/held_items/held_entities"));
+    body
+}
+
+fn migrate_links(entity_data: Option<&mut serde_json::Value>) {
+    let links = entity_data
+        .and_then(|entity_data| entity_data.get_mut("links"))
+        .and_then(serde_json::Value::as_object_mut);
+
+    for entity_links in links.into_iter().flat_map(|links| links.values_mut()) {
+        let to_links = entity_links
+            .get_mut("to_links")
+            .and_then(serde_json::Value::as_array_mut);
+        for to_link in to_links.into_iter().flatten() {
+            if let Some(actions) = to_link.pointer_mut("/link/AIWatchObj/scripted_actions") {
+                migrate_play_actions(actions);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SaveCompression {
+    None,
+    Deflate,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SaveHeader {
+    pub format_version: u32,
+    // Version of the game that wrote the save - informational only, useful for bug reports
+    pub game_build: String,
+    pub mission: String,
+    // Seconds since the unix epoch, or 0 if unknown (legacy saves)
+    pub timestamp: u64,
+    pub compression: SaveCompression,
+    // Copied from the body, so save slots can show them without loading the game state
+    #[serde(default)]
+    pub play_time: Duration,
+    #[serde(default = "Vector3::zero")]
+    pub position: Vector3<f32>,
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    InvalidHeader(String),
+    UnsupportedVersion { version: u32, supported: u32 },
+    InvalidSlotId(String),
+    Thumbnail(image::ImageError),
+    // The save was read, but the mission it was made in couldn't be loaded
+    MissionLoad(MissionLoadError),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "unable to access save: {}", err),
+            SaveError::Json(err) => write!(f, "unable to parse save: {}", err),
+            SaveError::InvalidHeader(reason) => write!(f, "invalid save header: {}", reason),
+            SaveError::UnsupportedVersion { version, supported } => write!(
+                f,
+                "save format version {} is newer than the supported version {}",
+                version, supported
+            ),
+            SaveError::InvalidSlotId(id) => write!(f, "invalid save slot id: {}", id),
+            SaveError::Thumbnail(err) => write!(f, "unable to access save thumbnail: {}", err),
+            SaveError::MissionLoad(err) => write!(f, "unable to load saved mission: {}", err),
+        }
+    }
+}
+
+impl error::Error for SaveError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            SaveError::Io(err) => Some(err),
+            SaveError::Json(err) => Some(err),
+            SaveError::InvalidHeader(_) => None,
+            SaveError::UnsupportedVersion { .. } => None,
+            SaveError::InvalidSlotId(_) => None,
+            SaveError::Thumbnail(err) => Some(err),
+            SaveError::MissionLoad(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> SaveError {
+        SaveError::Io(err)
+    }
+}
+
+impl From<image::ImageError> for SaveError {
+    fn from(err: image::ImageError) -> SaveError {
+        SaveError::Thumbnail(err)
+    }
+}
+
+impl From<MissionLoadError> for SaveError {
+    fn from(err: MissionLoadError) -> SaveError {
+        SaveError::MissionLoad(err)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(err: serde_json::Error) -> SaveError {
+        SaveError::Json(err)
+    }
+}
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct SaveData {
@@ -19,17 +180,151 @@ pub struct SaveData {
 }
 
 impl SaveData {
-    pub fn write<T: std::io::Write>(&self, writer: &mut T) {
-        let save_data_json = serde_json::to_string(&self).unwrap();
-        writer.write_all(save_data_json.as_bytes()).unwrap();
+    pub fn write<T: Write>(
+        &self,
+        writer: &mut T,
+        compression: SaveCompression,
+    ) -> Result<(), SaveError> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        let header = SaveHeader {
+            format_version: SAVE_FORMAT_VERSION,
+            game_build: env!("CARGO_PKG_VERSION").to_owned(),
+            mission: self.global_data.active_mission.clone(),
+            timestamp,
+            compression,
+            play_time: self.global_data.play_time,
+            position: self.global_data.position,
+        };
+        let header_json = serde_json::to_vec(&header)?;
+
+        let body_json = serde_json::to_vec(&self)?;
+        let body = match compression {
+            SaveCompression::None => body_json,
+            SaveCompression::Deflate => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&body_json)?;
+                encoder.finish()?
+            }
+        };
+
+        writer.write_all(SAVE_MAGIC)?;
+        writer.write_all(&(header_json.len() as u32).to_le_bytes())?;
+        writer.write_all(&header_json)?;
+        writer.write_all(&body)?;
+        Ok(())
     }
 
-    pub fn read<T: std::io::Read>(reader: &mut T) -> SaveData {
-        let mut save_data_json = String::new();
-        reader.read_to_string(&mut save_data_json).unwrap();
-        let save_data: SaveData = serde_json::from_str(&save_data_json).unwrap();
-        save_data
+    pub fn read<T: Read>(reader: &mut T) -> Result<SaveData, SaveError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        let (header, body) = split_container(&bytes)?;
+
+        let body_json: serde_json::Value = match header.compression {
+            SaveCompression::None => serde_json::from_slice(body)?,
+            SaveCompression::Deflate => {
+                let mut decompressed = Vec::new();
+                DeflateDecoder::new(body).read_to_end(&mut decompressed)?;
+                serde_json::from_slice(&decompressed)?
+            }
+        };
+
+        let body_json = SAVE_MIGRATIONS[header.format_version as usize..]
+            .iter()
+            .fold(body_json, |body, migrate| migrate(body));
+
+        let save_data: SaveData = serde_json::from_value(body_json)?;
+        Ok(save_data)
+    }
+
+    ///
+    /// read_header
+    ///
+    /// Read just the header of a save, without deserializing the game state
+    pub fn read_header<T: Read>(reader: &mut T) -> Result<SaveHeader, SaveError> {
+        let mut prefix = Vec::new();
+        reader
+            .by_ref()
+            .take(SAVE_MAGIC.len() as u64)
+            .read_to_end(&mut prefix)?;
+
+        if prefix != SAVE_MAGIC {
+            // Legacy save - the header has to come from the body, so there's no way around reading it all
+            reader.read_to_end(&mut prefix)?;
+            return legacy_header(&prefix);
+        }
+
+        let mut header_len = [0; 4];
+        reader.read_exact(&mut header_len)?;
+        let mut header_json = vec![0; u32::from_le_bytes(header_len) as usize];
+        reader.read_exact(&mut header_json)?;
+
+        let header: SaveHeader = serde_json::from_slice(&header_json)?;
+        check_version(&header)?;
+        Ok(header)
+    }
+}
+
+fn check_version(header: &SaveHeader) -> Result<(), SaveError> {
+    if header.format_version > SAVE_FORMAT_VERSION {
+        return Err(SaveError::UnsupportedVersion {
+            version: header.format_version,
+            supported: SAVE_FORMAT_VERSION,
+        });
     }
+    Ok(())
+}
+
+// Split a save into its header and (still encoded) body
+fn split_container(bytes: &[u8]) -> Result<(SaveHeader, &[u8]), SaveError> {
+    if !bytes.starts_with(SAVE_MAGIC) {
+        return Ok((legacy_header(bytes)?, bytes));
+    }
+
+    let rest = &bytes[SAVE_MAGIC.len()..];
+    if rest.len() < 4 {
+        return Err(SaveError::InvalidHeader("missing header length".to_owned()));
+    }
+    let header_len = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+    let rest = &rest[4..];
+    if rest.len() < header_len {
+        return Err(SaveError::InvalidHeader(format!(
+            "header length {} exceeds the remaining {} bytes",
+            header_len,
+            rest.len()
+        )));
+    }
+
+    let header: SaveHeader = serde_json::from_slice(&rest[..header_len])?;
+    check_version(&header)?;
+
+    Ok((header, &rest[header_len..]))
+}
+
+// Legacy saves have no header, so piece together what we can from the body
+fn legacy_header(body: &[u8]) -> Result<SaveHeader, SaveError> {
+    let body_json: serde_json::Value = serde_json::from_slice(body)?;
+    let global_data = &body_json["global_data"];
+    let mission = global_data["active_mission"]
+        .as_str()
+        .unwrap_or_default()
+        .to_owned();
+    let position =
+        serde_json::from_value(global_data["position"].clone()).unwrap_or_else(|_| Vector3::zero());
+
+    Ok(SaveHeader {
+        format_version: 0,
+        game_build: String::new(),
+        mission,
+        timestamp: 0,
+        compression: SaveCompression::None,
+        play_time: Duration::ZERO,
+        position,
+    })
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -39,4 +334,154 @@ pub struct GlobalData {
     pub quest_info: QuestInfo,
     pub held_items: HeldItemSaveData,
     pub active_mission: String,
+    // Saves made before the rng was persisted won't have this, so just start a fresh one
+    #[serde(default = "GameRng::from_entropy")]
+    pub rng: GameRng,
+    // Total time spent in game, across all missions
+    #[serde(default)]
+    pub play_time: Duration,
+    // Saves made before player stats existed start with a fresh character
+    #[serde(default = "PlayerStats::new")]
+    pub player_stats: PlayerStats,
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::vec3;
+
+    use super::*;
+
+    fn save_data() -> SaveData {
+        SaveData {
+            global_data: GlobalData {
+                position: vec3(1.0, 2.0, 3.0),
+                rotation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+                quest_info: QuestInfo::new(),
+                held_items: HeldItemSaveData::empty(),
+                active_mission: "medsci1.mis".to_owned(),
+                rng: GameRng::from_seed(42),
+                play_time: Duration::from_secs(90),
+                player_stats: PlayerStats::new(),
+            },
+            level_data: HashMap::new(),
+        }
+    }
+
+    fn roundtrip(compression: SaveCompression) -> SaveData {
+        let mut bytes = Vec::new();
+        save_data().write(&mut bytes, compression).unwrap();
+        SaveData::read(&mut bytes.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for compression in [SaveCompression::None, SaveCompression::Deflate] {
+            let loaded = roundtrip(compression);
+            assert_eq!(loaded.global_data.active_mission, "medsci1.mis");
+            assert_eq!(loaded.global_data.position, vec3(1.0, 2.0, 3.0));
+            assert_eq!(loaded.global_data.rng, GameRng::from_seed(42));
+        }
+    }
+
+    #[test]
+    fn test_read_header() {
+        let mut bytes = Vec::new();
+        save_data()
+            .write(&mut bytes, SaveCompression::Deflate)
+            .unwrap();
+
+        let header = SaveData::read_header(&mut bytes.as_slice()).unwrap();
+        assert_eq!(header.format_version, SAVE_FORMAT_VERSION);
+        assert_eq!(header.mission, "medsci1.mis");
+        assert_eq!(header.compression, SaveCompression::Deflate);
+        assert_eq!(header.play_time, Duration::from_secs(90));
+        assert_eq!(header.position, vec3(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_legacy_save_is_version_0() {
+        let bytes = serde_json::to_vec(&save_data()).unwrap();
+
+        let header = SaveData::read_header(&mut bytes.as_slice()).unwrap();
+        assert_eq!(header.format_version, 0);
+        assert_eq!(header.mission, "medsci1.mis");
+        assert_eq!(header.position, vec3(1.0, 2.0, 3.0));
+
+        let loaded = SaveData::read(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded.global_data.active_mission, "medsci1.mis");
+    }
+
+    #[test]
+    fn test_version_1_play_actions_are_migrated() {
+        let mut old_save = save_data();
+        old_save
+            .level_data
+            .insert("medsci1.mis".to_owned(), EntitySaveData::empty());
+        let mut body = serde_json::to_value(&old_save).unwrap();
+        let old_link = serde_json::json!({
+            "to_links": [{
+                "to_template_id": 0,
+                "to_entity_id": null,
+                "link": {
+                    "AIWatchObj": {
+                        "radius": 1.0,
+                        "height": 1.0,
+                        "scripted_actions": [{ "action_type": { "Play": "crumple" } }],
+                    }
+                },
+            }]
+        });
+        body["level_data"]["medsci1.mis"]["links"]["1"] = old_link.clone();
+        body["global_data"]["held_items"]["held_entities"]["links"]["2"] = old_link;
+
+        let body = serde_json::to_vec(&body).unwrap();
+        let header = serde_json::to_vec(&SaveHeader {
+            format_version: 1,
+            game_build: String::new(),
+            mission: "medsci1.mis".to_owned(),
+            timestamp: 0,
+            compression: SaveCompression::None,
+            play_time: Duration::ZERO,
+            position: Vector3::zero(),
+        })
+        .unwrap();
+        let mut bytes = SAVE_MAGIC.to_vec();
+        bytes.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&header);
+        bytes.extend_from_slice(&body);
+
+        let loaded = SaveData::read(&mut bytes.as_slice()).unwrap();
+        let expected = serde_json::json!({ "sound": "", "motion": "crumple" });
+        let play_action = "/to_links/0/link/AIWatchObj/scripted_actions/0/action_type/Play";
+        let level_links = &loaded.level_data["medsci1.mis"].links;
+        assert_eq!(level_links[&1].pointer(play_action), Some(&expected));
+        let held_links = &loaded.global_data.held_items.held_entities.links;
+        assert_eq!(held_links[&2].pointer(play_action), Some(&expected));
+
+        // Saving again records the current format version
+        let mut resaved = Vec::new();
+        loaded.write(&mut resaved, SaveCompression::None).unwrap();
+        let header = SaveData::read_header(&mut resaved.as_slice()).unwrap();
+        assert_eq!(header.format_version, SAVE_FORMAT_VERSION);
+    }
+
+    #[test]
+    fn test_newer_format_is_unsupported() {
+        let header = serde_json::to_vec(&SaveHeader {
+            format_version: SAVE_FORMAT_VERSION + 1,
+            game_build: String::new(),
+            mission: String::new(),
+            timestamp: 0,
+            compression: SaveCompression::None,
+            play_time: Duration::ZERO,
+            position: Vector3::zero(),
+        })
+        .unwrap();
+        let mut bytes = SAVE_MAGIC.to_vec();
+        bytes.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&header);
+
+        let err = SaveData::read(&mut bytes.as_slice()).unwrap_err();
+        assert!(matches!(err, SaveError::UnsupportedVersion { .. }));
+    }
 }
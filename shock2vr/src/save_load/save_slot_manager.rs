@@ -0,0 +1,418 @@
+///
+/// save_slot_manager.rs
+///
+/// Manages the save slots in the save folder. Each slot is a save file (`<id>.sav`), and optionally
+/// a thumbnail (`<id>.png`) next to it. The metadata shown in the menu - mission, play time,
+/// position - comes from the save header, so listing slots doesn't load any game state.
+///
+/// There are three kinds of slots:
+///  - Manual saves, which are only removed when deleted
+///  - A single quicksave, which is overwritten each time
+///  - Autosaves, made on level transitions, which are rotated so only the most recent are kept
+///
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use cgmath::Vector3;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::{SaveCompression, SaveData, SaveError, SaveThumbnail};
+
+pub const QUICKSAVE_SLOT_ID: &str = "quicksave";
+
+const AUTOSAVE_PREFIX: &str = "autosave";
+const MANUAL_SAVE_PREFIX: &str = "save";
+
+const SAVE_EXTENSION: &str = "sav";
+const THUMBNAIL_EXTENSION: &str = "png";
+
+const DEFAULT_MAX_AUTOSAVES: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SaveSlotKind {
+    Manual,
+    Quick,
+    Auto,
+}
+
+impl SaveSlotKind {
+    fn from_slot_id(id: &str) -> SaveSlotKind {
+        if id == QUICKSAVE_SLOT_ID {
+            SaveSlotKind::Quick
+        } else if id.starts_with(AUTOSAVE_PREFIX) {
+            SaveSlotKind::Auto
+        } else {
+            SaveSlotKind::Manual
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SaveSlot {
+    pub id: String,
+    pub kind: SaveSlotKind,
+    pub mission: String,
+    pub play_time: Duration,
+    pub position: Vector3<f32>,
+    // Seconds since the unix epoch
+    pub timestamp: u64,
+    pub thumbnail_path: Option<PathBuf>,
+}
+
+pub struct SaveSlotManager {
+    folder: PathBuf,
+    max_autosaves: usize,
+}
+
+impl SaveSlotManager {
+    pub fn new(folder: PathBuf) -> SaveSlotManager {
+        SaveSlotManager {
+            folder,
+            max_autosaves: DEFAULT_MAX_AUTOSAVES,
+        }
+    }
+
+    pub fn with_max_autosaves(self, max_autosaves: usize) -> SaveSlotManager {
+        SaveSlotManager {
+            max_autosaves,
+            ..self
+        }
+    }
+
+    pub fn folder(&self) -> &Path {
+        &self.folder
+    }
+
+    ///
+    /// list
+    ///
+    /// All the slots in the save folder, most recent first. Files that can't be read are skipped.
+    pub fn list(&self) -> Result<Vec<SaveSlot>, SaveError> {
+        let entries = match fs::read_dir(&self.folder) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut slots = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SAVE_EXTENSION) {
+                continue;
+            }
+
+            let maybe_id = path.file_stem().and_then(|stem| stem.to_str());
+            if let Some(id) = maybe_id {
+                match self.get(id) {
+                    Ok(slot) => slots.push(slot),
+                    Err(err) => warn!("skipping save {}: {}", path.display(), err),
+                }
+            }
+        }
+
+        slots.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| b.id.cmp(&a.id)));
+        Ok(slots)
+    }
+
+    pub fn get(&self, id: &str) -> Result<SaveSlot, SaveError> {
+        let save_path = self.slot_path(id, SAVE_EXTENSION)?;
+        let header = SaveData::read_header(&mut BufReader::new(File::open(save_path)?))?;
+
+        let thumbnail_path = self.slot_path(id, THUMBNAIL_EXTENSION)?;
+        Ok(SaveSlot {
+            id: id.to_owned(),
+            kind: SaveSlotKind::from_slot_id(id),
+            mission: header.mission,
+            play_time: header.play_time,
+            position: header.position,
+            timestamp: header.timestamp,
+            thumbnail_path: thumbnail_path.exists().then_some(thumbnail_path),
+        })
+    }
+
+    pub fn latest(&self, kind: SaveSlotKind) -> Result<Option<SaveSlot>, SaveError> {
+        Ok(self.list()?.into_iter().find(|slot| slot.kind == kind))
+    }
+
+    ///
+    /// create
+    ///
+    /// Write the save data to a new slot - or, for the quicksave, over the existing one.
+    /// Creating an autosave rotates out the oldest autosaves.
+    pub fn create(
+        &self,
+        kind: SaveSlotKind,
+        save_data: &SaveData,
+        thumbnail: Option<&SaveThumbnail>,
+    ) -> Result<SaveSlot, SaveError> {
+        fs::create_dir_all(&self.folder)?;
+
+        let id = match kind {
+            SaveSlotKind::Quick => QUICKSAVE_SLOT_ID.to_owned(),
+            SaveSlotKind::Auto => self.next_id(AUTOSAVE_PREFIX)?,
+            SaveSlotKind::Manual => self.next_id(MANUAL_SAVE_PREFIX)?,
+        };
+
+        // Write to a temporary file first, so a failed save doesn't clobber the quicksave
+        let save_path = self.slot_path(&id, SAVE_EXTENSION)?;
+        let temp_path = save_path.with_extension("tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&temp_path)?);
+            save_data.write(&mut writer, SaveCompression::Deflate)?;
+            writer.flush()?;
+        }
+        fs::rename(&temp_path, &save_path)?;
+
+        let thumbnail_path = self.slot_path(&id, THUMBNAIL_EXTENSION)?;
+        match thumbnail {
+            Some(thumbnail) => thumbnail.write_png(&thumbnail_path)?,
+            None => remove_if_exists(&thumbnail_path)?,
+        }
+
+        if kind == SaveSlotKind::Auto {
+            self.rotate(SaveSlotKind::Auto, self.max_autosaves)?;
+        }
+
+        self.get(&id)
+    }
+
+    ///
+    /// set_thumbnail
+    ///
+    /// Add a thumbnail to a slot that has already been saved
+    pub fn set_thumbnail(
+        &self,
+        id: &str,
+        thumbnail: &SaveThumbnail,
+    ) -> Result<SaveSlot, SaveError> {
+        // Make sure the slot still exists, so we don't leave a stray thumbnail behind
+        self.get(id)?;
+        thumbnail.write_png(&self.slot_path(id, THUMBNAIL_EXTENSION)?)?;
+        self.get(id)
+    }
+
+    pub fn load(&self, id: &str) -> Result<SaveData, SaveError> {
+        let save_path = self.slot_path(id, SAVE_EXTENSION)?;
+        SaveData::read(&mut BufReader::new(File::open(save_path)?))
+    }
+
+    pub fn load_thumbnail(&self, slot: &SaveSlot) -> Result<Option<SaveThumbnail>, SaveError> {
+        slot.thumbnail_path
+            .as_deref()
+            .map(SaveThumbnail::read_png)
+            .transpose()
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), SaveError> {
+        fs::remove_file(self.slot_path(id, SAVE_EXTENSION)?)?;
+        remove_if_exists(&self.slot_path(id, THUMBNAIL_EXTENSION)?)?;
+        Ok(())
+    }
+
+    ///
+    /// rotate
+    ///
+    /// Delete all but the `keep` most recent slots of the given kind
+    pub fn rotate(&self, kind: SaveSlotKind, keep: usize) -> Result<(), SaveError> {
+        let slots = self.list()?;
+        for slot in slots.iter().filter(|slot| slot.kind == kind).skip(keep) {
+            self.delete(&slot.id)?;
+        }
+        Ok(())
+    }
+
+    // Slot ids become file names, so keep them to a safe set of characters
+    fn slot_path(&self, id: &str, extension: &str) -> Result<PathBuf, SaveError> {
+        let is_valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+        if !is_valid {
+            return Err(SaveError::InvalidSlotId(id.to_owned()));
+        }
+
+        Ok(self.folder.join(format!("{}.{}", id, extension)))
+    }
+
+    // Ids are numbered one past the highest existing one, so they never get reused after a rotation -
+    // otherwise, a new save could sort before older ones made within the same second.
+    fn next_id(&self, prefix: &str) -> Result<String, SaveError> {
+        let highest = self
+            .list()?
+            .iter()
+            .filter_map(|slot| slot.id.strip_prefix(prefix)?.strip_prefix('-'))
+            .filter_map(|number| number.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0);
+
+        Ok(format!("{}-{:04}", prefix, highest + 1))
+    }
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), SaveError> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use cgmath::{vec3, Quaternion};
+
+    use super::*;
+    use crate::{
+        game_rng::GameRng,
+        player_stats::PlayerStats,
+        quest_info::QuestInfo,
+        save_load::{GlobalData, HeldItemSaveData},
+    };
+
+    fn save_data(mission: &str) -> SaveData {
+        SaveData {
+            global_data: GlobalData {
+                position: vec3(1.0, 2.0, 3.0),
+                rotation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+                quest_info: QuestInfo::new(),
+                held_items: HeldItemSaveData::empty(),
+                active_mission: mission.to_owned(),
+                rng: GameRng::from_seed(42),
+                play_time: Duration::from_secs(600),
+                player_stats: PlayerStats::new(),
+            },
+            level_data: HashMap::new(),
+        }
+    }
+
+    fn manager(name: &str) -> SaveSlotManager {
+        let folder =
+            std::env::temp_dir().join(format!("shock2quest-saves-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&folder);
+        SaveSlotManager::new(folder)
+    }
+
+    #[test]
+    fn test_create_list_delete() {
+        let manager = manager("create");
+        let thumbnail = SaveThumbnail::from_frame(2, 2, &[255; 12]).unwrap();
+
+        let slot = manager
+            .create(
+                SaveSlotKind::Manual,
+                &save_data("medsci1.mis"),
+                Some(&thumbnail),
+            )
+            .unwrap();
+        assert_eq!(slot.kind, SaveSlotKind::Manual);
+        assert_eq!(slot.mission, "medsci1.mis");
+        assert_eq!(slot.play_time, Duration::from_secs(600));
+        assert_eq!(slot.position, vec3(1.0, 2.0, 3.0));
+        assert_eq!(manager.load_thumbnail(&slot).unwrap(), Some(thumbnail));
+
+        let slots = manager.list().unwrap();
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots[0].id, slot.id);
+
+        let loaded = manager.load(&slot.id).unwrap();
+        assert_eq!(loaded.global_data.active_mission, "medsci1.mis");
+
+        manager.delete(&slot.id).unwrap();
+        assert!(manager.list().unwrap().is_empty());
+
+        fs::remove_dir_all(manager.folder()).unwrap();
+    }
+
+    #[test]
+    fn test_thumbnail_added_after_save() {
+        let manager = manager("thumbnail");
+        let thumbnail = SaveThumbnail::from_frame(2, 2, &[255; 12]).unwrap();
+
+        let slot = manager
+            .create(SaveSlotKind::Quick, &save_data("medsci1.mis"), None)
+            .unwrap();
+        assert_eq!(manager.load_thumbnail(&slot).unwrap(), None);
+
+        let slot = manager.set_thumbnail(&slot.id, &thumbnail).unwrap();
+        assert_eq!(
+            manager.load_thumbnail(&slot).unwrap(),
+            Some(thumbnail.clone())
+        );
+
+        // A slot that's gone (ie, rotated out) doesn't get a stray thumbnail
+        assert!(manager.set_thumbnail("autosave-0001", &thumbnail).is_err());
+        assert!(!manager.folder().join("autosave-0001.png").exists());
+
+        fs::remove_dir_all(manager.folder()).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_frames_have_no_thumbnail() {
+        assert_eq!(SaveThumbnail::from_frame(0, 0, &[]), None);
+        assert_eq!(SaveThumbnail::from_frame(2, 2, &[255; 11]), None);
+        assert_eq!(
+            SaveThumbnail::from_frame(u32::MAX, u32::MAX, &[255; 12]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_quicksave_is_overwritten() {
+        let manager = manager("quick");
+
+        manager
+            .create(SaveSlotKind::Quick, &save_data("medsci1.mis"), None)
+            .unwrap();
+        manager
+            .create(SaveSlotKind::Quick, &save_data("eng1.mis"), None)
+            .unwrap();
+
+        let slots = manager.list().unwrap();
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots[0].id, QUICKSAVE_SLOT_ID);
+        assert_eq!(slots[0].mission, "eng1.mis");
+
+        fs::remove_dir_all(manager.folder()).unwrap();
+    }
+
+    #[test]
+    fn test_autosaves_are_rotated() {
+        let manager = manager("auto").with_max_autosaves(2);
+
+        manager
+            .create(SaveSlotKind::Manual, &save_data("earth.mis"), None)
+            .unwrap();
+        for _ in 0..4 {
+            manager
+                .create(SaveSlotKind::Auto, &save_data("medsci1.mis"), None)
+                .unwrap();
+        }
+
+        let slots = manager.list().unwrap();
+        let autosaves: Vec<&str> = slots
+            .iter()
+            .filter(|slot| slot.kind == SaveSlotKind::Auto)
+            .map(|slot| slot.id.as_str())
+            .collect();
+        assert_eq!(autosaves, vec!["autosave-0004", "autosave-0003"]);
+        assert_eq!(slots.len(), 3);
+
+        fs::remove_dir_all(manager.folder()).unwrap();
+    }
+
+    #[test]
+    fn test_slot_ids_are_validated() {
+        let manager = manager("invalid");
+        assert!(matches!(
+            manager.load("../shock2"),
+            Err(SaveError::InvalidSlotId(_))
+        ));
+    }
+}
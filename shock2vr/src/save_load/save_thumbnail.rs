@@ -0,0 +1,77 @@
+///
+/// save_thumbnail.rs
+///
+/// A small image of the last rendered frame, shown next to a save slot in the menu. When a save is
+/// made, runtimes capture the next frame (if they can) and hand it to the game - the game itself
+/// never touches the GPU.
+///
+use std::path::Path;
+
+use super::SaveError;
+
+// Width, in pixels, of saved thumbnails - the height follows the aspect ratio of the frame
+pub const THUMBNAIL_WIDTH: u32 = 160;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SaveThumbnail {
+    pub width: u32,
+    pub height: u32,
+    // Tightly packed RGB, top row first
+    pub rgb: Vec<u8>,
+}
+
+impl SaveThumbnail {
+    ///
+    /// from_frame
+    ///
+    /// Downsample a captured frame to thumbnail size. The frame is tightly packed RGB, bottom row
+    /// first, as read back from OpenGL. Returns None for an empty frame, or one whose pixels don't
+    /// match its size.
+    pub fn from_frame(width: u32, height: u32, rgb: &[u8]) -> Option<SaveThumbnail> {
+        let (width, height) = (width as usize, height as usize);
+        let frame_len = width.checked_mul(height)?.checked_mul(3)?;
+        if frame_len == 0 || rgb.len() != frame_len {
+            return None;
+        }
+
+        let thumbnail_width = (THUMBNAIL_WIDTH as usize).min(width);
+        let thumbnail_height = (height * thumbnail_width / width).max(1);
+
+        let mut thumbnail_rgb = Vec::with_capacity(thumbnail_width * thumbnail_height * 3);
+        for y in 0..thumbnail_height {
+            // Flip vertically, since the frame starts at the bottom
+            let source_y = height - 1 - (y * height / thumbnail_height);
+            for x in 0..thumbnail_width {
+                let source_x = x * width / thumbnail_width;
+                let idx = (source_y * width + source_x) * 3;
+                thumbnail_rgb.extend_from_slice(&rgb[idx..idx + 3]);
+            }
+        }
+
+        Some(SaveThumbnail {
+            width: thumbnail_width as u32,
+            height: thumbnail_height as u32,
+            rgb: thumbnail_rgb,
+        })
+    }
+
+    pub fn write_png(&self, path: &Path) -> Result<(), SaveError> {
+        image::save_buffer(
+            path,
+            &self.rgb,
+            self.width,
+            self.height,
+            image::ColorType::Rgb8,
+        )?;
+        Ok(())
+    }
+
+    pub fn read_png(path: &Path) -> Result<SaveThumbnail, SaveError> {
+        let img = image::open(path)?.to_rgb8();
+        Ok(SaveThumbnail {
+            width: img.width(),
+            height: img.height(),
+            rgb: img.into_raw(),
+        })
+    }
+}
@@ -6,13 +6,15 @@ use cgmath::{
 };
 use dark::{properties::*, EnvSoundQuery, SCALE_FACTOR};
 use engine::audio::AudioHandle;
-use rand::{thread_rng, Rng};
+use rand::Rng;
 use shipyard::{EntityId, Get, IntoIter, IntoWithId, UniqueView, View, World};
 
 use crate::{
     creature,
     mission::{entity_creator::CreateEntityOptions, PlayerInfo},
     physics::{InternalCollisionGroups, PhysicsWorld},
+    player_stats::PlayerStats,
+    psi_powers::PsiBuff,
     runtime_props::{RuntimePropJointTransforms, RuntimePropTransform},
     scripts::{script_util::get_first_link_with_template_and_data, Effect},
     util,
@@ -22,8 +24,7 @@ use crate::{
 /// random_binomial
 ///
 /// Returns a random number between -1 and 1, where values around 0 are more likely
-pub fn random_binomial() -> f32 {
-    let mut rng = thread_rng();
+pub fn random_binomial(rng: &mut impl Rng) -> f32 {
     let a = rng.gen_range(0.0..1.0);
     let b = rng.gen_range(0.0..1.0);
     a - b
@@ -181,7 +182,10 @@ pub fn fire_ranged_weapon(world: &World, entity_id: EntityId, rotation: Quaterni
                 position: point3(0.0, 0.0, 0.0) + forward,
                 orientation: Quaternion::from_angle_y(Deg(90.0)),
                 root_transform: root_transform.0 * rot_matrix,
-                options: CreateEntityOptions::default(),
+                options: CreateEntityOptions {
+                    launched_by: Some(entity_id),
+                    ..CreateEntityOptions::default()
+                },
             });
 
             fire_effects.push(play_positional_sound(
@@ -287,7 +291,10 @@ pub fn fire_ranged_projectile(world: &World, entity_id: EntityId) -> Effect {
             orientation: Quaternion::from_angle_y(Deg(90.0)),
             // root_transform: transform * rot_matrix,
             root_transform: transform,
-            options: CreateEntityOptions::default(),
+            options: CreateEntityOptions {
+                launched_by: Some(entity_id),
+                ..CreateEntityOptions::default()
+            },
         }
     } else {
         Effect::NoEffect
@@ -331,10 +338,17 @@ pub fn play_positional_sound(
         audio_handle: AudioHandle::new(),
         query: EnvSoundQuery::from_tag_values(query),
         position: pos,
+        source: producing_entity,
     }
 }
 
 pub fn is_player_visible(from_entity: EntityId, world: &World, physics: &PhysicsWorld) -> bool {
+    // The Psionic Action Cloak hides the player completely
+    let player_stats = world.borrow::<UniqueView<PlayerStats>>().unwrap();
+    if player_stats.psi().has_buff(PsiBuff::Cloak) {
+        return false;
+    }
+
     let u_player = world.borrow::<UniqueView<PlayerInfo>>().unwrap();
     let v_current_pos = world.borrow::<View<PropPosition>>().unwrap();
 
@@ -3,10 +3,10 @@ use std::{cell::RefCell, collections::HashSet};
 use cgmath::{vec3, vec4, Deg, MetricSpace, Quaternion, Rotation3};
 use dark::{
     motion::{MotionFlags, MotionQueryItem},
-    properties::{Link, PropAISignalResponse, PropPosition},
+    properties::{Link, PropAISignalResponse, PropHitPoints, PropMaxHitPoints, PropPosition},
     SCALE_FACTOR,
 };
-use shipyard::{EntityId, Get, View, World};
+use shipyard::{EntityId, Get, UniqueView, View, World};
 
 use crate::{
     mission::PlayerInfo,
@@ -17,13 +17,30 @@ use crate::{
 
 use super::{
     ai_util::*,
+    awareness::{self, AlertLevel, Awareness},
     behavior::*,
+    behavior_selection::{AIProfile, IdleStyle},
     steering::{Steering, SteeringOutput},
     Effect, Message, MessagePayload, Script,
 };
+
+// What the AI does about its alert level
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum AlertResponse {
+    Idle,
+    Search,
+    Chase,
+    Flee,
+}
+
 pub struct AnimatedMonsterAI {
     last_hit_sensor: Option<EntityId>,
     current_behavior: Box<RefCell<dyn Behavior>>,
+    profile: AIProfile,
+    awareness: Awareness,
+    // The response the current behavior was picked for - None if it wasn't picked by awareness,
+    // ie, a scripted sequence
+    alert_response: Option<AlertResponse>,
     current_heading: Deg<f32>,
     is_dead: bool,
     took_damage: bool,
@@ -33,31 +50,84 @@ pub struct AnimatedMonsterAI {
 }
 
 impl AnimatedMonsterAI {
-    pub fn idle() -> AnimatedMonsterAI {
+    pub fn new(profile: AIProfile) -> AnimatedMonsterAI {
         AnimatedMonsterAI {
             is_dead: false,
             took_damage: false,
             //current_behavior: Box::new(RefCell::new(MeleeAttackBehavior)),
             //current_behavior: Box::new(RefCell::new(ChaseBehavior::new())),
             current_behavior: Box::new(RefCell::new(IdleBehavior)),
+            profile,
+            awareness: Awareness::new(),
+            // Picked when initialized
+            alert_response: None,
             current_heading: Deg(0.0),
             animation_seq: 0,
             last_hit_sensor: None,
-
             played_ai_watch_obj: HashSet::new(),
         }
     }
-    pub fn new() -> AnimatedMonsterAI {
-        AnimatedMonsterAI {
-            is_dead: false,
-            took_damage: false,
-            //current_behavior: Box::new(RefCell::new(MeleeAttackBehavior)),
-            //current_behavior: Box::new(RefCell::new(ChaseBehavior::new())),
-            current_behavior: Box::new(RefCell::new(RangedAttackBehavior)),
-            current_heading: Deg(0.0),
-            animation_seq: 0,
-            last_hit_sensor: None,
-            played_ai_watch_obj: HashSet::new(),
+
+    ///
+    /// next_behavior_from_awareness
+    ///
+    /// Picks a new behavior when the AI's alert level calls for a different response - idling when
+    /// unaware, searching where the player was last noticed when suspicious, and chasing when alert.
+    fn next_behavior_from_awareness(
+        &mut self,
+        world: &World,
+        entity_id: EntityId,
+    ) -> Option<Box<RefCell<dyn Behavior>>> {
+        let response = self.alert_response(world, entity_id);
+        if self.alert_response == Some(response)
+            || !self.current_behavior.borrow().is_interruptible()
+        {
+            return None;
+        }
+
+        self.alert_response = Some(response);
+        let behavior: Box<RefCell<dyn Behavior>> = match response {
+            AlertResponse::Idle => match self.profile.idle_style {
+                IdleStyle::Stand => Box::new(RefCell::new(IdleBehavior)),
+                IdleStyle::Patrol => Box::new(RefCell::new(PatrolBehavior::new(world, entity_id))),
+            },
+            AlertResponse::Search => match self.awareness.last_known_position() {
+                Some(position) => Box::new(RefCell::new(SearchBehavior::new(position))),
+                None => Box::new(RefCell::new(IdleBehavior)),
+            },
+            AlertResponse::Chase => {
+                Box::new(RefCell::new(ChaseBehavior::new(self.profile.combat_style)))
+            }
+            AlertResponse::Flee => Box::new(RefCell::new(FleeBehavior::new())),
+        };
+        Some(behavior)
+    }
+
+    fn alert_response(&self, world: &World, entity_id: EntityId) -> AlertResponse {
+        let level = self.awareness.level();
+        if level >= AlertLevel::Moderate && self.is_badly_hurt(world, entity_id) {
+            return AlertResponse::Flee;
+        }
+
+        match level {
+            AlertLevel::None => AlertResponse::Idle,
+            AlertLevel::Low | AlertLevel::Moderate if self.profile.investigates => {
+                AlertResponse::Search
+            }
+            AlertLevel::Low | AlertLevel::Moderate => AlertResponse::Idle,
+            AlertLevel::High => AlertResponse::Chase,
+        }
+    }
+
+    fn is_badly_hurt(&self, world: &World, entity_id: EntityId) -> bool {
+        let v_hit_points = world.borrow::<View<PropHitPoints>>().unwrap();
+        let v_max_hit_points = world.borrow::<View<PropMaxHitPoints>>().unwrap();
+
+        match (v_hit_points.get(entity_id), v_max_hit_points.get(entity_id)) {
+            (Ok(hit_points), Ok(max_hit_points)) => self
+                .profile
+                .should_flee(hit_points.hit_points, max_hit_points.hit_points),
+            _ => false,
         }
     }
 
@@ -169,6 +239,16 @@ impl AnimatedMonsterAI {
 impl Script for AnimatedMonsterAI {
     fn initialize(&mut self, entity_id: EntityId, world: &World) -> Effect {
         self.current_heading = current_yaw(entity_id, world);
+
+        if self.profile.starts_alert {
+            let player_pos = world.borrow::<UniqueView<PlayerInfo>>().unwrap().pos;
+            self.awareness.alert(player_pos);
+        }
+
+        if let Some(behavior) = self.next_behavior_from_awareness(world, entity_id) {
+            self.current_behavior = behavior;
+        }
+
         Effect::QueueAnimationBySchema {
             entity_id,
             motion_query_items: self.current_behavior.borrow().animation(),
@@ -200,18 +280,20 @@ impl Script for AnimatedMonsterAI {
                 _ => None,
             });
 
-        for (entity_id, watch_options) in ai_signal_resp {
-            if self.played_ai_watch_obj.contains(&entity_id) {
+        for (watch_obj_id, watch_options) in ai_signal_resp {
+            if self.played_ai_watch_obj.contains(&watch_obj_id) {
                 continue;
             }
 
-            if player_is_within_watch_obj(world, entity_id, watch_options.radius) {
+            if player_is_within_watch_obj(world, watch_obj_id, watch_options.radius) {
                 // Immediately switch to Scripted sequence Behavior
-                self.played_ai_watch_obj.insert(entity_id);
+                self.played_ai_watch_obj.insert(watch_obj_id);
                 self.current_behavior = Box::new(RefCell::new(ScriptedSequenceBehavior::new(
                     world,
+                    entity_id,
                     watch_options.scripted_actions.clone(),
                 )));
+                self.alert_response = None;
                 self.animation_seq += 1;
                 return Effect::QueueAnimationBySchema {
                     entity_id,
@@ -223,6 +305,11 @@ impl Script for AnimatedMonsterAI {
             }
         }
 
+        if !self.is_dead {
+            let stimuli = awareness::sense(&self.awareness, entity_id, world, physics);
+            self.awareness.update(&stimuli, time.elapsed.as_secs_f32());
+        }
+
         // Temporary steering behavior
         let (steering_output, steering_effects) = self
             .current_behavior
@@ -254,13 +341,17 @@ impl Script for AnimatedMonsterAI {
         physics: &PhysicsWorld,
         msg: &MessagePayload,
     ) -> Effect {
-        {
+        let behavior_effect = {
             self.current_behavior
                 .borrow_mut()
-                .handle_message(entity_id, world, physics, msg);
-        }
-        match msg {
+                .handle_message(entity_id, world, physics, msg)
+        };
+        let effect = match msg {
             MessagePayload::Damage { amount } => {
+                // Getting hurt puts the AI on full alert, even if it didn't see where it came from
+                let player_pos = world.borrow::<UniqueView<PlayerInfo>>().unwrap().pos;
+                self.awareness.alert(player_pos);
+
                 // TODO: Let behavior handle this?
                 //self.took_damage = true;
                 Effect::AdjustHitPoints {
@@ -271,12 +362,19 @@ impl Script for AnimatedMonsterAI {
             MessagePayload::TurnOn { from } => {
                 let v_prop_sig_resp = world.borrow::<View<PropAISignalResponse>>().unwrap();
 
-                if let Ok(prop_sig_resp) = v_prop_sig_resp.get(entity_id) {
+                let maybe_prop_sig_resp = v_prop_sig_resp
+                    .get(entity_id)
+                    .ok()
+                    .filter(|resp| self.awareness.should_respond_to_signal(resp.priority));
+
+                if let Some(prop_sig_resp) = maybe_prop_sig_resp {
                     // Immediately switch to Scripted sequence Behavior
                     self.current_behavior = Box::new(RefCell::new(ScriptedSequenceBehavior::new(
                         world,
+                        entity_id,
                         prop_sig_resp.actions.clone(),
                     )));
+                    self.alert_response = None;
                     self.animation_seq += 1;
                     Effect::QueueAnimationBySchema {
                         entity_id,
@@ -294,12 +392,19 @@ impl Script for AnimatedMonsterAI {
 
                 let v_prop_sig_resp = world.borrow::<View<PropAISignalResponse>>().unwrap();
 
-                if let Ok(prop_sig_resp) = v_prop_sig_resp.get(entity_id) {
+                let maybe_prop_sig_resp = v_prop_sig_resp
+                    .get(entity_id)
+                    .ok()
+                    .filter(|resp| self.awareness.should_respond_to_signal(resp.priority));
+
+                if let Some(prop_sig_resp) = maybe_prop_sig_resp {
                     // Immediately switch to Scripted sequence Behavior
                     self.current_behavior = Box::new(RefCell::new(ScriptedSequenceBehavior::new(
                         world,
+                        entity_id,
                         prop_sig_resp.actions.clone(),
                     )));
+                    self.alert_response = None;
                     self.animation_seq += 1;
                     Effect::QueueAnimationBySchema {
                         entity_id,
@@ -312,6 +417,21 @@ impl Script for AnimatedMonsterAI {
                     Effect::NoEffect
                 }
             }
+            MessagePayload::Alert => {
+                // Once the scripted sequence wraps up, the AI goes looking for the player
+                let player_pos = world.borrow::<UniqueView<PlayerInfo>>().unwrap().pos;
+                self.awareness.raise(AlertLevel::Moderate, player_pos);
+                Effect::NoEffect
+            }
+            MessagePayload::BecomeHostile => {
+                let player_pos = world.borrow::<UniqueView<PlayerInfo>>().unwrap().pos;
+                self.awareness.alert(player_pos);
+                Effect::NoEffect
+            }
+            MessagePayload::EnableInvestigate => {
+                self.profile.investigates = true;
+                Effect::NoEffect
+            }
             MessagePayload::AnimationCompleted => {
                 if self.is_dead {
                     Effect::NoEffect
@@ -330,10 +450,12 @@ impl Script for AnimatedMonsterAI {
                         selection_strategy: dark::motion::MotionQuerySelectionStrategy::Random,
                     }
                 } else {
-                    let next_behavior = {
-                        self.current_behavior
+                    let next_behavior = match self.next_behavior_from_awareness(world, entity_id) {
+                        Some(behavior) => NextBehavior::Next(behavior),
+                        None => self
+                            .current_behavior
                             .borrow_mut()
-                            .next_behavior(world, physics, entity_id)
+                            .next_behavior(world, physics, entity_id),
                     };
 
                     match next_behavior {
@@ -408,7 +530,8 @@ impl Script for AnimatedMonsterAI {
                 }
             }
             _ => Effect::NoEffect,
-        }
+        };
+        Effect::combine(vec![behavior_effect, effect])
     }
 }
 
@@ -0,0 +1,459 @@
+///
+/// awareness.rs
+///
+/// How aware an AI is of the player. Seeing the player, or hearing the noises it makes, builds up
+/// the AI's alertness, which decays again while it perceives nothing. The alert level that results
+/// drives which behavior the AI picks.
+///
+use cgmath::{Deg, EuclideanSpace, InnerSpace, MetricSpace, Rad, Vector3};
+use dark::{properties::AIPriority, SCALE_FACTOR};
+use shipyard::{EntityId, Get, Unique, UniqueView, View, World};
+
+use crate::{mission::PlayerInfo, physics::PhysicsWorld, runtime_props::RuntimePropLaunchedBy};
+
+use super::ai_util;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AlertLevel {
+    None,
+    Low,
+    Moderate,
+    High,
+}
+
+// Alertness needed to reach each level
+const LOW_ALERTNESS: f32 = 0.15;
+const MODERATE_ALERTNESS: f32 = 0.4;
+const HIGH_ALERTNESS: f32 = 0.75;
+
+// Alertness gained per second with the player in plain view, up close
+const SIGHT_ALERTNESS_PER_SECOND: f32 = 2.0;
+
+// Even at the edge of vision, the player is noticed eventually
+const MIN_SIGHT_FACTOR: f32 = 0.25;
+
+// Noises alone can make the AI suspicious, but not put it in combat
+const MAX_HEARING_ALERTNESS: f32 = 0.6;
+
+// Alertness lost per second while nothing is perceived
+const ALERTNESS_DECAY_PER_SECOND: f32 = 0.04;
+
+const DEFAULT_VISION_RANGE: f32 = 60.0 / SCALE_FACTOR;
+const DEFAULT_VISION_HALF_ANGLE: Deg<f32> = Deg(60.0);
+
+// How far noises carry
+pub const ENVIRONMENTAL_NOISE_RADIUS: f32 = 40.0 / SCALE_FACTOR;
+pub const PLAYER_NOISE_RADIUS: f32 = 20.0 / SCALE_FACTOR;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Stimulus {
+    Sight {
+        position: Vector3<f32>,
+        distance: f32,
+    },
+    Sound {
+        position: Vector3<f32>,
+        // 0.0 (barely audible) to 1.0 (right next to the AI)
+        loudness: f32,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Noise {
+    // The entity that made the noise
+    pub source: EntityId,
+    pub position: Vector3<f32>,
+    pub radius: f32,
+}
+
+impl Noise {
+    pub fn loudness_at(&self, position: Vector3<f32>) -> f32 {
+        let distance = self.position.distance(position);
+        (1.0 - distance / self.radius).max(0.0)
+    }
+}
+
+///
+/// NoiseEvents
+///
+/// Noises made during the last frame, for AI to hear. Noises emitted while handling effects are
+/// held back until the next frame's script update.
+#[derive(Unique, Clone, Debug, Default)]
+pub struct NoiseEvents {
+    heard: Vec<Noise>,
+    pending: Vec<Noise>,
+}
+
+impl NoiseEvents {
+    pub fn emit(&mut self, source: EntityId, position: Vector3<f32>, radius: f32) {
+        self.pending.push(Noise {
+            source,
+            position,
+            radius,
+        });
+    }
+
+    pub fn heard(&self) -> &[Noise] {
+        &self.heard
+    }
+
+    ///
+    /// advance
+    ///
+    /// Start a new frame - the noises emitted since the last call become audible
+    pub fn advance(&mut self) {
+        self.heard = std::mem::take(&mut self.pending);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Awareness {
+    // 0.0 (unaware) to 1.0 (fully alert)
+    alertness: f32,
+    last_known_position: Option<Vector3<f32>>,
+    vision_range: f32,
+    vision_half_angle: Deg<f32>,
+}
+
+impl Awareness {
+    pub fn new() -> Awareness {
+        Awareness {
+            alertness: 0.0,
+            last_known_position: None,
+            vision_range: DEFAULT_VISION_RANGE,
+            vision_half_angle: DEFAULT_VISION_HALF_ANGLE,
+        }
+    }
+
+    pub fn level(&self) -> AlertLevel {
+        if self.alertness >= HIGH_ALERTNESS {
+            AlertLevel::High
+        } else if self.alertness >= MODERATE_ALERTNESS {
+            AlertLevel::Moderate
+        } else if self.alertness >= LOW_ALERTNESS {
+            AlertLevel::Low
+        } else {
+            AlertLevel::None
+        }
+    }
+
+    pub fn last_known_position(&self) -> Option<Vector3<f32>> {
+        self.last_known_position
+    }
+
+    ///
+    /// alert
+    ///
+    /// Become fully alert at once - ie, when taking damage
+    pub fn alert(&mut self, position: Vector3<f32>) {
+        self.alertness = 1.0;
+        self.last_known_position = Some(position);
+    }
+
+    ///
+    /// raise
+    ///
+    /// Become at least as alert as the given level - ie, when tipped off by a scripted action
+    pub fn raise(&mut self, level: AlertLevel, position: Vector3<f32>) {
+        let alertness = match level {
+            AlertLevel::None => 0.0,
+            AlertLevel::Low => LOW_ALERTNESS,
+            AlertLevel::Moderate => MODERATE_ALERTNESS,
+            AlertLevel::High => HIGH_ALERTNESS,
+        };
+        self.alertness = self.alertness.max(alertness);
+        self.last_known_position = Some(position);
+    }
+
+    ///
+    /// is_in_view
+    ///
+    /// Whether the target is within the vision cone. Doesn't check whether anything is in the way.
+    pub fn is_in_view(
+        &self,
+        position: Vector3<f32>,
+        forward: Vector3<f32>,
+        target: Vector3<f32>,
+    ) -> bool {
+        let to_target = target - position;
+        let distance = to_target.magnitude();
+        if distance > self.vision_range {
+            return false;
+        }
+
+        if distance <= f32::EPSILON {
+            return true;
+        }
+
+        forward.angle(to_target) <= Rad::from(self.vision_half_angle)
+    }
+
+    ///
+    /// update
+    ///
+    /// Take in what the AI perceived this frame. Returns the new alert level, if it changed.
+    pub fn update(&mut self, stimuli: &[Stimulus], elapsed_seconds: f32) -> Option<AlertLevel> {
+        let previous_level = self.level();
+
+        let mut saw_player = false;
+        let mut heard_something = false;
+        for stimulus in stimuli {
+            match stimulus {
+                Stimulus::Sight { position, distance } => {
+                    let sight_factor = (1.0 - distance / self.vision_range).max(MIN_SIGHT_FACTOR);
+                    self.alertness += SIGHT_ALERTNESS_PER_SECOND * sight_factor * elapsed_seconds;
+                    self.last_known_position = Some(*position);
+                    saw_player = true;
+                }
+                Stimulus::Sound { position, loudness } => {
+                    let hearing_alertness = loudness * MAX_HEARING_ALERTNESS;
+                    if hearing_alertness > self.alertness {
+                        self.alertness = hearing_alertness;
+                        heard_something = true;
+                        if !saw_player {
+                            self.last_known_position = Some(*position);
+                        }
+                    }
+                }
+            }
+        }
+
+        if !saw_player && !heard_something {
+            self.alertness -= ALERTNESS_DECAY_PER_SECOND * elapsed_seconds;
+        }
+
+        self.alertness = self.alertness.clamp(0.0, 1.0);
+
+        let level = self.level();
+        if level == AlertLevel::None {
+            self.last_known_position = None;
+        }
+
+        if level != previous_level {
+            Some(level)
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// should_respond_to_signal
+    ///
+    /// Once in combat, the AI only breaks off for high priority signal responses
+    pub fn should_respond_to_signal(&self, priority: AIPriority) -> bool {
+        self.level() < AlertLevel::High || priority >= AIPriority::High
+    }
+}
+
+///
+/// sense
+///
+/// Gather what the AI can see and hear of the player this frame
+pub fn sense(
+    awareness: &Awareness,
+    entity_id: EntityId,
+    world: &World,
+    physics: &PhysicsWorld,
+) -> Vec<Stimulus> {
+    let (position, forward) = ai_util::get_position_and_forward(world, entity_id);
+    let position = position.to_vec();
+
+    let mut stimuli = Vec::new();
+
+    let player_pos = world.borrow::<UniqueView<PlayerInfo>>().unwrap().pos;
+    if awareness.is_in_view(position, forward, player_pos)
+        && ai_util::is_player_visible(entity_id, world, physics)
+    {
+        stimuli.push(Stimulus::Sight {
+            position: player_pos,
+            distance: position.distance(player_pos),
+        });
+    }
+
+    if let Ok(noise_events) = world.borrow::<UniqueView<NoiseEvents>>() {
+        for noise in noise_events.heard() {
+            // AI only listen out for the player - not each other's footsteps and barks
+            if !is_made_by_player(world, noise.source) {
+                continue;
+            }
+
+            let loudness = noise.loudness_at(position);
+            if loudness > 0.0 {
+                stimuli.push(Stimulus::Sound {
+                    position: noise.position,
+                    loudness,
+                });
+            }
+        }
+    }
+
+    stimuli
+}
+
+///
+/// is_made_by_player
+///
+/// Whether a noise came from the player - the player itself, what it is holding, or a projectile
+/// it fired
+fn is_made_by_player(world: &World, source: EntityId) -> bool {
+    let player = world.borrow::<UniqueView<PlayerInfo>>().unwrap();
+    if source == player.entity_id
+        || player.left_hand_entity_id == Some(source)
+        || player.right_hand_entity_id == Some(source)
+    {
+        return true;
+    }
+
+    let v_launched_by = world.borrow::<View<RuntimePropLaunchedBy>>().unwrap();
+    v_launched_by
+        .get(source)
+        .map(|launched_by| launched_by.0 == player.entity_id)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cgmath::{vec3, Quaternion};
+
+    fn sight(distance: f32) -> Stimulus {
+        Stimulus::Sight {
+            position: vec3(0.0, 0.0, distance),
+            distance,
+        }
+    }
+
+    #[test]
+    fn test_sight_raises_alert_level() {
+        let mut awareness = Awareness::new();
+        assert_eq!(awareness.level(), AlertLevel::None);
+
+        let mut levels = vec![];
+        for _ in 0..20 {
+            if let Some(level) = awareness.update(&[sight(1.0)], 0.1) {
+                levels.push(level);
+            }
+        }
+
+        assert_eq!(
+            levels,
+            vec![AlertLevel::Low, AlertLevel::Moderate, AlertLevel::High]
+        );
+        assert_eq!(awareness.last_known_position(), Some(vec3(0.0, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn test_hearing_makes_suspicious_but_not_hostile() {
+        let mut awareness = Awareness::new();
+        let sound = Stimulus::Sound {
+            position: vec3(5.0, 0.0, 0.0),
+            loudness: 1.0,
+        };
+
+        for _ in 0..10 {
+            awareness.update(&[sound.clone()], 0.1);
+        }
+
+        assert_eq!(awareness.level(), AlertLevel::Moderate);
+        assert_eq!(awareness.last_known_position(), Some(vec3(5.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_alertness_decays() {
+        let mut awareness = Awareness::new();
+        awareness.alert(vec3(1.0, 0.0, 0.0));
+        assert_eq!(awareness.level(), AlertLevel::High);
+
+        let mut levels = vec![];
+        for _ in 0..30 {
+            if let Some(level) = awareness.update(&[], 1.0) {
+                levels.push(level);
+            }
+        }
+
+        assert_eq!(
+            levels,
+            vec![AlertLevel::Moderate, AlertLevel::Low, AlertLevel::None]
+        );
+        assert_eq!(awareness.last_known_position(), None);
+    }
+
+    #[test]
+    fn test_raise_never_lowers() {
+        let mut awareness = Awareness::new();
+        awareness.raise(AlertLevel::Moderate, vec3(1.0, 0.0, 0.0));
+        assert_eq!(awareness.level(), AlertLevel::Moderate);
+        assert_eq!(awareness.last_known_position(), Some(vec3(1.0, 0.0, 0.0)));
+
+        awareness.alert(vec3(2.0, 0.0, 0.0));
+        awareness.raise(AlertLevel::Low, vec3(3.0, 0.0, 0.0));
+        assert_eq!(awareness.level(), AlertLevel::High);
+    }
+
+    #[test]
+    fn test_vision_cone() {
+        let awareness = Awareness::new();
+        let position = vec3(0.0, 0.0, 0.0);
+        let forward = vec3(0.0, 0.0, 1.0);
+
+        assert!(awareness.is_in_view(position, forward, vec3(0.0, 0.0, 10.0)));
+        assert!(awareness.is_in_view(position, forward, vec3(5.0, 0.0, 5.0)));
+        assert!(!awareness.is_in_view(position, forward, vec3(0.0, 0.0, -10.0)));
+        assert!(!awareness.is_in_view(
+            position,
+            forward,
+            vec3(0.0, 0.0, DEFAULT_VISION_RANGE + 1.0)
+        ));
+    }
+
+    #[test]
+    fn test_signal_priority() {
+        let mut awareness = Awareness::new();
+        assert!(awareness.should_respond_to_signal(AIPriority::Low));
+
+        awareness.alert(vec3(0.0, 0.0, 0.0));
+        assert!(!awareness.should_respond_to_signal(AIPriority::Normal));
+        assert!(awareness.should_respond_to_signal(AIPriority::VeryHigh));
+    }
+
+    #[test]
+    fn test_only_player_noises_count() {
+        let mut world = World::new();
+        let player = world.add_entity(());
+        let gun = world.add_entity(());
+        let monster = world.add_entity(());
+        let player_grenade = world.add_entity((RuntimePropLaunchedBy(player),));
+        let monster_grenade = world.add_entity((RuntimePropLaunchedBy(monster),));
+        world.add_unique(PlayerInfo {
+            pos: vec3(0.0, 0.0, 0.0),
+            rotation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+            entity_id: player,
+            left_hand_entity_id: None,
+            right_hand_entity_id: Some(gun),
+            inventory_entity_id: player,
+        });
+
+        assert!(is_made_by_player(&world, player));
+        assert!(is_made_by_player(&world, gun));
+        assert!(is_made_by_player(&world, player_grenade));
+        assert!(!is_made_by_player(&world, monster));
+        assert!(!is_made_by_player(&world, monster_grenade));
+    }
+
+    #[test]
+    fn test_noise_events_heard_next_frame() {
+        let mut noise_events = NoiseEvents::default();
+        noise_events.emit(EntityId::dead(), vec3(0.0, 0.0, 0.0), 10.0);
+        assert!(noise_events.heard().is_empty());
+
+        noise_events.advance();
+        assert_eq!(noise_events.heard().len(), 1);
+        assert_eq!(
+            noise_events.heard()[0].loudness_at(vec3(5.0, 0.0, 0.0)),
+            0.5
+        );
+
+        noise_events.advance();
+        assert!(noise_events.heard().is_empty());
+    }
+}
@@ -46,6 +46,14 @@ pub trait Behavior {
         Some((Steering::from_current(current_heading), Effect::NoEffect))
     }
 
+    ///
+    /// is_interruptible
+    ///
+    /// Whether the AI's awareness of the player can switch it to another behavior
+    fn is_interruptible(&self) -> bool {
+        true
+    }
+
     fn next_behavior(
         &mut self,
         _world: &World,
@@ -66,7 +74,7 @@ pub trait Behavior {
     }
 }
 
-pub fn random_behavior() -> Box<RefCell<dyn Behavior>> {
+pub fn random_behavior(rng: &mut impl Rng) -> Box<RefCell<dyn Behavior>> {
     let mut potential_behaviors: Vec<Box<RefCell<dyn Behavior>>> = vec![
         // Rc::new(MeleeAttackBehavior),
         // Rc::new(SearchBehavior),
@@ -76,7 +84,6 @@ pub fn random_behavior() -> Box<RefCell<dyn Behavior>> {
         //Rc::new(ChaseBehavior),
         //Rc::new(DieBehavior),
     ];
-    let mut rng = rand::thread_rng();
     let idx = rng.gen_range(0..potential_behaviors.len());
     potential_behaviors.remove(idx)
 }
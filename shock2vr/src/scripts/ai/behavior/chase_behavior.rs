@@ -2,16 +2,19 @@ use std::cell::RefCell;
 
 use cgmath::{Deg, InnerSpace};
 use dark::{motion::MotionQueryItem, properties::PropPosition, SCALE_FACTOR};
-use rand::Rng;
 use shipyard::*;
 
 use crate::{
     mission::PlayerInfo,
     physics::PhysicsWorld,
     scripts::{
-        ai::steering::{
-            self, ChasePlayerSteeringStrategy, CollisionAvoidanceSteeringStrategy, SteeringOutput,
-            SteeringStrategy,
+        ai::{
+            behavior_selection::CombatStyle,
+            steering::{
+                self, ChasePlayerSteeringStrategy, CollisionAvoidanceSteeringStrategy,
+                FleePlayerSteeringStrategy, PathFollowSteeringStrategy, SteeringOutput,
+                SteeringStrategy,
+            },
         },
         Effect,
     },
@@ -20,21 +23,49 @@ use crate::{
 
 use super::{Behavior, MeleeAttackBehavior, NextBehavior, RangedAttackBehavior};
 
+// The closest AI open fire from - ranged-only AI back away to it, the rest close in to melee
+const KEEP_DISTANCE: f32 = 15.0 / SCALE_FACTOR;
+
 pub struct ChaseBehavior {
+    combat_style: CombatStyle,
     steering_strategy: Box<dyn SteeringStrategy>,
+    back_away_steering_strategy: Box<dyn SteeringStrategy>,
 }
 
 impl ChaseBehavior {
-    pub fn new() -> ChaseBehavior {
+    pub fn new(combat_style: CombatStyle) -> ChaseBehavior {
         ChaseBehavior {
+            combat_style,
             steering_strategy: steering::chained(vec![
                 Box::new(
                     CollisionAvoidanceSteeringStrategy::conservative(), /* conservative so we can focus on the chase */
                 ),
+                Box::new(PathFollowSteeringStrategy::to_player()),
                 Box::new(ChasePlayerSteeringStrategy),
             ]),
+            back_away_steering_strategy: steering::chained(vec![
+                Box::new(CollisionAvoidanceSteeringStrategy::comprehensive()),
+                Box::new(FleePlayerSteeringStrategy),
+            ]),
         }
     }
+
+    ///
+    /// is_too_close
+    ///
+    /// Whether a ranged-only AI should back away from the player before it shoots
+    fn is_too_close(&self, world: &World, entity_id: EntityId) -> bool {
+        if self.combat_style != CombatStyle::Ranged {
+            return false;
+        }
+
+        let u_player = world.borrow::<UniqueView<PlayerInfo>>().unwrap();
+        let v_current_pos = world.borrow::<View<PropPosition>>().unwrap();
+        v_current_pos
+            .get(entity_id)
+            .map(|prop_pos| (prop_pos.position - u_player.pos).magnitude() < KEEP_DISTANCE)
+            .unwrap_or(false)
+    }
 }
 
 impl Behavior for ChaseBehavior {
@@ -50,8 +81,13 @@ impl Behavior for ChaseBehavior {
         entity_id: EntityId,
         time: &Time,
     ) -> Option<(SteeringOutput, Effect)> {
-        self.steering_strategy
-            .steer(current_heading, world, physics, entity_id, time)
+        if self.is_too_close(world, entity_id) {
+            self.back_away_steering_strategy
+                .steer(current_heading, world, physics, entity_id, time)
+        } else {
+            self.steering_strategy
+                .steer(current_heading, world, physics, entity_id, time)
+        }
     }
 
     fn animation(self: &ChaseBehavior) -> Vec<MotionQueryItem> {
@@ -68,26 +104,75 @@ impl Behavior for ChaseBehavior {
         _physics: &PhysicsWorld,
         entity_id: EntityId,
     ) -> NextBehavior {
-        let _rand = rand::thread_rng().gen_range(0..100);
         let u_player = world.borrow::<UniqueView<PlayerInfo>>().unwrap();
         let v_current_pos = world.borrow::<View<PropPosition>>().unwrap();
         //let v_transform = world.borrow::<View<RuntimePropTransform>>().unwrap();
 
         let melee_attack_distance = 8.0 / SCALE_FACTOR;
         let ranged_max_attack_distance = 40.0 / SCALE_FACTOR;
-        let ranged_min_attack_distance = 15.0 / SCALE_FACTOR;
+        let ranged_min_attack_distance = KEEP_DISTANCE;
 
         if let Ok(prop_pos) = v_current_pos.get(entity_id) {
             let distance = (prop_pos.position - u_player.pos).magnitude();
 
-            if distance > ranged_min_attack_distance && distance < ranged_max_attack_distance {
-                return NextBehavior::Next(Box::new(RefCell::new(RangedAttackBehavior)));
+            let (can_melee, can_shoot) = match self.combat_style {
+                CombatStyle::Melee => (true, false),
+                CombatStyle::Ranged => (false, true),
+                CombatStyle::RangedMelee => (true, true),
+            };
+
+            if can_shoot
+                && distance > ranged_min_attack_distance
+                && distance < ranged_max_attack_distance
+            {
+                return NextBehavior::Next(Box::new(RefCell::new(RangedAttackBehavior::new(
+                    self.combat_style,
+                ))));
             }
-            if distance < melee_attack_distance {
-                return NextBehavior::Next(Box::new(RefCell::new(MeleeAttackBehavior)));
+            if can_melee && distance < melee_attack_distance {
+                return NextBehavior::Next(Box::new(RefCell::new(MeleeAttackBehavior::new(
+                    self.combat_style,
+                ))));
             }
         }
 
         NextBehavior::Stay
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cgmath::{vec3, Quaternion};
+
+    fn create_world(distance_to_player: f32) -> (World, EntityId) {
+        let mut world = World::new();
+        let player = world.add_entity(());
+        world.add_unique(PlayerInfo {
+            pos: vec3(distance_to_player, 0.0, 0.0),
+            rotation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+            entity_id: player,
+            left_hand_entity_id: None,
+            right_hand_entity_id: None,
+            inventory_entity_id: player,
+        });
+        let ai = world.add_entity((PropPosition {
+            position: vec3(0.0, 0.0, 0.0),
+            cell: 0,
+            rotation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+        },));
+        (world, ai)
+    }
+
+    #[test]
+    fn test_ranged_ai_keep_their_distance() {
+        let ranged = ChaseBehavior::new(CombatStyle::Ranged);
+        let (near_world, near_ai) = create_world(KEEP_DISTANCE * 0.5);
+        assert!(ranged.is_too_close(&near_world, near_ai));
+        let (far_world, far_ai) = create_world(KEEP_DISTANCE * 2.0);
+        assert!(!ranged.is_too_close(&far_world, far_ai));
+
+        let ranged_melee = ChaseBehavior::new(CombatStyle::RangedMelee);
+        assert!(!ranged_melee.is_too_close(&near_world, near_ai));
+    }
+}
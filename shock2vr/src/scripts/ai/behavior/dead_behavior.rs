@@ -33,6 +33,10 @@ impl Behavior for DeadBehavior {
         Some((Steering::from_current(current_heading), Effect::NoEffect))
     }
 
+    fn is_interruptible(&self) -> bool {
+        false
+    }
+
     fn animation(self: &DeadBehavior) -> Vec<MotionQueryItem> {
         vec![MotionQueryItem::new("crumple").optional()]
     }
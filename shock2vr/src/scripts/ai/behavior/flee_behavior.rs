@@ -0,0 +1,58 @@
+use cgmath::Deg;
+use dark::motion::MotionQueryItem;
+use shipyard::{EntityId, World};
+
+use crate::{
+    physics::PhysicsWorld,
+    scripts::{
+        ai::steering::{
+            self, CollisionAvoidanceSteeringStrategy, FleePlayerSteeringStrategy, SteeringOutput,
+            SteeringStrategy,
+        },
+        Effect,
+    },
+    time::Time,
+};
+
+use super::Behavior;
+
+pub struct FleeBehavior {
+    steering_strategy: Box<dyn SteeringStrategy>,
+}
+
+impl FleeBehavior {
+    pub fn new() -> FleeBehavior {
+        FleeBehavior {
+            steering_strategy: steering::chained(vec![
+                Box::new(CollisionAvoidanceSteeringStrategy::comprehensive()),
+                Box::new(FleePlayerSteeringStrategy),
+            ]),
+        }
+    }
+}
+
+impl Behavior for FleeBehavior {
+    fn turn_speed(&self) -> Deg<f32> {
+        Deg(360.0)
+    }
+
+    fn steer(
+        &mut self,
+        current_heading: Deg<f32>,
+        world: &World,
+        physics: &PhysicsWorld,
+        entity_id: EntityId,
+        time: &Time,
+    ) -> Option<(SteeringOutput, Effect)> {
+        self.steering_strategy
+            .steer(current_heading, world, physics, entity_id, time)
+    }
+
+    fn animation(self: &FleeBehavior) -> Vec<MotionQueryItem> {
+        vec![
+            MotionQueryItem::new("locomote"),
+            MotionQueryItem::new("locourgent").optional(),
+            MotionQueryItem::with_value("direction", 0).optional(),
+        ]
+    }
+}
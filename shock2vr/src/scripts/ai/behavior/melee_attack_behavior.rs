@@ -9,15 +9,44 @@ use crate::{
     mission::PlayerInfo,
     physics::PhysicsWorld,
     scripts::{
-        ai::steering::{ChasePlayerSteeringStrategy, SteeringOutput, SteeringStrategy},
-        Effect,
+        ai::{
+            behavior_selection::CombatStyle,
+            steering::{ChasePlayerSteeringStrategy, SteeringOutput, SteeringStrategy},
+        },
+        Effect, Message, MessagePayload,
     },
     time::Time,
 };
 
 use super::{Behavior, ChaseBehavior, NextBehavior};
 
-pub struct MeleeAttackBehavior;
+const MELEE_ATTACK_DISTANCE: f32 = 8.0 / SCALE_FACTOR;
+
+// TODO: Pull this from the creature's melee weapon
+const MELEE_DAMAGE: f32 = 5.0;
+
+pub struct MeleeAttackBehavior {
+    // Kept to pick the next attack once the chase resumes
+    combat_style: CombatStyle,
+}
+
+impl MeleeAttackBehavior {
+    pub fn new(combat_style: CombatStyle) -> MeleeAttackBehavior {
+        MeleeAttackBehavior { combat_style }
+    }
+}
+
+fn is_player_in_range(world: &World, entity_id: EntityId) -> bool {
+    let u_player = world.borrow::<UniqueView<PlayerInfo>>().unwrap();
+    let v_current_pos = world.borrow::<View<PropPosition>>().unwrap();
+
+    if let Ok(prop_pos) = v_current_pos.get(entity_id) {
+        let distance = (prop_pos.position - u_player.pos).magnitude();
+        distance < MELEE_ATTACK_DISTANCE
+    } else {
+        false
+    }
+}
 
 impl Behavior for MeleeAttackBehavior {
     fn animation(self: &MeleeAttackBehavior) -> Vec<MotionQueryItem> {
@@ -45,18 +74,36 @@ impl Behavior for MeleeAttackBehavior {
         _physics: &PhysicsWorld,
         entity_id: EntityId,
     ) -> NextBehavior {
-        let u_player = world.borrow::<UniqueView<PlayerInfo>>().unwrap();
-        let v_current_pos = world.borrow::<View<PropPosition>>().unwrap();
-
-        let melee_attack_distance = 8.0 / SCALE_FACTOR;
-
-        if let Ok(prop_pos) = v_current_pos.get(entity_id) {
-            let distance = (prop_pos.position - u_player.pos).magnitude();
+        if is_player_in_range(world, entity_id) {
+            return NextBehavior::Stay;
+        }
+        NextBehavior::Next(Box::new(RefCell::new(ChaseBehavior::new(
+            self.combat_style,
+        ))))
+    }
 
-            if distance < melee_attack_distance {
-                return NextBehavior::Stay;
+    fn handle_message(
+        &mut self,
+        entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        match msg {
+            // Each attack animation is a single swing - if the player is still close
+            // by when it finishes, the swing connects.
+            MessagePayload::AnimationCompleted if is_player_in_range(world, entity_id) => {
+                let player_entity = world.borrow::<UniqueView<PlayerInfo>>().unwrap().entity_id;
+                Effect::Send {
+                    msg: Message {
+                        to: player_entity,
+                        payload: MessagePayload::Damage {
+                            amount: MELEE_DAMAGE,
+                        },
+                    },
+                }
             }
+            _ => Effect::NoEffect,
         }
-        NextBehavior::Next(Box::new(RefCell::new(ChaseBehavior::new())))
     }
 }
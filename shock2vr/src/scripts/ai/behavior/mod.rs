@@ -1,9 +1,11 @@
 mod behavior;
 mod chase_behavior;
 mod dead_behavior;
+mod flee_behavior;
 mod idle_behavior;
 mod melee_attack_behavior;
 mod noop_behavior;
+mod patrol_behavior;
 mod ranged_attack_behavior;
 mod scripted_sequence_behavior;
 mod search_behavior;
@@ -12,9 +14,11 @@ mod wander_behavior;
 pub use behavior::*;
 pub use chase_behavior::*;
 pub use dead_behavior::*;
+pub use flee_behavior::*;
 pub use idle_behavior::*;
 pub use melee_attack_behavior::*;
 pub use noop_behavior::*;
+pub use patrol_behavior::*;
 pub use ranged_attack_behavior::*;
 pub use scripted_sequence_behavior::*;
 pub use search_behavior::*;
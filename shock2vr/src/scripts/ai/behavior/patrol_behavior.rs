@@ -0,0 +1,117 @@
+use cgmath::{Deg, InnerSpace, Vector3};
+use dark::{
+    motion::MotionQueryItem,
+    properties::{Link, Links, PropPosition},
+    SCALE_FACTOR,
+};
+use shipyard::{EntityId, Get, IntoIter, IntoWithId, View, World};
+
+use crate::{
+    physics::PhysicsWorld,
+    scripts::{
+        ai::steering::{
+            self, ChaseEntitySteeringStrategy, CollisionAvoidanceSteeringStrategy,
+            PathFollowSteeringStrategy, SteeringOutput, SteeringStrategy,
+        },
+        script_util, Effect,
+    },
+    time::Time,
+};
+
+use super::Behavior;
+
+// Horizontal distance at which a patrol point counts as reached
+const PATROL_POINT_REACHED_DISTANCE: f32 = 3.0 / SCALE_FACTOR;
+
+///
+/// PatrolBehavior
+///
+/// Walks the patrol route - starting from the closest patrol point, and following the AIPatrol
+/// links from each point to the next.
+pub struct PatrolBehavior {
+    target: Option<EntityId>,
+    steering_strategy: Box<dyn SteeringStrategy>,
+}
+
+impl PatrolBehavior {
+    pub fn new(world: &World, entity_id: EntityId) -> PatrolBehavior {
+        let target = nearest_patrol_point(world, entity_id);
+        PatrolBehavior {
+            target,
+            steering_strategy: steering_to(target),
+        }
+    }
+}
+
+fn steering_to(target: Option<EntityId>) -> Box<dyn SteeringStrategy> {
+    match target {
+        Some(target_id) => steering::chained(vec![
+            Box::new(CollisionAvoidanceSteeringStrategy::conservative()),
+            Box::new(PathFollowSteeringStrategy::to_entity(target_id)),
+            Box::new(ChaseEntitySteeringStrategy::new(target_id)),
+        ]),
+        None => steering::chained(vec![]),
+    }
+}
+
+fn nearest_patrol_point(world: &World, entity_id: EntityId) -> Option<EntityId> {
+    let v_links = world.borrow::<View<Links>>().unwrap();
+    let v_current_pos = world.borrow::<View<PropPosition>>().unwrap();
+    let position = v_current_pos.get(entity_id).ok()?.position;
+
+    (&v_links, &v_current_pos)
+        .iter()
+        .with_id()
+        .filter(|(id, (links, _))| {
+            *id != entity_id
+                && links
+                    .to_links
+                    .iter()
+                    .any(|to_link| to_link.link == Link::AIPatrol)
+        })
+        .map(|(id, (_, patrol_pos))| (id, (patrol_pos.position - position).magnitude2()))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(id, _)| id)
+}
+
+fn horizontal_distance(world: &World, from: EntityId, to: EntityId) -> Option<f32> {
+    let v_current_pos = world.borrow::<View<PropPosition>>().unwrap();
+    let diff = v_current_pos.get(to).ok()?.position - v_current_pos.get(from).ok()?.position;
+    Some(Vector3::new(diff.x, 0.0, diff.z).magnitude())
+}
+
+impl Behavior for PatrolBehavior {
+    fn steer(
+        &mut self,
+        current_heading: Deg<f32>,
+        world: &World,
+        physics: &PhysicsWorld,
+        entity_id: EntityId,
+        time: &Time,
+    ) -> Option<(SteeringOutput, Effect)> {
+        if let Some(target_id) = self.target {
+            let is_reached = horizontal_distance(world, entity_id, target_id)
+                .map(|distance| distance < PATROL_POINT_REACHED_DISTANCE)
+                .unwrap_or(false);
+
+            if is_reached {
+                self.target = script_util::get_first_link_of_type(world, target_id, Link::AIPatrol);
+                self.steering_strategy = steering_to(self.target);
+            }
+        }
+
+        self.steering_strategy
+            .steer(current_heading, world, physics, entity_id, time)
+    }
+
+    fn animation(self: &PatrolBehavior) -> Vec<MotionQueryItem> {
+        if self.target.is_some() {
+            vec![
+                MotionQueryItem::new("locomote"),
+                MotionQueryItem::with_value("direction", 0).optional(),
+            ]
+        } else {
+            vec![MotionQueryItem::new("idlegesture")]
+        }
+    }
+}
@@ -8,7 +8,10 @@ use shipyard::*;
 use crate::{
     physics::PhysicsWorld,
     scripts::{
-        ai::steering::{ChasePlayerSteeringStrategy, SteeringOutput, SteeringStrategy},
+        ai::{
+            behavior_selection::CombatStyle,
+            steering::{ChasePlayerSteeringStrategy, SteeringOutput, SteeringStrategy},
+        },
         Effect,
     },
     time::Time,
@@ -16,7 +19,16 @@ use crate::{
 
 use super::{Behavior, ChaseBehavior, NextBehavior};
 
-pub struct RangedAttackBehavior;
+pub struct RangedAttackBehavior {
+    // Kept to pick the next attack once the chase resumes
+    combat_style: CombatStyle,
+}
+
+impl RangedAttackBehavior {
+    pub fn new(combat_style: CombatStyle) -> RangedAttackBehavior {
+        RangedAttackBehavior { combat_style }
+    }
+}
 
 impl Behavior for RangedAttackBehavior {
     fn animation(self: &RangedAttackBehavior) -> Vec<MotionQueryItem> {
@@ -44,6 +56,8 @@ impl Behavior for RangedAttackBehavior {
         _physics: &PhysicsWorld,
         _entity_id: EntityId,
     ) -> NextBehavior {
-        NextBehavior::Next(Box::new(RefCell::new(ChaseBehavior::new())))
+        NextBehavior::Next(Box::new(RefCell::new(ChaseBehavior::new(
+            self.combat_style,
+        ))))
     }
 }
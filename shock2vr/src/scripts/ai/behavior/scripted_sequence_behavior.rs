@@ -3,10 +3,12 @@ use std::{cell::RefCell, time::Duration};
 use cgmath::{vec3, Deg, InnerSpace};
 use dark::{
     motion::MotionQueryItem,
-    properties::{AIScriptedAction, AIScriptedActionType, PropPosition},
+    properties::{AIScriptedAction, AIScriptedActionType, Link, PropPosition},
     SCALE_FACTOR,
 };
+use engine::audio::AudioHandle;
 use shipyard::{EntityId, Get, View, World};
+use tracing::info;
 
 use crate::{
     physics::PhysicsWorld,
@@ -15,10 +17,11 @@ use crate::{
             ai_util,
             steering::{
                 self, ChaseEntitySteeringStrategy, ChasePlayerSteeringStrategy,
-                CollisionAvoidanceSteeringStrategy, Steering, SteeringOutput, SteeringStrategy,
+                CollisionAvoidanceSteeringStrategy, PathFollowSteeringStrategy, Steering,
+                SteeringOutput, SteeringStrategy,
             },
         },
-        script_util, Effect, Message,
+        script_util, Effect, Message, MessagePayload,
     },
     time::Time,
 };
@@ -30,11 +33,20 @@ pub struct ScriptedSequenceBehavior {
     queued_effects: Vec<Effect>,
     current_action_idx: i32,
     current_scripted_action: Box<RefCell<dyn ScriptedAction>>,
+    // Set once the last action completes
+    is_finished: bool,
 }
 
 impl ScriptedSequenceBehavior {
-    pub fn new(world: &World, actions: Vec<AIScriptedAction>) -> ScriptedSequenceBehavior {
-        let current_behavior = get_behavior_from_action(world, &actions[0]);
+    pub fn new(
+        world: &World,
+        entity_id: EntityId,
+        actions: Vec<AIScriptedAction>,
+    ) -> ScriptedSequenceBehavior {
+        let current_behavior: Box<RefCell<dyn ScriptedAction>> = match actions.first() {
+            Some(action) => get_behavior_from_action(world, entity_id, action, None),
+            None => Box::new(RefCell::new(NoopScriptedAction)),
+        };
         let initial_effect = current_behavior.borrow().initial_effect();
 
         ScriptedSequenceBehavior {
@@ -42,6 +54,7 @@ impl ScriptedSequenceBehavior {
             queued_effects: vec![initial_effect],
             current_action_idx: 0,
             current_scripted_action: current_behavior,
+            is_finished: false,
         }
     }
 }
@@ -55,6 +68,10 @@ impl Behavior for ScriptedSequenceBehavior {
         self.current_scripted_action.borrow().turn_speed()
     }
 
+    fn is_interruptible(&self) -> bool {
+        self.is_finished
+    }
+
     fn steer(
         &mut self,
         current_heading: Deg<f32>,
@@ -87,19 +104,27 @@ impl Behavior for ScriptedSequenceBehavior {
         _physics: &crate::physics::PhysicsWorld,
         entity_id: shipyard::EntityId,
     ) -> super::NextBehavior {
-        if self
+        if self.is_finished {
+            super::NextBehavior::NoOpinion
+        } else if self
             .current_scripted_action
             .borrow()
             .is_complete(entity_id, world)
         {
+            let outgoing_effect = self.current_scripted_action.borrow().completion_effect();
             if self.current_action_idx >= ((self.actions.len() as i32) - 1) {
+                self.is_finished = true;
+                self.queued_effects.push(outgoing_effect);
                 super::NextBehavior::NoOpinion
             } else {
-                let outgoing_effect = self.current_scripted_action.borrow().completion_effect();
                 self.current_action_idx += 1;
+                let destination =
+                    last_goto_destination(world, &self.actions[..self.current_action_idx as usize]);
                 let behavior = get_behavior_from_action(
                     world,
+                    entity_id,
                     &self.actions[self.current_action_idx as usize],
+                    destination,
                 );
                 self.current_scripted_action = behavior;
                 let incoming_effect = self.current_scripted_action.borrow().initial_effect();
@@ -118,12 +143,93 @@ impl Behavior for ScriptedSequenceBehavior {
 
 fn get_behavior_from_action(
     world: &World,
+    entity_id: EntityId,
     action: &AIScriptedAction,
+    destination: Option<EntityId>,
 ) -> Box<RefCell<dyn ScriptedAction>> {
     let current_behavior: Box<RefCell<dyn ScriptedAction>> = match &action.action_type {
-        AIScriptedActionType::Play(action_name) => Box::new(RefCell::new(
-            PlayAnimationScriptedAction::new(action_name.clone()),
+        AIScriptedActionType::Nothing => Box::new(RefCell::new(NoopScriptedAction)),
+        AIScriptedActionType::ScriptMessage(message) => Box::new(RefCell::new(
+            InstantScriptedAction(script_message(entity_id, entity_id, message)),
         )),
+        AIScriptedActionType::Alert => Box::new(RefCell::new(InstantScriptedAction(send_to(
+            entity_id,
+            MessagePayload::Alert,
+        )))),
+        AIScriptedActionType::BecomeHostile => Box::new(RefCell::new(InstantScriptedAction(
+            send_to(entity_id, MessagePayload::BecomeHostile),
+        ))),
+        AIScriptedActionType::EnableInvestigate => Box::new(RefCell::new(InstantScriptedAction(
+            send_to(entity_id, MessagePayload::EnableInvestigate),
+        ))),
+        AIScriptedActionType::Mprint(text) => {
+            info!("mprint: {}", text);
+            Box::new(RefCell::new(NoopScriptedAction))
+        }
+        AIScriptedActionType::MetaProperty {
+            action_type,
+            arg1: metaprop_name,
+            arg2: entity_name,
+        } => Box::new(RefCell::new(InstantScriptedAction(meta_property_effect(
+            world,
+            entity_id,
+            action_type,
+            metaprop_name,
+            entity_name,
+        )))),
+        AIScriptedActionType::AddLink {
+            link_type,
+            entity_name,
+        } => Box::new(RefCell::new(InstantScriptedAction(link_effect(
+            world,
+            entity_id,
+            link_type,
+            entity_name,
+            |from, to, link| Effect::AddLink { from, to, link },
+        )))),
+        AIScriptedActionType::RemoveLink {
+            link_type,
+            entity_name,
+        } => Box::new(RefCell::new(InstantScriptedAction(link_effect(
+            world,
+            entity_id,
+            link_type,
+            entity_name,
+            |from, to, link| Effect::RemoveLink { from, to, link },
+        )))),
+        AIScriptedActionType::Signal {
+            entity_name,
+            signal,
+        } => {
+            let effect = match find_entity(world, entity_id, entity_name) {
+                Some(to) => send_to(
+                    to,
+                    MessagePayload::Signal {
+                        name: signal.clone(),
+                    },
+                ),
+                None => Effect::NoEffect,
+            };
+            Box::new(RefCell::new(InstantScriptedAction(effect)))
+        }
+        AIScriptedActionType::DestScript(message) => {
+            let effect = match destination {
+                Some(to) => script_message(to, entity_id, message),
+                None => Effect::NoEffect,
+            };
+            Box::new(RefCell::new(InstantScriptedAction(effect)))
+        }
+        AIScriptedActionType::Play { sound, motion } => {
+            let sound_effect = play_sound_effect(world, entity_id, sound);
+            if motion.trim().is_empty() {
+                Box::new(RefCell::new(InstantScriptedAction(sound_effect)))
+            } else {
+                Box::new(RefCell::new(PlayAnimationScriptedAction::new(
+                    motion.clone(),
+                    sound_effect,
+                )))
+            }
+        }
         AIScriptedActionType::Face { entity_name } => {
             Box::new(RefCell::new(FaceScriptedAction::new(world, &entity_name)))
         }
@@ -138,11 +244,131 @@ fn get_behavior_from_action(
         AIScriptedActionType::Wait(duration) => {
             Box::new(RefCell::new(WaitScriptedAction::new(*duration)))
         }
-        _ => Box::new(RefCell::new(NoopScriptedAction)),
     };
     current_behavior
 }
 
+fn send_to(to: EntityId, payload: MessagePayload) -> Effect {
+    Effect::Send {
+        msg: Message { to, payload },
+    }
+}
+
+///
+/// play_sound_effect
+///
+/// Play the sound schema from where the AI is standing
+fn play_sound_effect(world: &World, entity_id: EntityId, sound: &str) -> Effect {
+    if sound.trim().is_empty() {
+        return Effect::NoEffect;
+    }
+
+    let v_current_pos = world.borrow::<View<PropPosition>>().unwrap();
+    match v_current_pos.get(entity_id) {
+        Ok(current_pos) => Effect::PlayPositionalSound {
+            handle: AudioHandle::new(),
+            name: sound.trim().to_owned(),
+            position: current_pos.position,
+            source: entity_id,
+        },
+        Err(_) => Effect::NoEffect,
+    }
+}
+
+///
+/// find_entity
+///
+/// The entity an action refers to by name - the AI itself when no name is given
+fn find_entity(world: &World, entity_id: EntityId, entity_name: &str) -> Option<EntityId> {
+    if entity_name.trim().is_empty() {
+        Some(entity_id)
+    } else {
+        script_util::get_first_entity_by_name(world, entity_name)
+    }
+}
+
+///
+/// last_goto_destination
+///
+/// The entity the most recent Goto action in the sequence headed for
+fn last_goto_destination(world: &World, actions: &[AIScriptedAction]) -> Option<EntityId> {
+    actions
+        .iter()
+        .rev()
+        .find_map(|action| match &action.action_type {
+            AIScriptedActionType::Goto { waypoint_name, .. } => Some(waypoint_name),
+            _ => None,
+        })
+        .and_then(|waypoint_name| script_util::get_first_entity_by_name(world, waypoint_name))
+}
+
+///
+/// script_message
+///
+/// Send a named script message. The scripts here understand a few messages directly, and respond
+/// to signals for everything else.
+fn script_message(to: EntityId, from: EntityId, message: &str) -> Effect {
+    let payload = match message.trim().to_ascii_lowercase().as_str() {
+        "" => return Effect::NoEffect,
+        "turnon" => MessagePayload::TurnOn { from },
+        "turnoff" => MessagePayload::TurnOff { from },
+        "frob" => MessagePayload::Frob,
+        "slay" => MessagePayload::Slay,
+        _ => MessagePayload::Signal {
+            name: message.to_owned(),
+        },
+    };
+    send_to(to, payload)
+}
+
+fn meta_property_effect(
+    world: &World,
+    entity_id: EntityId,
+    action_type: &str,
+    metaprop_name: &str,
+    entity_name: &str,
+) -> Effect {
+    let maybe_target = find_entity(world, entity_id, entity_name);
+    match (
+        action_type.trim().to_ascii_lowercase().as_str(),
+        maybe_target,
+    ) {
+        ("add", Some(target)) => Effect::AddMetaProperty {
+            entity_id: target,
+            metaprop_name: metaprop_name.to_owned(),
+        },
+        ("remove", Some(target)) => Effect::RemoveMetaProperty {
+            entity_id: target,
+            metaprop_name: metaprop_name.to_owned(),
+        },
+        _ => {
+            info!(
+                "unable to apply metaproperty action: {} {} {}",
+                action_type, metaprop_name, entity_name
+            );
+            Effect::NoEffect
+        }
+    }
+}
+
+fn link_effect(
+    world: &World,
+    entity_id: EntityId,
+    link_type: &str,
+    entity_name: &str,
+    create_effect: fn(EntityId, EntityId, Link) -> Effect,
+) -> Effect {
+    let maybe_link = Link::from_name(link_type);
+    let maybe_target = script_util::get_first_entity_by_name(world, entity_name);
+    match (maybe_link, maybe_target) {
+        (Some(link), Some(to)) => create_effect(entity_id, to, link),
+        _ => {
+            info!("unable to apply link action: {} {}", link_type, entity_name);
+            Effect::NoEffect
+        }
+    }
+}
+
 /// ScriptedAction
 /// animation:
 /// update (&mut self, etc)
@@ -184,11 +410,16 @@ trait ScriptedAction {
 
 pub struct PlayAnimationScriptedAction {
     animation_name: String,
+    // Played when the animation starts
+    sound_effect: Effect,
 }
 
 impl PlayAnimationScriptedAction {
-    pub fn new(animation_name: String) -> PlayAnimationScriptedAction {
-        PlayAnimationScriptedAction { animation_name }
+    pub fn new(animation_name: String, sound_effect: Effect) -> PlayAnimationScriptedAction {
+        PlayAnimationScriptedAction {
+            animation_name,
+            sound_effect,
+        }
     }
 }
 
@@ -215,6 +446,23 @@ impl ScriptedAction for PlayAnimationScriptedAction {
 
         vec![MotionQueryItem::new(&self.animation_name)]
     }
+
+    fn initial_effect(&self) -> Effect {
+        self.sound_effect.clone()
+    }
+}
+
+// An action that happens all at once, like sending a message
+pub struct InstantScriptedAction(Effect);
+
+impl ScriptedAction for InstantScriptedAction {
+    fn animation(self: &InstantScriptedAction) -> Vec<MotionQueryItem> {
+        vec![MotionQueryItem::new("__NULL_ANIMATION__")]
+    }
+
+    fn initial_effect(&self) -> Effect {
+        self.0.clone()
+    }
 }
 
 pub struct IdleScriptedAction;
@@ -310,6 +558,7 @@ impl GotoScriptedAction {
         )];
 
         if let Some(ent) = maybe_entity {
+            steering_strategies.push(Box::new(PathFollowSteeringStrategy::to_entity(ent)));
             steering_strategies.push(Box::new(ChaseEntitySteeringStrategy::new(ent)))
             //steering_strategies.push(Box::new(ChasePlayerSteeringStrategy))
         }
@@ -424,3 +673,255 @@ impl ScriptedAction for FaceScriptedAction {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cgmath::Quaternion;
+    use dark::properties::PropSymName;
+
+    fn create_world() -> (World, EntityId, EntityId) {
+        let mut world = World::new();
+        let ai = world.add_entity((PropSymName("Hybrid".to_owned()),));
+        let door = world.add_entity((PropSymName("Door".to_owned()),));
+        (world, ai, door)
+    }
+
+    // Plays the sequence through to the end, collecting the effects it emits
+    fn run_sequence(
+        world: &World,
+        entity_id: EntityId,
+        action_types: Vec<AIScriptedActionType>,
+    ) -> Vec<Effect> {
+        let physics = PhysicsWorld::new();
+        let time = Time {
+            elapsed: Duration::from_millis(16),
+            total: Duration::ZERO,
+        };
+        let actions = action_types
+            .into_iter()
+            .map(|action_type| AIScriptedAction { action_type })
+            .collect();
+
+        let mut behavior = ScriptedSequenceBehavior::new(world, entity_id, actions);
+        let mut effects = vec![];
+        for _ in 0..100 {
+            if let Some((_, effect)) = behavior.steer(Deg(0.0), world, &physics, entity_id, &time) {
+                effects.push(effect);
+            }
+
+            if behavior.is_interruptible() {
+                break;
+            }
+
+            behavior.next_behavior(world, &physics, entity_id);
+        }
+
+        assert!(behavior.is_interruptible());
+        Effect::flatten(effects)
+    }
+
+    fn sent_to(effect: &Effect) -> Option<(EntityId, MessagePayload)> {
+        match effect {
+            Effect::Send { msg } => Some((msg.to, msg.payload.clone())),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_signals_and_script_messages() {
+        let (world, ai, door) = create_world();
+        let effects = run_sequence(
+            &world,
+            ai,
+            vec![
+                AIScriptedActionType::Signal {
+                    entity_name: "door".to_owned(),
+                    signal: "Open".to_owned(),
+                },
+                AIScriptedActionType::Nothing,
+                AIScriptedActionType::ScriptMessage("TurnOn".to_owned()),
+            ],
+        );
+
+        let sent = effects.iter().filter_map(sent_to).collect::<Vec<_>>();
+        assert_eq!(sent.len(), 2);
+        assert!(
+            matches!(&sent[0], (to, MessagePayload::Signal { name }) if *to == door && name == "Open")
+        );
+        assert!(matches!(sent[1], (to, MessagePayload::TurnOn { from }) if to == ai && from == ai));
+    }
+
+    #[test]
+    fn test_ai_directives_are_sent_to_self() {
+        let (world, ai, _door) = create_world();
+        let effects = run_sequence(
+            &world,
+            ai,
+            vec![
+                AIScriptedActionType::Alert,
+                AIScriptedActionType::BecomeHostile,
+                AIScriptedActionType::EnableInvestigate,
+            ],
+        );
+
+        let sent = effects.iter().filter_map(sent_to).collect::<Vec<_>>();
+        assert_eq!(sent.len(), 3);
+        assert!(sent.iter().all(|(to, _)| *to == ai));
+        assert!(matches!(sent[0].1, MessagePayload::Alert));
+        assert!(matches!(sent[1].1, MessagePayload::BecomeHostile));
+        assert!(matches!(sent[2].1, MessagePayload::EnableInvestigate));
+    }
+
+    #[test]
+    fn test_links_and_metaproperties() {
+        let (world, ai, door) = create_world();
+        let effects = run_sequence(
+            &world,
+            ai,
+            vec![
+                AIScriptedActionType::AddLink {
+                    link_type: "SwitchLink".to_owned(),
+                    entity_name: "Door".to_owned(),
+                },
+                AIScriptedActionType::RemoveLink {
+                    link_type: "SwitchLink".to_owned(),
+                    entity_name: "Door".to_owned(),
+                },
+                AIScriptedActionType::MetaProperty {
+                    action_type: "Add".to_owned(),
+                    arg1: "M-Cowering".to_owned(),
+                    arg2: "".to_owned(),
+                },
+                AIScriptedActionType::MetaProperty {
+                    action_type: "Remove".to_owned(),
+                    arg1: "M-Cowering".to_owned(),
+                    arg2: "Door".to_owned(),
+                },
+                AIScriptedActionType::Mprint("done".to_owned()),
+            ],
+        );
+
+        assert_eq!(effects.len(), 4);
+        assert!(matches!(
+            &effects[0],
+            Effect::AddLink { from, to, link: Link::SwitchLink } if *from == ai && *to == door
+        ));
+        assert!(matches!(
+            &effects[1],
+            Effect::RemoveLink { from, to, link: Link::SwitchLink } if *from == ai && *to == door
+        ));
+        assert!(matches!(
+            &effects[2],
+            Effect::AddMetaProperty { entity_id, metaprop_name }
+                if *entity_id == ai && metaprop_name == "M-Cowering"
+        ));
+        assert!(matches!(
+            &effects[3],
+            Effect::RemoveMetaProperty { entity_id, metaprop_name }
+                if *entity_id == door && metaprop_name == "M-Cowering"
+        ));
+    }
+
+    #[test]
+    fn test_play_sound_without_motion() {
+        let (mut world, ai, _door) = create_world();
+        world.add_component(
+            ai,
+            PropPosition {
+                position: vec3(1.0, 2.0, 3.0),
+                cell: 0,
+                rotation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+            },
+        );
+
+        let actions = vec![AIScriptedAction {
+            action_type: AIScriptedActionType::Play {
+                sound: "hgrunt".to_owned(),
+                motion: "".to_owned(),
+            },
+        }];
+        let behavior = ScriptedSequenceBehavior::new(&world, ai, actions);
+        assert!(behavior.animation().is_empty());
+
+        let effects = run_sequence(
+            &world,
+            ai,
+            vec![AIScriptedActionType::Play {
+                sound: "hgrunt".to_owned(),
+                motion: "".to_owned(),
+            }],
+        );
+        assert_eq!(effects.len(), 1);
+        assert!(matches!(
+            &effects[0],
+            Effect::PlayPositionalSound { name, position, .. }
+                if name == "hgrunt" && *position == vec3(1.0, 2.0, 3.0)
+        ));
+    }
+
+    #[test]
+    fn test_unknown_targets_have_no_effect() {
+        let (world, ai, _door) = create_world();
+        let effects = run_sequence(
+            &world,
+            ai,
+            vec![
+                AIScriptedActionType::AddLink {
+                    link_type: "NotALink".to_owned(),
+                    entity_name: "Door".to_owned(),
+                },
+                AIScriptedActionType::Signal {
+                    entity_name: "Nobody".to_owned(),
+                    signal: "Open".to_owned(),
+                },
+                AIScriptedActionType::DestScript("TurnOn".to_owned()),
+            ],
+        );
+
+        assert!(effects.is_empty());
+    }
+
+    #[test]
+    fn test_last_action_completes() {
+        let (world, ai, door) = create_world();
+        let effects = run_sequence(
+            &world,
+            ai,
+            vec![AIScriptedActionType::Frob("Door".to_owned())],
+        );
+
+        let sent = effects.iter().filter_map(sent_to).collect::<Vec<_>>();
+        assert_eq!(sent.len(), 1);
+        assert!(matches!(sent[0], (to, MessagePayload::Frob) if to == door));
+    }
+
+    #[test]
+    fn test_empty_sequence() {
+        let (world, ai, _door) = create_world();
+        let effects = run_sequence(&world, ai, vec![]);
+        assert!(effects.is_empty());
+    }
+
+    #[test]
+    fn test_dest_script_goes_to_last_goto() {
+        let (world, _ai, door) = create_world();
+        let actions = vec![
+            AIScriptedActionType::Goto {
+                waypoint_name: "Nowhere".to_owned(),
+                speed: "".to_owned(),
+            },
+            AIScriptedActionType::Goto {
+                waypoint_name: "Door".to_owned(),
+                speed: "".to_owned(),
+            },
+            AIScriptedActionType::Wait(Duration::from_millis(100)),
+        ]
+        .into_iter()
+        .map(|action_type| AIScriptedAction { action_type })
+        .collect::<Vec<_>>();
+
+        assert_eq!(last_goto_destination(&world, &actions), Some(door));
+        assert_eq!(last_goto_destination(&world, &actions[..1]), None);
+    }
+}
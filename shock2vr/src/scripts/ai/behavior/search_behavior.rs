@@ -1,8 +1,35 @@
+use cgmath::{Deg, Vector3};
 use dark::motion::MotionQueryItem;
+use shipyard::{EntityId, World};
+
+use crate::{
+    physics::PhysicsWorld,
+    scripts::{
+        ai::steering::{
+            self, CollisionAvoidanceSteeringStrategy, PathFollowSteeringStrategy, SteeringOutput,
+            SteeringStrategy,
+        },
+        Effect,
+    },
+    time::Time,
+};
 
 use super::Behavior;
 
-pub struct SearchBehavior;
+pub struct SearchBehavior {
+    steering_strategy: Box<dyn SteeringStrategy>,
+}
+
+impl SearchBehavior {
+    pub fn new(last_known_position: Vector3<f32>) -> SearchBehavior {
+        SearchBehavior {
+            steering_strategy: steering::chained(vec![
+                Box::new(CollisionAvoidanceSteeringStrategy::conservative()),
+                Box::new(PathFollowSteeringStrategy::to_position(last_known_position)),
+            ]),
+        }
+    }
+}
 
 impl Behavior for SearchBehavior {
     fn animation(self: &SearchBehavior) -> Vec<MotionQueryItem> {
@@ -11,4 +38,16 @@ impl Behavior for SearchBehavior {
             MotionQueryItem::new("scan").optional(),
         ]
     }
+
+    fn steer(
+        &mut self,
+        current_heading: Deg<f32>,
+        world: &World,
+        physics: &PhysicsWorld,
+        entity_id: EntityId,
+        time: &Time,
+    ) -> Option<(SteeringOutput, Effect)> {
+        self.steering_strategy
+            .steer(current_heading, world, physics, entity_id, time)
+    }
 }
@@ -0,0 +1,220 @@
+///
+/// behavior_selection.rs
+///
+/// Decides how an AI behaves from its gamesys properties - the behavior set (P$AI), creature type,
+/// AI mode, aggression and defensive ability settings, patrol flag, and whether it has scripted
+/// responses - so that monsters act like their SS2 archetypes: hybrids melee and shoot, midwives
+/// keep their distance, protocol droids close in, monkeys run off when hurt.
+///
+use dark::properties::{
+    AIMode, AIRating, Link, PropAI, PropAIAggression, PropAIDefensive, PropAIMode, PropAIPatrol,
+    PropAISignalResponse, PropCreature,
+};
+use num_traits::FromPrimitive;
+use shipyard::{EntityId, Get, View, World};
+
+use crate::{creature::CreatureType, scripts::script_util};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CombatStyle {
+    Melee,
+    // Ranged-only AI back away from the player to keep a clear shot
+    Ranged,
+    RangedMelee,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdleStyle {
+    Stand,
+    Patrol,
+}
+
+// Monkeys break off and run once badly hurt
+const MONKEY_FLEE_HIT_POINT_FRACTION: f32 = 0.3;
+
+// Unaggressive AI only run once nearly dead
+const TIMID_FLEE_HIT_POINT_FRACTION: f32 = 0.15;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AIProfile {
+    pub combat_style: CombatStyle,
+    pub idle_style: IdleStyle,
+    // Below this fraction of its max hit points, the AI flees instead of fighting
+    pub flee_hit_point_fraction: Option<f32>,
+    // Whether the AI goes to look into things it is suspicious of
+    pub investigates: bool,
+    // Whether the AI is already in combat when the mission starts
+    pub starts_alert: bool,
+}
+
+impl AIProfile {
+    pub fn from_properties(
+        behavior_set: &str,
+        creature_type: Option<CreatureType>,
+        mode: Option<AIMode>,
+        aggression: Option<AIRating>,
+        defensive: bool,
+        patrols: bool,
+        has_scripted_responses: bool,
+    ) -> AIProfile {
+        let combat_style = match behavior_set.to_ascii_lowercase().as_str() {
+            "melee" | "protocol" => CombatStyle::Melee,
+            "ranged" | "rangedexplode" => CombatStyle::Ranged,
+            _ => CombatStyle::RangedMelee,
+        };
+
+        // AI with scripted responses wait in place for their cue
+        let idle_style = if patrols && !has_scripted_responses {
+            IdleStyle::Patrol
+        } else {
+            IdleStyle::Stand
+        };
+
+        let flee_hit_point_fraction = match (creature_type, aggression) {
+            (Some(CreatureType::Monkey), _) => Some(MONKEY_FLEE_HIT_POINT_FRACTION),
+            (_, Some(aggression)) if aggression <= AIRating::BelowAverage => {
+                Some(TIMID_FLEE_HIT_POINT_FRACTION)
+            }
+            _ => None,
+        };
+
+        // Sleeping AI don't get up for anything short of a fight, and defensive AI stay at their
+        // post
+        let investigates =
+            !defensive && !matches!(mode, Some(AIMode::Asleep | AIMode::SuperEfficient));
+
+        AIProfile {
+            combat_style,
+            idle_style,
+            flee_hit_point_fraction,
+            investigates,
+            starts_alert: mode == Some(AIMode::Combat),
+        }
+    }
+
+    pub fn for_entity(world: &World, entity_id: EntityId) -> AIProfile {
+        let v_ai = world.borrow::<View<PropAI>>().unwrap();
+        let v_creature = world.borrow::<View<PropCreature>>().unwrap();
+        let v_mode = world.borrow::<View<PropAIMode>>().unwrap();
+        let v_aggression = world.borrow::<View<PropAIAggression>>().unwrap();
+        let v_defensive = world.borrow::<View<PropAIDefensive>>().unwrap();
+        let v_patrol = world.borrow::<View<PropAIPatrol>>().unwrap();
+        let v_prop_sig_resp = world.borrow::<View<PropAISignalResponse>>().unwrap();
+
+        let has_watch_obj =
+            !script_util::get_all_links_with_template(world, entity_id, |link| match link {
+                Link::AIWatchObj(_) => Some(()),
+                _ => None,
+            })
+            .is_empty();
+
+        AIProfile::from_properties(
+            v_ai.get(entity_id).map(|ai| ai.0.as_str()).unwrap_or(""),
+            v_creature
+                .get(entity_id)
+                .ok()
+                .and_then(|creature| CreatureType::from_u32(creature.0)),
+            v_mode.get(entity_id).ok().map(|mode| mode.0),
+            v_aggression
+                .get(entity_id)
+                .ok()
+                .map(|aggression| aggression.0),
+            v_defensive
+                .get(entity_id)
+                .map(|defensive| defensive.0)
+                .unwrap_or(false),
+            v_patrol
+                .get(entity_id)
+                .map(|patrol| patrol.0)
+                .unwrap_or(false),
+            v_prop_sig_resp.get(entity_id).is_ok() || has_watch_obj,
+        )
+    }
+
+    ///
+    /// should_flee
+    ///
+    /// Whether the AI is hurt badly enough to run
+    pub fn should_flee(&self, hit_points: i32, max_hit_points: u32) -> bool {
+        match self.flee_hit_point_fraction {
+            Some(fraction) if max_hit_points > 0 => {
+                (hit_points as f32) < (max_hit_points as f32) * fraction
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combat_style_from_behavior_set() {
+        let combat_style = |behavior_set| {
+            AIProfile::from_properties(behavior_set, None, None, None, false, false, false)
+                .combat_style
+        };
+
+        assert_eq!(combat_style("Melee"), CombatStyle::Melee);
+        assert_eq!(combat_style("Protocol"), CombatStyle::Melee);
+        assert_eq!(combat_style("Ranged"), CombatStyle::Ranged);
+        assert_eq!(combat_style("RangedMelee"), CombatStyle::RangedMelee);
+        assert_eq!(combat_style("ShockDefault"), CombatStyle::RangedMelee);
+    }
+
+    #[test]
+    fn test_patrol_unless_scripted() {
+        let patrolling = AIProfile::from_properties("Melee", None, None, None, false, true, false);
+        assert_eq!(patrolling.idle_style, IdleStyle::Patrol);
+
+        let scripted = AIProfile::from_properties("Melee", None, None, None, false, true, true);
+        assert_eq!(scripted.idle_style, IdleStyle::Stand);
+    }
+
+    #[test]
+    fn test_mode() {
+        let mode =
+            |mode| AIProfile::from_properties("Melee", None, mode, None, false, false, false);
+
+        let asleep = mode(Some(AIMode::Asleep));
+        assert!(!asleep.investigates);
+        assert!(!asleep.starts_alert);
+
+        let combat = mode(Some(AIMode::Combat));
+        assert!(combat.investigates);
+        assert!(combat.starts_alert);
+    }
+
+    #[test]
+    fn test_defensive_ai_hold_their_post() {
+        let defensive = AIProfile::from_properties("Melee", None, None, None, true, false, false);
+        assert!(!defensive.investigates);
+    }
+
+    #[test]
+    fn test_monkeys_flee_when_hurt() {
+        let creature = |creature_type, aggression| {
+            AIProfile::from_properties(
+                "RangedMelee",
+                Some(creature_type),
+                None,
+                aggression,
+                false,
+                false,
+                false,
+            )
+        };
+
+        let monkey = creature(CreatureType::Monkey, None);
+        assert!(!monkey.should_flee(50, 100));
+        assert!(monkey.should_flee(20, 100));
+
+        let hybrid = creature(CreatureType::Human, Some(AIRating::Average));
+        assert!(!hybrid.should_flee(1, 100));
+
+        let timid_hybrid = creature(CreatureType::Human, Some(AIRating::BelowAverage));
+        assert!(!timid_hybrid.should_flee(20, 100));
+        assert!(timid_hybrid.should_flee(10, 100));
+    }
+}
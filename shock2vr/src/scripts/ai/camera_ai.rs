@@ -1,16 +1,31 @@
-use cgmath::{Deg, Quaternion, Rotation3};
-use shipyard::{EntityId, World};
+use cgmath::{vec3, Deg, InnerSpace, Quaternion, Rad, Rotation, Rotation3, Vector3};
+use dark::properties::PropPosition;
+use shipyard::{EntityId, Get, UniqueView, View, World};
 
-use crate::{physics::PhysicsWorld, time::Time};
+use crate::{mission::PlayerInfo, physics::PhysicsWorld, time::Time};
+
+use super::{
+    ai_util::{is_killed, is_player_visible},
+    Effect, Message, MessagePayload, Script,
+};
+
+// How long the player needs to stay in view before the camera raises the alert
+const CAMERA_SPOT_SECONDS: f32 = 1.5;
+
+// Half-angle of the cone the camera sees, around the direction its head is pointing
+const CAMERA_VIEW_HALF_ANGLE: Deg<f32> = Deg(35.0);
 
-use super::{Effect, MessagePayload, Script};
 pub struct CameraAI {
-    // TODO: Implement logic (alert behavior, etc)
+    seen_seconds: f32,
+    is_alert: bool,
 }
 
 impl CameraAI {
     pub fn new() -> CameraAI {
-        CameraAI {}
+        CameraAI {
+            seen_seconds: 0.0,
+            is_alert: false,
+        }
     }
 }
 
@@ -21,16 +36,46 @@ impl Script for CameraAI {
     fn update(
         &mut self,
         entity_id: EntityId,
-        _world: &World,
-        _physics: &PhysicsWorld,
+        world: &World,
+        physics: &PhysicsWorld,
         time: &Time,
     ) -> Effect {
-        let quat = Quaternion::from_angle_x(Deg(time.total.as_secs_f32().sin() * 90.0));
-        Effect::SetJointTransform {
-            entity_id,
-            joint_id: 1,
-            transform: quat.into(),
+        if is_killed(entity_id, world) {
+            return Effect::NoEffect;
         }
+
+        // The head sweeps back and forth - the camera only sees where it is currently pointing
+        let head_rotation = Quaternion::from_angle_x(Deg(time.total.as_secs_f32().sin() * 90.0));
+
+        if is_player_in_view(entity_id, world, head_rotation)
+            && is_player_visible(entity_id, world, physics)
+        {
+            self.seen_seconds += time.elapsed.as_secs_f32();
+        } else {
+            self.seen_seconds = 0.0;
+        }
+
+        let is_alert = self.seen_seconds >= CAMERA_SPOT_SECONDS;
+        let alert_effect = if is_alert != self.is_alert {
+            self.is_alert = is_alert;
+            Effect::Send {
+                msg: Message {
+                    to: entity_id,
+                    payload: MessagePayload::AlertnessChanged { is_alert },
+                },
+            }
+        } else {
+            Effect::NoEffect
+        };
+
+        Effect::combine(vec![
+            alert_effect,
+            Effect::SetJointTransform {
+                entity_id,
+                joint_id: 1,
+                transform: head_rotation.into(),
+            },
+        ])
     }
 
     fn handle_message(
@@ -43,3 +88,35 @@ impl Script for CameraAI {
         Effect::NoEffect
     }
 }
+
+fn is_player_in_view(entity_id: EntityId, world: &World, head_rotation: Quaternion<f32>) -> bool {
+    let v_position = world.borrow::<View<PropPosition>>().unwrap();
+    let player_info = world.borrow::<UniqueView<PlayerInfo>>().unwrap();
+    match v_position.get(entity_id) {
+        Ok(position) => {
+            let forward = (position.rotation * head_rotation).rotate_vector(vec3(0.0, 0.0, 1.0));
+            is_in_view_cone(forward, player_info.pos - position.position)
+        }
+        Err(_) => false,
+    }
+}
+
+fn is_in_view_cone(forward: Vector3<f32>, to_target: Vector3<f32>) -> bool {
+    to_target.magnitude2() > 0.0 && forward.angle(to_target) <= Rad::from(CAMERA_VIEW_HALF_ANGLE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_camera_only_sees_in_front() {
+        let forward = vec3(0.0, 0.0, 1.0);
+        assert!(is_in_view_cone(forward, vec3(0.0, 0.0, 10.0)));
+        assert!(is_in_view_cone(forward, vec3(3.0, 1.0, 10.0)));
+
+        // Off to the side, or behind
+        assert!(!is_in_view_cone(forward, vec3(10.0, 0.0, 1.0)));
+        assert!(!is_in_view_cone(forward, vec3(0.0, 0.0, -10.0)));
+    }
+}
@@ -1,4 +1,7 @@
 pub mod ai_util;
+pub mod awareness;
+pub mod behavior_selection;
+pub mod navigation;
 pub mod steering;
 
 mod animated_monster_ai;
@@ -0,0 +1,273 @@
+///
+/// navigation.rs
+///
+/// Navigation graph for the AI, and A* queries on it. The graph comes from the mission's AI path
+/// database when there is one - otherwise, it falls back to the rooms, linked by their portals.
+///
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+use cgmath::{InnerSpace, Vector3};
+use dark::{
+    mission::{path_database::PathDatabase, room_database::RoomDatabase},
+    SCALE_FACTOR,
+};
+use shipyard::Unique;
+
+#[derive(Clone, Debug)]
+pub struct NavigationEdge {
+    pub to_node: usize,
+    // Point the AI moves through to get to the next node - ie, the middle of a doorway
+    pub crossing: Vector3<f32>,
+    pub cost: f32,
+}
+
+#[derive(Unique, Clone, Debug, Default)]
+pub struct NavigationGraph {
+    nodes: Vec<Vector3<f32>>,
+    edges: Vec<Vec<NavigationEdge>>,
+}
+
+// Entry in the A* open set - ordered so the BinaryHeap pops the lowest estimated cost first
+struct OpenNode {
+    node: usize,
+    estimated_cost: f32,
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimated_cost == other.estimated_cost
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimated_cost.total_cmp(&self.estimated_cost)
+    }
+}
+
+impl NavigationGraph {
+    pub fn from_path_database(path_database: &PathDatabase) -> NavigationGraph {
+        let nodes = path_database.cells.iter().map(|cell| cell.center).collect();
+        let edges = path_database
+            .cells
+            .iter()
+            .map(|cell| {
+                cell.links
+                    .iter()
+                    .map(|link| NavigationEdge {
+                        to_node: link.to_cell,
+                        crossing: (link.edge.0 + link.edge.1) / 2.0,
+                        cost: link.cost,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        NavigationGraph { nodes, edges }
+    }
+
+    pub fn from_room_database(room_database: &RoomDatabase) -> NavigationGraph {
+        let room_id_to_node = room_database
+            .rooms
+            .iter()
+            .enumerate()
+            .map(|(idx, room)| (room.room_id as i32, idx))
+            .collect::<HashMap<i32, usize>>();
+
+        let nodes = room_database
+            .rooms
+            .iter()
+            .map(|room| room.center)
+            .collect::<Vec<Vector3<f32>>>();
+        let edges = room_database
+            .rooms
+            .iter()
+            .map(|room| {
+                room.portals
+                    .iter()
+                    .filter_map(|portal| {
+                        let to_node = *room_id_to_node.get(&portal.dest_room)?;
+                        let crossing = portal.center / SCALE_FACTOR;
+                        Some(NavigationEdge {
+                            to_node,
+                            crossing,
+                            cost: (crossing - room.center).magnitude()
+                                + (nodes[to_node] - crossing).magnitude(),
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        NavigationGraph { nodes, edges }
+    }
+
+    ///
+    /// from_level
+    ///
+    /// Prefer the AI path database, since it describes where the AI can actually walk
+    pub fn from_level(
+        path_database: &PathDatabase,
+        room_database: &RoomDatabase,
+    ) -> NavigationGraph {
+        if path_database.is_empty() {
+            NavigationGraph::from_room_database(room_database)
+        } else {
+            NavigationGraph::from_path_database(path_database)
+        }
+    }
+
+    ///
+    /// find_node
+    ///
+    /// The node closest to the position
+    pub fn find_node(&self, position: Vector3<f32>) -> Option<usize> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| (idx, (node - position).magnitude2()))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(idx, _)| idx)
+    }
+
+    ///
+    /// find_path
+    ///
+    /// Use A* to find the way from start to goal. Returns the points to move through, ending at the
+    /// goal, or None if there is no way to get there.
+    pub fn find_path(&self, start: Vector3<f32>, goal: Vector3<f32>) -> Option<Vec<Vector3<f32>>> {
+        let start_node = self.find_node(start)?;
+        let goal_node = self.find_node(goal)?;
+
+        let heuristic = |node: usize| (self.nodes[goal_node] - self.nodes[node]).magnitude();
+
+        let mut open = BinaryHeap::new();
+        let mut came_from: HashMap<usize, (usize, Vector3<f32>)> = HashMap::new();
+        let mut cost_so_far: HashMap<usize, f32> = HashMap::new();
+
+        open.push(OpenNode {
+            node: start_node,
+            estimated_cost: heuristic(start_node),
+        });
+        cost_so_far.insert(start_node, 0.0);
+
+        while let Some(OpenNode { node, .. }) = open.pop() {
+            if node == goal_node {
+                let mut path = vec![goal];
+                let mut current = goal_node;
+                while let Some((previous, crossing)) = came_from.get(&current) {
+                    path.push(*crossing);
+                    current = *previous;
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            let current_cost = cost_so_far[&node];
+            for edge in &self.edges[node] {
+                let new_cost = current_cost + edge.cost;
+                let is_better = cost_so_far
+                    .get(&edge.to_node)
+                    .map(|cost| new_cost < *cost)
+                    .unwrap_or(true);
+                if is_better {
+                    cost_so_far.insert(edge.to_node, new_cost);
+                    came_from.insert(edge.to_node, (node, edge.crossing));
+                    open.push(OpenNode {
+                        node: edge.to_node,
+                        estimated_cost: new_cost + heuristic(edge.to_node),
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cgmath::vec3;
+
+    fn edge(to_node: usize, crossing: Vector3<f32>, cost: f32) -> NavigationEdge {
+        NavigationEdge {
+            to_node,
+            crossing,
+            cost,
+        }
+    }
+
+    // A square of four rooms - 0 and 3 are opposite corners, and the way through 1 is expensive
+    fn square_graph() -> NavigationGraph {
+        NavigationGraph {
+            nodes: vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(10.0, 0.0, 0.0),
+                vec3(0.0, 0.0, 10.0),
+                vec3(10.0, 0.0, 10.0),
+            ],
+            edges: vec![
+                vec![
+                    edge(1, vec3(5.0, 0.0, 0.0), 50.0),
+                    edge(2, vec3(0.0, 0.0, 5.0), 10.0),
+                ],
+                vec![
+                    edge(0, vec3(5.0, 0.0, 0.0), 50.0),
+                    edge(3, vec3(10.0, 0.0, 5.0), 10.0),
+                ],
+                vec![
+                    edge(0, vec3(0.0, 0.0, 5.0), 10.0),
+                    edge(3, vec3(5.0, 0.0, 10.0), 10.0),
+                ],
+                vec![
+                    edge(1, vec3(10.0, 0.0, 5.0), 10.0),
+                    edge(2, vec3(5.0, 0.0, 10.0), 10.0),
+                ],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_find_path_takes_cheapest_route() {
+        let graph = square_graph();
+        let goal = vec3(9.0, 0.0, 11.0);
+        let path = graph.find_path(vec3(1.0, 0.0, 0.0), goal).unwrap();
+
+        assert_eq!(path, vec![vec3(0.0, 0.0, 5.0), vec3(5.0, 0.0, 10.0), goal]);
+    }
+
+    #[test]
+    fn test_find_path_within_node() {
+        let graph = square_graph();
+        let goal = vec3(1.0, 0.0, 1.0);
+        assert_eq!(graph.find_path(vec3(0.0, 0.0, 0.0), goal), Some(vec![goal]));
+    }
+
+    #[test]
+    fn test_find_path_unreachable() {
+        let mut graph = square_graph();
+        graph.nodes.push(vec3(100.0, 0.0, 100.0));
+        graph.edges.push(vec![]);
+
+        assert_eq!(
+            graph.find_path(vec3(0.0, 0.0, 0.0), vec3(100.0, 0.0, 100.0)),
+            None
+        );
+        assert_eq!(
+            NavigationGraph::default().find_path(vec3(0.0, 0.0, 0.0), vec3(1.0, 0.0, 1.0)),
+            None
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use std::time::Duration;
+
+use cgmath::{vec4, Deg, InnerSpace, Vector3};
+use dark::{properties::PropPosition, SCALE_FACTOR};
+
+use shipyard::{EntityId, Get, UniqueView, View, World};
+
+use crate::{
+    mission::PlayerInfo,
+    physics::PhysicsWorld,
+    scripts::{ai::navigation::NavigationGraph, Effect},
+    time::Time,
+    util::vec3_to_point3,
+};
+
+use super::{Steering, SteeringOutput, SteeringStrategy};
+
+// How often to recompute the path, since the target may be moving
+const REPLAN_INTERVAL: Duration = Duration::from_millis(1000);
+
+// Horizontal distance at which a waypoint counts as reached
+const WAYPOINT_REACHED_DISTANCE: f32 = 2.0 / SCALE_FACTOR;
+
+enum PathTarget {
+    Player,
+    Entity(EntityId),
+    Position(Vector3<f32>),
+}
+
+///
+/// PathFollowSteeringStrategy
+///
+/// Steers along a path through the navigation graph. When there is no graph, no path, or the target
+/// is in the same area, it has no opinion - so it should be chained before a strategy that heads
+/// straight for the target.
+pub struct PathFollowSteeringStrategy {
+    target: PathTarget,
+    waypoints: Vec<Vector3<f32>>,
+    time_since_plan: Option<Duration>,
+}
+
+impl PathFollowSteeringStrategy {
+    fn new(target: PathTarget) -> PathFollowSteeringStrategy {
+        PathFollowSteeringStrategy {
+            target,
+            waypoints: vec![],
+            time_since_plan: None,
+        }
+    }
+
+    pub fn to_player() -> PathFollowSteeringStrategy {
+        PathFollowSteeringStrategy::new(PathTarget::Player)
+    }
+
+    pub fn to_entity(entity_id: EntityId) -> PathFollowSteeringStrategy {
+        PathFollowSteeringStrategy::new(PathTarget::Entity(entity_id))
+    }
+
+    pub fn to_position(position: Vector3<f32>) -> PathFollowSteeringStrategy {
+        PathFollowSteeringStrategy::new(PathTarget::Position(position))
+    }
+
+    fn target_position(&self, world: &World) -> Option<Vector3<f32>> {
+        match self.target {
+            PathTarget::Player => {
+                let u_player = world.borrow::<UniqueView<PlayerInfo>>().unwrap();
+                Some(u_player.pos)
+            }
+            PathTarget::Entity(target_id) => {
+                let v_current_pos = world.borrow::<View<PropPosition>>().unwrap();
+                v_current_pos.get(target_id).ok().map(|p| p.position)
+            }
+            PathTarget::Position(position) => Some(position),
+        }
+    }
+}
+
+fn horizontal_distance(a: Vector3<f32>, b: Vector3<f32>) -> f32 {
+    let diff = a - b;
+    Vector3::new(diff.x, 0.0, diff.z).magnitude()
+}
+
+impl SteeringStrategy for PathFollowSteeringStrategy {
+    fn steer(
+        &mut self,
+        _current_heading: Deg<f32>,
+        world: &World,
+        _physics: &PhysicsWorld,
+        entity_id: EntityId,
+        time: &Time,
+    ) -> Option<(SteeringOutput, Effect)> {
+        let position = {
+            let v_current_pos = world.borrow::<View<PropPosition>>().unwrap();
+            v_current_pos.get(entity_id).ok()?.position
+        };
+
+        let should_replan = self
+            .time_since_plan
+            .map(|elapsed| elapsed + time.elapsed >= REPLAN_INTERVAL)
+            .unwrap_or(true);
+
+        if should_replan {
+            let target = self.target_position(world)?;
+            let graph = world.borrow::<UniqueView<NavigationGraph>>().ok()?;
+            self.waypoints = graph.find_path(position, target).unwrap_or_default();
+            self.time_since_plan = Some(Duration::ZERO);
+        } else {
+            self.time_since_plan = self.time_since_plan.map(|elapsed| elapsed + time.elapsed);
+        }
+
+        while self.waypoints.len() > 1
+            && horizontal_distance(self.waypoints[0], position) < WAYPOINT_REACHED_DISTANCE
+        {
+            self.waypoints.remove(0);
+        }
+
+        // Only the target itself is left - nothing in the way, so let the next strategy head there
+        if self.waypoints.len() <= 1 {
+            return None;
+        }
+
+        let mut lines = vec![];
+        let mut from = vec3_to_point3(position);
+        for waypoint in &self.waypoints {
+            let to = vec3_to_point3(*waypoint);
+            lines.push((from, to, vec4(0.0, 0.5, 1.0, 1.0)));
+            from = to;
+        }
+
+        Some((
+            Steering::turn_to_point(vec3_to_point3(position), vec3_to_point3(self.waypoints[0])),
+            Effect::DrawDebugLines { lines },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cgmath::{vec3, Quaternion};
+    use dark::mission::path_database::{PathCell, PathDatabase, PathLink};
+
+    // Two cells, where the way from the first to the second is through a doorway off to the side
+    fn two_cell_graph() -> NavigationGraph {
+        let cell = |center: Vector3<f32>, to_cell: usize| PathCell {
+            center,
+            vertices: vec![],
+            flags: 0,
+            links: vec![PathLink {
+                to_cell,
+                edge: (vec3(-1.0, 0.0, 5.0), vec3(1.0, 0.0, 5.0)),
+                ok_bits: 1,
+                cost: 10.0,
+            }],
+        };
+        NavigationGraph::from_path_database(&PathDatabase {
+            cells: vec![cell(vec3(0.0, 0.0, 0.0), 1), cell(vec3(10.0, 0.0, 10.0), 0)],
+        })
+    }
+
+    fn create_world(ai_position: Vector3<f32>) -> (World, EntityId) {
+        let mut world = World::new();
+        world.add_unique(two_cell_graph());
+        let ai = world.add_entity((PropPosition {
+            position: ai_position,
+            cell: 0,
+            rotation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+        },));
+        (world, ai)
+    }
+
+    fn steer(
+        strategy: &mut PathFollowSteeringStrategy,
+        world: &World,
+        entity_id: EntityId,
+    ) -> Option<Deg<f32>> {
+        let time = Time {
+            elapsed: Duration::from_millis(16),
+            total: Duration::ZERO,
+        };
+        strategy
+            .steer(Deg(0.0), world, &PhysicsWorld::new(), entity_id, &time)
+            .map(|(output, _)| output.desired_heading)
+    }
+
+    #[test]
+    fn test_heads_for_next_waypoint() {
+        let (world, ai) = create_world(vec3(0.0, 0.0, 0.0));
+        let mut strategy = PathFollowSteeringStrategy::to_position(vec3(10.0, 0.0, 10.0));
+
+        let expected = Steering::turn_to_point(
+            vec3_to_point3(vec3(0.0, 0.0, 0.0)),
+            vec3_to_point3(vec3(0.0, 0.0, 5.0)),
+        );
+        assert_eq!(
+            steer(&mut strategy, &world, ai),
+            Some(expected.desired_heading)
+        );
+    }
+
+    #[test]
+    fn test_defers_once_only_target_is_left() {
+        // Target in the same cell - nothing to path around
+        let (world, ai) = create_world(vec3(0.0, 0.0, 0.0));
+        let mut strategy = PathFollowSteeringStrategy::to_position(vec3(1.0, 0.0, 1.0));
+        assert_eq!(steer(&mut strategy, &world, ai), None);
+
+        // Standing in the doorway - the waypoint is reached, so head straight for the target
+        let (world, ai) = create_world(vec3(0.0, 0.0, 5.0));
+        let mut strategy = PathFollowSteeringStrategy::to_position(vec3(10.0, 0.0, 10.0));
+        assert_eq!(steer(&mut strategy, &world, ai), None);
+    }
+
+    #[test]
+    fn test_no_graph_has_no_opinion() {
+        let mut world = World::new();
+        let ai = world.add_entity((PropPosition {
+            position: vec3(0.0, 0.0, 0.0),
+            cell: 0,
+            rotation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+        },));
+        let mut strategy = PathFollowSteeringStrategy::to_position(vec3(10.0, 0.0, 10.0));
+        assert_eq!(steer(&mut strategy, &world, ai), None);
+    }
+}
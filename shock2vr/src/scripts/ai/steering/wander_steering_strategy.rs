@@ -3,9 +3,10 @@ use cgmath::{
 };
 
 
-use shipyard::{EntityId, World};
+use shipyard::{EntityId, UniqueViewMut, World};
 
 use crate::{
+    game_rng::GameRng,
     physics::{PhysicsWorld},
     scripts::{ai::ai_util::random_binomial, Effect},
     time::Time,
@@ -27,15 +28,15 @@ impl WanderSteeringStrategy {
     pub fn steer(
         &mut self,
         current_heading: Deg<f32>,
-        _world: &World,
+        world: &World,
         _physics: &PhysicsWorld,
         _entity_id: EntityId,
         time: &Time,
     ) -> Option<(SteeringOutput, Effect)> {
         if let Some(current_heading) = self.maybe_current_heading {
-            self.maybe_current_heading = Some(Deg(
-                current_heading.0 + 100.0 * random_binomial() * time.elapsed.as_secs_f32()
-            ))
+            let mut rng = world.borrow::<UniqueViewMut<GameRng>>().unwrap();
+            let delta = 100.0 * random_binomial(&mut *rng) * time.elapsed.as_secs_f32();
+            self.maybe_current_heading = Some(Deg(current_heading.0 + delta))
         } else {
             self.maybe_current_heading = Some(current_heading);
         };
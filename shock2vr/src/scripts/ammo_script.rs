@@ -0,0 +1,70 @@
+use cgmath::{vec3, InnerSpace};
+use dark::SCALE_FACTOR;
+use shipyard::{EntityId, UniqueView, World};
+
+use crate::{
+    mission::PlayerInfo, physics::PhysicsWorld, time::Time, util::get_position_from_transform,
+};
+
+use super::{weapon_kind::WeaponKind, Effect, Message, MessagePayload, Script};
+
+// How close the clip needs to be brought to the weapon held in the other hand to load it
+const RELOAD_DISTANCE: f32 = 1.0 / SCALE_FACTOR;
+
+// Ammo clips - reloading in VR is done by holding the clip in one hand, and bringing it
+// to the weapon held in the other hand
+pub struct AmmoScript {
+    is_loading: bool,
+}
+
+impl AmmoScript {
+    pub fn new() -> AmmoScript {
+        AmmoScript { is_loading: false }
+    }
+}
+
+impl Script for AmmoScript {
+    fn update(
+        &mut self,
+        entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        _time: &Time,
+    ) -> Effect {
+        let player_info = world.borrow::<UniqueView<PlayerInfo>>().unwrap();
+        let maybe_weapon = if player_info.left_hand_entity_id == Some(entity_id) {
+            player_info.right_hand_entity_id
+        } else if player_info.right_hand_entity_id == Some(entity_id) {
+            player_info.left_hand_entity_id
+        } else {
+            None
+        };
+
+        let weapon_id = match maybe_weapon {
+            Some(weapon_id) if WeaponKind::from_entity(world, weapon_id).is_some() => weapon_id,
+            _ => {
+                self.is_loading = false;
+                return Effect::NoEffect;
+            }
+        };
+
+        let clip_position = get_position_from_transform(world, entity_id, vec3(0.0, 0.0, 0.0));
+        let weapon_position = get_position_from_transform(world, weapon_id, vec3(0.0, 0.0, 0.0));
+        let is_in_range = (clip_position - weapon_position).magnitude() < RELOAD_DISTANCE;
+
+        // Only try to load once per gesture - the clip has to leave the weapon before trying again
+        let should_load = is_in_range && !self.is_loading;
+        self.is_loading = is_in_range;
+
+        if should_load {
+            Effect::Send {
+                msg: Message {
+                    to: weapon_id,
+                    payload: MessagePayload::Reload { ammo: entity_id },
+                },
+            }
+        } else {
+            Effect::NoEffect
+        }
+    }
+}
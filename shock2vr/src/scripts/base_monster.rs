@@ -1,11 +1,11 @@
-use dark::properties::{Link, PropAI, PropAISignalResponse};
+use dark::properties::PropAI;
 use shipyard::{EntityId, Get, View, World};
 
 use crate::{physics::PhysicsWorld, time::Time};
 
 use super::{
-    ai::{AnimatedMonsterAI, CameraAI, TurretAI},
-    script_util, Effect, MessagePayload, NoopScript, Script,
+    ai::{behavior_selection::AIProfile, AnimatedMonsterAI, CameraAI, TurretAI},
+    Effect, MessagePayload, NoopScript, Script,
 };
 
 pub struct BaseMonster {
@@ -21,16 +21,6 @@ impl BaseMonster {
 impl Script for BaseMonster {
     fn initialize(&mut self, entity_id: EntityId, world: &World) -> Effect {
         let v_ai = world.borrow::<View<PropAI>>().unwrap();
-        let v_prop_sig_resp = world.borrow::<View<PropAISignalResponse>>().unwrap();
-
-        let maybe_ai_signal_resp = script_util::get_first_link_with_template_and_data(
-            world,
-            entity_id,
-            |link| match link {
-                Link::AIWatchObj(data) => Some(data.clone()),
-                _ => None,
-            },
-        );
 
         let maybe_prop_ai = v_ai.get(entity_id);
 
@@ -40,26 +30,19 @@ impl Script for BaseMonster {
 
         let prop_ai = maybe_prop_ai.unwrap();
 
-        let ai: Box<dyn Script> =
-            if v_prop_sig_resp.get(entity_id).is_ok() || maybe_ai_signal_resp.is_some() {
-                Box::new(AnimatedMonsterAI::idle())
-            } else {
-                match prop_ai.0.to_ascii_lowercase().as_str() {
-                    "camera" => Box::new(CameraAI::new()),
-                    "melee" => Box::new(AnimatedMonsterAI::new()),
-                    "ranged" => Box::new(AnimatedMonsterAI::new()),
-                    "rangedmelee" => Box::new(AnimatedMonsterAI::new()),
-                    "rangedexplode" => Box::new(AnimatedMonsterAI::new()),
-                    "protocol" => Box::new(AnimatedMonsterAI::new()),
-                    "shockdefault" => Box::new(AnimatedMonsterAI::new()),
-                    "turret" => Box::new(TurretAI::new()),
-                    //TODO:
-                    "grub" => Box::new(NoopScript {}),
-                    "swarmer" => Box::new(NoopScript {}),
+        // Cameras and turrets have their own AI - everything else is an animated monster, with
+        // behavior picked from its AI properties
+        let ai: Box<dyn Script> = match prop_ai.0.to_ascii_lowercase().as_str() {
+            "camera" => Box::new(CameraAI::new()),
+            "turret" => Box::new(TurretAI::new()),
+            //TODO:
+            "grub" => Box::new(NoopScript {}),
+            "swarmer" => Box::new(NoopScript {}),
 
-                    _ => Box::new(AnimatedMonsterAI::idle()),
-                }
-            };
+            _ => Box::new(AnimatedMonsterAI::new(AIProfile::for_entity(
+                world, entity_id,
+            ))),
+        };
 
         self.ai = ai;
 
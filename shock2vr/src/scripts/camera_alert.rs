@@ -0,0 +1,38 @@
+use shipyard::{EntityId, World};
+
+use crate::physics::PhysicsWorld;
+
+use super::{script_util::send_to_all_switch_links, Effect, MessagePayload, Script};
+
+///
+/// CameraAlert
+///
+/// When the camera spots the player, sets off the security alarm and turns on anything linked to
+/// the camera (alarm lights, turrets, etc).
+pub struct CameraAlert {}
+impl CameraAlert {
+    pub fn new() -> CameraAlert {
+        CameraAlert {}
+    }
+}
+impl Script for CameraAlert {
+    fn handle_message(
+        &mut self,
+        entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        match msg {
+            MessagePayload::AlertnessChanged { is_alert: true } => Effect::combine(vec![
+                Effect::TriggerAlarm { entity_id },
+                send_to_all_switch_links(
+                    world,
+                    entity_id,
+                    MessagePayload::TurnOn { from: entity_id },
+                ),
+            ]),
+            _ => Effect::NoEffect,
+        }
+    }
+}
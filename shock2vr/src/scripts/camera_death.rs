@@ -0,0 +1,54 @@
+use shipyard::{EntityId, World};
+
+use crate::{physics::PhysicsWorld, time::Time};
+
+use super::{ai::ai_util::is_killed, Effect, MessagePayload, Script};
+
+///
+/// CameraDeath
+///
+/// Destroying a camera resets the security alarm it set off - alarms raised by other cameras, or
+/// by a failed hack, keep going.
+pub struct CameraDeath {
+    is_dead: bool,
+}
+impl CameraDeath {
+    pub fn new() -> CameraDeath {
+        CameraDeath { is_dead: false }
+    }
+}
+impl Script for CameraDeath {
+    fn update(
+        &mut self,
+        entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        _time: &Time,
+    ) -> Effect {
+        if self.is_dead || !is_killed(entity_id, world) {
+            return Effect::NoEffect;
+        }
+
+        self.is_dead = true;
+        Effect::combine(vec![
+            Effect::DisableAlarmRaisedBy { entity_id },
+            Effect::SlayEntity { entity_id },
+        ])
+    }
+
+    fn handle_message(
+        &mut self,
+        entity_id: EntityId,
+        _world: &World,
+        _physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        match msg {
+            MessagePayload::Slay if !self.is_dead => {
+                self.is_dead = true;
+                Effect::DisableAlarmRaisedBy { entity_id }
+            }
+            _ => Effect::NoEffect,
+        }
+    }
+}
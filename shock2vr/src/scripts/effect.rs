@@ -1,7 +1,7 @@
 use cgmath::{Matrix4, Point3, Quaternion, Vector2, Vector3, Vector4};
 use dark::{
     motion::{MotionQueryItem, MotionQuerySelectionStrategy},
-    properties::{KeyCard, QuestBitValue},
+    properties::{KeyCard, Link, PropGunState, QuestBitValue},
     EnvSoundQuery,
 };
 use engine::audio::AudioHandle;
@@ -10,6 +10,9 @@ use shipyard::{Component, EntityId};
 use crate::{
     gui::{GuiComponentRenderInfo, GuiHandle},
     mission::entity_creator::CreateEntityOptions,
+    player_stats::{Stat, WeaponSkill},
+    psi_powers::PsiPower,
+    save_load::SaveSlotKind,
     vr_config::Handedness,
 };
 
@@ -17,14 +20,14 @@ use super::Message;
 
 #[derive(Clone, Debug)]
 pub enum GlobalEffect {
-    // Save the game state to the given file_name
+    // Save the game state to a new save slot (or over the quicksave)
     Save {
-        file_name: String,
+        kind: SaveSlotKind,
     },
 
-    // Load the game state from the given file_naem
+    // Load the game state from the save slot with the given id
     Load {
-        file_name: String,
+        slot_id: String,
     },
 
     TransitionLevel {
@@ -49,11 +52,123 @@ pub enum Effect {
         delta: i32,
     },
 
+    AdjustPsiPoints {
+        delta: i32,
+    },
+
+    // Permanently raise one of the player's stats
+    IncreasePlayerStat {
+        stat: Stat,
+    },
+
+    // Activate or deactivate an implant that boosts one of the player's stats
+    TogglePlayerImplant {
+        name: String,
+        stat: Stat,
+    },
+
+    // Mark the object as hacked, so the hack stays in effect
+    SetHacked {
+        entity_id: EntityId,
+    },
+
+    Unlock {
+        entity_id: EntityId,
+    },
+
+    // Set off the security alarm
+    TriggerAlarm {
+        entity_id: EntityId,
+    },
+
+    DisableAlarm,
+
+    // Turn off the security alarm, if this entity is what set it off
+    DisableAlarmRaisedBy {
+        entity_id: EntityId,
+    },
+
+    // Spend nanites, and apply the effect only if the player could pay - the balance is checked
+    // and spent together, so two purchases in one frame can't both be paid for with the same nanites
+    Purchase {
+        price: u32,
+        effect: Box<Effect>,
+    },
+
+    // Spend cyber modules to raise a weapon skill a level
+    TrainWeaponSkill {
+        skill: WeaponSkill,
+    },
+
+    BeginResearch {
+        name: String,
+        duration_seconds: f32,
+    },
+
+    AdvanceResearch {
+        elapsed_seconds: f32,
+    },
+
+    // Spend psi points on a power, starting its cooldown and any buffs
+    UsePsiPower {
+        power: PsiPower,
+    },
+
+    // Spend cyber modules to learn a psi power
+    LearnPsiPower {
+        power: PsiPower,
+    },
+
+    // Switch the psi amp to the next power the player has learned
+    SelectNextPsiPower,
+
+    // Pull whatever the psi amp is pointed at into the player's free hand
+    Telekinesis {
+        entity_id: EntityId,
+    },
+
+    // Update the ammo, condition, or modification level of a weapon
+    SetGunState {
+        entity_id: EntityId,
+        gun_state: PropGunState,
+    },
+
+    // Make this Quantum Bio-Reconstruction machine the place the player comes back to on death
+    ActivateResurrectionMachine {
+        entity_id: EntityId,
+    },
+
     ApplyForce {
         entity_id: EntityId,
         force: Vector3<f32>,
     },
 
+    // Link one entity to another at runtime, ie, from an AI scripted action
+    AddLink {
+        from: EntityId,
+        to: EntityId,
+        link: Link,
+    },
+
+    // Remove any links of the same kind between the entities
+    RemoveLink {
+        from: EntityId,
+        to: EntityId,
+        link: Link,
+    },
+
+    // Apply the properties of a metaproperty to the entity
+    AddMetaProperty {
+        entity_id: EntityId,
+        metaprop_name: String,
+    },
+
+    // Take away the properties of a metaproperty, falling back to the entity's own archetypes
+    RemoveMetaProperty {
+        entity_id: EntityId,
+        metaprop_name: String,
+    },
+
     ChangeModel {
         entity_id: EntityId,
         model_name: String,
@@ -116,10 +231,20 @@ pub enum Effect {
         handle: AudioHandle,
         name: String,
     },
+    // A sound schema played from a point in the level, rather than by the player
+    PlayPositionalSound {
+        handle: AudioHandle,
+        name: String,
+        position: Vector3<f32>,
+        // The entity making the sound, so AI can tell who they heard
+        source: EntityId,
+    },
     PlayEnvironmentalSound {
         audio_handle: AudioHandle,
         query: EnvSoundQuery,
         position: Vector3<f32>,
+        // The entity making the sound, so AI can tell who they heard
+        source: EntityId,
     },
     PositionInventory {
         position: Vector3<f32>,
@@ -70,6 +70,7 @@ fn do_recharge(world: &World, entity_id: EntityId, with: &EntityId) -> Effect {
         audio_handle: AudioHandle::new(),
         query: EnvSoundQuery::from_tag_values(query),
         position: pos.position,
+        source: entity_id,
     };
 
     Effect::combine(vec![recharge_effect, sound_effect])
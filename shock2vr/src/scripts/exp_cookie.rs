@@ -0,0 +1,40 @@
+use dark::properties::PropExp;
+use shipyard::{EntityId, Get, View, World};
+
+use crate::physics::PhysicsWorld;
+
+use super::{Effect, MessagePayload, Script};
+
+// Cyber modules - awarded to the player as soon as they are picked up
+pub struct ExpCookie {}
+impl ExpCookie {
+    pub fn new() -> ExpCookie {
+        ExpCookie {}
+    }
+}
+
+impl Script for ExpCookie {
+    fn handle_message(
+        &mut self,
+        entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        match msg {
+            MessagePayload::Frob | MessagePayload::Hold => {
+                let v_amount = world.borrow::<View<PropExp>>().unwrap();
+                let award_amount = v_amount.get(entity_id).map(|p| p.0).unwrap_or(0);
+                Effect::Combined {
+                    effects: vec![
+                        Effect::AwardXP {
+                            amount: award_amount,
+                        },
+                        Effect::DestroyEntity { entity_id },
+                    ],
+                }
+            }
+            _ => Effect::NoEffect,
+        }
+    }
+}
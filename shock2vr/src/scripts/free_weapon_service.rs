@@ -0,0 +1,65 @@
+use shipyard::{EntityId, World};
+
+use crate::physics::PhysicsWorld;
+
+use super::{weapon_kind::WeaponKind, Effect, Message, MessagePayload, Script};
+
+// One-time tools (freemodify, freerepair) that modify or repair a weapon at no cost. They're used
+// deliberately - by pointing a held weapon at the tool and pulling the trigger - so that just
+// brushing a weapon against one doesn't use it up.
+pub struct FreeWeaponService {
+    payload: MessagePayload,
+    is_used: bool,
+}
+
+impl FreeWeaponService {
+    pub fn modify() -> FreeWeaponService {
+        FreeWeaponService {
+            payload: MessagePayload::Modify,
+            is_used: false,
+        }
+    }
+
+    pub fn repair() -> FreeWeaponService {
+        FreeWeaponService {
+            payload: MessagePayload::Repair,
+            is_used: false,
+        }
+    }
+
+    fn service(&mut self, world: &World, entity_id: EntityId, weapon_id: EntityId) -> Effect {
+        if self.is_used || WeaponKind::from_entity(world, weapon_id).is_none() {
+            return Effect::NoEffect;
+        }
+        self.is_used = true;
+
+        Effect::combine(vec![
+            Effect::Send {
+                msg: Message {
+                    to: weapon_id,
+                    payload: self.payload.clone(),
+                },
+            },
+            Effect::DestroyEntity { entity_id },
+        ])
+    }
+}
+
+impl Script for FreeWeaponService {
+    fn handle_message(
+        &mut self,
+        entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        match msg {
+            MessagePayload::Hover {
+                held_entity_id: Some(weapon_id),
+                is_triggered: true,
+                ..
+            } => self.service(world, entity_id, *weapon_id),
+            _ => Effect::NoEffect,
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use cgmath::{vec2, Vector2, Vector3};
-use dark::properties::{Link, PropInventoryDimensions, PropObjIcon};
+use dark::properties::{Link, PropInventoryDimensions, PropLocked, PropObjIcon};
 
 use shipyard::{EntityId, Get, View, World};
 
@@ -13,6 +13,10 @@ use crate::gui;
 
 use crate::scripts::{Effect, MessagePayload};
 
+use super::hack::{
+    draw_hack, pay_for_hack_retry, start_hack, try_hack_node, use_ice_pick, HackGame, HackTarget,
+};
+
 pub struct ContainerGui {
     background_image: String,
     width: f32,
@@ -21,6 +25,8 @@ pub struct ContainerGui {
     inv_offset_y: f32,
     num_slots_x: usize,
     num_slots_y: usize,
+    // Set for containers that can be hacked open - they stay shut while they're locked
+    hack_target: Option<HackTarget>,
 }
 
 impl ContainerGui {
@@ -33,6 +39,14 @@ impl ContainerGui {
             inv_offset_y: 160.0,
             num_slots_x: 4,
             num_slots_y: 4,
+            hack_target: None,
+        }
+    }
+
+    pub fn hackable_crate() -> ContainerGui {
+        ContainerGui {
+            hack_target: Some(HackTarget::Crate),
+            ..ContainerGui::loot_container()
         }
     }
 
@@ -45,18 +59,34 @@ impl ContainerGui {
             inv_offset_y: 18.0,
             num_slots_x: 15,
             num_slots_y: 3,
+            hack_target: None,
         }
     }
+
+    // The hack to show instead of the contents, if the container is hackable and still locked
+    fn locked_hack_target(&self, world: &World, entity_id: EntityId) -> Option<HackTarget> {
+        let v_locked = world.borrow::<View<PropLocked>>().unwrap();
+        let is_locked = v_locked
+            .get(entity_id)
+            .map(|locked| locked.0)
+            .unwrap_or(false);
+        self.hack_target.filter(|_| is_locked)
+    }
 }
 
 #[derive(Clone, Debug, Default)]
-pub struct ContainerGuiState {}
+pub struct ContainerGuiState {
+    // Set while a locked container is being hacked
+    hack: Option<HackGame>,
+}
 
 #[derive(Clone)]
 pub enum ContainerGuiMsg {
     GrabbedWithLeftHand(EntityId),
     GrabbedWithRightHand(EntityId),
     Frob(EntityId),
+    TryHackNode(usize, usize),
+    RetryHack,
 }
 
 impl Gui<ContainerGuiState, ContainerGuiMsg> for ContainerGui {
@@ -65,13 +95,28 @@ impl Gui<ContainerGuiState, ContainerGuiMsg> for ContainerGui {
         maybe_cursor: &Option<GuiCursor>,
         entity_id: EntityId,
         world: &World,
-        _state: &ContainerGuiState,
+        state: &ContainerGuiState,
     ) -> Vec<GuiComponent<ContainerGuiMsg>> {
         let mut components: Vec<GuiComponent<ContainerGuiMsg>> =
             vec![gui::image(self.background_image.as_str())
                 .with_position(vec2(0.0, 0.0))
                 .with_size(vec2(self.width, self.height))];
 
+        if let Some(target) = self.locked_hack_target(world, entity_id) {
+            match state.hack.clone().or_else(|| start_hack(world, target)) {
+                Some(game) => components.extend(draw_hack(
+                    &game,
+                    ContainerGuiMsg::TryHackNode,
+                    ContainerGuiMsg::RetryHack,
+                )),
+                None => components.push(
+                    gui::text(&format!("Requires Hacking {}", target.required_skill()))
+                        .with_position(vec2(10.0, 15.0)),
+                ),
+            }
+            return components;
+        }
+
         let mut contained_entities =
             script_util::get_all_links_with_data(world, entity_id, |link| match link {
                 Link::Contains(ordinal) => Some(*ordinal),
@@ -168,8 +213,8 @@ impl Gui<ContainerGuiState, ContainerGuiMsg> for ContainerGui {
 
     fn handle_msg(
         &self,
-        _entity_id: EntityId,
-        _world: &World,
+        entity_id: EntityId,
+        world: &World,
         state: &ContainerGuiState,
         msg: &ContainerGuiMsg,
     ) -> (ContainerGuiState, Effect) {
@@ -201,7 +246,42 @@ impl Gui<ContainerGuiState, ContainerGuiMsg> for ContainerGui {
                     },
                 },
             ),
+            ContainerGuiMsg::TryHackNode(x, y) => {
+                let maybe_game = self
+                    .locked_hack_target(world, entity_id)
+                    .and_then(|target| {
+                        state
+                            .hack
+                            .clone()
+                            .or_else(|| start_hack(world, target))
+                            .map(|game| (target, game))
+                    });
+                match maybe_game {
+                    Some((target, game)) => {
+                        let (game, effect) = try_hack_node(world, entity_id, target, &game, *x, *y);
+                        (ContainerGuiState { hack: Some(game) }, effect)
+                    }
+                    None => (state.clone(), Effect::NoEffect),
+                }
+            }
+            ContainerGuiMsg::RetryHack if pay_for_hack_retry(world) => {
+                (ContainerGuiState { hack: None }, Effect::NoEffect)
+            }
+            ContainerGuiMsg::RetryHack => (state.clone(), Effect::NoEffect),
         }
         //(state.clone(), Effect::NoEffect)
     }
+
+    fn handle_script_msg(
+        &self,
+        entity_id: EntityId,
+        world: &World,
+        state: &ContainerGuiState,
+        msg: &MessagePayload,
+    ) -> Option<(ContainerGuiState, Effect)> {
+        // An ICE pick opens a locked container without the minigame
+        self.locked_hack_target(world, entity_id)
+            .and_then(|target| use_ice_pick(world, entity_id, target, msg))
+            .map(|effect| (state.clone(), effect))
+    }
 }
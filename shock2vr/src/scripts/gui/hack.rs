@@ -0,0 +1,527 @@
+use cgmath::{vec2, Vector2, Vector3};
+use dark::properties::{PropHacked, PropScripts};
+use engine::audio::AudioHandle;
+use rand::Rng;
+use shipyard::{EntityId, Get, UniqueView, UniqueViewMut, View, World};
+
+use crate::{
+    game_rng::GameRng,
+    gui::{self, Gui, GuiComponent, GuiConfig, GuiCursor},
+    player_stats::{Currency, PlayerStats, TechSkill},
+    scripts::{script_util::*, Effect, MessagePayload},
+};
+
+// The hack succeeds once enough nodes in a row - across, down, or diagonally - are lit
+pub const HACK_GRID_SIZE: usize = 5;
+const NODES_IN_A_ROW: usize = 3;
+
+// Odds of lighting a node, at the skill the target requires - each extra level of skill adds to it
+const BASE_SUCCESS_CHANCE: f32 = 0.4;
+const SUCCESS_CHANCE_PER_LEVEL: f32 = 0.1;
+const MIN_SUCCESS_CHANCE: f32 = 0.1;
+const MAX_SUCCESS_CHANCE: f32 = 0.9;
+
+// Odds of tripping ICE, which ends the hack and raises the alarm
+const BASE_CRITICAL_CHANCE: f32 = 0.08;
+const CRITICAL_CHANCE_PER_LEVEL: f32 = 0.02;
+
+// Nanites it costs to try again after a failed hack - the first attempt is free
+pub const HACK_RETRY_NANITE_COST: u32 = 10;
+
+// Script of ICE picks, which hack an object without the minigame
+const ICE_PICK_SCRIPT: &str = "freehack";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HackNode {
+    Unknown,
+    Lit,
+    Burnt,
+    Ice,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HackOutcome {
+    InProgress,
+    Success,
+    // Every row has a burnt node - the hack can be retried
+    Failure,
+    // ICE was tripped - the alarm goes off
+    CriticalFailure,
+}
+
+#[derive(Clone, Debug)]
+pub struct HackGame {
+    nodes: Vec<HackNode>,
+    success_chance: f32,
+    critical_chance: f32,
+}
+
+impl HackGame {
+    pub fn new(hack_skill: u32, required_skill: u32) -> HackGame {
+        let extra_levels = hack_skill as f32 - required_skill as f32;
+        HackGame {
+            nodes: vec![HackNode::Unknown; HACK_GRID_SIZE * HACK_GRID_SIZE],
+            success_chance: (BASE_SUCCESS_CHANCE + SUCCESS_CHANCE_PER_LEVEL * extra_levels)
+                .clamp(MIN_SUCCESS_CHANCE, MAX_SUCCESS_CHANCE),
+            critical_chance: (BASE_CRITICAL_CHANCE - CRITICAL_CHANCE_PER_LEVEL * extra_levels)
+                .clamp(0.0, BASE_CRITICAL_CHANCE),
+        }
+    }
+
+    pub fn success_chance(&self) -> f32 {
+        self.success_chance
+    }
+
+    pub fn node(&self, x: usize, y: usize) -> HackNode {
+        self.nodes[y * HACK_GRID_SIZE + x]
+    }
+
+    ///
+    /// try_node
+    ///
+    /// Attempt to light a node, given a roll between 0 and 1. Each node can only be tried once,
+    /// and only while the hack is in progress.
+    pub fn try_node(&mut self, x: usize, y: usize, roll: f32) -> HackOutcome {
+        let idx = y * HACK_GRID_SIZE + x;
+        if self.outcome() == HackOutcome::InProgress && self.nodes[idx] == HackNode::Unknown {
+            self.nodes[idx] = if roll < self.critical_chance {
+                HackNode::Ice
+            } else if roll < self.critical_chance + self.success_chance {
+                HackNode::Lit
+            } else {
+                HackNode::Burnt
+            };
+        }
+        self.outcome()
+    }
+
+    pub fn outcome(&self) -> HackOutcome {
+        if self.nodes.contains(&HackNode::Ice) {
+            return HackOutcome::CriticalFailure;
+        }
+
+        let lines = get_lines();
+        let is_lit = |idx: &usize| self.nodes[*idx] == HackNode::Lit;
+        let is_open = |idx: &usize| self.nodes[*idx] != HackNode::Burnt;
+        if lines.iter().any(|line| line.iter().all(is_lit)) {
+            HackOutcome::Success
+        } else if lines.iter().any(|line| line.iter().all(is_open)) {
+            HackOutcome::InProgress
+        } else {
+            HackOutcome::Failure
+        }
+    }
+}
+
+// Every run of nodes that would complete the hack
+fn get_lines() -> Vec<Vec<usize>> {
+    let size = HACK_GRID_SIZE as i32;
+    let directions = [(1, 0), (0, 1), (1, 1), (1, -1)];
+    let mut lines = Vec::new();
+    for y in 0..size {
+        for x in 0..size {
+            for (dx, dy) in directions {
+                let line: Vec<usize> = (0..NODES_IN_A_ROW as i32)
+                    .map(|i| (x + dx * i, y + dy * i))
+                    .filter(|(x, y)| *x >= 0 && *x < size && *y >= 0 && *y < size)
+                    .map(|(x, y)| (y * size + x) as usize)
+                    .collect();
+                if line.len() == NODES_IN_A_ROW {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+    lines
+}
+
+// What kind of object is being hacked - drives the skill needed, and what a successful hack does
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HackTarget {
+    Crate,
+    SecurityComputer,
+    Device,
+    Keypad,
+    Replicator,
+}
+
+impl HackTarget {
+    pub fn required_skill(&self) -> u32 {
+        match self {
+            HackTarget::Keypad | HackTarget::SecurityComputer => 1,
+            HackTarget::Crate | HackTarget::Replicator => 2,
+            HackTarget::Device => 3,
+        }
+    }
+
+    ///
+    /// success_effect
+    ///
+    /// Effect of successfully hacking the object - also used when an ICE pick is used on it
+    pub fn success_effect(&self, world: &World, entity_id: EntityId) -> Effect {
+        let target_effect = match self {
+            // The crate shows its contents once it is unlocked
+            HackTarget::Crate => Effect::Unlock { entity_id },
+            HackTarget::SecurityComputer => Effect::DisableAlarm,
+            // Hacking a device (like a turret) shuts it down
+            HackTarget::Device => send_to_all_switch_links_and_self(
+                world,
+                entity_id,
+                MessagePayload::TurnOff { from: entity_id },
+            ),
+            HackTarget::Keypad => send_to_all_switch_links_and_self(
+                world,
+                entity_id,
+                MessagePayload::TurnOn { from: entity_id },
+            ),
+            // Hacked replicators are cheaper - the replicator checks the hacked flag for prices
+            HackTarget::Replicator => Effect::NoEffect,
+        };
+
+        Effect::combine(vec![
+            Effect::SetHacked { entity_id },
+            target_effect,
+            Effect::PlaySound {
+                handle: AudioHandle::new(),
+                name: "hacksucc".to_owned(),
+            },
+        ])
+    }
+
+    fn outcome_effect(&self, world: &World, entity_id: EntityId, outcome: HackOutcome) -> Effect {
+        match outcome {
+            HackOutcome::Success => self.success_effect(world, entity_id),
+            HackOutcome::CriticalFailure => Effect::TriggerAlarm { entity_id },
+            HackOutcome::InProgress | HackOutcome::Failure => Effect::NoEffect,
+        }
+    }
+}
+
+pub fn is_hacked(world: &World, entity_id: EntityId) -> bool {
+    let v_hacked = world.borrow::<View<PropHacked>>().unwrap();
+    v_hacked
+        .get(entity_id)
+        .map(|hacked| hacked.0)
+        .unwrap_or(false)
+}
+
+fn is_ice_pick(world: &World, entity_id: EntityId) -> bool {
+    let v_scripts = world.borrow::<View<PropScripts>>().unwrap();
+    v_scripts
+        .get(entity_id)
+        .map(|prop_scripts| {
+            prop_scripts
+                .scripts
+                .iter()
+                .any(|script| script.eq_ignore_ascii_case(ICE_PICK_SCRIPT))
+        })
+        .unwrap_or(false)
+}
+
+///
+/// use_ice_pick
+///
+/// ICE picks are used by letting go of one while pointing at a hackable object - that hacks it
+/// without the minigame, and uses up the pick. Returns the effect if the message is an ICE pick
+/// being given to an object that isn't hacked yet.
+pub fn use_ice_pick(
+    world: &World,
+    entity_id: EntityId,
+    target: HackTarget,
+    msg: &MessagePayload,
+) -> Option<Effect> {
+    match msg {
+        MessagePayload::ProvideForConsumption { entity }
+            if is_ice_pick(world, *entity) && !is_hacked(world, entity_id) =>
+        {
+            Some(Effect::combine(vec![
+                target.success_effect(world, entity_id),
+                Effect::DestroyEntity { entity_id: *entity },
+            ]))
+        }
+        _ => None,
+    }
+}
+
+///
+/// start_hack
+///
+/// Start a new hack on the target, with the odds based on the player's hacking skill. Returns
+/// None if the player isn't skilled enough to attempt it.
+pub fn start_hack(world: &World, target: HackTarget) -> Option<HackGame> {
+    let player_stats = world.borrow::<UniqueView<PlayerStats>>().unwrap();
+    let hack_skill = player_stats.tech_skill(TechSkill::Hacking);
+    if hack_skill < target.required_skill() {
+        None
+    } else {
+        Some(HackGame::new(hack_skill, target.required_skill()))
+    }
+}
+
+///
+/// pay_for_hack_retry
+///
+/// Charge the player for another attempt at a failed hack. Returns whether they could pay - the
+/// balance is checked and spent in one go, so two retries can't both be paid for by the same nanites.
+pub fn pay_for_hack_retry(world: &World) -> bool {
+    let mut player_stats = world.borrow::<UniqueViewMut<PlayerStats>>().unwrap();
+    player_stats.spend_currency(Currency::Nanites, HACK_RETRY_NANITE_COST)
+}
+
+///
+/// try_hack_node
+///
+/// Try a node in an in-progress hack, returning the updated game and the effect of the hack
+/// ending, if it did
+pub fn try_hack_node(
+    world: &World,
+    entity_id: EntityId,
+    target: HackTarget,
+    game: &HackGame,
+    x: usize,
+    y: usize,
+) -> (HackGame, Effect) {
+    let roll = {
+        let mut rng = world.borrow::<UniqueViewMut<GameRng>>().unwrap();
+        rng.gen::<f32>()
+    };
+
+    let mut next_game = game.clone();
+    let previous_outcome = next_game.outcome();
+    let outcome = next_game.try_node(x, y, roll);
+    let effect = if outcome != previous_outcome {
+        target.outcome_effect(world, entity_id, outcome)
+    } else {
+        Effect::NoEffect
+    };
+    (next_game, effect)
+}
+
+///
+/// draw_hack
+///
+/// Components for a hack in progress, or its result. Shared by all the GUIs that can be hacked.
+pub fn draw_hack<TMsg: Clone>(
+    game: &HackGame,
+    try_node: impl Fn(usize, usize) -> TMsg,
+    retry: TMsg,
+) -> Vec<GuiComponent<TMsg>> {
+    let node_size = 30.0;
+    let padding = 4.0;
+    let left_margin = 9.0;
+    let top_margin = 50.0;
+
+    let mut components = vec![gui::text(&format!(
+        "Hacking - {}%",
+        (game.success_chance() * 100.0).round()
+    ))
+    .with_position(vec2(10.0, 15.0))];
+
+    for y in 0..HACK_GRID_SIZE {
+        for x in 0..HACK_GRID_SIZE {
+            let position = vec2(
+                left_margin + (node_size + padding) * x as f32,
+                top_margin + (node_size + padding) * y as f32,
+            );
+            let size = vec2(node_size, node_size);
+            let label = match game.node(x, y) {
+                HackNode::Unknown => {
+                    components.push(
+                        gui::button(try_node(x, y))
+                            .with_position(position)
+                            .with_size(size),
+                    );
+                    continue;
+                }
+                HackNode::Lit => "+",
+                HackNode::Burnt => "x",
+                HackNode::Ice => "!",
+            };
+            components.push(
+                gui::image("key0.pcx")
+                    .with_position(position)
+                    .with_size(size)
+                    .with_alpha(1.0),
+            );
+            components.push(gui::text(label).with_position(position + vec2(11.0, 8.0)));
+        }
+    }
+
+    let status_y = top_margin + (node_size + padding) * HACK_GRID_SIZE as f32 + 10.0;
+    let status = match game.outcome() {
+        HackOutcome::InProgress => None,
+        HackOutcome::Success => Some("Hack successful"),
+        HackOutcome::Failure => Some("Hack failed"),
+        HackOutcome::CriticalFailure => Some("ICE detected - alarm raised"),
+    };
+    if let Some(status) = status {
+        components.push(gui::text(status).with_position(vec2(10.0, status_y)));
+    }
+
+    if game.outcome() == HackOutcome::Failure {
+        components.push(
+            gui::button(retry)
+                .with_position(vec2(10.0, status_y + 20.0))
+                .with_size(vec2(168.0, 30.0)),
+        );
+        components.push(
+            gui::text(&format!("Retry - {} nanites", HACK_RETRY_NANITE_COST))
+                .with_position(vec2(40.0, status_y + 28.0)),
+        );
+    }
+
+    components
+}
+
+// Hackable objects that are only a hack, with no other interface - crates, security computers,
+// and devices like turrets
+pub struct HackGui {
+    target: HackTarget,
+}
+
+impl HackGui {
+    pub fn new(target: HackTarget) -> HackGui {
+        HackGui { target }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct HackGuiState {
+    game: Option<HackGame>,
+}
+
+#[derive(Clone)]
+pub enum HackMsg {
+    TryNode(usize, usize),
+    Retry,
+}
+
+impl Gui<HackGuiState, HackMsg> for HackGui {
+    fn get_components(
+        &self,
+        _cursor: &Option<GuiCursor>,
+        entity_id: EntityId,
+        world: &World,
+        state: &HackGuiState,
+    ) -> Vec<GuiComponent<HackMsg>> {
+        let mut components: Vec<GuiComponent<HackMsg>> = vec![gui::image("keypad2.pcx")
+            .with_position(vec2(0.0, 0.0))
+            .with_size(vec2(188.0, 296.0))];
+
+        let maybe_game = state
+            .game
+            .clone()
+            .or_else(|| start_hack(world, self.target));
+
+        if is_hacked(world, entity_id) {
+            components.push(gui::text("Hacked").with_position(vec2(10.0, 15.0)));
+        } else if let Some(game) = maybe_game {
+            components.extend(draw_hack(&game, HackMsg::TryNode, HackMsg::Retry));
+        } else {
+            components.push(
+                gui::text(&format!(
+                    "Requires Hacking {}",
+                    self.target.required_skill()
+                ))
+                .with_position(vec2(10.0, 15.0)),
+            );
+        }
+
+        components
+    }
+
+    fn get_config(&self) -> GuiConfig {
+        GuiConfig {
+            world_offset: Vector3::new(0.0, 0.0, -0.1),
+            screen_size_in_pixels: Vector2::new(188.0, 296.0),
+        }
+    }
+
+    fn handle_msg(
+        &self,
+        entity_id: EntityId,
+        world: &World,
+        state: &HackGuiState,
+        msg: &HackMsg,
+    ) -> (HackGuiState, Effect) {
+        match msg {
+            HackMsg::TryNode(x, y) => {
+                match state
+                    .game
+                    .clone()
+                    .or_else(|| start_hack(world, self.target))
+                {
+                    Some(game) => {
+                        let (game, effect) =
+                            try_hack_node(world, entity_id, self.target, &game, *x, *y);
+                        (HackGuiState { game: Some(game) }, effect)
+                    }
+                    None => (state.clone(), Effect::NoEffect),
+                }
+            }
+            HackMsg::Retry if pay_for_hack_retry(world) => {
+                (HackGuiState { game: None }, Effect::NoEffect)
+            }
+            HackMsg::Retry => (state.clone(), Effect::NoEffect),
+        }
+    }
+
+    fn handle_script_msg(
+        &self,
+        entity_id: EntityId,
+        world: &World,
+        state: &HackGuiState,
+        msg: &MessagePayload,
+    ) -> Option<(HackGuiState, Effect)> {
+        use_ice_pick(world, entity_id, self.target, msg).map(|effect| (state.clone(), effect))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_three_lit_nodes_in_a_row_succeeds() {
+        let mut game = HackGame::new(3, 1);
+        assert_eq!(game.try_node(0, 0, 0.5), HackOutcome::InProgress);
+        assert_eq!(game.try_node(1, 1, 0.5), HackOutcome::InProgress);
+        assert_eq!(game.try_node(2, 2, 0.5), HackOutcome::Success);
+
+        // Once the hack is over, nodes can't be tried anymore
+        assert_eq!(game.try_node(3, 3, 0.99), HackOutcome::Success);
+        assert_eq!(game.node(3, 3), HackNode::Unknown);
+    }
+
+    #[test]
+    fn test_burning_every_row_fails() {
+        let mut game = HackGame::new(1, 1);
+        let mut outcome = HackOutcome::InProgress;
+        for y in 0..HACK_GRID_SIZE {
+            for x in 0..HACK_GRID_SIZE {
+                outcome = game.try_node(x, y, 0.99);
+            }
+        }
+        assert_eq!(outcome, HackOutcome::Failure);
+    }
+
+    #[test]
+    fn test_tripping_ice_is_a_critical_failure() {
+        let mut game = HackGame::new(1, 1);
+        assert_eq!(game.try_node(2, 2, 0.0), HackOutcome::CriticalFailure);
+        assert_eq!(game.node(2, 2), HackNode::Ice);
+        assert_eq!(game.try_node(0, 0, 0.5), HackOutcome::CriticalFailure);
+        assert_eq!(game.node(0, 0), HackNode::Unknown);
+    }
+
+    #[test]
+    fn test_hack_skill_improves_odds() {
+        let novice = HackGame::new(1, 1);
+        let expert = HackGame::new(4, 1);
+        assert!(expert.success_chance() > novice.success_chance());
+        assert!(expert.critical_chance < novice.critical_chance);
+
+        // Someone skilled enough never trips ICE
+        assert_eq!(HackGame::new(6, 1).critical_chance, 0.0);
+    }
+}
@@ -8,17 +8,39 @@ use crate::gui::{self, ButtonHoverBehavior, Gui, GuiComponent, GuiConfig, GuiCur
 
 use crate::scripts::{script_util::*, Effect, MessagePayload};
 
-pub struct KeyPadGui;
+use super::hack::{
+    draw_hack, is_hacked, pay_for_hack_retry, start_hack, try_hack_node, use_ice_pick, HackGame,
+    HackTarget,
+};
+
+pub struct KeyPadGui {
+    is_hackable: bool,
+}
+
+impl KeyPadGui {
+    pub fn hackable() -> KeyPadGui {
+        KeyPadGui { is_hackable: true }
+    }
+
+    pub fn unhackable() -> KeyPadGui {
+        KeyPadGui { is_hackable: false }
+    }
+}
 
 #[derive(Clone, Debug, Default)]
 pub struct KeyPadState {
     current_value: Option<u32>,
+    // Set while the keypad is being hacked, instead of having a code entered
+    hack: Option<HackGame>,
 }
 
 #[derive(Clone)]
 pub enum KeyPadMsg {
     ButtonPressed(u32),
     Clear,
+    StartHack,
+    TryHackNode(usize, usize),
+    RetryHack,
 }
 
 fn get_texture_for_char(char: char) -> String {
@@ -54,10 +76,24 @@ impl Gui<KeyPadState, KeyPadMsg> for KeyPadGui {
     fn get_components(
         &self,
         _cursor: &Option<GuiCursor>,
-        _entity_id: EntityId,
-        _world: &World,
-        _state: &KeyPadState,
+        entity_id: EntityId,
+        world: &World,
+        state: &KeyPadState,
     ) -> Vec<GuiComponent<KeyPadMsg>> {
+        if let Some(game) = &state.hack {
+            if !is_hacked(world, entity_id) {
+                let mut components = vec![gui::image("keypad2.pcx")
+                    .with_position(vec2(0.0, 0.0))
+                    .with_size(vec2(188.0, 296.0))];
+                components.extend(draw_hack(
+                    game,
+                    KeyPadMsg::TryHackNode,
+                    KeyPadMsg::RetryHack,
+                ));
+                return components;
+            }
+        }
+
         let button_width = 45.0;
         let button_height = 60.0;
         let left_margin = 15.0;
@@ -162,7 +198,20 @@ impl Gui<KeyPadState, KeyPadMsg> for KeyPadGui {
                 .with_hover(ButtonHoverBehavior::Texture("keyn1.pcx".to_owned())),
         ];
 
-        if let Some(v) = _state.current_value {
+        if self.is_hackable && !is_hacked(world, entity_id) {
+            let hack_position = vec2(
+                left_margin + (button_width + padding) * 2.0,
+                top_margin + (button_height + padding) * 3.0,
+            );
+            components.push(
+                gui::button(KeyPadMsg::StartHack)
+                    .with_position(hack_position)
+                    .with_size(vec2(button_width, button_height)),
+            );
+            components.push(gui::text("Hack").with_position(hack_position + vec2(8.0, 24.0)));
+        }
+
+        if let Some(v) = state.current_value {
             components.extend(draw_number(v))
         }
         components
@@ -190,6 +239,34 @@ impl Gui<KeyPadState, KeyPadMsg> for KeyPadGui {
             name: "bkeypad".to_owned(),
         };
 
+        match msg {
+            KeyPadMsg::RetryHack if !pay_for_hack_retry(world) => {
+                return (state.clone(), Effect::NoEffect);
+            }
+            KeyPadMsg::StartHack | KeyPadMsg::RetryHack => {
+                let new_state = KeyPadState {
+                    hack: start_hack(world, HackTarget::Keypad),
+                    ..state.clone()
+                };
+                return (new_state, press_effect);
+            }
+            KeyPadMsg::TryHackNode(x, y) => {
+                return match &state.hack {
+                    Some(game) => {
+                        let (game, effect) =
+                            try_hack_node(world, entity_id, HackTarget::Keypad, game, *x, *y);
+                        let new_state = KeyPadState {
+                            hack: Some(game),
+                            ..state.clone()
+                        };
+                        (new_state, effect)
+                    }
+                    None => (state.clone(), Effect::NoEffect),
+                };
+            }
+            _ => (),
+        }
+
         let new_state = match msg {
             KeyPadMsg::ButtonPressed(n) => {
                 let new_value = match state.current_value {
@@ -206,11 +283,10 @@ impl Gui<KeyPadState, KeyPadMsg> for KeyPadGui {
                 };
                 KeyPadState {
                     current_value: Some(new_value),
+                    hack: None,
                 }
             }
-            KeyPadMsg::Clear => KeyPadState {
-                current_value: None,
-            },
+            _ => KeyPadState::default(),
         };
 
         // Check if the keypad code matches
@@ -242,4 +318,18 @@ impl Gui<KeyPadState, KeyPadMsg> for KeyPadGui {
             Effect::combine(vec![additional_effect, press_effect]),
         )
     }
+
+    fn handle_script_msg(
+        &self,
+        entity_id: EntityId,
+        world: &World,
+        state: &KeyPadState,
+        msg: &MessagePayload,
+    ) -> Option<(KeyPadState, Effect)> {
+        if !self.is_hackable {
+            return None;
+        }
+        use_ice_pick(world, entity_id, HackTarget::Keypad, msg)
+            .map(|effect| (state.clone(), effect))
+    }
 }
@@ -1,11 +1,13 @@
 mod container;
 mod elevator;
 mod gamepig;
+mod hack;
 mod keypad;
 mod replicator;
 
 pub use container::*;
 pub use elevator::*;
 pub use gamepig::*;
+pub use hack::*;
 pub use keypad::*;
 pub use replicator::*;
@@ -1,5 +1,5 @@
 use cgmath::{vec2, vec3, Vector2, Vector3};
-use dark::properties::PropReplicatorContents;
+use dark::properties::{PropReplicatorContents, PropReplicatorHackedContents};
 use engine::audio::AudioHandle;
 use num_traits::ToPrimitive;
 
@@ -8,21 +8,35 @@ use shipyard::{EntityId, Get, UniqueView, View, World};
 use crate::{
     gui::{Gui, GuiComponent, GuiConfig, GuiCursor},
     mission::GlobalEntityMetadata,
+    player_stats::{Currency, PlayerStats},
     util::{get_position_from_transform, get_rotation_from_transform},
 };
 
 use crate::gui;
 
-use crate::scripts::{script_util::*, Effect};
+use crate::scripts::{script_util::*, Effect, MessagePayload};
+
+use super::hack::{
+    draw_hack, is_hacked, pay_for_hack_retry, start_hack, try_hack_node, use_ice_pick, HackGame,
+    HackTarget,
+};
 
 pub struct ReplicatorGui;
 
 #[derive(Clone, Debug, Default)]
-pub struct ReplicatorState {}
+pub struct ReplicatorState {
+    // Set while the replicator is being hacked
+    hack: Option<HackGame>,
+    // Feedback from the last purchase, ie, not having enough nanites
+    status: Option<String>,
+}
 
 #[derive(Clone)]
 pub enum ReplicatorMsg {
-    SelectItem(String),
+    SelectItem(usize),
+    StartHack,
+    TryHackNode(usize, usize),
+    RetryHack,
 }
 
 impl Gui<ReplicatorState, ReplicatorMsg> for ReplicatorGui {
@@ -31,15 +45,14 @@ impl Gui<ReplicatorState, ReplicatorMsg> for ReplicatorGui {
         _cursor: &Option<GuiCursor>,
         entity_id: EntityId,
         world: &World,
-        _state: &ReplicatorState,
+        state: &ReplicatorState,
     ) -> Vec<GuiComponent<ReplicatorMsg>> {
         let button_height = 60.0;
         let initial_padding_y = 10.0;
         let button_width = 188.0;
         let button_padding = 4.0;
 
-        let v_prop_replicator = world.borrow::<View<PropReplicatorContents>>().unwrap();
-        let replicator_contents = v_prop_replicator.get(entity_id).unwrap();
+        let player_stats = world.borrow::<UniqueView<PlayerStats>>().unwrap();
 
         let entity_metadata = world.borrow::<UniqueView<GlobalEntityMetadata>>().unwrap();
 
@@ -56,20 +69,58 @@ impl Gui<ReplicatorState, ReplicatorMsg> for ReplicatorGui {
         let mut components: Vec<GuiComponent<ReplicatorMsg>> = vec![gui::image("replic.pcx")
             .with_position(vec2(0.0, 0.0))
             .with_size(vec2(188.0, 296.0))];
-        for i in 0..6 {
-            let float_i = i.to_f32().unwrap();
 
-            if replicator_contents.object_names[i].is_empty() {
-                continue;
+        let already_hacked = is_hacked(world, entity_id);
+        if let Some(game) = &state.hack {
+            if !already_hacked {
+                components.extend(draw_hack(
+                    game,
+                    ReplicatorMsg::TryHackNode,
+                    ReplicatorMsg::RetryHack,
+                ));
+                return components;
             }
+        }
+
+        if !already_hacked {
+            components.push(
+                gui::button(ReplicatorMsg::StartHack)
+                    .with_position(vec2(138.0, 262.0))
+                    .with_size(vec2(45.0, 30.0)),
+            );
+            components.push(gui::text("Hack").with_position(vec2(146.0, 270.0)));
+        }
+
+        let status = state
+            .status
+            .clone()
+            .unwrap_or_else(|| format!("Nanites: {}", player_stats.balance(Currency::Nanites)));
+        components.push(gui::text(&status).with_position(vec2(10.0, 270.0)));
+
+        let replicator_contents = match get_replicator_contents(world, entity_id) {
+            Some(replicator_contents) => replicator_contents,
+            None => return components,
+        };
 
-            let obj_name = replicator_contents.object_names[i].clone();
+        let items = replicator_contents
+            .object_names
+            .iter()
+            .zip(replicator_contents.costs.iter());
+        for (i, (obj_name, base_price)) in items.enumerate() {
+            let float_i = i.to_f32().unwrap();
 
-            let metadata = entity_metadata.0.get(&obj_name).unwrap();
-            let obj_icon = metadata.obj_icon.as_ref().unwrap();
+            let maybe_metadata = entity_metadata.0.get(obj_name).and_then(|metadata| {
+                let obj_icon = metadata.obj_icon.as_ref()?;
+                Some((metadata, obj_icon))
+            });
+            let (metadata, obj_icon) = match maybe_metadata {
+                Some(metadata) => metadata,
+                // Empty slot, or an object that can't be shown
+                None => continue,
+            };
 
             components.push(
-                gui::button(ReplicatorMsg::SelectItem(obj_name.clone()))
+                gui::button(ReplicatorMsg::SelectItem(i))
                     .with_position(vec2(
                         0.0,
                         initial_padding_y + (button_height + button_padding) * float_i,
@@ -86,6 +137,12 @@ impl Gui<ReplicatorState, ReplicatorMsg> for ReplicatorGui {
                     button_height / 2.0 + (button_height + button_padding) * float_i,
                 )));
             }
+
+            let price = player_stats.replicator_price(*base_price);
+            components.push(gui::text(&price.to_string()).with_position(vec2(
+                150.0,
+                button_height / 2.0 + (button_height + button_padding) * float_i,
+            )));
         }
 
         // components.push(GuiComponent::Text {
@@ -120,13 +177,36 @@ impl Gui<ReplicatorState, ReplicatorMsg> for ReplicatorGui {
         msg: &ReplicatorMsg,
     ) -> (ReplicatorState, Effect) {
         match msg {
-            ReplicatorMsg::SelectItem(item) => {
+            ReplicatorMsg::SelectItem(index) => {
+                let maybe_item = get_replicator_contents(world, entity_id).and_then(|contents| {
+                    let template_name = contents.object_names.get(*index)?.to_owned();
+                    let base_price = *contents.costs.get(*index)?;
+                    Some((template_name, base_price))
+                });
                 let maybe_link =
                     get_first_link_of_type(world, entity_id, dark::properties::Link::Replicator);
-                let link = maybe_link.unwrap();
+                let ((template_name, base_price), link) = match (maybe_item, maybe_link) {
+                    (Some(item), Some(link)) => (item, link),
+                    // Nothing to sell, or nowhere to put it
+                    _ => return (state.clone(), Effect::NoEffect),
+                };
+
+                let player_stats = world.borrow::<UniqueView<PlayerStats>>().unwrap();
+                let price = player_stats.replicator_price(base_price);
+
+                // Only for feedback - the purchase itself checks the balance when it is spent
+                if player_stats.balance(Currency::Nanites) < price {
+                    return (
+                        ReplicatorState {
+                            hack: None,
+                            status: Some("Not enough nanites".to_owned()),
+                        },
+                        Effect::NoEffect,
+                    );
+                }
 
                 let eff = Effect::CreateEntityByTemplateName {
-                    template_name: item.to_owned(),
+                    template_name,
                     position: get_position_from_transform(world, link, vec3(0.0, 0.0, 0.0)),
                     orientation: get_rotation_from_transform(world, link),
                 };
@@ -136,8 +216,68 @@ impl Gui<ReplicatorState, ReplicatorMsg> for ReplicatorGui {
                     name: "replic2e".to_owned(),
                 };
 
-                (state.clone(), Effect::combine(vec![eff, sound_eff]))
+                (
+                    ReplicatorState::default(),
+                    Effect::Purchase {
+                        price,
+                        effect: Box::new(Effect::combine(vec![eff, sound_eff])),
+                    },
+                )
             }
+            ReplicatorMsg::RetryHack if !pay_for_hack_retry(world) => {
+                (state.clone(), Effect::NoEffect)
+            }
+            ReplicatorMsg::StartHack | ReplicatorMsg::RetryHack => (
+                ReplicatorState {
+                    hack: start_hack(world, HackTarget::Replicator),
+                    status: None,
+                },
+                Effect::NoEffect,
+            ),
+            ReplicatorMsg::TryHackNode(x, y) => match &state.hack {
+                Some(game) => {
+                    let (game, effect) =
+                        try_hack_node(world, entity_id, HackTarget::Replicator, game, *x, *y);
+                    (
+                        ReplicatorState {
+                            hack: Some(game),
+                            status: None,
+                        },
+                        effect,
+                    )
+                }
+                None => (state.clone(), Effect::NoEffect),
+            },
         }
     }
+
+    fn handle_script_msg(
+        &self,
+        entity_id: EntityId,
+        world: &World,
+        state: &ReplicatorState,
+        msg: &MessagePayload,
+    ) -> Option<(ReplicatorState, Effect)> {
+        use_ice_pick(world, entity_id, HackTarget::Replicator, msg)
+            .map(|effect| (state.clone(), effect))
+    }
+}
+
+///
+/// get_replicator_contents
+///
+/// The items (and prices) offered by the replicator - hacking it unlocks a different selection,
+/// if it has one.
+fn get_replicator_contents(world: &World, entity_id: EntityId) -> Option<PropReplicatorContents> {
+    if is_hacked(world, entity_id) {
+        let v_hacked_contents = world
+            .borrow::<View<PropReplicatorHackedContents>>()
+            .unwrap();
+        if let Ok(hacked_contents) = v_hacked_contents.get(entity_id) {
+            return Some(hacked_contents.0.clone());
+        }
+    }
+
+    let v_prop_replicator = world.borrow::<View<PropReplicatorContents>>().unwrap();
+    v_prop_replicator.get(entity_id).ok().cloned()
 }
@@ -1,7 +1,7 @@
 use dark::properties::{CollisionType, PropCollisionType};
-use shipyard::{EntityId, Get, View, World};
+use shipyard::{EntityId, Get, UniqueView, View, World};
 
-use crate::physics::PhysicsWorld;
+use crate::{mission::PlayerInfo, physics::PhysicsWorld};
 
 use super::{Effect, MessagePayload, Script, Message};
 
@@ -32,7 +32,7 @@ impl Script for InternalCollisionType {
     fn handle_message(
         &mut self,
         entity_id: EntityId,
-        _world: &World,
+        world: &World,
         _physics: &PhysicsWorld,
         msg: &MessagePayload,
     ) -> Effect {
@@ -51,11 +51,25 @@ impl Script for InternalCollisionType {
                         Effect::NoEffect
                     }
                 };
-                let damage_effect = Effect::Send {
-                    msg: Message {
-                        to: *with,
-                        payload: MessagePayload::Damage { amount: 1.0 },
-                    },
+                // Only things that break on impact (ie, projectiles) hurt the player - otherwise
+                // they would take damage from bumping into crates. Anything else can be hurt by
+                // whatever hits it, like a thrown object.
+                let is_player = world
+                    .borrow::<UniqueView<PlayerInfo>>()
+                    .map(|player_info| player_info.entity_id == *with)
+                    .unwrap_or(false);
+                let damage_effect = if !is_player
+                    || self.collision_flags.intersects(
+                        CollisionType::SLAY_ON_IMPACT | CollisionType::DESTROY_ON_IMPACT,
+                    ) {
+                    Effect::Send {
+                        msg: Message {
+                            to: *with,
+                            payload: MessagePayload::Damage { amount: 1.0 },
+                        },
+                    }
+                } else {
+                    Effect::NoEffect
                 };
                 Effect::Multiple(vec![initial_effect, damage_effect])
             }
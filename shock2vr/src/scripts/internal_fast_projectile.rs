@@ -27,10 +27,22 @@ use crate::{
 
 use super::{Effect, MessagePayload, Script};
 
-pub struct InternalFastProjectileScript { velocity: Vector3<f32> }
+// Same as the default for PhysicsWorld::ray_cast
+const MAX_PROJECTILE_DISTANCE: f32 = 100.0;
+
+pub struct InternalFastProjectileScript {
+    velocity: Vector3<f32>,
+    launched_by: Option<EntityId>,
+}
 impl InternalFastProjectileScript {
-    pub fn new(velocity: Vector3<f32>) -> InternalFastProjectileScript {
-        InternalFastProjectileScript { velocity}
+    pub fn new(
+        velocity: Vector3<f32>,
+        launched_by: Option<EntityId>,
+    ) -> InternalFastProjectileScript {
+        InternalFastProjectileScript {
+            velocity,
+            launched_by,
+        }
     }
 }
 
@@ -54,7 +66,14 @@ impl Script for InternalFastProjectileScript {
         // let forward = xform.transform_vector(vec3(0.0, 0.0, -1.0));
         let forward = self.velocity.normalize();
         let start_point = current_position - forward * SCALE_FACTOR * 0.25;
-        let maybe_hit_spot = projectile_ray_cast(start_point, forward, physics, distance, world);
+        let maybe_hit_spot = projectile_ray_cast(
+            start_point,
+            forward,
+            physics,
+            distance,
+            world,
+            self.launched_by,
+        );
 
         if let Some(RayCastResult {
             hit_point,
@@ -148,15 +167,27 @@ impl Script for InternalFastProjectileScript {
     }
 }
 
-fn projectile_ray_cast(start_point: Point3<f32>, forward: cgmath::Vector3<f32>, physics: &PhysicsWorld, distance: f32, world: &World) -> Option<RayCastResult> {
-     let mut maybe_hit_spot = physics.ray_cast( start_point,
+fn projectile_ray_cast(
+    start_point: Point3<f32>,
+    forward: cgmath::Vector3<f32>,
+    physics: &PhysicsWorld,
+    distance: f32,
+    world: &World,
+    launched_by: Option<EntityId>,
+) -> Option<RayCastResult> {
+    let mut maybe_hit_spot = physics.ray_cast2(
+        start_point,
         forward * distance,
+        MAX_PROJECTILE_DISTANCE,
         InternalCollisionGroups::ENTITY
             // Sometimes, the hitbox can stick out past the bounding box...
             // so we should still check for it here
-            | InternalCollisionGroups::HITBOX 
+            | InternalCollisionGroups::HITBOX
             | InternalCollisionGroups::SELECTABLE
+            | InternalCollisionGroups::PLAYER
             | InternalCollisionGroups::WORLD,
+        launched_by,
+        true,
     );
 
     // If we hit an entity with a hitbox, scan again for the hitbox
@@ -165,12 +196,15 @@ fn projectile_ray_cast(start_point: Point3<f32>, forward: cgmath::Vector3<f32>,
 
         if let Some(hit_entity_id) = &hit_spot.maybe_entity_id {
             if does_entity_have_hitboxes(world, *hit_entity_id) {
-                maybe_hit_spot = physics.ray_cast(
+                maybe_hit_spot = physics.ray_cast2(
                     start_point,
                     forward * distance,
+                    MAX_PROJECTILE_DISTANCE,
                     InternalCollisionGroups::HITBOX
                         | InternalCollisionGroups::SELECTABLE
                         | InternalCollisionGroups::WORLD,
+                    launched_by,
+                    true,
                 );
             }
         }
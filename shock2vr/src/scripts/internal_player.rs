@@ -0,0 +1,33 @@
+use shipyard::{EntityId, World};
+
+use crate::physics::PhysicsWorld;
+
+use super::{Effect, MessagePayload, Script};
+
+// Script attached to the player entity, so that projectiles and attacks can
+// hurt the player the same way they hurt any other entity.
+pub struct InternalPlayer {}
+
+impl InternalPlayer {
+    pub fn new() -> InternalPlayer {
+        InternalPlayer {}
+    }
+}
+
+impl Script for InternalPlayer {
+    fn handle_message(
+        &mut self,
+        entity_id: EntityId,
+        _world: &World,
+        _physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        match msg {
+            MessagePayload::Damage { amount } => Effect::AdjustHitPoints {
+                entity_id,
+                delta: -(amount.round() as i32),
+            },
+            _ => Effect::NoEffect,
+        }
+    }
+}
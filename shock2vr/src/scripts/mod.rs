@@ -1,20 +1,26 @@
 pub mod ai;
 pub mod effect;
 
+mod ammo_script;
 mod base_button;
 mod base_elevator;
 mod base_monster;
+mod camera_alert;
+mod camera_death;
 mod choose_service;
 mod core_room;
 mod create_sound;
 mod dead_power_cell;
 mod destroy_all_by_name;
 mod energy_station;
+mod exp_cookie;
+mod free_weapon_service;
 mod frob_qb;
 mod gui;
 mod internal_collision_type;
 pub mod internal_fast_projectile;
 mod internal_keycard_script;
+mod internal_player;
 mod internal_simple_health;
 mod internal_switch_held_model;
 mod level_change_button;
@@ -23,8 +29,16 @@ mod melee_weapon;
 mod obj_consume_button;
 mod once_room;
 mod once_router;
+mod player_hypo;
+mod psi_amp;
+mod psi_trainer;
+mod researchable;
+mod resurrect_machine;
 mod room_trigger;
+mod script_registry;
 pub mod script_util;
+mod stat_boost;
+mod stat_boost_implant;
 mod std_door;
 mod tool_consumable;
 mod trap_delay;
@@ -52,42 +66,56 @@ mod trigger_multi;
 mod tweq_depressable;
 mod tweqable;
 mod use_sound;
+mod weapon_kind;
+mod weapon_modify;
 mod weapon_script;
+mod weapon_trainer;
 use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 
 use cgmath::{Point2, Vector3};
 use dark::motion::MotionFlags;
 pub use effect::*;
+pub use script_registry::*;
 
 use shipyard::{EntityId, World};
 use tracing::{info, span, trace, warn, Level};
 
+use crate::player_stats::Stat;
 use crate::vr_config::Handedness;
 use crate::{physics::PhysicsWorld, time::Time};
 
 use crate::gui::gui_script;
 
 use self::choose_service::ChooseServiceScript;
-use self::gui::{ContainerGui, ElevatorGui, GamePigGui, KeyPadGui, ReplicatorGui};
+use self::gui::{
+    ContainerGui, ElevatorGui, GamePigGui, HackGui, HackTarget, KeyPadGui, ReplicatorGui,
+};
 use self::internal_switch_held_model::InternalSwitchHeldModelScript;
 use self::trap_signal::TrapSignal;
+use self::weapon_kind::WeaponKind;
 use self::{
-    base_button::BaseButton, base_elevator::BaseElevator, base_monster::BaseMonster, core_room::*,
+    ammo_script::AmmoScript, base_button::BaseButton, base_elevator::BaseElevator,
+    base_monster::BaseMonster, camera_alert::CameraAlert, camera_death::CameraDeath, core_room::*,
     create_sound::*, dead_power_cell::DeadPowerCell, destroy_all_by_name::DestroyAllByName,
-    energy_station::EnergyStation, frob_qb::FrobQB, internal_collision_type::InternalCollisionType,
-    internal_keycard_script::KeyCardScript, internal_simple_health::InternalSimpleHealth,
-    level_change_button::LevelChangeButton, logdiscscript::LogDiscScript,
-    melee_weapon::MeleeWeapon, obj_consume_button::ObjConsumeButton, once_room::OnceRoom,
-    once_router::OnceRouter, room_trigger::RoomTrigger, std_door::StdDoor,
-    tool_consumable::ToolConsumable, trap_delay::TrapDelay, trap_destroyer::TrapDestroyer,
-    trap_email::TrapEmail, trap_exp_once::TrapEXPOnce, trap_inverter::TrapInverter,
-    trap_new_tripwire::TrapNewTripwire, trap_on_filter::TrapOffFilter,
+    energy_station::EnergyStation, exp_cookie::ExpCookie, free_weapon_service::FreeWeaponService,
+    frob_qb::FrobQB, internal_collision_type::InternalCollisionType,
+    internal_keycard_script::KeyCardScript, internal_player::InternalPlayer,
+    internal_simple_health::InternalSimpleHealth, level_change_button::LevelChangeButton,
+    logdiscscript::LogDiscScript, melee_weapon::MeleeWeapon, obj_consume_button::ObjConsumeButton,
+    once_room::OnceRoom, once_router::OnceRouter, player_hypo::PlayerHypo, psi_amp::PsiAmpScript,
+    psi_trainer::PsiTrainer, researchable::ResearchableScript, resurrect_machine::ResurrectMachine,
+    room_trigger::RoomTrigger, stat_boost::StatBoost, stat_boost_implant::StatBoostImplant,
+    std_door::StdDoor, tool_consumable::ToolConsumable, trap_delay::TrapDelay,
+    trap_destroyer::TrapDestroyer, trap_email::TrapEmail, trap_exp_once::TrapEXPOnce,
+    trap_inverter::TrapInverter, trap_new_tripwire::TrapNewTripwire, trap_on_filter::TrapOffFilter,
     trap_qb_filter::TrapQBFilter, trap_qb_neg_filter::TrapQBNegFilter, trap_qb_set::TrapQBSet,
     trap_questbit_simple::TrapQuestbitSimple, trap_router::TrapRouter, trap_slayer::TrapSlayer,
     trap_sound::TrapSound, trap_teleport::TrapTeleport, trap_teleport_player::TrapTeleportPlayer,
     trap_trip_level::TrapTripLevel, trap_tweq::TrapTweq, trigger_collide::TriggerCollide,
     trigger_multi::TriggerMulti, tweq_depressable::TweqDepressable, tweqable::Tweqable,
-    use_sound::UseSound, weapon_script::WeaponScript,
+    use_sound::UseSound, weapon_modify::WeaponModify, weapon_script::WeaponScript,
+    weapon_trainer::WeaponTrainer,
 };
 
 #[derive(Clone, Debug)]
@@ -113,6 +141,11 @@ pub enum MessagePayload {
 
     // Gameplay events
     Recharge,
+    Reload {
+        ammo: EntityId,
+    }, // load this ammo clip into the weapon
+    Modify, // upgrade the weapon to its next modification level
+    Repair, // restore the weapon to full condition
     ProvideForConsumption {
         entity: EntityId,
     }, // propose to consume this entity
@@ -124,6 +157,12 @@ pub enum MessagePayload {
     Signal {
         name: String,
     },
+    AlertnessChanged {
+        is_alert: bool,
+    }, // the AI has spotted (or lost track of) the player
+    Alert,             // the AI is tipped off about the player, and goes looking
+    BecomeHostile,     // the AI goes after the player
+    EnableInvestigate, // the AI may go looking into things it is suspicious of
 
     Slay, // kill the entity
 
@@ -148,6 +187,7 @@ pub enum MessagePayload {
     // VR Interactions
     TriggerPull,    // player started pulling the trigger
     TriggerRelease, // player stopped pulling the trigger
+    AltPress,       // player pressed the A button while holding the item
     Hold,
     Drop,
 
@@ -277,18 +317,6 @@ impl UnimplementedScript {
         }
     }
 }
-struct PanicOnLoadScript {
-    name: String,
-}
-
-impl PanicOnLoadScript {
-    pub fn new(name: &str) -> PanicOnLoadScript {
-        PanicOnLoadScript {
-            name: name.to_owned(),
-        }
-    }
-}
-
 struct PanicOnMessageScript;
 impl Script for PanicOnMessageScript {
     fn handle_message(
@@ -302,20 +330,6 @@ impl Script for PanicOnMessageScript {
     }
 }
 
-impl Script for PanicOnLoadScript {
-    fn initialize(&mut self, _entity_id: EntityId, _world: &World) -> Effect {
-        panic!("Unimplemented script: {}", self.name);
-    }
-    fn handle_message(
-        &mut self,
-        _entity_id: EntityId,
-        _world: &World,
-        _physics: &PhysicsWorld,
-        _msg: &MessagePayload,
-    ) -> Effect {
-        panic!("Unimplemented script: {}", self.name);
-    }
-}
 struct NoopScript {}
 impl NoopScript {
     pub fn new() -> NoopScript {
@@ -336,338 +350,34 @@ impl Script for NoopScript {
 }
 
 pub struct ScriptWorld {
+    registry: Rc<ScriptRegistry>,
     entity_has_initialized: HashMap<EntityId, bool>,
     entity_to_scripts: HashMap<EntityId, Vec<Box<dyn Script>>>,
     message_queue: Vec<Message>,
 }
 
 impl ScriptWorld {
-    pub fn new() -> ScriptWorld {
+    pub fn new(registry: Rc<ScriptRegistry>) -> ScriptWorld {
         ScriptWorld {
+            registry,
             entity_has_initialized: HashMap::new(),
             entity_to_scripts: HashMap::new(),
             message_queue: Vec::new(),
         }
     }
 
-    fn create_script(script_name: String) -> Box<dyn Script> {
-        match script_name.to_ascii_lowercase().as_str() {
-            // PROJECTILE stuff
-            "lasershot" => Box::new(NoopScript::new()),
-            "timedgrenade" => Box::new(NoopScript::new()),
-            "transluceinoutprop" => Box::new(NoopScript::new()),
-
-            // KEYCARD stuff
-            "trapunlock" => Box::new(NoopScript::new()),
-
-            "createsound" => Box::new(CreateSound::new()),
-
-            // INTERACTIVE stuff
-            "trapslayer" => Box::new(TrapSlayer::new()),
-            "deadpowercell" => Box::new(DeadPowerCell::new()),
-            "energystation" => Box::new(EnergyStation::new()),
-            "toolconsumable" => Box::new(ToolConsumable::new()),
-
-            // AI stuff
-            "trapsignal" => Box::new(TrapSignal::new()),
-            "gooegg" => Box::new(Tweqable::new()),
-            "grubegg" => Box::new(Tweqable::new()),
-            "swarmeregg" => Box::new(Tweqable::new()),
-            "containerscript" => gui_script(Box::new(ContainerGui::loot_container())),
-
-            "lootable" => Box::new(NoopScript::new()),
-
-            // Weapons
-            "weaponscript" => Box::new(CompositeScript::new(vec![
-                Box::new(WeaponScript::new()),
-                Box::new(InternalSwitchHeldModelScript::new()),
-            ])),
-            "pistolmodify" => Box::new(NoopScript::new()),
-
-            // TODO: Necessary
-            "changeinterface" => Box::new(NoopScript::new()),
-            "reducehp" => Box::new(NoopScript::new()),
-            "engineremoverad" => Box::new(NoopScript::new()),
-            "radroom" => Box::new(NoopScript::new()),
-            "trapspawn" => Box::new(NoopScript::new()),
-            // ops1 cutscene
-            "transluceinoutholo" => Box::new(NoopScript::new()),
-            "cs9_doorreporter" => Box::new(NoopScript::new()),
-            "cs9_eggsandgrubs" => Box::new(NoopScript::new()),
-            "cs9_mastercontrol" => Box::new(NoopScript::new()),
-            "cs9_holorumbler" => Box::new(NoopScript::new()),
-            "cs9_shodanscreen" => Box::new(NoopScript::new()),
-            "sitdownrightnowmp" => Box::new(NoopScript::new()),
-            "trapdestroyteleport" => Box::new(NoopScript::new()),
-            "triggerdamage" => Box::new(NoopScript::new()),
-            // many.micontain
-            "brain" => Box::new(NoopScript::new()),
-            "wormheartimplant" => Box::new(NoopScript::new()),
-            "wormskin" => Box::new(NoopScript::new()),
-            // shodan.mis
-            "toggleshodantexture" => Box::new(NoopScript::new()),
-            "changedelay" => Box::new(NoopScript::new()), //?
-            "shodanhead" => Box::new(NoopScript::new()),  //?
-            "shodanshield" => Box::new(NoopScript::new()), //?
-            "seatplayer" => Box::new(TrapTeleportPlayer::new()), // This is used in final battle -does this do anything else besides teleport?
-            "dieshodandie" => Box::new(NoopScript::new()),       // end cutscene!
-            "teleportpath" => Box::new(NoopScript::new()),       // end cutscene!
-            "translucebydamage" => Box::new(NoopScript::new()),  // end cutscene!
-
-            // TODO: Should these actually be implemented?
-            "earthtext" => Box::new(NoopScript::new()),
-            "trapgravity" => Box::new(NoopScript {}), // medsci1 - vent that falls
-            "trapmessage" => Box::new(NoopScript {}), // eng2 - installing override. What prop for message? Where to load string?
-            "charmable" => Box::new(NoopScript::new()),
-            "transientcorpse" => Box::new(NoopScript::new()),
-            "whiteout" => Box::new(NoopScript::new()),
-            "vaporizeinventory" => Box::new(NoopScript::new()),
-
-            // Internal
-            "internal_collision_type" => Box::new(InternalCollisionType::new()),
-            "internal_inventory" => gui_script(Box::new(ContainerGui::inv_container())),
-            // "internal_inventory" => Box::new(PanicOnLoadScript::new("internal_inventory")),
-            "internal_keycard" => Box::new(KeyCardScript::new()),
-            "internal_room_trigger" => Box::new(RoomTrigger::new()),
-            "internal_simple_health" => Box::new(InternalSimpleHealth::new()),
-            // Implemented
-            "basebutton" => Box::new(BaseButton::new()),
-            "baseelevator" => Box::new(BaseElevator::new()),
-            "destroyallbyname" => Box::new(DestroyAllByName::new()),
-            "levelchangebutton" => Box::new(LevelChangeButton::new()),
-            "logdiscscript" => Box::new(LogDiscScript::new()),
-            "oncerouter" => Box::new(OnceRouter::new()),
-            "stddoor" => Box::new(StdDoor::new()),
-            "trapdelay" => Box::new(TrapDelay::new()),
-            "trapterminator" => Box::new(TrapDestroyer::new()), // TODO: What is the difference between Terminate vs Destroyer vs Destroy?
-            "trapdestroyer" => Box::new(TrapDestroyer::new()),
-            "trapdestroy" => Box::new(TrapDestroyer::new()),
-            "trapemail" => Box::new(TrapEmail::new()),
-            "trapexponce" => Box::new(TrapEXPOnce::new()),
-            "trapinverter" => Box::new(TrapInverter::new()),
-            "trapnewtripwire" => Box::new(TrapNewTripwire::new()),
-            "trapofffilter" => Box::new(TrapOffFilter::new()),
-            "traponfilter" => Box::new(TrapOffFilter::new()),
-            "trapteleportplayer" => Box::new(TrapTeleportPlayer::new()),
-            "traprouter" => Box::new(TrapRouter::new()),
-            "trapsound" => Box::new(TrapSound::new()),
-            "trapsoundamb" => Box::new(TrapSound::new()),
-            "trapteleport" => Box::new(TrapTeleport::new()),
-            "traptriplevel" => Box::new(TrapTripLevel::new()),
-            "triggermulti" => Box::new(TriggerMulti::new()), // nacelle control
-            "triggercollide" => Box::new(TriggerCollide::new()),
-            "usesound" => Box::new(UseSound::new()),
-
-            // TWEQ stuff
-            "traptweq" => Box::new(CompositeScript::new(vec![
-                Box::new(Tweqable::new()),
-                Box::new(TrapTweq::new()),
-            ])),
-            "tweqdepressable" => Box::new(TweqDepressable::new()), // send tweqstart, then tweqoff?
-            "tweqable" => Box::new(Tweqable::new()),               // send tweqstart on frob?
-            "modelswappable" => Box::new(Tweqable::new()), // send tweqstart on signal on? eng1: fluidics control? same as traptweq?
-            "tweqlockedbutton" => Box::new(CompositeScript::new(vec![
-                Box::new(BaseButton::new()),
-                Box::new(TweqDepressable::new()),
-            ])),
-            "objconsumebutton" => Box::new(CompositeScript::new(vec![
-                Box::new(ObjConsumeButton::new()),
-                Box::new(Tweqable::new()),
-            ])),
-
-            // Rooms:
-            "baseroom" => Box::new(CoreRoom::new()),
-            "coreroom" => Box::new(CoreRoom::new()),
-            "onceroom" => Box::new(OnceRoom::new()),
-            "emailroom" => Box::new(TrapEmail::new()),
-            "zerogravroom" => Box::new(CoreRoom::new()),
-
-            // Questbits:
-            "frobqb" => Box::new(FrobQB::new()),
-            "trapqbnegfilter" => Box::new(TrapQBNegFilter::new()),
-            "trapqbfilter" => Box::new(TrapQBFilter::new()),
-            "trapqbset" => Box::new(TrapQBSet::new()),
-            "trapquestbitsimple" => Box::new(TrapQuestbitSimple::new()),
-
-            // TODO:
-            "simplelevelchangebutton" => Box::new(UnimplementedScript::new(&script_name)), // rec1
-            "freezefx" => Box::new(UnimplementedScript::new(&script_name)), // command1
-            "torpedolift" => Box::new(UnimplementedScript::new(&script_name)), // rick1
-            "torpedohack" => Box::new(UnimplementedScript::new(&script_name)), // rick1
-            "eraseradiation" => Box::new(UnimplementedScript::new(&script_name)), // rick1
-
-            // station:
-            "oldstylebaseelevator" => Box::new(UnimplementedScript::new(&script_name)),
-            "choosemission" => Box::new(PanicOnLoadScript::new(&script_name)),
-
-            // "trapquestbit" => Box::new(UnimplementedScript {
-            //     name: "trapquestbit".to_owned(),
-            // }),
-
-            // TODO:
-
-            // partially implemented:
-            "keypadunhackable" => gui_script(Box::new(KeyPadGui)),
-            "keypad" => gui_script(Box::new(KeyPadGui)),
-            "securitycomputer" => Box::new(UnimplementedScript::new(&script_name)),
-            "resurrectmachine" => Box::new(BaseButton {}),
-            "twostatebutton" => Box::new(BaseButton::new()),
-
-            // weapons:
-            "delaygrenade" => Box::new(UnimplementedScript::new(&script_name)),
-            "annelidmodify" => Box::new(UnimplementedScript::new(&script_name)),
-            "empmodify" => Box::new(NoopScript::new()),
-            "lasermodify" => Box::new(NoopScript::new()),
-            "fusionmodify" => Box::new(NoopScript::new()),
-            "riflemodify" => Box::new(NoopScript::new()),
-            "stasismodify" => Box::new(NoopScript::new()),
-            "shotgunmodify" => Box::new(NoopScript::new()),
-            "energyweapon" => Box::new(NoopScript::new()),
-            "grenademodify" => Box::new(NoopScript::new()),
-            "weapontrainer" => Box::new(UnimplementedScript::new(&script_name)),
-            "wrench" => Box::new(CompositeScript::new(vec![
-                Box::new(MeleeWeapon::new()),
-                Box::new(InternalSwitchHeldModelScript::new()),
-            ])),
-            "psiampscript" => Box::new(CompositeScript::new(vec![
-                Box::new(WeaponScript::new()),
-                Box::new(InternalSwitchHeldModelScript::new()),
-            ])),
-            "stasismodify" => Box::new(UnimplementedScript::new(&script_name)),
-            "viralmodify" => Box::new(UnimplementedScript::new(&script_name)),
-
-            //goodies:
-            "expcookie" => Box::new(UnimplementedScript::new(&script_name)), // cyber modules
-            "medkitscript" => Box::new(UnimplementedScript::new(&script_name)), // cyber modules
-            "speedpatch" => Box::new(UnimplementedScript::new(&script_name)), // speed boost
-            "radpatch" => Box::new(UnimplementedScript::new(&script_name)),  // speed boost
-            "autoinstallsoft" => Box::new(UnimplementedScript::new(&script_name)), // auto install software
-            "strboost" => Box::new(UnimplementedScript::new(&script_name)),        // strength boost
-            "intboost" => Box::new(UnimplementedScript::new(&script_name)),
-            "statboostimplant" => Box::new(UnimplementedScript::new(&script_name)),
-
-            // earth:
-            "comestible" => Box::new(UnimplementedScript::new(&script_name)),
-            "liquor" => Box::new(NoopScript::new()),
-
-            // Not implemented - new medsci1 ones:
-            "apparition" => Box::new(UnimplementedScript::new(&script_name)),
-            "ectoplasm" => Box::new(UnimplementedScript::new(&script_name)),
-            "medpatchscript" => Box::new(UnimplementedScript::new(&script_name)),
-            "psikitscript" => Box::new(UnimplementedScript::new(&script_name)),
-            "trapterminator" => Box::new(UnimplementedScript::new(&script_name)),
-            "computer" => Box::new(UnimplementedScript::new(&script_name)),
-            "lightsoundon" => Box::new(NoopScript::new()),
-            "hackablecrate" => Box::new(UnimplementedScript::new(&script_name)),
-            "turret" => Box::new(UnimplementedScript::new(&script_name)),
-            "triggerdestroy" => Box::new(NoopScript::new()),
-
-            // skill point machines
-            "psitrainer" => Box::new(UnimplementedScript::new(&script_name)),
-            "techtrainer" => Box::new(UnimplementedScript::new(&script_name)),
-            "statstrainer" => Box::new(UnimplementedScript::new(&script_name)),
-            "traitmachine" => Box::new(NoopScript::new()),
-
-            // medsci2
-            // Keycard in watt's office
-            "minigameboy" => gui_script(Box::new(GamePigGui)),
-            "minigamecart" => Box::new(NoopScript::new()),
-            "forcedoor" => Box::new(UnimplementedScript::new(&script_name)),
-            "wormpilescript" => Box::new(UnimplementedScript::new(&script_name)),
-            "trapradcleanse" => Box::new(UnimplementedScript::new(&script_name)),
-            "armorscript" => Box::new(NoopScript::new()),
-            "battery" => Box::new(UnimplementedScript::new(&script_name)),
-            "healingstation" => Box::new(UnimplementedScript::new(&script_name)),
-            "brokenhealingstation" => Box::new(UnimplementedScript::new(&script_name)),
-
-            // eng1
-            "healinggland" => Box::new(UnimplementedScript::new(&script_name)),
-            "researchableusescript" => Box::new(UnimplementedScript::new(&script_name)),
-            "beakerscript" => Box::new(UnimplementedScript::new(&script_name)),
-            "trapmetapropbylist" => Box::new(NoopScript::new()),
-
-            // eng2
-            "overlord" => Box::new(UnimplementedScript::new(&script_name)),
-            "freemodify" => Box::new(UnimplementedScript::new(&script_name)),
-            "manybrain" => Box::new(UnimplementedScript::new(&script_name)),
-            "trapsuicide" => Box::new(UnimplementedScript::new(&script_name)),
-            // many ride?
-            "paralyzeplayers" => Box::new(UnimplementedScript::new(&script_name)),
-            "standupagain" => Box::new(UnimplementedScript::new(&script_name)),
-            "sitdownrightnow" => Box::new(UnimplementedScript::new(&script_name)),
-
-            // hydro1
-            "transluceinout" => Box::new(UnimplementedScript::new(&script_name)),
-            "freerepair" => Box::new(UnimplementedScript::new(&script_name)),
-            "cancerstick" => Box::new(UnimplementedScript::new(&script_name)),
-
-            // hydro2
-            "trapparticle" => Box::new(UnimplementedScript::new(&script_name)),
-            "freehack" => Box::new(UnimplementedScript::new(&script_name)),
-
-            // hydro3
-            "poweredarmor" => Box::new(UnimplementedScript::new(&script_name)),
-
-            // ops2
-            "slotmachine" => Box::new(UnimplementedScript::new(&script_name)),
-
-            // rec1
-            // elevator buttons
-            "elevatorbutton" => gui_script(Box::new(ElevatorGui)),
-            "pictureswap" => Box::new(NoopScript::new()),
-            "testimplant" => Box::new(UnimplementedScript::new(&script_name)),
-
-            // ric2:
-            "shakeyourbooty" => Box::new(UnimplementedScript::new(&script_name)), // what does this one do?
-
-            // command1: some crazy scripts here
-            "rerouteelevatorbutton" => Box::new(NoopScript::new()),
-            "trapambientoff" => Box::new(NoopScript::new()),
-            "trapcollideoff" => Box::new(NoopScript::new()),
-            "tweqbutton" => Box::new(NoopScript::new()),
-            "tweqtrap" => Box::new(NoopScript::new()),
-            "putbombinreplicator" => Box::new(NoopScript::new()),
-            "trapunref" => Box::new(NoopScript::new()),
-
-            // shodan
-            // TODO: What's the difference between base elevator / dont stop elevator?
-            "dontstopelevator" => Box::new(BaseElevator::continuous()),
-
-            // Not implemented
-            // TODO: Handle keypad code
-            "ammoscript" => Box::new(NoopScript::new()),
-            //"BaseElevator" => Box::new(UnimplementedScript::new(&name)),
-            "baselight" => Box::new(NoopScript {}),
-            //"baseai" => Box::new(PanicOnLoadScript::new(&script_name)),
-            "baseai" => Box::new(NoopScript::new()),
-            "basemonster" => Box::new(BaseMonster::new()),
-            "cameraalert" => Box::new(UnimplementedScript::new(&script_name)),
-            "cameradeath" => Box::new(UnimplementedScript::new(&script_name)),
-            "censor" => Box::new(UnimplementedScript::new(&script_name)),
-            "censorme" => Box::new(UnimplementedScript::new(&script_name)),
-            "creaturecontainer" => Box::new(NoopScript {}),
-            "chemical" => Box::new(NoopScript::new()),
-            "chooseservice" => Box::new(ChooseServiceScript::new()),
-            "infocomputer" => Box::new(UnimplementedScript::new(&script_name)),
-            "lootable" => Box::new(UnimplementedScript::new(&script_name)),
-            "minigamecart" => Box::new(UnimplementedScript::new(&script_name)),
-            "reducehp" => Box::new(UnimplementedScript::new(&script_name)),
-            "reducepsi" => Box::new(UnimplementedScript::new(&script_name)),
-            "replicatorscript" => gui_script(Box::new(ReplicatorGui)),
-            "researchablescript" => Box::new(UnimplementedScript::new(&script_name)),
-            "setupinitialdebrief" => Box::new(NoopScript {}),
-            "toxinpatch" => Box::new(UnimplementedScript::new(&script_name)),
-            "transientcorpse" => Box::new(UnimplementedScript::new(&script_name)),
-            // Need to read ambient hacked property
-            "triggerecology" => Box::new(UnimplementedScript::new(&script_name)),
-            "triggerecologydiff" => Box::new(UnimplementedScript::new(&script_name)),
-            "unhackhack" => Box::new(UnimplementedScript::new(&script_name)),
-            _ => Box::new(PanicOnLoadScript { name: script_name }),
-        }
+    pub fn registry(&self) -> &ScriptRegistry {
+        &self.registry
     }
 
     pub fn add_entity(&mut self, entity_id: EntityId, script_name: &str) {
-        let script = Self::create_script(script_name.to_ascii_lowercase());
+        if self.registry.status(script_name) == ScriptStatus::Missing {
+            warn!(
+                "entity {:?} uses unknown script: {}",
+                entity_id, script_name
+            );
+        }
+        let script = self.registry.create(script_name);
         self.add_entity2(entity_id, script);
     }
 
@@ -757,3 +467,348 @@ impl ScriptWorld {
         ret
     }
 }
+
+pub(crate) fn register_builtin_scripts(registry: &mut ScriptRegistry) {
+    // PROJECTILE stuff
+    registry.register_noop("lasershot");
+    registry.register_noop("timedgrenade");
+    registry.register_noop("transluceinoutprop");
+
+    // KEYCARD stuff
+    registry.register_noop("trapunlock");
+
+    registry.register("createsound", || Box::new(CreateSound::new()));
+
+    // INTERACTIVE stuff
+    registry.register("trapslayer", || Box::new(TrapSlayer::new()));
+    registry.register("deadpowercell", || Box::new(DeadPowerCell::new()));
+    registry.register("energystation", || Box::new(EnergyStation::new()));
+    registry.register("toolconsumable", || Box::new(ToolConsumable::new()));
+
+    // AI stuff
+    registry.register("trapsignal", || Box::new(TrapSignal::new()));
+    registry.register("gooegg", || Box::new(Tweqable::new()));
+    registry.register("grubegg", || Box::new(Tweqable::new()));
+    registry.register("swarmeregg", || Box::new(Tweqable::new()));
+    registry.register("containerscript", || {
+        gui_script(Box::new(ContainerGui::loot_container()))
+    });
+
+    registry.register_noop("lootable");
+
+    // Weapons
+    registry.register("weaponscript", || {
+        Box::new(CompositeScript::new(vec![
+            Box::new(WeaponScript::new()),
+            Box::new(InternalSwitchHeldModelScript::new()),
+        ]))
+    });
+    for modify_script in [
+        "pistolmodify",
+        "shotgunmodify",
+        "riflemodify",
+        "lasermodify",
+        "empmodify",
+        "grenademodify",
+        "fusionmodify",
+        "stasismodify",
+        "annelidmodify",
+        "viralmodify",
+    ] {
+        let kind = WeaponKind::from_modify_script(modify_script).unwrap();
+        registry.register(modify_script, move || Box::new(WeaponModify::new(kind)));
+    }
+    registry.register("ammoscript", || Box::new(AmmoScript::new()));
+
+    // TODO: Necessary
+    registry.register_noop("changeinterface");
+    registry.register_noop("reducehp");
+    registry.register_noop("engineremoverad");
+    registry.register_noop("radroom");
+    registry.register_noop("trapspawn");
+    // ops1 cutscene
+    registry.register_noop("transluceinoutholo");
+    registry.register_noop("cs9_doorreporter");
+    registry.register_noop("cs9_eggsandgrubs");
+    registry.register_noop("cs9_mastercontrol");
+    registry.register_noop("cs9_holorumbler");
+    registry.register_noop("cs9_shodanscreen");
+    registry.register_noop("sitdownrightnowmp");
+    registry.register_noop("trapdestroyteleport");
+    registry.register_noop("triggerdamage");
+    // many.micontain
+    registry.register_noop("brain");
+    registry.register_noop("wormheartimplant");
+    registry.register_noop("wormskin");
+    // shodan.mis
+    registry.register_noop("toggleshodantexture");
+    registry.register_noop("changedelay"); //?
+    registry.register_noop("shodanhead"); //?
+    registry.register_noop("shodanshield"); //?
+    registry.register("seatplayer", || Box::new(TrapTeleportPlayer::new())); // This is used in final battle -does this do anything else besides teleport?
+    registry.register_noop("dieshodandie"); // end cutscene!
+    registry.register_noop("teleportpath"); // end cutscene!
+    registry.register_noop("translucebydamage"); // end cutscene!
+
+    // TODO: Should these actually be implemented?
+    registry.register_noop("earthtext");
+    registry.register_noop("trapgravity"); // medsci1 - vent that falls
+    registry.register_noop("trapmessage"); // eng2 - installing override. What prop for message? Where to load string?
+    registry.register_noop("charmable");
+    registry.register_noop("transientcorpse");
+    registry.register_noop("whiteout");
+    registry.register_noop("vaporizeinventory");
+
+    // Internal
+    registry.register("internal_collision_type", || {
+        Box::new(InternalCollisionType::new())
+    });
+    registry.register("internal_inventory", || {
+        gui_script(Box::new(ContainerGui::inv_container()))
+    });
+    // "internal_inventory" => Box::new(PanicOnLoadScript::new("internal_inventory")),
+    registry.register("internal_keycard", || Box::new(KeyCardScript::new()));
+    registry.register("internal_player", || Box::new(InternalPlayer::new()));
+    registry.register("internal_room_trigger", || Box::new(RoomTrigger::new()));
+    registry.register("internal_simple_health", || {
+        Box::new(InternalSimpleHealth::new())
+    });
+    // Implemented
+    registry.register("basebutton", || Box::new(BaseButton::new()));
+    registry.register("baseelevator", || Box::new(BaseElevator::new()));
+    registry.register("destroyallbyname", || Box::new(DestroyAllByName::new()));
+    registry.register("levelchangebutton", || Box::new(LevelChangeButton::new()));
+    registry.register("logdiscscript", || Box::new(LogDiscScript::new()));
+    registry.register("oncerouter", || Box::new(OnceRouter::new()));
+    registry.register("resurrectmachine", || Box::new(ResurrectMachine::new()));
+    registry.register("stddoor", || Box::new(StdDoor::new()));
+    registry.register("trapdelay", || Box::new(TrapDelay::new()));
+    registry.register("trapterminator", || Box::new(TrapDestroyer::new())); // TODO: What is the difference between Terminate vs Destroyer vs Destroy?
+    registry.register("trapdestroyer", || Box::new(TrapDestroyer::new()));
+    registry.register("trapdestroy", || Box::new(TrapDestroyer::new()));
+    registry.register("trapemail", || Box::new(TrapEmail::new()));
+    registry.register("trapexponce", || Box::new(TrapEXPOnce::new()));
+    registry.register("trapinverter", || Box::new(TrapInverter::new()));
+    registry.register("trapnewtripwire", || Box::new(TrapNewTripwire::new()));
+    registry.register("trapofffilter", || Box::new(TrapOffFilter::new()));
+    registry.register("traponfilter", || Box::new(TrapOffFilter::new()));
+    registry.register("trapteleportplayer", || Box::new(TrapTeleportPlayer::new()));
+    registry.register("traprouter", || Box::new(TrapRouter::new()));
+    registry.register("trapsound", || Box::new(TrapSound::new()));
+    registry.register("trapsoundamb", || Box::new(TrapSound::new()));
+    registry.register("trapteleport", || Box::new(TrapTeleport::new()));
+    registry.register("traptriplevel", || Box::new(TrapTripLevel::new()));
+    registry.register("triggermulti", || Box::new(TriggerMulti::new())); // nacelle control
+    registry.register("triggercollide", || Box::new(TriggerCollide::new()));
+    registry.register("usesound", || Box::new(UseSound::new()));
+
+    // TWEQ stuff
+    registry.register("traptweq", || {
+        Box::new(CompositeScript::new(vec![
+            Box::new(Tweqable::new()),
+            Box::new(TrapTweq::new()),
+        ]))
+    });
+    registry.register("tweqdepressable", || Box::new(TweqDepressable::new())); // send tweqstart, then tweqoff?
+    registry.register("tweqable", || Box::new(Tweqable::new())); // send tweqstart on frob?
+    registry.register("modelswappable", || Box::new(Tweqable::new())); // send tweqstart on signal on? eng1: fluidics control? same as traptweq?
+    registry.register("tweqlockedbutton", || {
+        Box::new(CompositeScript::new(vec![
+            Box::new(BaseButton::new()),
+            Box::new(TweqDepressable::new()),
+        ]))
+    });
+    registry.register("objconsumebutton", || {
+        Box::new(CompositeScript::new(vec![
+            Box::new(ObjConsumeButton::new()),
+            Box::new(Tweqable::new()),
+        ]))
+    });
+
+    // Rooms:
+    registry.register("baseroom", || Box::new(CoreRoom::new()));
+    registry.register("coreroom", || Box::new(CoreRoom::new()));
+    registry.register("onceroom", || Box::new(OnceRoom::new()));
+    registry.register("emailroom", || Box::new(TrapEmail::new()));
+    registry.register("zerogravroom", || Box::new(CoreRoom::new()));
+
+    // Questbits:
+    registry.register("frobqb", || Box::new(FrobQB::new()));
+    registry.register("trapqbnegfilter", || Box::new(TrapQBNegFilter::new()));
+    registry.register("trapqbfilter", || Box::new(TrapQBFilter::new()));
+    registry.register("trapqbset", || Box::new(TrapQBSet::new()));
+    registry.register("trapquestbitsimple", || Box::new(TrapQuestbitSimple::new()));
+
+    // TODO:
+    registry.register_unimplemented("simplelevelchangebutton"); // rec1
+    registry.register_unimplemented("freezefx"); // command1
+    registry.register_unimplemented("torpedolift"); // rick1
+    registry.register_unimplemented("torpedohack"); // rick1
+    registry.register_unimplemented("eraseradiation"); // rick1
+
+    // station:
+    registry.register_unimplemented("oldstylebaseelevator");
+    registry.register_unimplemented("choosemission");
+
+    // "trapquestbit" => Box::new(UnimplementedScript {
+    //     name: "trapquestbit".to_owned(),
+    // }),
+
+    // TODO:
+
+    // partially implemented:
+    registry.register("keypadunhackable", || {
+        gui_script(Box::new(KeyPadGui::unhackable()))
+    });
+    registry.register("keypad", || gui_script(Box::new(KeyPadGui::hackable())));
+    registry.register("securitycomputer", || {
+        gui_script(Box::new(HackGui::new(HackTarget::SecurityComputer)))
+    });
+    registry.register("twostatebutton", || Box::new(BaseButton::new()));
+
+    // weapons:
+    registry.register_unimplemented("delaygrenade");
+    registry.register_noop("energyweapon");
+    registry.register("weapontrainer", || Box::new(WeaponTrainer::new()));
+    registry.register("wrench", || {
+        Box::new(CompositeScript::new(vec![
+            Box::new(MeleeWeapon::new()),
+            Box::new(InternalSwitchHeldModelScript::new()),
+        ]))
+    });
+    registry.register("psiampscript", || {
+        Box::new(CompositeScript::new(vec![
+            Box::new(PsiAmpScript::new()),
+            Box::new(InternalSwitchHeldModelScript::new()),
+        ]))
+    });
+
+    //goodies:
+    registry.register("expcookie", || Box::new(ExpCookie::new())); // cyber modules
+    registry.register_unimplemented("medkitscript"); // cyber modules
+    registry.register_unimplemented("speedpatch"); // speed boost
+    registry.register_unimplemented("radpatch"); // speed boost
+    registry.register_unimplemented("autoinstallsoft"); // auto install software
+    registry.register("strboost", || Box::new(StatBoost::new(Stat::Strength))); // strength boost
+    registry.register("intboost", || Box::new(StatBoost::new(Stat::Cyber)));
+    registry.register("statboostimplant", || Box::new(StatBoostImplant::new()));
+
+    // earth:
+    registry.register_unimplemented("comestible");
+    registry.register_noop("liquor");
+
+    // Not implemented - new medsci1 ones:
+    registry.register_unimplemented("apparition");
+    registry.register_unimplemented("ectoplasm");
+    registry.register("medpatchscript", || Box::new(PlayerHypo::health()));
+    registry.register("psikitscript", || Box::new(PlayerHypo::psi()));
+    registry.register_unimplemented("computer");
+    registry.register_noop("lightsoundon");
+    registry.register("hackablecrate", || {
+        gui_script(Box::new(ContainerGui::hackable_crate()))
+    });
+    registry.register_unimplemented("turret");
+    registry.register_noop("triggerdestroy");
+
+    // skill point machines
+    registry.register("psitrainer", || Box::new(PsiTrainer::new()));
+    registry.register_unimplemented("techtrainer");
+    registry.register_unimplemented("statstrainer");
+    registry.register_noop("traitmachine");
+
+    // medsci2
+    // Keycard in watt's office
+    registry.register("minigameboy", || gui_script(Box::new(GamePigGui)));
+    registry.register_noop("minigamecart");
+    registry.register_unimplemented("forcedoor");
+    registry.register_unimplemented("wormpilescript");
+    registry.register_unimplemented("trapradcleanse");
+    registry.register_noop("armorscript");
+    registry.register_unimplemented("battery");
+    registry.register_unimplemented("healingstation");
+    registry.register_unimplemented("brokenhealingstation");
+
+    // eng1
+    registry.register_unimplemented("healinggland");
+    registry.register("researchableusescript", || {
+        Box::new(ResearchableScript::new())
+    });
+    // Chemicals aren't needed for research until the gamesys research properties are read
+    registry.register_unimplemented("beakerscript");
+    registry.register_noop("trapmetapropbylist");
+
+    // eng2
+    registry.register_unimplemented("overlord");
+    registry.register("freemodify", || Box::new(FreeWeaponService::modify()));
+    registry.register_unimplemented("manybrain");
+    registry.register_unimplemented("trapsuicide");
+    // many ride?
+    registry.register_unimplemented("paralyzeplayers");
+    registry.register_unimplemented("standupagain");
+    registry.register_unimplemented("sitdownrightnow");
+
+    // hydro1
+    registry.register_unimplemented("transluceinout");
+    registry.register("freerepair", || Box::new(FreeWeaponService::repair()));
+    registry.register_unimplemented("cancerstick");
+
+    // hydro2
+    registry.register_unimplemented("trapparticle");
+    // ICE picks are used by giving them to a hackable object - its hack GUI handles them
+    registry.register_noop("freehack");
+
+    // hydro3
+    registry.register_unimplemented("poweredarmor");
+
+    // ops2
+    registry.register_unimplemented("slotmachine");
+
+    // rec1
+    // elevator buttons
+    registry.register("elevatorbutton", || gui_script(Box::new(ElevatorGui)));
+    registry.register_noop("pictureswap");
+    registry.register_unimplemented("testimplant");
+
+    // ric2:
+    registry.register_unimplemented("shakeyourbooty"); // what does this one do?
+
+    // command1: some crazy scripts here
+    registry.register_noop("rerouteelevatorbutton");
+    registry.register_noop("trapambientoff");
+    registry.register_noop("trapcollideoff");
+    registry.register_noop("tweqbutton");
+    registry.register_noop("tweqtrap");
+    registry.register_noop("putbombinreplicator");
+    registry.register_noop("trapunref");
+
+    // shodan
+    // TODO: What's the difference between base elevator / dont stop elevator?
+    registry.register("dontstopelevator", || Box::new(BaseElevator::continuous()));
+
+    // Not implemented
+    // TODO: Handle keypad code
+    //"BaseElevator" => Box::new(UnimplementedScript::new(&name)),
+    registry.register_noop("baselight");
+    //"baseai" => Box::new(PanicOnLoadScript::new(&script_name)),
+    registry.register_noop("baseai");
+    registry.register("basemonster", || Box::new(BaseMonster::new()));
+    registry.register("cameraalert", || Box::new(CameraAlert::new()));
+    registry.register("cameradeath", || Box::new(CameraDeath::new()));
+    registry.register_unimplemented("censor");
+    registry.register_unimplemented("censorme");
+    registry.register_noop("creaturecontainer");
+    registry.register_noop("chemical");
+    registry.register("chooseservice", || Box::new(ChooseServiceScript::new()));
+    registry.register_unimplemented("infocomputer");
+    registry.register_unimplemented("reducepsi");
+    registry.register("replicatorscript", || gui_script(Box::new(ReplicatorGui)));
+    registry.register("researchablescript", || Box::new(ResearchableScript::new()));
+    registry.register_noop("setupinitialdebrief");
+    registry.register_unimplemented("toxinpatch");
+    // Need to read ambient hacked property
+    registry.register_unimplemented("triggerecology");
+    registry.register_unimplemented("triggerecologydiff");
+    registry.register("unhackhack", || {
+        gui_script(Box::new(HackGui::new(HackTarget::Device)))
+    });
+}
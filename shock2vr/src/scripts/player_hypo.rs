@@ -0,0 +1,65 @@
+use shipyard::{EntityId, UniqueView, World};
+
+use crate::{mission::PlayerInfo, physics::PhysicsWorld, player_stats::PlayerStats};
+
+use super::{Effect, MessagePayload, Script};
+
+const MED_HYPO_HIT_POINTS: i32 = 10;
+const PSI_HYPO_PSI_POINTS: i32 = 20;
+
+enum HypoKind {
+    Health,
+    Psi,
+}
+
+// Hypos (medpatchscript, psikitscript) - restore the player's hit points or psi points when used
+pub struct PlayerHypo {
+    kind: HypoKind,
+}
+
+impl PlayerHypo {
+    pub fn health() -> PlayerHypo {
+        PlayerHypo {
+            kind: HypoKind::Health,
+        }
+    }
+
+    pub fn psi() -> PlayerHypo {
+        PlayerHypo {
+            kind: HypoKind::Psi,
+        }
+    }
+}
+
+impl Script for PlayerHypo {
+    fn handle_message(
+        &mut self,
+        entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        match msg {
+            MessagePayload::Frob | MessagePayload::TriggerPull => {
+                let player_stats = world.borrow::<UniqueView<PlayerStats>>().unwrap();
+                let restore_effect = match self.kind {
+                    HypoKind::Health => {
+                        let player_entity = world.borrow::<UniqueView<PlayerInfo>>().unwrap();
+                        Effect::AdjustHitPoints {
+                            entity_id: player_entity.entity_id,
+                            delta: player_stats.hypo_amount(MED_HYPO_HIT_POINTS),
+                        }
+                    }
+                    HypoKind::Psi => Effect::AdjustPsiPoints {
+                        delta: player_stats.hypo_amount(PSI_HYPO_PSI_POINTS),
+                    },
+                };
+
+                Effect::Combined {
+                    effects: vec![restore_effect, Effect::DestroyEntity { entity_id }],
+                }
+            }
+            _ => Effect::NoEffect,
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use dark::properties::Link;
+use engine::audio::AudioHandle;
+use shipyard::{EntityId, UniqueView, World};
+use tracing::info;
+
+use crate::{
+    mission::PlayerInfo, physics::PhysicsWorld, player_stats::PlayerStats,
+    psi_powers::PsiPowerEffect,
+};
+
+use super::{
+    script_util::{get_all_links_with_template, play_environmental_sound},
+    weapon_script::create_projectile,
+    Effect, Message, MessagePayload, Script,
+};
+
+///
+/// PsiAmpScript
+///
+/// Channels the selected psi power when the trigger is pulled, as long as the player has the psi
+/// points and the power isn't cooling down. The A button cycles through the learned powers.
+pub struct PsiAmpScript {}
+impl PsiAmpScript {
+    pub fn new() -> PsiAmpScript {
+        PsiAmpScript {}
+    }
+}
+
+impl Script for PsiAmpScript {
+    fn handle_message(
+        &mut self,
+        entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        match msg {
+            MessagePayload::TriggerPull => {
+                let player_stats = world.borrow::<UniqueView<PlayerStats>>().unwrap();
+                let maybe_power = player_stats
+                    .psi()
+                    .selected()
+                    .filter(|power| player_stats.can_use_psi_power(*power));
+
+                // Only spend the psi points if the whole power can be channelled
+                let maybe_power_effects = maybe_power.and_then(|power| {
+                    power
+                        .effects()
+                        .iter()
+                        .map(|effect| to_effect(world, entity_id, effect))
+                        .collect::<Option<Vec<Effect>>>()
+                        .map(|effects| (power, effects))
+                });
+
+                match maybe_power_effects {
+                    Some((power, power_effects)) => {
+                        let mut effects = vec![
+                            Effect::UsePsiPower { power },
+                            play_environmental_sound(
+                                world,
+                                entity_id,
+                                "shoot",
+                                vec![],
+                                AudioHandle::new(),
+                            ),
+                        ];
+                        effects.extend(power_effects);
+                        Effect::combine(effects)
+                    }
+                    None => play_environmental_sound(
+                        world,
+                        entity_id,
+                        "empty",
+                        vec![],
+                        AudioHandle::new(),
+                    ),
+                }
+            }
+            MessagePayload::AltPress => Effect::SelectNextPsiPower,
+            _ => Effect::NoEffect,
+        }
+    }
+}
+
+///
+/// to_effect
+///
+/// The game effect for part of a psi power. None if it can't be channelled - ie, the amp doesn't
+/// have a Projectile link for the power.
+fn to_effect(world: &World, entity_id: EntityId, psi_effect: &PsiPowerEffect) -> Option<Effect> {
+    let effect = match psi_effect {
+        PsiPowerEffect::Projectile { index } => {
+            let projectiles = get_all_links_with_template(world, entity_id, |link| match link {
+                Link::Projectile(data) => Some(*data),
+                _ => None,
+            });
+            match projectiles.get(*index) {
+                Some((template_id, options)) => {
+                    create_projectile(world, entity_id, *template_id, options)
+                }
+                None => {
+                    info!("psi amp is missing projectile link: {}", index);
+                    return None;
+                }
+            }
+        }
+        // Buffs are applied along with the psi cost
+        PsiPowerEffect::Buff { .. } => Effect::NoEffect,
+        PsiPowerEffect::Telekinesis => Effect::Telekinesis { entity_id },
+        PsiPowerEffect::RechargeHeldItems => {
+            let player_info = world.borrow::<UniqueView<PlayerInfo>>().unwrap();
+            Effect::combine(
+                [
+                    player_info.left_hand_entity_id,
+                    player_info.right_hand_entity_id,
+                ]
+                .into_iter()
+                .flatten()
+                .filter(|held_entity_id| *held_entity_id != entity_id)
+                .map(|held_entity_id| Effect::Send {
+                    msg: Message {
+                        to: held_entity_id,
+                        payload: MessagePayload::Recharge,
+                    },
+                })
+                .collect(),
+            )
+        }
+        PsiPowerEffect::AdjustHitPoints { delta } => {
+            let player_entity_id = world.borrow::<UniqueView<PlayerInfo>>().unwrap().entity_id;
+            Effect::AdjustHitPoints {
+                entity_id: player_entity_id,
+                delta: *delta,
+            }
+        }
+        PsiPowerEffect::AdjustPsiPoints { delta } => Effect::AdjustPsiPoints { delta: *delta },
+    };
+    Some(effect)
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{vec3, Matrix4, Quaternion};
+    use dark::properties::{Links, ProjectileOptions, ToLink};
+
+    use super::*;
+    use crate::{
+        psi_powers::{PsiPower, ALL_PSI_POWERS},
+        runtime_props::RuntimePropTransform,
+    };
+
+    const CRYOKINESIS_TEMPLATE_ID: i32 = -100;
+
+    struct TestWorld {
+        world: World,
+        player: EntityId,
+        amp: EntityId,
+        held_weapon: EntityId,
+    }
+
+    // The amp's Projectile links, by template id
+    fn create_world(projectile_template_ids: &[i32]) -> TestWorld {
+        let mut world = World::new();
+        let player = world.add_entity(());
+        let inventory = world.add_entity(());
+        let held_weapon = world.add_entity(());
+        let links = Links {
+            to_links: projectile_template_ids
+                .iter()
+                .map(|template_id| ToLink {
+                    to_template_id: *template_id,
+                    to_entity_id: None,
+                    link: Link::Projectile(ProjectileOptions {
+                        order: 0,
+                        setting: 0,
+                    }),
+                })
+                .collect(),
+        };
+        let amp = world.add_entity((RuntimePropTransform(Matrix4::from_scale(1.0)), links));
+
+        world.add_unique(PlayerInfo {
+            pos: vec3(0.0, 0.0, 0.0),
+            rotation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+            entity_id: player,
+            left_hand_entity_id: Some(amp),
+            right_hand_entity_id: Some(held_weapon),
+            inventory_entity_id: inventory,
+        });
+
+        TestWorld {
+            world,
+            player,
+            amp,
+            held_weapon,
+        }
+    }
+
+    fn power_effects(test_world: &TestWorld, power: PsiPower) -> Option<Vec<Effect>> {
+        power
+            .effects()
+            .iter()
+            .map(|effect| to_effect(&test_world.world, test_world.amp, effect))
+            .collect()
+    }
+
+    #[test]
+    fn test_projectile_power_launches_template() {
+        let test_world = create_world(&[CRYOKINESIS_TEMPLATE_ID]);
+        let effects = power_effects(&test_world, PsiPower::ProjectedCryokinesis).unwrap();
+
+        assert_eq!(effects.len(), 1);
+        assert!(matches!(
+            &effects[0],
+            Effect::CreateEntity { template_id, options, .. }
+                if *template_id == CRYOKINESIS_TEMPLATE_ID
+                    && options.launched_by == Some(test_world.player)
+        ));
+    }
+
+    #[test]
+    fn test_missing_projectile_link_is_not_channelled() {
+        let test_world = create_world(&[]);
+        for power in [
+            PsiPower::ProjectedCryokinesis,
+            PsiPower::ProjectedPyrokinesis,
+            PsiPower::PsionicHypnogenesis,
+        ] {
+            assert!(power_effects(&test_world, power).is_none());
+        }
+    }
+
+    #[test]
+    fn test_healing_powers_target_player() {
+        let test_world = create_world(&[]);
+        for (power, expected_delta) in [
+            (PsiPower::NeuralDecontamination, 10),
+            (PsiPower::SomaTransference, 25),
+        ] {
+            let effects = power_effects(&test_world, power).unwrap();
+            assert_eq!(effects.len(), 1);
+            assert!(matches!(
+                effects[0],
+                Effect::AdjustHitPoints { entity_id, delta }
+                    if entity_id == test_world.player && delta == expected_delta
+            ));
+        }
+
+        let effects = power_effects(&test_world, PsiPower::RecursivePsionicAmplification).unwrap();
+        assert!(matches!(
+            effects[..],
+            [
+                Effect::AdjustHitPoints { delta: -5, .. },
+                Effect::AdjustPsiPoints { delta: 10 }
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_telekinesis_and_recharge() {
+        let test_world = create_world(&[]);
+
+        let effects = power_effects(&test_world, PsiPower::KineticRedirection).unwrap();
+        assert!(matches!(
+            effects[..],
+            [Effect::Telekinesis { entity_id }] if entity_id == test_world.amp
+        ));
+
+        // Recharges what's in the other hand, but not the amp itself
+        let effects = power_effects(&test_world, PsiPower::ElectronCascade).unwrap();
+        let recharged = Effect::flatten(effects)
+            .into_iter()
+            .filter_map(|effect| match effect {
+                Effect::Send { msg } if matches!(msg.payload, MessagePayload::Recharge) => {
+                    Some(msg.to)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(recharged, vec![test_world.held_weapon]);
+    }
+
+    #[test]
+    fn test_buffs_have_no_direct_effect() {
+        let test_world = create_world(&[]);
+        for power in [
+            PsiPower::PsychoReflectiveScreen,
+            PsiPower::PsionicActionCloak,
+            PsiPower::MetacreativeBarrier,
+        ] {
+            let effects = power_effects(&test_world, power).unwrap();
+            assert!(Effect::flatten(effects).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_every_power_can_be_channelled() {
+        let test_world = create_world(&[CRYOKINESIS_TEMPLATE_ID, -101, -102]);
+        for power in ALL_PSI_POWERS {
+            assert!(
+                power_effects(&test_world, power).is_some(),
+                "{} can't be channelled",
+                power.name()
+            );
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use shipyard::{EntityId, UniqueView, World};
+
+use crate::{
+    physics::PhysicsWorld,
+    player_stats::{PlayerStats, Stat},
+};
+
+use super::{Effect, MessagePayload, Script};
+
+// Psi upgrade units - frobbing the unit spends cyber modules to learn the next psi power the
+// player's psi level allows
+pub struct PsiTrainer {}
+
+impl PsiTrainer {
+    pub fn new() -> PsiTrainer {
+        PsiTrainer {}
+    }
+}
+
+impl Script for PsiTrainer {
+    fn handle_message(
+        &mut self,
+        _entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        match msg {
+            MessagePayload::Frob => {
+                let player_stats = world.borrow::<UniqueView<PlayerStats>>().unwrap();
+                match player_stats
+                    .psi()
+                    .next_to_learn(player_stats.stat(Stat::Psi))
+                {
+                    Some(power) => Effect::LearnPsiPower { power },
+                    None => Effect::NoEffect,
+                }
+            }
+            _ => Effect::NoEffect,
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use dark::properties::{Link, PropLog, PropObjName, PropObjShortName, PropScripts};
+use shipyard::{EntityId, Get, UniqueView, View, World};
+
+use crate::{
+    mission::PlayerInfo,
+    physics::PhysicsWorld,
+    player_stats::{PlayerStats, TechSkill},
+    time::Time,
+};
+
+use super::{script_util::get_all_links_with_data, Effect, MessagePayload, Script};
+
+// Time, in seconds, to research an object at the lowest Research skill - each level divides it
+const BASE_RESEARCH_SECONDS: f32 = 120.0;
+
+// Objects that can be researched (researchablescript) - using the object starts the research,
+// which then progresses over time, as long as the object is carried.
+// Objects with researchableusescript can't be used until they are researched.
+// TODO: Research in the original game also needs chemicals to be carried - those requirements
+// live in the gamesys research properties, which aren't read yet, so they aren't enforced.
+pub struct ResearchableScript;
+
+impl ResearchableScript {
+    pub fn new() -> ResearchableScript {
+        ResearchableScript
+    }
+}
+
+impl Script for ResearchableScript {
+    fn update(
+        &mut self,
+        entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        time: &Time,
+    ) -> Effect {
+        let name = match get_object_name(world, entity_id) {
+            Some(name) => name,
+            None => return Effect::NoEffect,
+        };
+
+        let player_stats = world.borrow::<UniqueView<PlayerStats>>().unwrap();
+        let remaining_seconds = match player_stats.active_research() {
+            Some(research) if research.name == name => research.remaining_seconds,
+            _ => return Effect::NoEffect,
+        };
+
+        // If there are several of the same object, only the first one drives the research
+        let maybe_researching_entity = get_possessed_entities(world)
+            .into_iter()
+            .find(|id| get_object_name(world, *id).as_ref() == Some(&name));
+        if maybe_researching_entity != Some(entity_id) {
+            return Effect::NoEffect;
+        }
+
+        let elapsed_seconds = time.elapsed.as_secs_f32();
+        let advance_effect = Effect::AdvanceResearch { elapsed_seconds };
+        if remaining_seconds > elapsed_seconds {
+            return advance_effect;
+        }
+
+        Effect::combine(vec![
+            advance_effect,
+            get_research_complete_email(world, entity_id),
+        ])
+    }
+
+    fn handle_message(
+        &mut self,
+        entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        match msg {
+            MessagePayload::Frob | MessagePayload::TriggerPull => {
+                let player_stats = world.borrow::<UniqueView<PlayerStats>>().unwrap();
+                match get_object_name(world, entity_id) {
+                    Some(name) if !player_stats.is_researched(&name) => {
+                        let research_skill = player_stats.tech_skill(TechSkill::Research);
+                        Effect::BeginResearch {
+                            name,
+                            duration_seconds: BASE_RESEARCH_SECONDS / research_skill as f32,
+                        }
+                    }
+                    _ => Effect::NoEffect,
+                }
+            }
+            _ => Effect::NoEffect,
+        }
+    }
+}
+
+///
+/// needs_research
+///
+/// Whether the object can't be used yet, because it has to be researched first
+pub fn needs_research(world: &World, entity_id: EntityId) -> bool {
+    let v_scripts = world.borrow::<View<PropScripts>>().unwrap();
+    let is_researchable_use = v_scripts
+        .get(entity_id)
+        .map(|prop_scripts| {
+            prop_scripts
+                .scripts
+                .iter()
+                .any(|script| script.eq_ignore_ascii_case("researchableusescript"))
+        })
+        .unwrap_or(false);
+
+    if !is_researchable_use {
+        return false;
+    }
+
+    let player_stats = world.borrow::<UniqueView<PlayerStats>>().unwrap();
+    get_object_name(world, entity_id)
+        .map(|name| !player_stats.is_researched(&name))
+        .unwrap_or(false)
+}
+
+// Lowercase name of the object, preferring the short name
+fn get_object_name(world: &World, entity_id: EntityId) -> Option<String> {
+    let v_short_name = world.borrow::<View<PropObjShortName>>().unwrap();
+    let v_name = world.borrow::<View<PropObjName>>().unwrap();
+    v_short_name
+        .get(entity_id)
+        .map(|PropObjShortName(name)| name.to_owned())
+        .or_else(|_| {
+            v_name
+                .get(entity_id)
+                .map(|PropObjName(name)| name.to_owned())
+        })
+        .ok()
+        .map(|name| name.to_ascii_lowercase())
+}
+
+// Everything the player is carrying - in either hand, or in the inventory
+fn get_possessed_entities(world: &World) -> Vec<EntityId> {
+    let player_info = world.borrow::<UniqueView<PlayerInfo>>().unwrap();
+    let mut possessed: Vec<EntityId> = player_info
+        .left_hand_entity_id
+        .into_iter()
+        .chain(player_info.right_hand_entity_id)
+        .collect();
+
+    let contained =
+        get_all_links_with_data(world, player_info.inventory_entity_id, |link| match link {
+            Link::Contains(ordinal) => Some(*ordinal),
+            _ => None,
+        });
+    possessed.extend(contained.into_iter().map(|(id, _)| id));
+    possessed
+}
+
+fn get_research_complete_email(world: &World, entity_id: EntityId) -> Effect {
+    let v_log = world.borrow::<View<PropLog>>().unwrap();
+    match v_log.get(entity_id) {
+        Ok(log) if log.deck > 0 && log.email > 0 => Effect::PlayEmail {
+            deck: log.deck,
+            email: log.email,
+            force: false,
+        },
+        _ => Effect::NoEffect,
+    }
+}
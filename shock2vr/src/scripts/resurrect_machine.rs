@@ -0,0 +1,40 @@
+use shipyard::{EntityId, World};
+
+use crate::physics::PhysicsWorld;
+
+use super::{base_button::BaseButton, Effect, MessagePayload, Script};
+
+// Quantum Bio-Reconstruction machine - once activated, the player is brought
+// back here (for a fee) if they die in this mission.
+pub struct ResurrectMachine {
+    button: BaseButton,
+}
+
+impl ResurrectMachine {
+    pub fn new() -> ResurrectMachine {
+        ResurrectMachine {
+            button: BaseButton::new(),
+        }
+    }
+}
+
+impl Script for ResurrectMachine {
+    fn handle_message(
+        &mut self,
+        entity_id: EntityId,
+        world: &World,
+        physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        let button_effect = self.button.handle_message(entity_id, world, physics, msg);
+        match msg {
+            MessagePayload::Frob if !self.button.is_locked(entity_id, world) => {
+                Effect::combine(vec![
+                    button_effect,
+                    Effect::ActivateResurrectionMachine { entity_id },
+                ])
+            }
+            _ => button_effect,
+        }
+    }
+}
@@ -0,0 +1,251 @@
+///
+/// script_registry.rs
+///
+/// Maps script names (as they appear in the PropScripts property) to the scripts that implement them.
+/// The built-in scripts are registered by `with_builtin_scripts`, and additional scripts can be
+/// registered on top - for example, by tests or mods.
+///
+use std::collections::HashMap;
+
+use dark::properties::PropScripts;
+use serde::Serialize;
+
+use super::{NoopScript, Script, UnimplementedScript};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum ScriptStatus {
+    Implemented,
+    Noop,
+    Unimplemented,
+    Missing,
+}
+
+struct ScriptRegistration {
+    status: ScriptStatus,
+    factory: Box<dyn Fn() -> Box<dyn Script>>,
+}
+
+pub struct ScriptRegistry {
+    scripts: HashMap<String, ScriptRegistration>,
+}
+
+impl ScriptRegistry {
+    pub fn empty() -> ScriptRegistry {
+        ScriptRegistry {
+            scripts: HashMap::new(),
+        }
+    }
+
+    pub fn with_builtin_scripts() -> ScriptRegistry {
+        let mut registry = ScriptRegistry::empty();
+        super::register_builtin_scripts(&mut registry);
+        registry
+    }
+
+    ///
+    /// register
+    ///
+    /// Register an implementation for the script. Script names are case-insensitive,
+    /// and registering an existing name replaces the previous implementation.
+    pub fn register<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn() -> Box<dyn Script> + 'static,
+    {
+        self.insert(name, ScriptStatus::Implemented, Box::new(factory));
+    }
+
+    ///
+    /// register_noop
+    ///
+    /// Register a script that we know about, but intentionally do nothing for
+    pub fn register_noop(&mut self, name: &str) {
+        self.insert(
+            name,
+            ScriptStatus::Noop,
+            Box::new(|| Box::new(NoopScript::new())),
+        );
+    }
+
+    ///
+    /// register_unimplemented
+    ///
+    /// Register a script that we know about, but still needs to be implemented
+    pub fn register_unimplemented(&mut self, name: &str) {
+        let script_name = name.to_ascii_lowercase();
+        self.insert(
+            name,
+            ScriptStatus::Unimplemented,
+            Box::new(move || Box::new(UnimplementedScript::new(&script_name))),
+        );
+    }
+
+    fn insert(
+        &mut self,
+        name: &str,
+        status: ScriptStatus,
+        factory: Box<dyn Fn() -> Box<dyn Script>>,
+    ) {
+        self.scripts.insert(
+            name.to_ascii_lowercase(),
+            ScriptRegistration { status, factory },
+        );
+    }
+
+    pub fn status(&self, name: &str) -> ScriptStatus {
+        self.scripts
+            .get(&name.to_ascii_lowercase())
+            .map(|registration| registration.status)
+            .unwrap_or(ScriptStatus::Missing)
+    }
+
+    ///
+    /// create
+    ///
+    /// Instantiate the script registered for `name`. Unknown scripts fall back
+    /// to a script that ignores all messages, instead of panicking.
+    pub fn create(&self, name: &str) -> Box<dyn Script> {
+        let script_name = name.to_ascii_lowercase();
+        match self.scripts.get(&script_name) {
+            Some(registration) => (registration.factory)(),
+            None => Box::new(UnimplementedScript::new(&script_name)),
+        }
+    }
+
+    ///
+    /// coverage_report
+    ///
+    /// Summarize which of the scripts used by a set of entities are implemented,
+    /// along with how many entities use each script
+    pub fn coverage_report<'a>(
+        &self,
+        entity_scripts: impl IntoIterator<Item = &'a PropScripts>,
+    ) -> ScriptCoverageReport {
+        let mut script_to_entity_count: HashMap<String, usize> = HashMap::new();
+        for scripts in entity_scripts {
+            let mut entity_script_names = scripts
+                .scripts
+                .iter()
+                .map(|s| s.to_ascii_lowercase())
+                .collect::<Vec<String>>();
+            entity_script_names.sort_unstable();
+            entity_script_names.dedup();
+
+            for script_name in entity_script_names {
+                *script_to_entity_count.entry(script_name).or_insert(0) += 1;
+            }
+        }
+
+        let mut entries = script_to_entity_count
+            .into_iter()
+            .map(|(name, entity_count)| ScriptCoverageEntry {
+                status: self.status(&name),
+                name,
+                entity_count,
+            })
+            .collect::<Vec<ScriptCoverageEntry>>();
+
+        // Group by status, and then put the most-used scripts first
+        entries.sort_by(|a, b| {
+            a.status
+                .cmp(&b.status)
+                .then(b.entity_count.cmp(&a.entity_count))
+                .then(a.name.cmp(&b.name))
+        });
+
+        ScriptCoverageReport { entries }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ScriptCoverageEntry {
+    pub name: String,
+    pub status: ScriptStatus,
+    pub entity_count: usize,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ScriptCoverageReport {
+    pub entries: Vec<ScriptCoverageEntry>,
+}
+
+impl ScriptCoverageReport {
+    pub fn with_status(&self, status: ScriptStatus) -> impl Iterator<Item = &ScriptCoverageEntry> {
+        self.entries.iter().filter(move |e| e.status == status)
+    }
+
+    ///
+    /// script_count
+    ///
+    /// Number of distinct scripts with the given status
+    pub fn script_count(&self, status: ScriptStatus) -> usize {
+        self.with_status(status).count()
+    }
+
+    ///
+    /// entity_count
+    ///
+    /// Number of entity-script usages with the given status
+    pub fn entity_count(&self, status: ScriptStatus) -> usize {
+        self.with_status(status).map(|e| e.entity_count).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scripts(names: &[&str]) -> PropScripts {
+        PropScripts {
+            scripts: names.iter().map(|s| s.to_string()).collect(),
+            inherits: false,
+        }
+    }
+
+    #[test]
+    fn test_unknown_script_does_not_panic() {
+        let registry = ScriptRegistry::empty();
+        assert_eq!(registry.status("NotARealScript"), ScriptStatus::Missing);
+        let _script = registry.create("NotARealScript");
+    }
+
+    #[test]
+    fn test_registered_scripts_are_case_insensitive() {
+        let mut registry = ScriptRegistry::empty();
+        registry.register("MyScript", || Box::new(NoopScript::new()));
+        registry.register_noop("QuietScript");
+        registry.register_unimplemented("LaterScript");
+
+        assert_eq!(registry.status("myscript"), ScriptStatus::Implemented);
+        assert_eq!(registry.status("QUIETSCRIPT"), ScriptStatus::Noop);
+        assert_eq!(registry.status("laterscript"), ScriptStatus::Unimplemented);
+    }
+
+    #[test]
+    fn test_coverage_report_counts_entities() {
+        let mut registry = ScriptRegistry::empty();
+        registry.register("StdDoor", || Box::new(NoopScript::new()));
+        registry.register_noop("BaseLight");
+
+        let entities = vec![
+            scripts(&["StdDoor"]),
+            scripts(&["StdDoor", "BaseLight"]),
+            scripts(&["Turret"]),
+        ];
+
+        let report = registry.coverage_report(&entities);
+
+        assert_eq!(report.script_count(ScriptStatus::Implemented), 1);
+        assert_eq!(report.entity_count(ScriptStatus::Implemented), 2);
+        assert_eq!(report.entity_count(ScriptStatus::Noop), 1);
+        assert_eq!(report.script_count(ScriptStatus::Missing), 1);
+        assert_eq!(report.entries[0].name, "stddoor");
+    }
+
+    #[test]
+    fn test_builtin_scripts_are_registered() {
+        let registry = ScriptRegistry::with_builtin_scripts();
+        assert_eq!(registry.status("StdDoor"), ScriptStatus::Implemented);
+        assert_eq!(registry.status("BaseLight"), ScriptStatus::Noop);
+        assert_eq!(registry.status("Turret"), ScriptStatus::Unimplemented);
+    }
+}
@@ -219,6 +219,7 @@ pub fn play_environmental_sound(
             audio_handle,
             query,
             position: point3_to_vec3(position),
+            source: entity_id,
         }
     } else {
         Effect::NoEffect
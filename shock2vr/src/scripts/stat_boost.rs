@@ -0,0 +1,36 @@
+use shipyard::{EntityId, World};
+
+use crate::{physics::PhysicsWorld, player_stats::Stat};
+
+use super::{Effect, MessagePayload, Script};
+
+// Single-use boosters (strboost, intboost) - permanently raise one of the player's stats
+pub struct StatBoost {
+    stat: Stat,
+}
+
+impl StatBoost {
+    pub fn new(stat: Stat) -> StatBoost {
+        StatBoost { stat }
+    }
+}
+
+impl Script for StatBoost {
+    fn handle_message(
+        &mut self,
+        entity_id: EntityId,
+        _world: &World,
+        _physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        match msg {
+            MessagePayload::Frob | MessagePayload::TriggerPull => Effect::Combined {
+                effects: vec![
+                    Effect::IncreasePlayerStat { stat: self.stat },
+                    Effect::DestroyEntity { entity_id },
+                ],
+            },
+            _ => Effect::NoEffect,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use dark::properties::PropObjName;
+use shipyard::{EntityId, Get, View, World};
+use tracing::warn;
+
+use crate::{physics::PhysicsWorld, player_stats::Stat};
+
+use super::{Effect, MessagePayload, Script};
+
+// Implants (statboostimplant) - boost a stat while active. Using the implant toggles it on or off.
+// All the implants share the script, so the boosted stat comes from the object name.
+pub struct StatBoostImplant {}
+
+impl StatBoostImplant {
+    pub fn new() -> StatBoostImplant {
+        StatBoostImplant {}
+    }
+}
+
+fn stat_for_implant(name: &str) -> Option<Stat> {
+    let name = name.to_ascii_lowercase();
+    if name.contains("brawn") {
+        Some(Stat::Strength)
+    } else if name.contains("endura") {
+        Some(Stat::Endurance)
+    } else if name.contains("swift") {
+        Some(Stat::Agility)
+    } else if name.contains("smart") || name.contains("cyber") {
+        Some(Stat::Cyber)
+    } else if name.contains("psi") || name.contains("worm") {
+        Some(Stat::Psi)
+    } else {
+        None
+    }
+}
+
+impl Script for StatBoostImplant {
+    fn handle_message(
+        &mut self,
+        entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        match msg {
+            MessagePayload::Frob | MessagePayload::TriggerPull => {
+                let v_obj_name = world.borrow::<View<PropObjName>>().unwrap();
+                if let Ok(PropObjName(name)) = v_obj_name.get(entity_id) {
+                    match stat_for_implant(name) {
+                        Some(stat) => Effect::TogglePlayerImplant {
+                            name: name.clone(),
+                            stat,
+                        },
+                        None => {
+                            warn!("unknown stat boost implant: {}", name);
+                            Effect::NoEffect
+                        }
+                    }
+                } else {
+                    Effect::NoEffect
+                }
+            }
+            _ => Effect::NoEffect,
+        }
+    }
+}
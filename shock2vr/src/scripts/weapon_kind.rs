@@ -0,0 +1,188 @@
+use dark::properties::PropScripts;
+use shipyard::{EntityId, Get, View, World};
+
+use crate::player_stats::WeaponSkill;
+
+// Each weapon can be modified twice
+pub const MAX_MODIFICATION: u32 = 2;
+
+// The kind of weapon, which drives clip size, the ammo it takes, and what modifying it does.
+// The gamesys doesn't describe these directly, but every weapon has a '*modify' script
+// specific to its kind - so that's what we go by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeaponKind {
+    Pistol,
+    Shotgun,
+    AssaultRifle,
+    LaserPistol,
+    EmpRifle,
+    GrenadeLauncher,
+    FusionCannon,
+    StasisField,
+    WormLauncher,
+    ViralProliferator,
+}
+
+impl WeaponKind {
+    pub fn from_modify_script(script_name: &str) -> Option<WeaponKind> {
+        match script_name.to_ascii_lowercase().as_str() {
+            "pistolmodify" => Some(WeaponKind::Pistol),
+            "shotgunmodify" => Some(WeaponKind::Shotgun),
+            "riflemodify" => Some(WeaponKind::AssaultRifle),
+            "lasermodify" => Some(WeaponKind::LaserPistol),
+            "empmodify" => Some(WeaponKind::EmpRifle),
+            "grenademodify" => Some(WeaponKind::GrenadeLauncher),
+            "fusionmodify" => Some(WeaponKind::FusionCannon),
+            "stasismodify" => Some(WeaponKind::StasisField),
+            "annelidmodify" => Some(WeaponKind::WormLauncher),
+            "viralmodify" => Some(WeaponKind::ViralProliferator),
+            _ => None,
+        }
+    }
+
+    ///
+    /// from_entity
+    ///
+    /// Look up the kind of weapon from the entity's scripts
+    pub fn from_entity(world: &World, entity_id: EntityId) -> Option<WeaponKind> {
+        let v_scripts = world.borrow::<View<PropScripts>>().unwrap();
+        v_scripts.get(entity_id).ok().and_then(|prop_scripts| {
+            prop_scripts
+                .scripts
+                .iter()
+                .find_map(|script| WeaponKind::from_modify_script(script))
+        })
+    }
+
+    pub fn weapon_skill(&self) -> WeaponSkill {
+        match self {
+            WeaponKind::Pistol | WeaponKind::Shotgun | WeaponKind::AssaultRifle => {
+                WeaponSkill::Standard
+            }
+            WeaponKind::LaserPistol | WeaponKind::EmpRifle | WeaponKind::StasisField => {
+                WeaponSkill::Energy
+            }
+            WeaponKind::GrenadeLauncher | WeaponKind::FusionCannon => WeaponSkill::Heavy,
+            WeaponKind::WormLauncher | WeaponKind::ViralProliferator => WeaponSkill::Exotic,
+        }
+    }
+
+    ///
+    /// tech_difficulty
+    ///
+    /// The Modify or Repair skill needed to work on the weapon - more exotic weapons need more skill
+    pub fn tech_difficulty(&self) -> u32 {
+        match self.weapon_skill() {
+            WeaponSkill::Standard => 1,
+            WeaponSkill::Energy => 2,
+            WeaponSkill::Heavy => 3,
+            WeaponSkill::Exotic => 4,
+        }
+    }
+
+    ///
+    /// required_modify_skill
+    ///
+    /// The Modify skill needed to upgrade the weapon from its current modification level
+    pub fn required_modify_skill(&self, modification: u32) -> u32 {
+        self.tech_difficulty() + modification
+    }
+
+    ///
+    /// is_energy
+    ///
+    /// Energy weapons hold a charge instead of a clip, and are refilled at recharging stations
+    pub fn is_energy(&self) -> bool {
+        matches!(
+            self,
+            WeaponKind::LaserPistol
+                | WeaponKind::EmpRifle
+                | WeaponKind::FusionCannon
+                | WeaponKind::StasisField
+        )
+    }
+
+    fn base_clip_size(&self) -> u32 {
+        match self {
+            WeaponKind::Pistol => 12,
+            WeaponKind::Shotgun => 6,
+            WeaponKind::AssaultRifle => 36,
+            WeaponKind::LaserPistol => 20,
+            WeaponKind::EmpRifle => 10,
+            WeaponKind::GrenadeLauncher => 6,
+            WeaponKind::FusionCannon => 20,
+            WeaponKind::StasisField => 20,
+            WeaponKind::WormLauncher => 20,
+            WeaponKind::ViralProliferator => 20,
+        }
+    }
+
+    ///
+    /// clip_size
+    ///
+    /// Rounds (or charge) the weapon holds - each modification adds half the base size again
+    pub fn clip_size(&self, modification: u32) -> u32 {
+        let base = self.base_clip_size();
+        base + base * modification.min(MAX_MODIFICATION) / 2
+    }
+
+    ///
+    /// ammo_setting
+    ///
+    /// Which setting (projectile) an ammo clip loads into this weapon, based on the clip's name.
+    /// Returns None if the clip doesn't fit the weapon.
+    pub fn ammo_setting(&self, ammo_name: &str) -> Option<u32> {
+        let name = ammo_name.to_ascii_lowercase();
+        let settings: &[&str] = match self {
+            WeaponKind::Pistol | WeaponKind::AssaultRifle => {
+                &["standard", "anti-personnel", "armor-piercing"]
+            }
+            WeaponKind::Shotgun => &["slug", "pellet"],
+            WeaponKind::GrenadeLauncher => &["frag", "emp", "incendiary", "proximity", "toxin"],
+            WeaponKind::WormLauncher => &["worm"],
+            WeaponKind::ViralProliferator => &["viral"],
+            // Energy weapons are recharged, not reloaded
+            _ => &[],
+        };
+
+        settings
+            .iter()
+            .position(|keyword| name.contains(keyword))
+            .map(|idx| idx as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modifications_increase_clip_size() {
+        let pistol = WeaponKind::Pistol;
+        assert_eq!(pistol.clip_size(0), 12);
+        assert_eq!(pistol.clip_size(1), 18);
+        assert_eq!(pistol.clip_size(2), 24);
+        assert_eq!(pistol.clip_size(5), 24);
+    }
+
+    #[test]
+    fn test_second_modification_needs_more_skill() {
+        assert_eq!(WeaponKind::Pistol.required_modify_skill(0), 1);
+        assert_eq!(WeaponKind::Pistol.required_modify_skill(1), 2);
+        assert_eq!(WeaponKind::ViralProliferator.required_modify_skill(1), 5);
+    }
+
+    #[test]
+    fn test_ammo_only_fits_matching_weapons() {
+        assert_eq!(
+            WeaponKind::AssaultRifle.ammo_setting("Armor-Piercing Clip"),
+            Some(2)
+        );
+        assert_eq!(
+            WeaponKind::Shotgun.ammo_setting("Armor-Piercing Clip"),
+            None
+        );
+        assert_eq!(WeaponKind::Shotgun.ammo_setting("Pellet Shot"), Some(1));
+        assert_eq!(WeaponKind::LaserPistol.ammo_setting("Standard Clip"), None);
+    }
+}
@@ -0,0 +1,132 @@
+use dark::properties::{PropGunState, MAX_GUN_CONDITION};
+use engine::audio::AudioHandle;
+use shipyard::{EntityId, UniqueView, World};
+use tracing::info;
+
+use crate::{
+    physics::PhysicsWorld,
+    player_stats::{Currency, PlayerStats, TechSkill},
+};
+
+use super::{
+    script_util::play_environmental_sound,
+    weapon_kind::{WeaponKind, MAX_MODIFICATION},
+    weapon_script::get_gun_state,
+    Effect, MessagePayload, Script,
+};
+
+// Nanites it costs to modify or repair a weapon without a tool
+const MODIFY_NANITE_COST: u32 = 25;
+const REPAIR_NANITE_COST: u32 = 10;
+
+// The '*modify' scripts on each weapon - handles upgrading and repairing the weapon. Frobbing the
+// weapon works on it with the player's own skills and nanites: a broken weapon is repaired, otherwise
+// it is modified. Tools (freemodify, freerepair) send Modify or Repair directly, which is free.
+pub struct WeaponModify {
+    kind: WeaponKind,
+}
+
+impl WeaponModify {
+    pub fn new(kind: WeaponKind) -> WeaponModify {
+        WeaponModify { kind }
+    }
+
+    fn modify(&self, world: &World, entity_id: EntityId, gun_state: PropGunState) -> Effect {
+        if gun_state.modification >= MAX_MODIFICATION {
+            return Effect::NoEffect;
+        }
+
+        Effect::combine(vec![
+            Effect::SetGunState {
+                entity_id,
+                gun_state: PropGunState {
+                    modification: gun_state.modification + 1,
+                    ..gun_state
+                },
+            },
+            play_environmental_sound(world, entity_id, "modify", vec![], AudioHandle::new()),
+        ])
+    }
+
+    fn repair(&self, world: &World, entity_id: EntityId, gun_state: PropGunState) -> Effect {
+        Effect::combine(vec![
+            Effect::SetGunState {
+                entity_id,
+                gun_state: PropGunState {
+                    condition: MAX_GUN_CONDITION,
+                    jammed: false,
+                    ..gun_state
+                },
+            },
+            play_environmental_sound(world, entity_id, "repair", vec![], AudioHandle::new()),
+        ])
+    }
+
+    fn work_on_weapon(&self, world: &World, entity_id: EntityId) -> Effect {
+        let gun_state = get_gun_state(world, entity_id, self.kind);
+        let player_stats = world.borrow::<UniqueView<PlayerStats>>().unwrap();
+
+        let (skill, required_skill, cost) = if gun_state.is_broken() {
+            (
+                TechSkill::Repair,
+                self.kind.tech_difficulty(),
+                REPAIR_NANITE_COST,
+            )
+        } else if gun_state.modification < MAX_MODIFICATION {
+            (
+                TechSkill::Modify,
+                self.kind.required_modify_skill(gun_state.modification),
+                MODIFY_NANITE_COST,
+            )
+        } else {
+            return Effect::NoEffect;
+        };
+
+        if player_stats.tech_skill(skill) < required_skill {
+            info!(
+                "{:?} {} needed to work on {:?}",
+                skill, required_skill, self.kind
+            );
+            return play_environmental_sound(world, entity_id, "empty", vec![], AudioHandle::new());
+        }
+
+        if player_stats.balance(Currency::Nanites) < cost {
+            info!("{} nanites needed to work on {:?}", cost, self.kind);
+            return play_environmental_sound(world, entity_id, "empty", vec![], AudioHandle::new());
+        }
+
+        let work_effect = if gun_state.is_broken() {
+            self.repair(world, entity_id, gun_state)
+        } else {
+            self.modify(world, entity_id, gun_state)
+        };
+
+        Effect::Purchase {
+            price: cost,
+            effect: Box::new(work_effect),
+        }
+    }
+}
+
+impl Script for WeaponModify {
+    fn handle_message(
+        &mut self,
+        entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        match msg {
+            MessagePayload::Frob => self.work_on_weapon(world, entity_id),
+            MessagePayload::Modify => {
+                let gun_state = get_gun_state(world, entity_id, self.kind);
+                self.modify(world, entity_id, gun_state)
+            }
+            MessagePayload::Repair => {
+                let gun_state = get_gun_state(world, entity_id, self.kind);
+                self.repair(world, entity_id, gun_state)
+            }
+            _ => Effect::NoEffect,
+        }
+    }
+}
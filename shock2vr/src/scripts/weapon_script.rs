@@ -1,31 +1,185 @@
 use cgmath::{point3, Deg, Matrix4, Quaternion, Rotation, Rotation3, Transform};
-use dark::properties::{GunFlashOptions, Link, ProjectileOptions};
+use dark::properties::{
+    GunFlashOptions, Link, ProjectileOptions, PropGunState, PropObjName, PropPlayerGun,
+};
 use engine::audio::AudioHandle;
-use shipyard::{EntityId, Get, View, World};
+use rand::Rng;
+use shipyard::{EntityId, Get, UniqueView, UniqueViewMut, View, World};
 
 use crate::{
-    mission::entity_creator::CreateEntityOptions,
+    game_rng::GameRng,
+    mission::{entity_creator::CreateEntityOptions, PlayerInfo},
     physics::PhysicsWorld,
     runtime_props::{RuntimePropTransform, RuntimePropVhots},
+    time::Time,
     vr_config,
 };
 
 use super::{
-    script_util::{
-        get_all_links_with_template, get_first_link_with_template_and_data,
-        play_environmental_sound,
-    },
+    researchable::needs_research,
+    script_util::{get_all_links_with_template, play_environmental_sound},
+    weapon_kind::WeaponKind,
     Effect, MessagePayload, Script,
 };
 
-pub struct WeaponScript;
+// Condition lost each time the weapon is fired
+const CONDITION_LOSS_PER_SHOT: f32 = 0.5;
+
+// Below this condition, the weapon starts to jam - more often the closer it gets to breaking
+const JAM_CONDITION: f32 = 40.0;
+const MAX_JAM_CHANCE: f32 = 0.25;
+
+// Reload time, in seconds, for weapons that don't describe their reload
+const DEFAULT_RELOAD_TIME: f32 = 1.5;
+const MIN_RELOAD_TIME: f32 = 0.5;
+const MAX_RELOAD_TIME: f32 = 4.0;
+
+struct PendingReload {
+    remaining_time: f32,
+    // Ammo setting of the clip being loaded - the rest of the gun state is read when the reload
+    // finishes, so repairs or modifications made in the meantime are kept
+    setting: u32,
+    // The clip being loaded - it's only used up once the reload finishes, so nothing is lost
+    // if the reload is interrupted
+    ammo_entity_id: EntityId,
+}
+
+pub struct WeaponScript {
+    reload: Option<PendingReload>,
+}
 impl WeaponScript {
     pub fn new() -> WeaponScript {
-        WeaponScript
+        WeaponScript { reload: None }
+    }
+
+    fn try_fire(&mut self, world: &World, entity_id: EntityId, kind: WeaponKind) -> Effect {
+        let gun_state = get_gun_state(world, entity_id, kind);
+
+        if self.reload.is_some()
+            || gun_state.is_broken()
+            || gun_state.jammed
+            || gun_state.ammo_count <= 0
+        {
+            return play_environmental_sound(world, entity_id, "empty", vec![], AudioHandle::new());
+        }
+
+        if does_jam(world, &gun_state) {
+            let jammed_state = PropGunState {
+                jammed: true,
+                ..gun_state
+            };
+            return Effect::combine(vec![
+                Effect::SetGunState {
+                    entity_id,
+                    gun_state: jammed_state,
+                },
+                play_environmental_sound(world, entity_id, "empty", vec![], AudioHandle::new()),
+            ]);
+        }
+
+        let next_state = PropGunState {
+            ammo_count: gun_state.ammo_count - 1,
+            condition: (gun_state.condition - CONDITION_LOSS_PER_SHOT).max(0.0),
+            ..gun_state.clone()
+        };
+
+        Effect::combine(vec![
+            fire(world, entity_id, gun_state.setting),
+            Effect::SetGunState {
+                entity_id,
+                gun_state: next_state,
+            },
+        ])
+    }
+
+    fn start_reload(
+        &mut self,
+        world: &World,
+        entity_id: EntityId,
+        kind: WeaponKind,
+        ammo_entity_id: EntityId,
+    ) -> Effect {
+        if self.reload.is_some() {
+            return Effect::NoEffect;
+        }
+
+        let v_obj_name = world.borrow::<View<PropObjName>>().unwrap();
+        let maybe_setting = v_obj_name
+            .get(ammo_entity_id)
+            .ok()
+            .and_then(|PropObjName(name)| kind.ammo_setting(name));
+
+        if let Some(setting) = maybe_setting {
+            self.reload = Some(PendingReload {
+                remaining_time: get_reload_time(world, entity_id),
+                setting,
+                ammo_entity_id,
+            });
+
+            play_environmental_sound(world, entity_id, "reload", vec![], AudioHandle::new())
+        } else {
+            Effect::NoEffect
+        }
     }
 }
 
 impl Script for WeaponScript {
+    fn initialize(&mut self, entity_id: EntityId, world: &World) -> Effect {
+        // Weapons start out fully loaded, unless the mission or save says otherwise
+        let v_gun_state = world.borrow::<View<PropGunState>>().unwrap();
+        match WeaponKind::from_entity(world, entity_id) {
+            Some(kind) if !v_gun_state.contains(entity_id) => Effect::SetGunState {
+                entity_id,
+                gun_state: PropGunState::new(kind.clip_size(0) as i32),
+            },
+            _ => Effect::NoEffect,
+        }
+    }
+
+    fn update(
+        &mut self,
+        entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        time: &Time,
+    ) -> Effect {
+        if let Some(mut reload) = self.reload.take() {
+            reload.remaining_time -= time.elapsed.as_secs_f32();
+            if reload.remaining_time > 0.0 {
+                self.reload = Some(reload);
+                return Effect::NoEffect;
+            }
+
+            // The clip may have been used up in the meantime - then there's nothing to load
+            let v_obj_name = world.borrow::<View<PropObjName>>().unwrap();
+            let maybe_kind = WeaponKind::from_entity(world, entity_id);
+            let kind = match maybe_kind {
+                Some(kind) if v_obj_name.contains(reload.ammo_entity_id) => kind,
+                _ => return Effect::NoEffect,
+            };
+
+            // Whatever was left in the old clip is lost
+            let gun_state = get_gun_state(world, entity_id, kind);
+            let reloaded_state = PropGunState {
+                ammo_count: kind.clip_size(gun_state.modification) as i32,
+                setting: reload.setting,
+                jammed: false,
+                ..gun_state
+            };
+
+            return Effect::combine(vec![
+                Effect::DestroyEntity {
+                    entity_id: reload.ammo_entity_id,
+                },
+                Effect::SetGunState {
+                    entity_id,
+                    gun_state: reloaded_state,
+                },
+            ]);
+        }
+        Effect::NoEffect
+    }
+
     fn handle_message(
         &mut self,
         entity_id: EntityId,
@@ -33,58 +187,128 @@ impl Script for WeaponScript {
         _physics: &PhysicsWorld,
         msg: &MessagePayload,
     ) -> Effect {
+        // Weapons without a kind don't keep track of ammo or condition
+        let maybe_kind = WeaponKind::from_entity(world, entity_id);
         match msg {
-            MessagePayload::TriggerPull => {
-                let sound_effect =
-                    play_environmental_sound(world, entity_id, "shoot", vec![], AudioHandle::new());
-                //Create muzzle flash
-                let muzzle_flashes =
-                    get_all_links_with_template(world, entity_id, |link| match link {
-                        Link::GunFlash(data) => Some(*data),
-                        _ => None,
-                    });
-
-                // TODO: Handle setting or ammo type? This just picks the very first projectile
-                let maybe_projectile =
-                    get_first_link_with_template_and_data(world, entity_id, |link| match link {
-                        Link::Projectile(data) => Some(*data),
-                        _ => None,
-                    });
-
-                let projectile_effect = Effect::Multiple(
-                    maybe_projectile
-                        .into_iter()
-                        .map(|(template_id, options)| {
-                            create_projectile(world, entity_id, template_id, &options)
-                        })
-                        .collect(),
-                );
-
-                let muzzle_flash_effect = Effect::Multiple(
-                    muzzle_flashes
-                        .into_iter()
-                        .map(|(template_id, options)| {
-                            create_muzzle_flash(world, entity_id, template_id, &options)
-                        })
-                        .collect(),
-                );
-                // let offset = obj_rotation * vec3(0.0128545, 0.5026805, -3.0933015) / SCALE_FACTOR;
-
-                // let muzzle_flash_effect = Effect::CreateEntity {
-                //     template_id: -2653,
-                //     position: position + offset,
-                //     orientation: *obj_rotation
-                //         * Quaternion::from_axis_angle(vec3(0.0, 1.0, 0.0), Rad(PI / 2.0)),
-                // };
-
-                Effect::Multiple(vec![sound_effect, muzzle_flash_effect, projectile_effect])
+            MessagePayload::TriggerPull if needs_research(world, entity_id) => {
+                play_environmental_sound(world, entity_id, "empty", vec![], AudioHandle::new())
             }
+            MessagePayload::TriggerPull => match maybe_kind {
+                Some(kind) => self.try_fire(world, entity_id, kind),
+                None => fire(world, entity_id, 0),
+            },
+            MessagePayload::Reload { ammo } => match maybe_kind {
+                Some(kind) => self.start_reload(world, entity_id, kind, *ammo),
+                None => Effect::NoEffect,
+            },
+            MessagePayload::Recharge => match maybe_kind {
+                Some(kind) if kind.is_energy() => {
+                    let gun_state = get_gun_state(world, entity_id, kind);
+                    let charge = kind.clip_size(gun_state.modification) as i32;
+                    if gun_state.ammo_count < charge {
+                        Effect::SetGunState {
+                            entity_id,
+                            gun_state: PropGunState {
+                                ammo_count: charge,
+                                ..gun_state
+                            },
+                        }
+                    } else {
+                        Effect::NoEffect
+                    }
+                }
+                _ => Effect::NoEffect,
+            },
             MessagePayload::TriggerRelease => Effect::NoEffect,
             _ => Effect::NoEffect,
         }
     }
 }
 
+///
+/// get_gun_state
+///
+/// Current state of the weapon - weapons that haven't been initialized yet are fully loaded
+pub fn get_gun_state(world: &World, entity_id: EntityId, kind: WeaponKind) -> PropGunState {
+    let v_gun_state = world.borrow::<View<PropGunState>>().unwrap();
+    v_gun_state
+        .get(entity_id)
+        .cloned()
+        .unwrap_or_else(|_| PropGunState::new(kind.clip_size(0) as i32))
+}
+
+fn does_jam(world: &World, gun_state: &PropGunState) -> bool {
+    if gun_state.condition >= JAM_CONDITION {
+        return false;
+    }
+
+    let jam_chance = (JAM_CONDITION - gun_state.condition) / JAM_CONDITION * MAX_JAM_CHANCE;
+    let mut rng = world.borrow::<UniqueViewMut<GameRng>>().unwrap();
+    rng.gen::<f32>() < jam_chance
+}
+
+///
+/// get_reload_time
+///
+/// The gun dips down by the reload pitch and comes back up, at the reload rate
+fn get_reload_time(world: &World, entity_id: EntityId) -> f32 {
+    let v_player_gun = world.borrow::<View<PropPlayerGun>>().unwrap();
+    match v_player_gun.get(entity_id) {
+        Ok(player_gun) if player_gun.reload_rate > 0 => (2.0 * player_gun.reload_pitch as f32
+            / player_gun.reload_rate as f32)
+            .clamp(MIN_RELOAD_TIME, MAX_RELOAD_TIME),
+        _ => DEFAULT_RELOAD_TIME,
+    }
+}
+
+fn fire(world: &World, entity_id: EntityId, setting: u32) -> Effect {
+    let sound_effect =
+        play_environmental_sound(world, entity_id, "shoot", vec![], AudioHandle::new());
+    //Create muzzle flash
+    let muzzle_flashes = get_all_links_with_template(world, entity_id, |link| match link {
+        Link::GunFlash(data) => Some(*data),
+        _ => None,
+    });
+
+    // The setting picks the projectile - falling back to the first one, if the weapon doesn't have that many
+    let projectiles = get_all_links_with_template(world, entity_id, |link| match link {
+        Link::Projectile(data) => Some(*data),
+        _ => None,
+    });
+    let maybe_projectile = projectiles
+        .get(setting as usize)
+        .or_else(|| projectiles.first())
+        .cloned();
+
+    let projectile_effect = Effect::Multiple(
+        maybe_projectile
+            .into_iter()
+            .map(|(template_id, options)| {
+                create_projectile(world, entity_id, template_id, &options)
+            })
+            .collect(),
+    );
+
+    let muzzle_flash_effect = Effect::Multiple(
+        muzzle_flashes
+            .into_iter()
+            .map(|(template_id, options)| {
+                create_muzzle_flash(world, entity_id, template_id, &options)
+            })
+            .collect(),
+    );
+    // let offset = obj_rotation * vec3(0.0128545, 0.5026805, -3.0933015) / SCALE_FACTOR;
+
+    // let muzzle_flash_effect = Effect::CreateEntity {
+    //     template_id: -2653,
+    //     position: position + offset,
+    //     orientation: *obj_rotation
+    //         * Quaternion::from_axis_angle(vec3(0.0, 1.0, 0.0), Rad(PI / 2.0)),
+    // };
+
+    Effect::Multiple(vec![sound_effect, muzzle_flash_effect, projectile_effect])
+}
+
 fn create_muzzle_flash(
     world: &World,
     entity_id: EntityId,
@@ -122,7 +346,7 @@ fn create_muzzle_flash(
     }
 }
 
-fn create_projectile(
+pub fn create_projectile(
     world: &World,
     entity_id: EntityId,
     projectile_template_id: i32,
@@ -159,6 +383,8 @@ fn create_projectile(
     // Adjust the vhot position to be in the same coordinate space as the weapon
     let position = inv_rot_matrix.transform_point(vhot);
 
+    let player_entity = world.borrow::<UniqueView<PlayerInfo>>().unwrap().entity_id;
+
     Effect::CreateEntity {
         template_id: projectile_template_id,
         position,
@@ -168,6 +394,7 @@ fn create_projectile(
         root_transform: transform.0 * rot_matrix * projectile_rotation,
         options: CreateEntityOptions {
             force_visible: true,
+            launched_by: Some(player_entity),
         },
     }
 }
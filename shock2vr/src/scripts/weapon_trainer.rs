@@ -0,0 +1,49 @@
+use shipyard::{EntityId, World};
+
+use crate::physics::PhysicsWorld;
+
+use super::{weapon_kind::WeaponKind, Effect, MessagePayload, Script};
+
+// Weapon upgrade units - pulling the trigger while holding a weapon over the unit spends cyber
+// modules to train the skill for that kind of weapon
+pub struct WeaponTrainer {
+    was_triggered: bool,
+}
+
+impl WeaponTrainer {
+    pub fn new() -> WeaponTrainer {
+        WeaponTrainer {
+            was_triggered: false,
+        }
+    }
+}
+
+impl Script for WeaponTrainer {
+    fn handle_message(
+        &mut self,
+        _entity_id: EntityId,
+        world: &World,
+        _physics: &PhysicsWorld,
+        msg: &MessagePayload,
+    ) -> Effect {
+        match msg {
+            MessagePayload::Hover {
+                held_entity_id,
+                is_triggered,
+                ..
+            } => {
+                // Only train once per trigger pull
+                let should_train = *is_triggered && !self.was_triggered;
+                self.was_triggered = *is_triggered;
+
+                match held_entity_id.and_then(|id| WeaponKind::from_entity(world, id)) {
+                    Some(kind) if should_train => Effect::TrainWeaponSkill {
+                        skill: kind.weapon_skill(),
+                    },
+                    _ => Effect::NoEffect,
+                }
+            }
+            _ => Effect::NoEffect,
+        }
+    }
+}
@@ -0,0 +1,208 @@
+///
+/// security_system.rs
+///
+/// The level-wide security alarm. Cameras that spot the player raise the alarm, which counts down
+/// and brings in reinforcements until it runs out, the camera that raised it is destroyed, or a
+/// security computer is hacked. The alarm for each mission is kept in QuestInfo, so it is saved
+/// along with it.
+///
+use cgmath::{MetricSpace, Vector3};
+use dark::SCALE_FACTOR;
+use serde::{Deserialize, Serialize};
+use shipyard::{EntityId, Unique};
+
+pub const ALARM_DURATION_SECONDS: f32 = 120.0;
+
+// Reinforcements arrive periodically while the alarm is going, up to a limit
+const SPAWN_INTERVAL_SECONDS: f32 = 20.0;
+const MAX_SPAWNS_PER_ALARM: u32 = 3;
+
+// How far from where the player was spotted reinforcements come in, so they don't appear right on
+// top of the player
+pub const REINFORCEMENT_SPAWN_DISTANCE: f32 = 40.0 / SCALE_FACTOR;
+
+// Templates to pick reinforcements from
+pub const ALARM_REINFORCEMENTS: [&str; 2] = ["Pipe Hybrid", "Shotgun Hybrid"];
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct Alarm {
+    pub remaining_seconds: f32,
+    // Where the player was spotted - reinforcements are sent in nearby
+    pub position: Vector3<f32>,
+    next_spawn_seconds: f32,
+    spawn_count: u32,
+    // What set off the alarm (ie, a camera) - not saved, since entity ids change when a save
+    // is loaded
+    #[serde(skip)]
+    raised_by: Option<EntityId>,
+}
+
+#[derive(Unique, Clone, Debug, Default)]
+pub struct SecuritySystem {
+    alarm: Option<Alarm>,
+}
+
+impl SecuritySystem {
+    pub fn new(alarm: Option<Alarm>) -> SecuritySystem {
+        SecuritySystem { alarm }
+    }
+
+    pub fn alarm(&self) -> Option<&Alarm> {
+        self.alarm.as_ref()
+    }
+
+    pub fn is_alarm_active(&self) -> bool {
+        self.alarm.is_some()
+    }
+
+    ///
+    /// raise_alarm
+    ///
+    /// Set off the alarm, if it isn't going already. Returns whether a new alarm was raised.
+    pub fn raise_alarm(&mut self, position: Vector3<f32>, raised_by: EntityId) -> bool {
+        if self.alarm.is_some() {
+            return false;
+        }
+
+        self.alarm = Some(Alarm {
+            remaining_seconds: ALARM_DURATION_SECONDS,
+            position,
+            next_spawn_seconds: SPAWN_INTERVAL_SECONDS,
+            spawn_count: 0,
+            raised_by: Some(raised_by),
+        });
+        true
+    }
+
+    ///
+    /// reset_alarm
+    ///
+    /// Turn off the alarm. Returns whether there was an alarm going.
+    pub fn reset_alarm(&mut self) -> bool {
+        self.alarm.take().is_some()
+    }
+
+    ///
+    /// reset_alarm_raised_by
+    ///
+    /// Turn off the alarm, but only if it was set off by the entity. Returns whether it was.
+    pub fn reset_alarm_raised_by(&mut self, entity_id: EntityId) -> bool {
+        let is_raised_by = self
+            .alarm
+            .as_ref()
+            .map(|alarm| alarm.raised_by == Some(entity_id))
+            .unwrap_or(false);
+        is_raised_by && self.reset_alarm()
+    }
+
+    ///
+    /// update
+    ///
+    /// Count down the alarm. Returns where the player was spotted, if it is time to send in
+    /// reinforcements.
+    pub fn update(&mut self, elapsed_seconds: f32) -> Option<Vector3<f32>> {
+        let alarm = self.alarm.as_mut()?;
+        alarm.remaining_seconds -= elapsed_seconds;
+        if alarm.remaining_seconds <= 0.0 {
+            self.alarm = None;
+            return None;
+        }
+
+        alarm.next_spawn_seconds -= elapsed_seconds;
+        if alarm.next_spawn_seconds <= 0.0 && alarm.spawn_count < MAX_SPAWNS_PER_ALARM {
+            alarm.next_spawn_seconds += SPAWN_INTERVAL_SECONDS;
+            alarm.spawn_count += 1;
+            Some(alarm.position)
+        } else {
+            None
+        }
+    }
+}
+
+///
+/// choose_reinforcement_spawn
+///
+/// Where to bring in reinforcements, out of the candidate positions (ie, where the level's other
+/// creatures are) - the nearest one at least REINFORCEMENT_SPAWN_DISTANCE from where the player
+/// was spotted. None if every candidate is too close.
+pub fn choose_reinforcement_spawn(
+    spotted_position: Vector3<f32>,
+    candidates: impl IntoIterator<Item = Vector3<f32>>,
+) -> Option<Vector3<f32>> {
+    candidates
+        .into_iter()
+        .map(|position| (position, position.distance(spotted_position)))
+        .filter(|(_, distance)| *distance >= REINFORCEMENT_SPAWN_DISTANCE)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(position, _)| position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera(id: u64) -> EntityId {
+        EntityId::from_inner(id).unwrap()
+    }
+
+    #[test]
+    fn test_alarm_counts_down() {
+        let mut security = SecuritySystem::default();
+        assert!(security.raise_alarm(Vector3::new(0.0, 0.0, 0.0), camera(1)));
+        assert!(!security.raise_alarm(Vector3::new(1.0, 0.0, 0.0), camera(1)));
+
+        security.update(ALARM_DURATION_SECONDS - 1.0);
+        assert!(security.is_alarm_active());
+        security.update(1.0);
+        assert!(!security.is_alarm_active());
+    }
+
+    #[test]
+    fn test_alarm_sends_limited_reinforcements() {
+        let position = Vector3::new(1.0, 2.0, 3.0);
+        let mut security = SecuritySystem::default();
+        security.raise_alarm(position, camera(1));
+
+        assert_eq!(security.update(SPAWN_INTERVAL_SECONDS - 1.0), None);
+        assert_eq!(security.update(1.0), Some(position));
+
+        let spawns = (0..MAX_SPAWNS_PER_ALARM + 1)
+            .filter_map(|_| security.update(SPAWN_INTERVAL_SECONDS))
+            .count() as u32;
+        assert_eq!(spawns, MAX_SPAWNS_PER_ALARM - 1);
+    }
+
+    #[test]
+    fn test_reset_alarm() {
+        let mut security = SecuritySystem::default();
+        assert!(!security.reset_alarm());
+        security.raise_alarm(Vector3::new(0.0, 0.0, 0.0), camera(1));
+        assert!(security.reset_alarm());
+        assert_eq!(security.update(SPAWN_INTERVAL_SECONDS), None);
+    }
+
+    #[test]
+    fn test_reinforcements_spawn_away_from_the_player() {
+        let spotted = Vector3::new(0.0, 0.0, 0.0);
+        let too_close = Vector3::new(REINFORCEMENT_SPAWN_DISTANCE / 2.0, 0.0, 0.0);
+        let near = Vector3::new(0.0, 0.0, REINFORCEMENT_SPAWN_DISTANCE * 2.0);
+        let far = Vector3::new(REINFORCEMENT_SPAWN_DISTANCE * 4.0, 0.0, 0.0);
+
+        assert_eq!(
+            choose_reinforcement_spawn(spotted, vec![far, too_close, near]),
+            Some(near)
+        );
+        assert_eq!(choose_reinforcement_spawn(spotted, vec![too_close]), None);
+    }
+
+    #[test]
+    fn test_only_the_camera_that_raised_the_alarm_resets_it() {
+        let mut security = SecuritySystem::default();
+        security.raise_alarm(Vector3::new(0.0, 0.0, 0.0), camera(1));
+
+        assert!(!security.reset_alarm_raised_by(camera(2)));
+        assert!(security.is_alarm_active());
+        assert!(security.reset_alarm_raised_by(camera(1)));
+        assert!(!security.is_alarm_active());
+    }
+}
@@ -1,8 +1,9 @@
 use std::time::Duration;
 
+use serde::{Deserialize, Serialize};
 use shipyard::Unique;
 
-#[derive(Unique, Clone, Debug, Default)]
+#[derive(Unique, Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Time {
     pub elapsed: Duration,
     pub total: Duration,
@@ -26,6 +26,7 @@ pub struct VirtualHand {
     rotation: Quaternion<f32>,
     trigger_value: f32,
     squeeze_value: f32,
+    a_value: f32,
     raytrace_hit: Option<RayCastResult>,
 
     // Keep track of last frobbed entity so frobbing is 'semi-auto'
@@ -88,6 +89,7 @@ impl VirtualHand {
             },
             trigger_value: 0.0,
             squeeze_value: 0.0,
+            a_value: 0.0,
             raytrace_hit: None,
             last_frobbed_entity: None,
             hand_state: HandState::Empty,
@@ -230,6 +232,7 @@ impl VirtualHand {
                         rotation: hand_rotation,
                         trigger_value: input_hand.trigger_value,
                         squeeze_value: input_hand.squeeze_value,
+                        a_value: input_hand.a_value,
                         raytrace_hit: None,
                         last_frobbed_entity: None,
                         hand_state: HandState::Empty,
@@ -257,6 +260,15 @@ impl VirtualHand {
                         });
                     }
 
+                    if prev.a_value < 0.5 && input_hand.a_value > 0.5 {
+                        msgs.push(VirtualHandEffect::OutMessage {
+                            message: Message {
+                                to: entity_id,
+                                payload: MessagePayload::AltPress,
+                            },
+                        });
+                    }
+
                     if prev.trigger_value > 0.5 && input_hand.trigger_value < 0.5 {
                         println!("releasing!");
                         msgs.push(VirtualHandEffect::OutMessage {
@@ -279,6 +291,7 @@ impl VirtualHand {
                         rotation: hand_rotation,
                         trigger_value: input_hand.trigger_value,
                         squeeze_value: input_hand.squeeze_value,
+                        a_value: input_hand.a_value,
                         raytrace_hit: None,
                         last_frobbed_entity: None,
                         hand_state: next_hand_state,
@@ -463,6 +476,7 @@ fn handle_empty_hand_state(
         rotation: hand_rotation,
         trigger_value: input_hand.trigger_value,
         squeeze_value: input_hand.squeeze_value,
+        a_value: input_hand.a_value,
         raytrace_hit: result,
         last_frobbed_entity,
         hand_state: next_hand_state,
@@ -485,7 +499,7 @@ fn get_held_position_orientation(
     vr_config::get_vr_hand_model_adjustments_from_entity(entity_id, world, handedness)
 }
 
-fn can_grab_item(world: &World, entity_id: EntityId) -> bool {
+pub fn can_grab_item(world: &World, entity_id: EntityId) -> bool {
     let v_prop_frobinfo = world.borrow::<View<PropFrobInfo>>().unwrap();
 
     if let Ok(frob_info) = v_prop_frobinfo.get(entity_id) {
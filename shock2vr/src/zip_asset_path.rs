@@ -7,7 +7,7 @@ use std::{
 };
 
 use engine::assets::asset_paths::{AbstractAssetPath, ReadableAndSeekable};
-use zip::ZipArchive;
+use zip::{result::ZipResult, ZipArchive};
 
 pub struct ZipAssetPath {
     archive: Mutex<ZipArchive<BufReader<File>>>,
@@ -15,18 +15,18 @@ pub struct ZipAssetPath {
 }
 
 impl ZipAssetPath {
-    pub fn new(zip_path: String) -> Box<ZipAssetPath> {
+    pub fn new(zip_path: String) -> ZipResult<Box<ZipAssetPath>> {
         Self::new2(zip_path, true)
     }
 
-    pub fn new2(zip_path: String, collapse_paths: bool) -> Box<ZipAssetPath> {
-        let file = File::open(zip_path).unwrap();
+    pub fn new2(zip_path: String, collapse_paths: bool) -> ZipResult<Box<ZipAssetPath>> {
+        let file = File::open(zip_path)?;
         let reader = BufReader::new(file);
 
-        let mut archive = zip::ZipArchive::new(reader).unwrap();
+        let mut archive = zip::ZipArchive::new(reader)?;
         let mut asset_to_path = HashMap::new();
         for i in 0..archive.len() {
-            let file = archive.by_index(i).unwrap();
+            let file = archive.by_index(i)?;
             let outpath = match file.enclosed_name() {
                 Some(path) => path,
                 None => {
@@ -52,10 +52,10 @@ impl ZipAssetPath {
                 }
             }
         }
-        Box::new(ZipAssetPath {
+        Ok(Box::new(ZipAssetPath {
             archive: Mutex::new(archive),
             asset_to_path,
-        })
+        }))
     }
 }
 